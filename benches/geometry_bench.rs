@@ -0,0 +1,291 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use projgeom_rs::batch::{incident_mask, incident_mask32, meet_many, meet_many32, PointSoA};
+use projgeom_rs::filtered::{coincident_filtered, orientation_filtered};
+use projgeom_rs::normalization::{
+    harm_conj_with, meet_with, GcdAlways, GcdThreshold, NoNormalization, NormalizationPolicy,
+};
+use projgeom_rs::predicates::orientation;
+use projgeom_rs::{
+    coincident, harm_conj, involution, Join, PgLine, PgLine32, PgPoint, PgPoint32,
+    ProjectivePlanePrimitive,
+};
+
+fn points(n: i64, seed: i64) -> Vec<PgPoint> {
+    (0..n)
+        .map(|i| {
+            PgPoint::new([
+                (i * 7 + seed) % 1001 - 500,
+                (i * 13 + seed) % 997 - 498,
+                1 + i % 5,
+            ])
+        })
+        .collect()
+}
+
+fn bench_meet(c: &mut Criterion) {
+    let pts_a = points(10_000, 3);
+    let pts_b = points(10_000, 11);
+    c.bench_function("meet scalar", |b| {
+        b.iter(|| {
+            black_box(&pts_a)
+                .iter()
+                .zip(black_box(&pts_b))
+                .map(|(p, q)| p.join(q))
+                .collect::<Vec<PgLine>>()
+        })
+    });
+    c.bench_function("meet_many", |b| {
+        b.iter(|| meet_many(black_box(&pts_a), black_box(&pts_b)))
+    });
+}
+
+fn bench_incident(c: &mut Criterion) {
+    let pts = points(10_000, 5);
+    let ln = PgLine::new([3, -2, 7]);
+    c.bench_function("incident scalar", |b| {
+        b.iter(|| {
+            black_box(&pts)
+                .iter()
+                .map(|pt| pt.incident(black_box(&ln)))
+                .collect::<Vec<bool>>()
+        })
+    });
+    c.bench_function("incident_mask", |b| {
+        b.iter(|| incident_mask(black_box(&pts), black_box(&ln)))
+    });
+}
+
+/// Points with pseudo-random coordinates of absolute value below `2^bits`.
+fn random_points(n: usize, seed: u64, bits: u32) -> Vec<PgPoint> {
+    let mut state = seed;
+    let mut next = move || {
+        // xorshift64
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        (state >> (63 - bits)) as i64 - (1 << bits)
+    };
+    (0..n)
+        .map(|_| PgPoint::new([next(), next(), next() | 1]))
+        .collect()
+}
+
+fn bench_filtered(c: &mut Criterion) {
+    // large coordinates, for which the exact determinant needs i128
+    let pts = random_points(3_000, 88_172_645_463_325_252, 40);
+    let triples: Vec<&[PgPoint]> = pts.chunks_exact(3).collect();
+    c.bench_function("orientation exact", |b| {
+        b.iter(|| {
+            black_box(&triples)
+                .iter()
+                .map(|t| orientation(&t[0], &t[1], &t[2]))
+                .collect::<Vec<_>>()
+        })
+    });
+    c.bench_function("orientation filtered", |b| {
+        b.iter(|| {
+            black_box(&triples)
+                .iter()
+                .map(|t| orientation_filtered(&t[0], &t[1], &t[2]))
+                .collect::<Vec<_>>()
+        })
+    });
+    // with small coordinates, the i64 arithmetic of the generic `coincident` is hard to beat;
+    // the filter pays off where the exact computation needs i128, as for `orientation` above
+    let pts = random_points(3_000, 88_172_645_463_325_252, 20);
+    let triples: Vec<&[PgPoint]> = pts.chunks_exact(3).collect();
+    c.bench_function("coincident generic", |b| {
+        b.iter(|| {
+            black_box(&triples)
+                .iter()
+                .map(|t| coincident(&t[0], &t[1], &t[2]))
+                .collect::<Vec<bool>>()
+        })
+    });
+    c.bench_function("coincident filtered", |b| {
+        b.iter(|| {
+            black_box(&triples)
+                .iter()
+                .map(|t| coincident_filtered(&t[0], &t[1], &t[2]))
+                .collect::<Vec<_>>()
+        })
+    });
+}
+
+fn bench_harm_conj(c: &mut Criterion) {
+    // triples on the lines through the origin and the points of `points`
+    let triples: Vec<[PgPoint; 3]> = points(1_000, 17)
+        .into_iter()
+        .map(|pt| {
+            let [x, y, z] = pt.coord;
+            [[0, 0, 1], [x, y, z], [2 * x, 2 * y, 3 * z]].map(PgPoint::new)
+        })
+        .collect();
+    c.bench_function("harm_conj generic", |b| {
+        b.iter(|| {
+            black_box(&triples)
+                .iter()
+                .map(|[p, q, r]| harm_conj(p, q, r))
+                .collect::<Vec<PgPoint>>()
+        })
+    });
+    c.bench_function("harm_conj PgPoint", |b| {
+        b.iter(|| {
+            black_box(&triples)
+                .iter()
+                .map(|[p, q, r]| p.harm_conj(q, r))
+                .collect::<Vec<PgPoint>>()
+        })
+    });
+
+    // a chain of reflections in two mirrors, normalized to keep the coordinates small
+    let (origin_1, mirror_1) = (PgPoint::new([1, 0, 0]), PgLine::new([1, 0, -3]));
+    let (origin_2, mirror_2) = (PgPoint::new([0, 1, 0]), PgLine::new([0, 1, 2]));
+    let start = PgPoint::new([1, 1, 1]);
+    c.bench_function("involution chain generic", |b| {
+        b.iter(|| {
+            (0..100).fold(black_box(start.clone()), |pt, _| {
+                let pt = involution(&origin_1, &mirror_1, &pt);
+                involution(&origin_2, &mirror_2, &pt).normalized()
+            })
+        })
+    });
+    c.bench_function("involution chain PgPoint", |b| {
+        b.iter(|| {
+            (0..100).fold(black_box(start.clone()), |pt, _| {
+                let pt = origin_1.involution(&mirror_1, &pt);
+                origin_2.involution(&mirror_2, &pt).normalized()
+            })
+        })
+    });
+}
+
+fn bench_normalization(c: &mut Criterion) {
+    fn run<P: NormalizationPolicy + Default>(c: &mut Criterion, name: &str, policy: P) {
+        let triples: Vec<[PgPoint; 3]> = points(1_000, 17)
+            .into_iter()
+            .map(|pt| {
+                let [x, y, z] = pt.coord;
+                [[0, 0, 1], [x, y, z], [2 * x, 2 * y, 3 * z]].map(PgPoint::new)
+            })
+            .collect();
+        c.bench_function(&format!("harm_conj policy {}", name), |b| {
+            b.iter(|| {
+                black_box(&triples)
+                    .iter()
+                    .map(|[p, q, r]| harm_conj_with::<_, PgLine, _>(p, q, r, &policy))
+                    .collect::<Vec<PgPoint>>()
+            })
+        });
+        let pts_a = points(10_000, 3);
+        let pts_b = points(10_000, 11);
+        c.bench_function(&format!("meet policy {}", name), |b| {
+            b.iter(|| {
+                black_box(&pts_a)
+                    .iter()
+                    .zip(black_box(&pts_b))
+                    .map(|(p, q)| meet_with(p, q, &policy))
+                    .collect::<Vec<PgLine>>()
+            })
+        });
+    }
+    run(c, "none", NoNormalization);
+    run(c, "gcd always", GcdAlways);
+    run(c, "gcd threshold 32", GcdThreshold::<32>);
+}
+
+fn bench_compact(c: &mut Criterion) {
+    // a large array, where the halved memory footprint of i32 coordinates matters
+    let pts_a = points(1_000_000, 3);
+    let pts_b = points(1_000_000, 11);
+    let narrow = |pts: &[PgPoint]| -> Vec<PgPoint32> {
+        pts.iter()
+            .map(|pt| PgPoint32::try_from(pt.clone()).unwrap())
+            .collect()
+    };
+    let (pts32_a, pts32_b) = (narrow(&pts_a), narrow(&pts_b));
+    c.bench_function("meet i64 array", |b| {
+        b.iter(|| {
+            black_box(&pts_a)
+                .iter()
+                .zip(black_box(&pts_b))
+                .map(|(p, q)| p.join(q))
+                .collect::<Vec<PgLine>>()
+        })
+    });
+    c.bench_function("meet i32 array", |b| {
+        b.iter(|| {
+            black_box(&pts32_a)
+                .iter()
+                .zip(black_box(&pts32_b))
+                .map(|(p, q)| p.join(q))
+                .collect::<Vec<PgLine32>>()
+        })
+    });
+    c.bench_function("meet_many i64 array", |b| {
+        b.iter(|| meet_many(black_box(&pts_a), black_box(&pts_b)))
+    });
+    c.bench_function("meet_many i32 array", |b| {
+        b.iter(|| meet_many32(black_box(&pts32_a), black_box(&pts32_b)))
+    });
+    let ln = PgLine::new([3, -2, 7]);
+    let ln32 = PgLine32::new([3, -2, 7]);
+    c.bench_function("incident i64 array", |b| {
+        b.iter(|| {
+            black_box(&pts_a)
+                .iter()
+                .filter(|pt| pt.incident(black_box(&ln)))
+                .count()
+        })
+    });
+    c.bench_function("incident i32 array", |b| {
+        b.iter(|| {
+            black_box(&pts32_a)
+                .iter()
+                .filter(|pt| pt.incident(black_box(&ln32)))
+                .count()
+        })
+    });
+    c.bench_function("incident_mask i64 array", |b| {
+        b.iter(|| incident_mask(black_box(&pts_a), black_box(&ln)))
+    });
+    c.bench_function("incident_mask i32 array", |b| {
+        b.iter(|| incident_mask32(black_box(&pts32_a), black_box(&ln32)))
+    });
+}
+
+fn bench_soa(c: &mut Criterion) {
+    let pts = points(1_000_000, 5);
+    let soa = PointSoA::from_points(&pts);
+    let ln = PgLine::new([3, -2, 7]);
+    let pt = PgPoint::new([4, 1, -3]);
+    c.bench_function("incident_mask AoS", |b| {
+        b.iter(|| incident_mask(black_box(&pts), black_box(&ln)))
+    });
+    c.bench_function("incident_mask SoA", |b| {
+        b.iter(|| black_box(&soa).incident_mask(black_box(&ln)))
+    });
+    c.bench_function("meet fixed point AoS", |b| {
+        b.iter(|| {
+            black_box(&pts)
+                .iter()
+                .map(|p| p.join(black_box(&pt)))
+                .collect::<Vec<PgLine>>()
+        })
+    });
+    c.bench_function("meet fixed point SoA", |b| {
+        b.iter(|| black_box(&soa).meet(black_box(&pt)))
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_meet,
+    bench_incident,
+    bench_filtered,
+    bench_harm_conj,
+    bench_normalization,
+    bench_compact,
+    bench_soa
+);
+criterion_main!(benches);
@@ -0,0 +1,375 @@
+// Segments and rays of the affine (Euclidean) plane
+
+use crate::error::GeometryError;
+use crate::pg_object::{EuclidLine, EuclidPoint, PgLine, PgPoint};
+use crate::pg_plane::{Join, ProjectivePlanePrimitive};
+use crate::predicates::{compare_coord, line_position, orientation, Orientation};
+use core::cmp::Ordering;
+
+/// The `Intersection` enum is the intersection of a segment with a segment or a ray.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Intersection {
+    Empty,
+    Point(EuclidPoint),
+    /// Collinear objects overlapping in more than one point.
+    Segment(Segment),
+}
+
+/// Convert to the point type of the predicates.
+#[inline]
+fn to_pg(pt: &EuclidPoint) -> PgPoint {
+    PgPoint::new(pt.coord)
+}
+
+/// The coordinate (0 for x, 1 for y) that orders the points of a line: x unless it is vertical.
+#[inline]
+fn axis_of(ln: &EuclidLine) -> usize {
+    if ln.coord[1] != 0 {
+        0
+    } else {
+        1
+    }
+}
+
+/// Compare two affine points by one of their affine coordinates.
+#[inline]
+fn cmp_along(
+    pt_p: &EuclidPoint,
+    pt_q: &EuclidPoint,
+    axis: usize,
+) -> Result<Ordering, GeometryError> {
+    compare_coord(&to_pg(pt_p), &to_pg(pt_q), axis)
+}
+
+/// The intersection of collinear ranges `[lo, hi]` along an axis.
+fn overlap(lo: EuclidPoint, hi: EuclidPoint, axis: usize) -> Result<Intersection, GeometryError> {
+    Ok(match cmp_along(&lo, &hi, axis)? {
+        Ordering::Greater => Intersection::Empty,
+        Ordering::Equal => Intersection::Point(lo),
+        Ordering::Less => Intersection::Segment(Segment::new(lo, hi)),
+    })
+}
+
+/// The `Segment` struct is the line segment between two distinct affine points.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    pub start: EuclidPoint,
+    pub end: EuclidPoint,
+}
+
+impl Segment {
+    /// Create a segment from its endpoints.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the endpoints coincide.
+    #[inline]
+    pub fn new(start: EuclidPoint, end: EuclidPoint) -> Self {
+        assert!(start != end, "segment endpoints must be distinct");
+        Self { start, end }
+    }
+
+    /// Return the line carrying the segment.
+    #[inline]
+    pub fn line(&self) -> EuclidLine {
+        self.start.join(&self.end)
+    }
+
+    /// Return the midpoint of the segment.
+    #[inline]
+    pub fn midpoint(&self) -> EuclidPoint {
+        self.start.midpoint(&self.end)
+    }
+
+    /// The endpoints ordered along `axis`.
+    fn sorted(&self, axis: usize) -> Result<(EuclidPoint, EuclidPoint), GeometryError> {
+        Ok(match cmp_along(&self.start, &self.end, axis)? {
+            Ordering::Greater => (self.end.clone(), self.start.clone()),
+            _ => (self.start.clone(), self.end.clone()),
+        })
+    }
+
+    /// The function `contains_point` checks if an affine point lies on the segment, including its
+    /// endpoints.
+    ///
+    /// Returns:
+    ///
+    /// The function `contains_point` returns a boolean value, or `GeometryError::PointAtInfinity`
+    /// if the point or an endpoint is an ideal point.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::affine::Segment;
+    /// use projgeom_rs::EuclidPoint;
+    /// let seg = Segment::new(EuclidPoint::new([0, 0, 1]), EuclidPoint::new([4, 2, 1]));
+    /// assert_eq!(seg.contains_point(&EuclidPoint::new([2, 1, 1])), Ok(true));
+    /// assert_eq!(seg.contains_point(&EuclidPoint::new([6, 3, 1])), Ok(false));
+    /// ```
+    pub fn contains_point(&self, pt: &EuclidPoint) -> Result<bool, GeometryError> {
+        if orientation(&to_pg(&self.start), &to_pg(&self.end), &to_pg(pt))?
+            != Orientation::Collinear
+        {
+            return Ok(false);
+        }
+        let axis = axis_of(&self.line());
+        let (lo, hi) = self.sorted(axis)?;
+        Ok(cmp_along(&lo, pt, axis)? != Ordering::Greater
+            && cmp_along(pt, &hi, axis)? != Ordering::Greater)
+    }
+
+    /// The function `intersect_segment` computes the intersection with another segment.
+    ///
+    /// Returns:
+    ///
+    /// The function `intersect_segment` returns the `Intersection`, which is a segment for
+    /// overlapping collinear segments, or `GeometryError::PointAtInfinity` if an endpoint is an
+    /// ideal point.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::affine::{Intersection, Segment};
+    /// use projgeom_rs::EuclidPoint;
+    /// let seg_1 = Segment::new(EuclidPoint::new([0, 0, 1]), EuclidPoint::new([2, 2, 1]));
+    /// let seg_2 = Segment::new(EuclidPoint::new([0, 2, 1]), EuclidPoint::new([2, 0, 1]));
+    /// assert_eq!(
+    ///     seg_1.intersect_segment(&seg_2),
+    ///     Ok(Intersection::Point(EuclidPoint::new([1, 1, 1])))
+    /// );
+    /// ```
+    pub fn intersect_segment(&self, other: &Segment) -> Result<Intersection, GeometryError> {
+        let (ln_l, ln_m) = (self.line(), other.line());
+        if ln_l == ln_m {
+            let axis = axis_of(&ln_l);
+            let ((lo_1, hi_1), (lo_2, hi_2)) = (self.sorted(axis)?, other.sorted(axis)?);
+            let lo = match cmp_along(&lo_1, &lo_2, axis)? {
+                Ordering::Less => lo_2,
+                _ => lo_1,
+            };
+            let hi = match cmp_along(&hi_1, &hi_2, axis)? {
+                Ordering::Greater => hi_2,
+                _ => hi_1,
+            };
+            return overlap(lo, hi, axis);
+        }
+        let pt = ln_l.meet(&ln_m);
+        if pt.coord[2] != 0 && self.contains_point(&pt)? && other.contains_point(&pt)? {
+            Ok(Intersection::Point(pt))
+        } else {
+            Ok(Intersection::Empty)
+        }
+    }
+
+    /// The function `clip` returns the part of the segment on the closed positive side of a line,
+    /// where `a * x + b * y + c >= 0` for the line `[a : b : c]`.
+    ///
+    /// Returns:
+    ///
+    /// The function `clip` returns the clipped segment, `None` if nothing of the segment but at
+    /// most one endpoint remains, or `GeometryError::PointAtInfinity`.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::affine::Segment;
+    /// use projgeom_rs::{EuclidLine, EuclidPoint};
+    /// let seg = Segment::new(EuclidPoint::new([-2, 1, 1]), EuclidPoint::new([4, 1, 1]));
+    /// // x >= 0
+    /// let clipped = seg.clip(&EuclidLine::new([1, 0, 0])).unwrap();
+    /// assert_eq!(clipped, Some(Segment::new(EuclidPoint::new([0, 1, 1]), EuclidPoint::new([4, 1, 1]))));
+    /// ```
+    pub fn clip(&self, ln: &EuclidLine) -> Result<Option<Segment>, GeometryError> {
+        let ln_pg = PgLine::new(ln.coord);
+        let side_1 = line_position(&ln_pg, &to_pg(&self.start))?;
+        let side_2 = line_position(&ln_pg, &to_pg(&self.end))?;
+        let cut = || self.line().meet(ln);
+        Ok(match (side_1, side_2) {
+            (Ordering::Less, Ordering::Greater) => Some(Segment::new(cut(), self.end.clone())),
+            (Ordering::Greater, Ordering::Less) => Some(Segment::new(self.start.clone(), cut())),
+            (Ordering::Less, _) | (_, Ordering::Less) => None,
+            _ => Some(self.clone()),
+        })
+    }
+}
+
+/// The `Ray` struct is the half-line starting at an affine point and passing through another.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ray {
+    pub origin: EuclidPoint,
+    pub through: EuclidPoint,
+}
+
+impl Ray {
+    /// Create a ray from its origin and another point on it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the points coincide.
+    #[inline]
+    pub fn new(origin: EuclidPoint, through: EuclidPoint) -> Self {
+        assert!(origin != through, "ray needs two distinct points");
+        Self { origin, through }
+    }
+
+    /// Return the line carrying the ray.
+    #[inline]
+    pub fn line(&self) -> EuclidLine {
+        self.origin.join(&self.through)
+    }
+
+    /// The function `contains_point` checks if an affine point lies on the ray, including its
+    /// origin.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::affine::Ray;
+    /// use projgeom_rs::EuclidPoint;
+    /// let ray = Ray::new(EuclidPoint::new([0, 0, 1]), EuclidPoint::new([1, 1, 1]));
+    /// assert_eq!(ray.contains_point(&EuclidPoint::new([5, 5, 1])), Ok(true));
+    /// assert_eq!(ray.contains_point(&EuclidPoint::new([-1, -1, 1])), Ok(false));
+    /// ```
+    pub fn contains_point(&self, pt: &EuclidPoint) -> Result<bool, GeometryError> {
+        if orientation(&to_pg(&self.origin), &to_pg(&self.through), &to_pg(pt))?
+            != Orientation::Collinear
+        {
+            return Ok(false);
+        }
+        let axis = axis_of(&self.line());
+        let side = cmp_along(&self.origin, pt, axis)?;
+        Ok(side == Ordering::Equal || side == cmp_along(&self.origin, &self.through, axis)?)
+    }
+
+    /// The function `intersect_segment` computes the intersection with a segment.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::affine::{Intersection, Ray, Segment};
+    /// use projgeom_rs::EuclidPoint;
+    /// let ray = Ray::new(EuclidPoint::new([0, 0, 1]), EuclidPoint::new([1, 0, 1]));
+    /// let seg = Segment::new(EuclidPoint::new([3, -1, 1]), EuclidPoint::new([3, 1, 1]));
+    /// assert_eq!(
+    ///     ray.intersect_segment(&seg),
+    ///     Ok(Intersection::Point(EuclidPoint::new([3, 0, 1])))
+    /// );
+    /// ```
+    pub fn intersect_segment(&self, seg: &Segment) -> Result<Intersection, GeometryError> {
+        let (ln_l, ln_m) = (self.line(), seg.line());
+        if ln_l == ln_m {
+            let axis = axis_of(&ln_l);
+            let (lo, hi) = seg.sorted(axis)?;
+            let forward = cmp_along(&self.origin, &self.through, axis)? == Ordering::Less;
+            return if forward {
+                match cmp_along(&lo, &self.origin, axis)? {
+                    Ordering::Less => overlap(self.origin.clone(), hi, axis),
+                    _ => overlap(lo, hi, axis),
+                }
+            } else {
+                match cmp_along(&hi, &self.origin, axis)? {
+                    Ordering::Greater => overlap(lo, self.origin.clone(), axis),
+                    _ => overlap(lo, hi, axis),
+                }
+            };
+        }
+        let pt = ln_l.meet(&ln_m);
+        if pt.coord[2] != 0 && self.contains_point(&pt)? && seg.contains_point(&pt)? {
+            Ok(Intersection::Point(pt))
+        } else {
+            Ok(Intersection::Empty)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pt(x: i64, y: i64) -> EuclidPoint {
+        EuclidPoint::new([x, y, 1])
+    }
+
+    #[test]
+    fn test_segment() {
+        let seg = Segment::new(pt(0, 0), pt(4, 4));
+        assert_eq!(seg.midpoint(), pt(2, 2));
+        assert_eq!(
+            seg.contains_point(&EuclidPoint::new([-8, -8, -2])),
+            Ok(true)
+        );
+        assert_eq!(seg.contains_point(&pt(5, 5)), Ok(false));
+        assert_eq!(
+            seg.contains_point(&EuclidPoint::new([1, 1, 0])),
+            Err(GeometryError::PointAtInfinity)
+        );
+
+        // touching at an endpoint
+        let seg_t = Segment::new(pt(4, 4), pt(6, 0));
+        assert_eq!(
+            seg.intersect_segment(&seg_t),
+            Ok(Intersection::Point(pt(4, 4)))
+        );
+        // parallel and disjoint
+        let seg_p = Segment::new(pt(0, 1), pt(4, 5));
+        assert_eq!(seg.intersect_segment(&seg_p), Ok(Intersection::Empty));
+        // lines cross outside of the segments
+        let seg_o = Segment::new(pt(5, 0), pt(6, -1));
+        assert_eq!(seg.intersect_segment(&seg_o), Ok(Intersection::Empty));
+        // collinear overlaps
+        let seg_c = Segment::new(pt(6, 6), pt(2, 2));
+        assert_eq!(
+            seg.intersect_segment(&seg_c),
+            Ok(Intersection::Segment(Segment::new(pt(2, 2), pt(4, 4))))
+        );
+        let seg_d = Segment::new(pt(4, 4), pt(7, 7));
+        assert_eq!(
+            seg.intersect_segment(&seg_d),
+            Ok(Intersection::Point(pt(4, 4)))
+        );
+        let seg_e = Segment::new(pt(5, 5), pt(7, 7));
+        assert_eq!(seg.intersect_segment(&seg_e), Ok(Intersection::Empty));
+        // vertical segments are ordered by y
+        let seg_v = Segment::new(pt(1, 3), pt(1, -3));
+        let seg_w = Segment::new(pt(1, 0), pt(1, 9));
+        assert_eq!(
+            seg_v.intersect_segment(&seg_w),
+            Ok(Intersection::Segment(Segment::new(pt(1, 0), pt(1, 3))))
+        );
+    }
+
+    #[test]
+    fn test_clip() {
+        let seg = Segment::new(pt(0, 0), pt(4, 4));
+        // y <= 1, i.e. -y + 1 >= 0
+        let ln = EuclidLine::new([0, -1, 1]);
+        assert_eq!(seg.clip(&ln), Ok(Some(Segment::new(pt(0, 0), pt(1, 1)))));
+        // x >= 5
+        assert_eq!(seg.clip(&EuclidLine::new([1, 0, -5])), Ok(None));
+        // x >= -1
+        assert_eq!(seg.clip(&EuclidLine::new([1, 0, 1])), Ok(Some(seg.clone())));
+    }
+
+    #[test]
+    fn test_ray() {
+        let ray = Ray::new(pt(1, 1), pt(0, 1));
+        assert_eq!(ray.contains_point(&pt(-7, 1)), Ok(true));
+        assert_eq!(ray.contains_point(&pt(2, 1)), Ok(false));
+        assert_eq!(ray.contains_point(&pt(1, 1)), Ok(true));
+        let seg = Segment::new(pt(-2, 0), pt(-2, 5));
+        assert_eq!(
+            ray.intersect_segment(&seg),
+            Ok(Intersection::Point(pt(-2, 1)))
+        );
+        let behind = Segment::new(pt(3, 0), pt(3, 5));
+        assert_eq!(ray.intersect_segment(&behind), Ok(Intersection::Empty));
+        // collinear
+        let seg_c = Segment::new(pt(-3, 1), pt(4, 1));
+        assert_eq!(
+            ray.intersect_segment(&seg_c),
+            Ok(Intersection::Segment(Segment::new(pt(-3, 1), pt(1, 1))))
+        );
+        let seg_d = Segment::new(pt(2, 1), pt(4, 1));
+        assert_eq!(ray.intersect_segment(&seg_d), Ok(Intersection::Empty));
+    }
+}
@@ -0,0 +1,233 @@
+// Approximate projective geometry with f64 coordinates
+
+use crate::pg_object::{define_join, PgLine, PgPoint, RatLine, RatPoint};
+use crate::pg_plane::{ProjectivePlane, ProjectivePlanePrimitive};
+use fractions::Fraction;
+
+/// The relative tolerance of the comparisons in this module.
+pub const EPSILON: f64 = 1e-9;
+
+/// The `ApproxScalar` struct is an `f64` compared with the relative tolerance `EPSILON`, so that
+/// it can serve as the `Value` of `ProjectivePlane`.
+///
+/// Caveat: the comparison is not transitive, so `Eq` holds only approximately. NaN compares
+/// unequal to everything.
+#[derive(Debug, Clone, Copy, Default, PartialOrd)]
+pub struct ApproxScalar(pub f64);
+
+impl PartialEq for ApproxScalar {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        let scale = self.0.abs().max(other.0.abs()).max(1.0);
+        (self.0 - other.0).abs() <= EPSILON * scale
+    }
+}
+
+impl Eq for ApproxScalar {}
+
+#[inline]
+fn dot(v_a: &[f64; 3], v_b: &[f64; 3]) -> f64 {
+    v_a[0] * v_b[0] + v_a[1] * v_b[1] + v_a[2] * v_b[2]
+}
+
+#[inline]
+fn cross(v_a: &[f64; 3], v_b: &[f64; 3]) -> [f64; 3] {
+    [
+        v_a[1] * v_b[2] - v_a[2] * v_b[1],
+        v_a[2] * v_b[0] - v_a[0] * v_b[2],
+        v_a[0] * v_b[1] - v_a[1] * v_b[0],
+    ]
+}
+
+/// Check whether `|v_a · v_b| <= EPSILON |v_a| |v_b|`, comparing squares to avoid square roots.
+#[inline]
+fn nearly_orthogonal(v_a: &[f64; 3], v_b: &[f64; 3]) -> bool {
+    let prod = dot(v_a, v_b);
+    prod * prod <= EPSILON * EPSILON * dot(v_a, v_a) * dot(v_b, v_b)
+}
+
+/// Check whether `|v_a × v_b| <= EPSILON |v_a| |v_b|`, i.e. the vectors are nearly parallel.
+#[inline]
+fn nearly_parallel(v_a: &[f64; 3], v_b: &[f64; 3]) -> bool {
+    let v_c = cross(v_a, v_b);
+    dot(&v_c, &v_c) <= EPSILON * EPSILON * dot(v_a, v_a) * dot(v_b, v_b)
+}
+
+#[inline]
+fn to_f64(x: &Fraction<i64>) -> f64 {
+    *x.numer() as f64 / *x.denom() as f64
+}
+
+macro_rules! define_approx_object {
+    ($(#[$attr:meta])* $point:ident, $line:ident, $pg_point:ident, $rat_point:ident) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy)]
+        pub struct $point {
+            /// Homogeneous coordinate
+            pub coord: [f64; 3],
+        }
+
+        impl $point {
+            #[inline]
+            pub const fn new(coord: [f64; 3]) -> Self {
+                Self { coord }
+            }
+
+            /// Scale the coordinates to unit length, to keep repeated constructions from
+            /// overflowing or underflowing. The zero vector is returned unchanged.
+            #[inline]
+            pub fn normalized(&self) -> Self {
+                let norm = dot(&self.coord, &self.coord).sqrt();
+                if norm == 0.0 {
+                    *self
+                } else {
+                    Self::new(self.coord.map(|x| x / norm))
+                }
+            }
+        }
+
+        /// Equality up to scaling, within the tolerance `EPSILON`. The zero vector equals
+        /// everything, as for the exact types.
+        impl PartialEq for $point {
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                nearly_parallel(&self.coord, &other.coord)
+            }
+        }
+
+        impl Eq for $point {}
+
+        impl From<$pg_point> for $point {
+            #[inline]
+            fn from(obj: $pg_point) -> Self {
+                Self::new(obj.coord.map(|x| x as f64))
+            }
+        }
+
+        impl From<$rat_point> for $point {
+            #[inline]
+            fn from(obj: $rat_point) -> Self {
+                Self::new(obj.coord.each_ref().map(to_f64))
+            }
+        }
+
+        impl ProjectivePlanePrimitive<$line> for $point {
+            /// Incidence within the tolerance `EPSILON`, relative to the lengths of the coordinate
+            /// vectors.
+            #[inline]
+            fn incident(&self, rhs: &$line) -> bool {
+                nearly_orthogonal(&self.coord, &rhs.coord)
+            }
+
+            #[inline]
+            fn meet(&self, rhs: &Self) -> $line {
+                $line::new(cross(&self.coord, &rhs.coord))
+            }
+        }
+
+        impl ProjectivePlane<$line, ApproxScalar> for $point {
+            #[inline]
+            fn aux(&self) -> $line {
+                $line::new(self.coord)
+            }
+
+            #[inline]
+            fn dot(&self, line: &$line) -> ApproxScalar {
+                ApproxScalar(dot(&self.coord, &line.coord))
+            }
+
+            #[inline]
+            fn parametrize(&self, lambda: ApproxScalar, other: &Self, mu: ApproxScalar) -> Self {
+                Self::new([0, 1, 2].map(|i| lambda.0 * self.coord[i] + mu.0 * other.coord[i]))
+            }
+        }
+    };
+}
+
+define_approx_object!(
+    /// The `ApproxPoint` struct is a point of the projective plane with `f64` coordinates, for
+    /// fast approximate geometry, e.g. for visualization or sampling.
+    ///
+    /// Caveats: incidence and equality are tested with the relative tolerance `EPSILON`, so they
+    /// are not transitive, and nearly degenerate configurations (e.g. the meet of nearly parallel
+    /// lines) lose precision. Use the exact types where the answer matters.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::approx::{ApproxLine, ApproxPoint};
+    /// use projgeom_rs::{harm_conj, Join, PgPoint, ProjectivePlanePrimitive};
+    /// let pt_p = ApproxPoint::new([0.1, 0.2, 1.0]);
+    /// let pt_q = ApproxPoint::from(PgPoint::new([3, 1, 10]));
+    /// let ln_l = pt_p.join(&pt_q);
+    /// assert!(ln_l.incident(&ApproxPoint::new([0.5, 0.0, 1.0])));
+    /// // the harmonic conjugate of the midpoint is at infinity
+    /// let pt_r = ApproxPoint::new([0.2, 0.15, 1.0]);
+    /// let pt_s = harm_conj(&pt_p, &pt_q, &pt_r);
+    /// assert_eq!(pt_s.to_affine(), None);
+    /// assert_eq!(harm_conj(&pt_p, &pt_q, &pt_s), pt_r);
+    /// ```
+    ApproxPoint, ApproxLine, PgPoint, RatPoint
+);
+
+define_approx_object!(
+    /// The `ApproxLine` struct is a line of the projective plane with `f64` coordinates; see
+    /// `ApproxPoint` for the caveats.
+    ApproxLine, ApproxPoint, PgLine, RatLine
+);
+define_join!(impl [] ApproxPoint [], ApproxLine []);
+
+impl ApproxPoint {
+    /// Return the affine coordinates `(x/z, y/z)`, or `None` for a point at infinity within the
+    /// tolerance `EPSILON`.
+    pub fn to_affine(&self) -> Option<(f64, f64)> {
+        let [x, y, z] = self.coord;
+        if z * z <= EPSILON * EPSILON * dot(&self.coord, &self.coord) {
+            None
+        } else {
+            Some((x / z, y / z))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pg_plane::{check_axiom, check_pappus, coincident, harm_conj};
+
+    #[test]
+    fn test_approx() {
+        let pt_p = ApproxPoint::new([1.0, 3.0, 2.0]);
+        let pt_q = ApproxPoint::new([-2.0, 1.0, -1.0]);
+        let ln_l = ApproxLine::new([0.5, -0.25, 1.0]);
+        check_axiom(&pt_p, &pt_q, &ln_l);
+
+        let co1 = [[0.0, 0.0, 1.0], [0.5, 0.0, 1.0], [1.5, 0.0, 1.0]].map(ApproxPoint::new);
+        let co2 = [[0.1, 1.0, 1.0], [0.3, 2.0, 1.0], [0.7, 4.0, 1.0]].map(ApproxPoint::new);
+        assert!(check_pappus(&co1, &co2));
+        assert!(!coincident(
+            &co2[0],
+            &co2[1],
+            &ApproxPoint::new([0.7, 4.1, 1.0])
+        ));
+
+        // conversions from the exact types agree with the exact constructions
+        let [pt_a, pt_b, pt_c] = [[1, 0, 1], [5, 0, 1], [2, 0, 1]].map(PgPoint::new);
+        let exact = harm_conj(&pt_a, &pt_b, &pt_c);
+        let approx = harm_conj(
+            &ApproxPoint::from(pt_a),
+            &ApproxPoint::from(pt_b),
+            &ApproxPoint::from(pt_c),
+        );
+        assert_eq!(approx, ApproxPoint::from(exact.clone()));
+        assert_eq!(approx.to_affine(), Some((-1.0, 0.0)));
+        assert_eq!(ApproxPoint::new([1.0, 2.0, 1e-12]).to_affine(), None);
+        let rat = RatPoint::new([Fraction::new(1, 3), Fraction::new(-2, 3), Fraction::from(1)]);
+        assert_eq!(
+            ApproxPoint::from(rat),
+            ApproxPoint::new([1.0, -2.0, 3.0]).normalized()
+        );
+        assert_eq!(ApproxScalar(1.0), ApproxScalar(1.0 + 1e-12));
+        assert_ne!(ApproxScalar(1.0), ApproxScalar(1.0 + 1e-6));
+    }
+}
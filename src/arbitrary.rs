@@ -0,0 +1,478 @@
+// Random configurations for property-based testing with quickcheck
+
+use crate::ck_plane::CayleyKleinPlanePrimitive;
+use crate::conic::Conic;
+use crate::pg_object::{in_general_position, normalize_homogeneous, Coord3, PgLine, PgPoint};
+use crate::pg_plane::{coincident, ProjectivePlane};
+use crate::transform::Transform;
+use core::marker::PhantomData;
+use quickcheck::{Arbitrary, Gen};
+
+/// Bound on the absolute value of generated coordinates. Theorem checks such as
+/// `check_desargue` multiply coordinates up to twelve times, and this bound keeps the
+/// result well inside `i64`.
+pub const COORD_BOUND: i64 = 10;
+
+/// Draw an integer from `-bound..=bound`.
+#[inline]
+pub(crate) fn small(g: &mut Gen, bound: i64) -> i64 {
+    i64::arbitrary(g).rem_euclid(2 * bound + 1) - bound
+}
+
+/// Draw a nonzero coordinate vector with entries bounded by `COORD_BOUND`.
+fn small_coord(g: &mut Gen) -> [i64; 3] {
+    loop {
+        let coord = [(); 3].map(|_| small(g, COORD_BOUND));
+        if coord != [0, 0, 0] {
+            return coord;
+        }
+    }
+}
+
+/// The sum of the absolute values of all coordinates, which every shrink step decreases, so that
+/// shrinking terminates.
+fn size(points: &[PgPoint]) -> u64 {
+    points
+        .iter()
+        .flat_map(|pt| pt.coord)
+        .map(i64::unsigned_abs)
+        .sum()
+}
+
+/// Smaller nonzero coordinate vectors: the reduced vector, then each entry shrunk towards zero.
+fn shrink_coord(coord: &[i64; 3]) -> Vec<[i64; 3]> {
+    let mut result = vec![normalize_homogeneous(coord)];
+    for i in 0..3 {
+        for value in coord[i].shrink() {
+            let mut smaller = *coord;
+            smaller[i] = value;
+            result.push(smaller);
+        }
+    }
+    let bound: u64 = coord.iter().map(|x| x.unsigned_abs()).sum();
+    result.retain(|c| *c != [0, 0, 0] && c.iter().map(|x| x.unsigned_abs()).sum::<u64>() < bound);
+    result
+}
+
+/// Shrink a configuration by shrinking one point at a time, keeping the smaller candidates that
+/// satisfy the invariant.
+fn shrink_points<const N: usize>(
+    points: &[PgPoint; N],
+    valid: impl Fn(&[PgPoint; N]) -> bool,
+) -> Vec<[PgPoint; N]> {
+    let mut result = Vec::new();
+    for i in 0..N {
+        for coord in shrink_coord(&points[i].coord) {
+            let mut smaller = points.clone();
+            smaller[i] = PgPoint::new(coord);
+            if valid(&smaller) {
+                result.push(smaller);
+            }
+        }
+    }
+    result
+}
+
+/// Points shrink towards small coordinates, never to the zero vector.
+impl Arbitrary for PgPoint {
+    fn arbitrary(g: &mut Gen) -> Self {
+        PgPoint::new(small_coord(g))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        Box::new(shrink_coord(&self.coord).into_iter().map(PgPoint::new))
+    }
+}
+
+/// Lines shrink like points.
+impl Arbitrary for PgLine {
+    fn arbitrary(g: &mut Gen) -> Self {
+        PgLine::new(small_coord(g))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        Box::new(shrink_coord(&self.coord).into_iter().map(PgLine::new))
+    }
+}
+
+#[inline]
+fn is_triangle([pt_a, pt_b, pt_c]: &[PgPoint; 3]) -> bool {
+    !coincident(pt_a, pt_b, pt_c)
+}
+
+#[inline]
+fn is_collinear_triple([pt_p, pt_q, pt_r]: &[PgPoint; 3]) -> bool {
+    pt_p != pt_q && pt_q != pt_r && pt_r != pt_p && coincident(pt_p, pt_q, pt_r)
+}
+
+/// Three points that are not collinear. Shrinking keeps the triangle non-degenerate.
+#[derive(Debug, Clone)]
+pub struct Triangle(pub [PgPoint; 3]);
+
+impl Arbitrary for Triangle {
+    fn arbitrary(g: &mut Gen) -> Self {
+        loop {
+            let triangle = [(); 3].map(|_| PgPoint::arbitrary(g));
+            if is_triangle(&triangle) {
+                return Triangle(triangle);
+            }
+        }
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        Box::new(
+            shrink_points(&self.0, is_triangle)
+                .into_iter()
+                .map(Triangle),
+        )
+    }
+}
+
+/// Three distinct points on a common line. The third point is a small combination of the
+/// first two, so its coordinates may exceed `COORD_BOUND` by a small factor.
+#[derive(Debug, Clone)]
+pub struct CollinearTriple(pub [PgPoint; 3]);
+
+impl Arbitrary for CollinearTriple {
+    fn arbitrary(g: &mut Gen) -> Self {
+        loop {
+            let pt_p = PgPoint::arbitrary(g);
+            let pt_q = PgPoint::arbitrary(g);
+            let (lambda, mu) = (small(g, 3), small(g, 3));
+            if pt_p == pt_q || lambda == 0 || mu == 0 {
+                continue;
+            }
+            let pt_r = pt_p.parametrize(lambda, &pt_q, mu);
+            return CollinearTriple([pt_p, pt_q, pt_r]);
+        }
+    }
+
+    /// Shrinking keeps the points distinct and collinear. Besides shrinking single points, the
+    /// third point is replaced by the sum or difference of the first two, which a shrunk first
+    /// or second point is combined with as well.
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let [pt_p, pt_q, _] = &self.0;
+        let mut result = Vec::new();
+        let mut pairs = vec![(pt_p.clone(), pt_q.clone())];
+        pairs.extend(pt_p.shrink().map(|pt| (pt, pt_q.clone())));
+        pairs.extend(pt_q.shrink().map(|pt| (pt_p.clone(), pt)));
+        for (pt_a, pt_b) in pairs {
+            for mu in [1, -1] {
+                let pt_c = pt_a.parametrize(1, &pt_b, mu);
+                result.push([pt_a.clone(), pt_b.clone(), pt_c]);
+            }
+        }
+        result.extend(shrink_points(&self.0, is_collinear_triple));
+        let bound = size(&self.0);
+        result.retain(|triple| size(triple) < bound && is_collinear_triple(triple));
+        Box::new(result.into_iter().map(CollinearTriple))
+    }
+}
+
+/// Four points, no three of which are collinear.
+#[derive(Debug, Clone)]
+pub struct GeneralQuadrangle(pub [PgPoint; 4]);
+
+impl Arbitrary for GeneralQuadrangle {
+    fn arbitrary(g: &mut Gen) -> Self {
+        loop {
+            let quadrangle = [(); 4].map(|_| PgPoint::arbitrary(g));
+            if in_general_position(&quadrangle) {
+                return GeneralQuadrangle(quadrangle);
+            }
+        }
+    }
+
+    /// Shrinking keeps the four points in general position.
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let smaller = shrink_points(&self.0, in_general_position);
+        Box::new(smaller.into_iter().map(GeneralQuadrangle))
+    }
+}
+
+/// Smaller matrices: each entry shrunk towards zero, together with its mirror image if
+/// `symmetric`, keeping those that satisfy the invariant.
+fn shrink_matrix(
+    mat: &[[i64; 3]; 3],
+    symmetric: bool,
+    valid: impl Fn(&[[i64; 3]; 3]) -> bool,
+) -> Vec<[[i64; 3]; 3]> {
+    let mut result = Vec::new();
+    for i in 0..3 {
+        for j in 0..3 {
+            if symmetric && j < i {
+                continue;
+            }
+            for value in mat[i][j].shrink() {
+                let mut smaller = *mat;
+                smaller[i][j] = value;
+                if symmetric {
+                    smaller[j][i] = value;
+                }
+                if valid(&smaller) {
+                    result.push(smaller);
+                }
+            }
+        }
+    }
+    result
+}
+
+#[inline]
+fn is_nondegenerate(mat: &[[i64; 3]; 3]) -> bool {
+    !Conic::new(*mat).is_degenerate()
+}
+
+/// Nondegenerate conics: random symmetric matrices with entries bounded by `COORD_BOUND` and a
+/// nonzero determinant. Shrinking keeps the matrix symmetric and nonsingular.
+impl Arbitrary for Conic {
+    fn arbitrary(g: &mut Gen) -> Self {
+        loop {
+            let [a, b, c, d, e, f] = [(); 6].map(|_| small(g, COORD_BOUND));
+            let mat = [[a, b, d], [b, c, e], [d, e, f]];
+            if is_nondegenerate(&mat) {
+                return Conic::new(mat);
+            }
+        }
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let smaller = shrink_matrix(&self.mat, true, is_nondegenerate);
+        Box::new(smaller.into_iter().map(Conic::new))
+    }
+}
+
+/// Invertible transforms: random matrices with entries bounded by `COORD_BOUND` and a nonzero
+/// determinant. Shrinking keeps the matrix nonsingular.
+impl Arbitrary for Transform {
+    fn arbitrary(g: &mut Gen) -> Self {
+        loop {
+            let mat = [(); 3].map(|_| [(); 3].map(|_| small(g, COORD_BOUND)));
+            if let Ok(trans) = Transform::new(mat) {
+                return trans;
+            }
+        }
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let smaller = shrink_matrix(&self.mat, false, |mat| Transform::new(*mat).is_ok());
+        Box::new(
+            smaller
+                .into_iter()
+                .filter_map(|mat| Transform::new(mat).ok()),
+        )
+    }
+}
+
+/// A triangle of a Cayley-Klein plane whose measurements are defined: no vertex lies on its
+/// own polar and no side passes through its own pole, i.e. neither touches the absolute, and
+/// no vertex is the pole of its opposite side, so that the altitudes and the orthocenter exist.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::arbitrary::CKTriangle;
+/// use projgeom_rs::{orthocenter, tri_altitude, HyperbolicLine, HyperbolicPoint};
+/// use projgeom_rs::ProjectivePlanePrimitive;
+/// use quickcheck::{Arbitrary, Gen};
+/// let mut g = Gen::new(10);
+/// let triangle = CKTriangle::<HyperbolicPoint, HyperbolicLine>::arbitrary(&mut g).vertices;
+/// let [_, _, t_3] = tri_altitude(&triangle);
+/// assert!(orthocenter(&triangle).incident(&t_3));
+/// ```
+#[derive(Debug, Clone)]
+pub struct CKTriangle<Point, Line> {
+    /// The vertices
+    pub vertices: [Point; 3],
+    dual: PhantomData<fn() -> Line>,
+}
+
+impl<Point, Line> CKTriangle<Point, Line>
+where
+    Point: CayleyKleinPlanePrimitive<Line>,
+    Line: CayleyKleinPlanePrimitive<Point>,
+{
+    /// Check if the triangle is nondegenerate and has measurements defined.
+    fn is_valid(vertices: &[Point; 3]) -> bool {
+        let [a_1, a_2, a_3] = vertices;
+        if coincident(a_1, a_2, a_3) {
+            return false;
+        }
+        [(a_1, a_2, a_3), (a_2, a_3, a_1), (a_3, a_1, a_2)]
+            .into_iter()
+            .all(|(vertex, pt_p, pt_q)| {
+                let side = pt_p.meet(pt_q);
+                !vertex.incident(&vertex.perp())
+                    && !side.incident(&side.perp())
+                    && *vertex != side.perp()
+            })
+    }
+}
+
+impl<Point, Line> Arbitrary for CKTriangle<Point, Line>
+where
+    Point: CayleyKleinPlanePrimitive<Line> + Coord3 + Clone + Send + 'static,
+    Line: CayleyKleinPlanePrimitive<Point> + Clone + 'static,
+{
+    fn arbitrary(g: &mut Gen) -> Self {
+        loop {
+            let vertices = [(); 3].map(|_| Point::from_coord(small_coord(g)));
+            if Self::is_valid(&vertices) {
+                return CKTriangle {
+                    vertices,
+                    dual: PhantomData,
+                };
+            }
+        }
+    }
+
+    /// Shrinking keeps the measurements defined.
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let points = self.vertices.clone().map(|pt| PgPoint::new(*pt.coords()));
+        let smaller = shrink_points(&points, |points| {
+            Self::is_valid(&points.clone().map(|pt| Point::from_coord(pt.coord)))
+        });
+        Box::new(smaller.into_iter().map(|points| CKTriangle {
+            vertices: points.map(|pt| Point::from_coord(pt.coord)),
+            dual: PhantomData,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ck_plane::{orthocenter, tri_altitude};
+    use crate::pg_object::check_desargues_involution;
+    use crate::pg_object::{EllipticLine, EllipticPoint, EuclidLine, EuclidPoint};
+    use crate::pg_object::{HyperbolicLine, HyperbolicPoint};
+    use crate::pg_plane::{
+        check_axiom, check_desargue, check_pappus, harm_conj, involution, ProjectivePlanePrimitive,
+    };
+    use quickcheck_macros::quickcheck;
+
+    /// Shrink greedily, as quickcheck does for a property that always fails.
+    fn shrink_fully<T: Arbitrary>(mut value: T) -> T {
+        while let Some(smaller) = value.shrink().next() {
+            value = smaller;
+        }
+        value
+    }
+
+    #[test]
+    fn test_shrink() {
+        let max_coord =
+            |points: &[PgPoint]| points.iter().flat_map(|pt| pt.coord).map(i64::abs).max();
+
+        let pt = shrink_fully(PgPoint::new([-9, 6, 0]));
+        assert_eq!(pt.coord, [0, 1, 0]);
+        let triangle = shrink_fully(Triangle(
+            [[7, -3, 10], [4, 4, 9], [-8, 2, 5]].map(PgPoint::new),
+        ));
+        assert!(is_triangle(&triangle.0));
+        assert_eq!(max_coord(&triangle.0), Some(1));
+
+        let [pt_p, pt_q] = [[9, -4, 7], [-6, 10, 3]].map(PgPoint::new);
+        let pt_r = pt_p.parametrize(3, &pt_q, -2);
+        let triple = shrink_fully(CollinearTriple([pt_p, pt_q, pt_r]));
+        assert!(is_collinear_triple(&triple.0));
+        assert!(max_coord(&triple.0) <= Some(2));
+
+        let quadrangle = [[10, 3, -7], [-5, 9, 2], [6, -8, 4], [1, 7, 9]].map(PgPoint::new);
+        let quadrangle = shrink_fully(GeneralQuadrangle(quadrangle));
+        assert!(in_general_position(&quadrangle.0));
+        assert!(max_coord(&quadrangle.0) <= Some(2));
+    }
+
+    #[quickcheck]
+    fn prop_shrink_preserves_invariants(triangle: Triangle, triple: CollinearTriple) -> bool {
+        triangle.shrink().all(|smaller| is_triangle(&smaller.0))
+            && triple
+                .shrink()
+                .all(|smaller| is_collinear_triple(&smaller.0))
+    }
+
+    #[quickcheck]
+    fn prop_conic_polarity(conic: Conic, pt_p: PgPoint) -> bool {
+        // the polarity of a nondegenerate conic is a bijection
+        conic.pole(&conic.polar(&pt_p)) == pt_p
+            && conic.shrink().all(|smaller| !smaller.is_degenerate())
+    }
+
+    #[quickcheck]
+    fn prop_transform(trans: Transform, conic: Conic, pt_p: PgPoint) -> bool {
+        let inverse = trans.inverse().unwrap();
+        let image = trans.apply_conic(&conic);
+        inverse.apply(&trans.apply(&pt_p)) == pt_p
+            && image.contains(&trans.apply(&pt_p)) == conic.contains(&pt_p)
+            && trans.shrink().all(|smaller| smaller.inverse().is_ok())
+    }
+
+    fn check_ck_triangle<Point, Line>(triangle: &CKTriangle<Point, Line>) -> bool
+    where
+        Point: CayleyKleinPlanePrimitive<Line> + Clone,
+        Line: CayleyKleinPlanePrimitive<Point>,
+    {
+        let [t_1, t_2, t_3] = tri_altitude(&triangle.vertices);
+        let pt_o = orthocenter(&triangle.vertices);
+        pt_o.incident(&t_1) && pt_o.incident(&t_2) && pt_o.incident(&t_3)
+    }
+
+    #[quickcheck]
+    fn prop_ck_triangle(
+        hyp: CKTriangle<HyperbolicPoint, HyperbolicLine>,
+        ell: CKTriangle<EllipticPoint, EllipticLine>,
+        euclid: CKTriangle<EuclidPoint, EuclidLine>,
+    ) -> bool {
+        check_ck_triangle(&hyp)
+            && check_ck_triangle(&ell)
+            && check_ck_triangle(&euclid)
+            && hyp
+                .shrink()
+                .all(|smaller| CKTriangle::is_valid(&smaller.vertices))
+    }
+
+    #[quickcheck]
+    fn prop_axiom(pt_p: PgPoint, pt_q: PgPoint, ln_l: PgLine) -> bool {
+        check_axiom(&pt_p, &pt_q, &ln_l);
+        true
+    }
+
+    #[quickcheck]
+    fn prop_collinear_triple(triple: CollinearTriple) -> bool {
+        let [pt_p, pt_q, pt_r] = &triple.0;
+        coincident(pt_p, pt_q, pt_r)
+    }
+
+    #[quickcheck]
+    fn prop_harm_conj_fast_path(triple: CollinearTriple) -> bool {
+        let [pt_a, pt_b, pt_c] = &triple.0;
+        pt_a.harm_conj(pt_b, pt_c) == harm_conj(pt_a, pt_b, pt_c)
+    }
+
+    #[quickcheck]
+    fn prop_involution_fast_path(origin: PgPoint, mirror: PgLine, pt_p: PgPoint) -> bool {
+        // the harmonic homology is only defined for a center off the axis, and other points
+        pt_p == origin
+            || origin.incident(&mirror)
+            || origin.involution(&mirror, &pt_p) == involution(&origin, &mirror, &pt_p)
+    }
+
+    #[quickcheck]
+    fn prop_pappus(coline_1: CollinearTriple, coline_2: CollinearTriple) -> bool {
+        check_pappus(&coline_1.0, &coline_2.0)
+    }
+
+    #[quickcheck]
+    fn prop_desargue(tri1: Triangle, tri2: Triangle) -> bool {
+        check_desargue(&tri1.0, &tri2.0)
+    }
+
+    #[quickcheck]
+    fn prop_desargues_involution(quadrangle: GeneralQuadrangle, ln_l: PgLine) -> bool {
+        let [pt_a, pt_b, pt_c, pt_d] = &quadrangle.0;
+        // the involution is only defined on lines avoiding the vertices
+        [pt_a, pt_b, pt_c, pt_d].iter().any(|pt| pt.incident(&ln_l))
+            || check_desargues_involution(&quadrangle.0, &ln_l)
+    }
+}
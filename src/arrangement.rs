@@ -0,0 +1,450 @@
+// Arrangements of lines and their incidence structure
+
+use crate::duality::Dualize;
+use crate::pg_object::{PgLine, PgPoint};
+use crate::pg_plane::ProjectivePlanePrimitive;
+use std::collections::HashMap;
+
+/// The `Arrangement` struct is the incidence structure of a finite set of lines: the points in
+/// which at least two of the lines meet, and which lines pass through each point.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Arrangement {
+    /// The lines, as given
+    pub lines: Vec<PgLine>,
+    /// The intersection points, in order of their first appearance
+    pub points: Vec<PgPoint>,
+    /// `incidences[i]` lists the indices of the lines through `points[i]`, in increasing order
+    pub incidences: Vec<Vec<usize>>,
+}
+
+impl Arrangement {
+    /// The function `new` computes the arrangement of a set of lines. Every pair of distinct lines
+    /// contributes its meet; coincident meets are grouped into one point. Repeated lines do not
+    /// meet each other, but both pass through the points of the line.
+    ///
+    /// Arguments:
+    ///
+    /// * `lines`: The lines.
+    ///
+    /// Returns:
+    ///
+    /// The function `new` returns the `Arrangement`.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::arrangement::Arrangement;
+    /// use projgeom_rs::PgLine;
+    /// // the three lines x = 0, y = 0 and x = y through the origin, and the line at infinity
+    /// let lines = [[1, 0, 0], [0, 1, 0], [1, -1, 0], [0, 0, 1]].map(PgLine::new);
+    /// let arr = Arrangement::new(&lines);
+    /// assert_eq!(arr.points.len(), 4);
+    /// assert_eq!(arr.incidences[0], vec![0, 1, 2]);
+    /// assert_eq!(arr.multiplicity_counts(), vec![0, 0, 3, 1]);
+    /// ```
+    pub fn new(lines: &[PgLine]) -> Self {
+        let mut index: HashMap<PgPoint, usize> = HashMap::new();
+        let mut points = Vec::new();
+        let mut incidences: Vec<Vec<usize>> = Vec::new();
+        for (i, ln_l) in lines.iter().enumerate() {
+            for (j, ln_m) in lines.iter().enumerate().skip(i + 1) {
+                let pt = ln_l.meet(ln_m);
+                if pt.coord == [0, 0, 0] {
+                    continue;
+                }
+                let k = *index.entry(pt.clone()).or_insert_with(|| {
+                    points.push(pt);
+                    incidences.push(Vec::new());
+                    points.len() - 1
+                });
+                incidences[k].extend([i, j]);
+            }
+        }
+        for lines_through in incidences.iter_mut() {
+            lines_through.sort_unstable();
+            lines_through.dedup();
+        }
+        Self {
+            lines: lines.to_vec(),
+            points,
+            incidences,
+        }
+    }
+
+    /// Return the number of lines through the `i`-th point.
+    #[inline]
+    pub fn multiplicity(&self, i: usize) -> usize {
+        self.incidences[i].len()
+    }
+
+    /// Return the indices of the points on the `j`-th line, in increasing order.
+    pub fn points_on(&self, j: usize) -> Vec<usize> {
+        (0..self.points.len())
+            .filter(|&i| self.incidences[i].binary_search(&j).is_ok())
+            .collect()
+    }
+
+    /// The function `multiplicity_counts` returns the numbers of points by multiplicity: the
+    /// `m`-th entry is the number of points on exactly `m` lines.
+    pub fn multiplicity_counts(&self) -> Vec<usize> {
+        let max = self.incidences.iter().map(Vec::len).max().unwrap_or(1);
+        let mut counts = vec![0; max + 1];
+        for lines_through in &self.incidences {
+            counts[lines_through.len()] += 1;
+        }
+        counts
+    }
+}
+
+/// The `PointArrangement` struct is the dual of an `Arrangement`: a finite set of points, the
+/// lines through at least two of them, and which points lie on each line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PointArrangement {
+    /// The points, as given
+    pub points: Vec<PgPoint>,
+    /// The connecting lines, in order of their first appearance
+    pub lines: Vec<PgLine>,
+    /// `incidences[j]` lists the indices of the points on `lines[j]`, in increasing order
+    pub incidences: Vec<Vec<usize>>,
+}
+
+impl PointArrangement {
+    /// The function `new` computes the lines connecting a set of points, as the dual of the
+    /// arrangement of the dual lines.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::arrangement::PointArrangement;
+    /// use projgeom_rs::PgPoint;
+    /// // the vertices and the center of a square
+    /// let points = [[0, 0, 1], [2, 0, 1], [2, 2, 1], [0, 2, 1], [1, 1, 1]].map(PgPoint::new);
+    /// let arr = PointArrangement::new(&points);
+    /// // four sides and two diagonals, the diagonals through three points
+    /// assert_eq!(arr.lines.len(), 6);
+    /// assert_eq!(arr.incidences.iter().filter(|on| on.len() == 3).count(), 2);
+    /// ```
+    pub fn new(points: &[PgPoint]) -> Self {
+        Arrangement::new(&points.to_vec().dualize()).dualize()
+    }
+}
+
+impl Dualize for Arrangement {
+    type Dual = PointArrangement;
+
+    fn dualize(&self) -> PointArrangement {
+        PointArrangement {
+            points: self.lines.dualize(),
+            lines: self.points.dualize(),
+            incidences: self.incidences.clone(),
+        }
+    }
+}
+
+impl Dualize for PointArrangement {
+    type Dual = Arrangement;
+
+    fn dualize(&self) -> Arrangement {
+        Arrangement {
+            lines: self.points.dualize(),
+            points: self.lines.dualize(),
+            incidences: self.incidences.clone(),
+        }
+    }
+}
+
+/// The function `incidence_matrix` returns the 0/1 incidence matrix of points and lines: the
+/// entry in row `i` and column `j` is 1 if the `i`-th point lies on the `j`-th line.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::arrangement::incidence_matrix;
+/// use projgeom_rs::{PgLine, PgPoint};
+/// let points = [[0, 0, 1], [1, 0, 1]].map(PgPoint::new);
+/// let lines = [[0, 1, 0], [1, 0, 0], [1, 0, -1]].map(PgLine::new);
+/// assert_eq!(incidence_matrix(&points, &lines), vec![vec![1, 1, 0], vec![1, 0, 1]]);
+/// ```
+pub fn incidence_matrix(points: &[PgPoint], lines: &[PgLine]) -> Vec<Vec<u8>> {
+    points
+        .iter()
+        .map(|pt| lines.iter().map(|ln| u8::from(pt.incident(ln))).collect())
+        .collect()
+}
+
+/// The `ConfigurationIsomorphism` struct is a labeled isomorphism from an abstract configuration
+/// to concrete points and lines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigurationIsomorphism {
+    /// `points[a]` is the index of the concrete point of the abstract point `a`
+    pub points: Vec<usize>,
+    /// `lines[b]` is the index of the concrete line of the abstract line (block) `b`
+    pub lines: Vec<usize>,
+}
+
+/// Incidence data of points and lines for the isomorphism search.
+struct Incidences {
+    /// `on[b]` lists the points of line `b`, in increasing order
+    on: Vec<Vec<usize>>,
+    /// `degree[a]` is the number of lines through point `a`
+    degree: Vec<usize>,
+    /// `joined[a][c]` tells if the points `a` and `c` share a line
+    joined: Vec<Vec<bool>>,
+}
+
+impl Incidences {
+    fn new(num_points: usize, on: Vec<Vec<usize>>) -> Self {
+        let mut degree = vec![0; num_points];
+        let mut joined = vec![vec![false; num_points]; num_points];
+        for block in &on {
+            for &a in block {
+                degree[a] += 1;
+                for &c in block {
+                    joined[a][c] = true;
+                }
+            }
+        }
+        Self { on, degree, joined }
+    }
+}
+
+/// Extend the partial point map `assigned` by backtracking.
+fn search_points(
+    abstract_inc: &Incidences,
+    concrete: &Incidences,
+    assigned: &mut Vec<usize>,
+    used: &mut [bool],
+) -> bool {
+    let a = assigned.len();
+    if a == abstract_inc.degree.len() {
+        return match_lines(abstract_inc, concrete, assigned).is_some();
+    }
+    for p in 0..used.len() {
+        if used[p]
+            || concrete.degree[p] != abstract_inc.degree[a]
+            || (0..a).any(|c| abstract_inc.joined[a][c] != concrete.joined[p][assigned[c]])
+        {
+            continue;
+        }
+        assigned.push(p);
+        used[p] = true;
+        if search_points(abstract_inc, concrete, assigned, used) {
+            return true;
+        }
+        assigned.pop();
+        used[p] = false;
+    }
+    false
+}
+
+/// Match the abstract lines to concrete lines with the same points under a complete point map.
+fn match_lines(
+    abstract_inc: &Incidences,
+    concrete: &Incidences,
+    point_map: &[usize],
+) -> Option<Vec<usize>> {
+    let mut used = vec![false; concrete.on.len()];
+    let mut line_map = Vec::with_capacity(abstract_inc.on.len());
+    for block in &abstract_inc.on {
+        let mut image: Vec<usize> = block.iter().map(|&a| point_map[a]).collect();
+        image.sort_unstable();
+        let j = (0..concrete.on.len()).find(|&j| !used[j] && concrete.on[j] == image)?;
+        used[j] = true;
+        line_map.push(j);
+    }
+    Some(line_map)
+}
+
+/// The function `realize_configuration` checks whether points and lines realize a prescribed
+/// abstract configuration, such as the Pappus configuration `(9_3)` or the Desargues
+/// configuration `(10_3)`, and finds a labeled isomorphism.
+///
+/// The search backtracks over the assignments of the points, pruned by the number of lines
+/// through each point and by which pairs of points are joined by a line.
+///
+/// Arguments:
+///
+/// * `points`: The concrete points.
+/// * `lines`: The concrete lines.
+/// * `blocks`: The abstract configuration on the points `0..points.len()`: `blocks[b]` lists the
+///   points of the abstract line `b`.
+///
+/// Returns:
+///
+/// The function `realize_configuration` returns the isomorphism, or `None` if the incidence
+/// structure of the points and lines differs from the abstract one.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::arrangement::realize_configuration;
+/// use projgeom_rs::{Join, PgPoint};
+/// // a triangle with its three sides
+/// let points = [[0, 0, 1], [1, 0, 1], [0, 1, 1]].map(PgPoint::new);
+/// let lines = [points[1].join(&points[2]), points[0].join(&points[1]), points[0].join(&points[2])];
+/// let blocks = [vec![0, 1], vec![1, 2], vec![2, 0]];
+/// let iso = realize_configuration(&points, &lines, &blocks).unwrap();
+/// assert_eq!(iso.lines.len(), 3);
+/// ```
+pub fn realize_configuration(
+    points: &[PgPoint],
+    lines: &[PgLine],
+    blocks: &[Vec<usize>],
+) -> Option<ConfigurationIsomorphism> {
+    if lines.len() != blocks.len() {
+        return None;
+    }
+    let num_points = points.len();
+    if blocks.iter().flatten().any(|&a| a >= num_points) {
+        return None;
+    }
+    let concrete = Incidences::new(
+        num_points,
+        lines
+            .iter()
+            .map(|ln| {
+                (0..num_points)
+                    .filter(|&i| points[i].incident(ln))
+                    .collect()
+            })
+            .collect(),
+    );
+    let abstract_inc = Incidences::new(
+        num_points,
+        blocks
+            .iter()
+            .map(|block| {
+                let mut block = block.clone();
+                block.sort_unstable();
+                block.dedup();
+                block
+            })
+            .collect(),
+    );
+    let mut assigned = Vec::with_capacity(num_points);
+    let mut used = vec![false; num_points];
+    if !search_points(&abstract_inc, &concrete, &mut assigned, &mut used) {
+        return None;
+    }
+    let lines = match_lines(&abstract_inc, &concrete, &assigned)?;
+    Some(ConfigurationIsomorphism {
+        points: assigned,
+        lines,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pg_plane::{Join, ProjectivePlane};
+
+    #[test]
+    fn test_point_arrangement() {
+        let points = [[0, 0, 1], [2, 0, 1], [2, 2, 1], [0, 2, 1], [1, 1, 1]].map(PgPoint::new);
+        let arr = PointArrangement::new(&points);
+        for (ln, on) in arr.lines.iter().zip(&arr.incidences) {
+            let expected: Vec<usize> = (0..points.len())
+                .filter(|&i| points[i].incident(ln))
+                .collect();
+            assert_eq!(*on, expected);
+        }
+        assert_eq!(arr.dualize().dualize(), arr);
+        assert_eq!(arr.dualize().multiplicity_counts(), vec![0, 0, 4, 2]);
+    }
+
+    #[test]
+    fn test_complete_quadrilateral() {
+        let lines = [[1, 2, 3], [-2, 1, 5], [3, -1, 2], [1, 1, -7]].map(PgLine::new);
+        let arr = Arrangement::new(&lines);
+        assert_eq!(arr.points.len(), 6);
+        assert_eq!(arr.multiplicity_counts(), vec![0, 0, 6]);
+        for j in 0..4 {
+            assert_eq!(arr.points_on(j).len(), 3);
+        }
+        // a repeated line passes through the same points
+        let arr = Arrangement::new(&[lines[0].clone(), lines[1].clone(), lines[0].clone()]);
+        assert_eq!(arr.points.len(), 1);
+        assert_eq!(arr.incidences[0], vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_pappus_configuration() {
+        let pt_a = [[0, 0, 1], [1, 0, 1], [3, 0, 1]].map(PgPoint::new);
+        let pt_b = [[0, 1, 1], [2, 1, 1], [3, 1, 1]].map(PgPoint::new);
+        let mut lines = vec![pt_a[0].join(&pt_a[1]), pt_b[0].join(&pt_b[1])];
+        let mut diagonal = Vec::new();
+        for (i, j) in [(0, 1), (0, 2), (1, 2)] {
+            let ln_1 = pt_a[i].join(&pt_b[j]);
+            let ln_2 = pt_a[j].join(&pt_b[i]);
+            diagonal.push(ln_1.meet(&ln_2));
+            lines.extend([ln_1, ln_2]);
+        }
+        lines.push(diagonal[0].join(&diagonal[1]));
+        let arr = Arrangement::new(&lines);
+        // the nine points of the configuration lie on three of the nine lines
+        assert_eq!(arr.multiplicity_counts()[3], 9);
+        let on_pappus_line = arr.points_on(8);
+        assert_eq!(
+            on_pappus_line
+                .iter()
+                .filter(|&&i| arr.multiplicity(i) == 3)
+                .count(),
+            3
+        );
+    }
+
+    /// The Desargues configuration `(10_3)`: the center `0`, the triangles `1, 2, 3` and `4, 5, 6`
+    /// and the points `7, 8, 9` on the axis.
+    const DESARGUES: [[usize; 3]; 10] = [
+        [0, 1, 4],
+        [0, 2, 5],
+        [0, 3, 6],
+        [1, 2, 9],
+        [4, 5, 9],
+        [1, 3, 8],
+        [4, 6, 8],
+        [2, 3, 7],
+        [5, 6, 7],
+        [7, 8, 9],
+    ];
+
+    #[test]
+    fn test_realize_configuration() {
+        let pt_o = PgPoint::new([0, 0, 1]);
+        let tri_a = [[2, 1, 1], [-1, 3, 1], [1, -2, 1]].map(PgPoint::new);
+        let tri_b = [
+            tri_a[0].parametrize(2, &pt_o, 1),
+            tri_a[1].parametrize(3, &pt_o, 2),
+            tri_a[2].parametrize(-1, &pt_o, 1),
+        ];
+        let side = |tri: &[PgPoint; 3], i: usize, j: usize| tri[i].join(&tri[j]);
+        let axis_points =
+            [(1, 2), (0, 2), (0, 1)].map(|(i, j)| side(&tri_a, i, j).meet(&side(&tri_b, i, j)));
+        let mut points = vec![pt_o];
+        points.extend(tri_a);
+        points.extend(tri_b);
+        points.extend(axis_points.clone());
+        let blocks = DESARGUES.map(|block| block.to_vec());
+        let mut lines: Vec<PgLine> = DESARGUES
+            .iter()
+            .map(|[a, b, _]| points[*a].join(&points[*b]))
+            .collect();
+        // shuffle the labels
+        points.reverse();
+        lines.rotate_left(3);
+        let iso = realize_configuration(&points, &lines, &blocks).unwrap();
+        for (b, block) in blocks.iter().enumerate() {
+            for &a in block {
+                assert!(points[iso.points[a]].incident(&lines[iso.lines[b]]));
+            }
+        }
+        let matrix = incidence_matrix(&points, &lines);
+        assert!(matrix.iter().all(|row| row.iter().sum::<u8>() == 3));
+
+        // replace the axis by another line through two of its points
+        let axis = axis_points[0].join(&axis_points[1]);
+        let last = lines.iter().position(|ln| *ln == axis).unwrap();
+        lines[last] = axis_points[0].join(&PgPoint::new([5, 7, 1]));
+        assert_eq!(realize_configuration(&points, &lines, &blocks), None);
+    }
+}
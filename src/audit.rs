@@ -0,0 +1,198 @@
+// Operation counts and coordinate growth of the exact kernel, recorded per thread
+
+use core::cell::Cell;
+use core::fmt;
+
+/// The kernel operations counted by the audit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Meet,
+    Incident,
+    Dot,
+    Parametrize,
+    HarmConj,
+    Normalize,
+}
+
+/// The `Stats` struct counts the operations of the exact kernel on the i64 points and lines of
+/// `pg_object`, and records the largest coordinate they produced, which measures coordinate
+/// growth in deep constructions. Derived constructions such as `harm_conj` on other types count
+/// as the meets and parametrizations they consist of.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub meets: u64,
+    pub incidences: u64,
+    pub dots: u64,
+    pub parametrizations: u64,
+    /// Calls of the fast path `PgPoint::harm_conj`.
+    pub harm_conjs: u64,
+    pub normalizations: u64,
+    /// Bit length of the largest absolute value of a coordinate or dot product produced.
+    pub max_bits: u32,
+}
+
+impl Stats {
+    const ZERO: Stats = Stats {
+        meets: 0,
+        incidences: 0,
+        dots: 0,
+        parametrizations: 0,
+        harm_conjs: 0,
+        normalizations: 0,
+        max_bits: 0,
+    };
+
+    /// The total number of operations.
+    #[inline]
+    pub const fn total(&self) -> u64 {
+        self.meets
+            + self.incidences
+            + self.dots
+            + self.parametrizations
+            + self.harm_conjs
+            + self.normalizations
+    }
+
+    /// Add the counts of `other` and take the larger coordinate size.
+    #[inline]
+    pub fn merge(&mut self, other: &Stats) {
+        self.meets += other.meets;
+        self.incidences += other.incidences;
+        self.dots += other.dots;
+        self.parametrizations += other.parametrizations;
+        self.harm_conjs += other.harm_conjs;
+        self.normalizations += other.normalizations;
+        self.max_bits = self.max_bits.max(other.max_bits);
+    }
+}
+
+impl fmt::Display for Stats {
+    /// Format the report, one line per operation followed by the total and the coordinate size.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "meet              {:>10}", self.meets)?;
+        writeln!(f, "incident          {:>10}", self.incidences)?;
+        writeln!(f, "dot               {:>10}", self.dots)?;
+        writeln!(f, "parametrize       {:>10}", self.parametrizations)?;
+        writeln!(f, "harm_conj         {:>10}", self.harm_conjs)?;
+        writeln!(f, "normalize         {:>10}", self.normalizations)?;
+        writeln!(f, "total             {:>10}", self.total())?;
+        write!(f, "largest result    {:>5} bits", self.max_bits)
+    }
+}
+
+std::thread_local! {
+    static STATS: Cell<Stats> = const { Cell::new(Stats::ZERO) };
+}
+
+/// Count an operation and record the size of its result.
+#[inline]
+pub(crate) fn record(op: Op, coord: &[i64]) {
+    STATS.with(|cell| {
+        let mut stats = cell.get();
+        match op {
+            Op::Meet => stats.meets += 1,
+            Op::Incident => stats.incidences += 1,
+            Op::Dot => stats.dots += 1,
+            Op::Parametrize => stats.parametrizations += 1,
+            Op::HarmConj => stats.harm_conjs += 1,
+            Op::Normalize => stats.normalizations += 1,
+        }
+        let bits = coord
+            .iter()
+            .map(|x| u64::BITS - x.unsigned_abs().leading_zeros())
+            .max()
+            .unwrap_or(0);
+        stats.max_bits = stats.max_bits.max(bits);
+        cell.set(stats);
+    });
+}
+
+/// Return the statistics of the current thread since it started or since the last `reset`.
+#[inline]
+pub fn stats() -> Stats {
+    STATS.with(Cell::get)
+}
+
+/// Reset the statistics of the current thread.
+#[inline]
+pub fn reset() {
+    STATS.with(|cell| cell.set(Stats::ZERO));
+}
+
+/// The function `audited` runs a computation and returns its result together with the statistics
+/// of that call alone. The counts also go into the statistics of the thread, so calls can nest.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::audit::audited;
+/// use projgeom_rs::{harm_conj, PgPoint};
+/// let [pt_a, pt_b, pt_c] = [[1, 0, 1], [5, 0, 1], [2, 0, 1]].map(PgPoint::new);
+/// // the generic construction takes three meets and one parametrization
+/// let (pt_d, stats) = audited(|| harm_conj(&pt_a, &pt_b, &pt_c));
+/// assert_eq!(pt_d, PgPoint::new([-1, 0, 1]));
+/// assert_eq!((stats.meets, stats.parametrizations), (3, 1));
+/// // the fast path counts as a single operation
+/// let (_, stats) = audited(|| pt_a.harm_conj(&pt_b, &pt_c));
+/// assert_eq!((stats.total(), stats.harm_conjs), (1, 1));
+/// println!("{}", stats);
+/// ```
+pub fn audited<R>(compute: impl FnOnce() -> R) -> (R, Stats) {
+    let outer = STATS.with(|cell| cell.replace(Stats::ZERO));
+    let result = compute();
+    let inner = stats();
+    let mut total = outer;
+    total.merge(&inner);
+    STATS.with(|cell| cell.set(total));
+    (result, inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pg_object::{det3, PgPoint};
+    use crate::pg_plane::{Join, ProjectivePlane, ProjectivePlanePrimitive};
+
+    #[test]
+    fn test_audit() {
+        reset();
+        let pt_p = PgPoint::new([1, 3, 2]);
+        let pt_q = PgPoint::new([-2, 1, -1]);
+        let ((), inner) = audited(|| {
+            let ln_l = pt_p.join(&pt_q);
+            assert!(pt_p.incident(&ln_l));
+            let _ = pt_p.parametrize(3, &pt_q, 4).normalized();
+            let mut pt_r = PgPoint::new([2, 4, 6]);
+            pt_r.normalize();
+        });
+        assert_eq!(
+            (inner.meets, inner.incidences, inner.parametrizations),
+            (1, 1, 1)
+        );
+        assert_eq!((inner.normalizations, inner.total()), (1, 4));
+        let _ = pt_p.dot(&pt_q.aux());
+        assert_eq!(stats().total(), 5);
+
+        // the coordinates grow along a chain of harmonic conjugates
+        reset();
+        let mut pts = [[0, 0, 1], [1, 0, 1], [3, 0, 2]].map(PgPoint::new);
+        let mut sizes = Vec::new();
+        for _ in 0..4 {
+            let (pt_d, stats) = audited(|| pts[0].harm_conj(&pts[1], &pts[2]));
+            sizes.push(stats.max_bits);
+            pts = [pts[1].clone(), pts[2].clone(), pt_d];
+        }
+        assert!(sizes.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert_eq!(stats().harm_conjs, 4);
+        assert_eq!(stats().max_bits, sizes[3]);
+        assert!(stats().to_string().contains("harm_conj                  4"));
+    }
+
+    #[test]
+    #[should_panic(expected = "audit: arithmetic overflows i64")]
+    fn test_audit_overflow() {
+        // the determinant wraps around silently in release builds without the audit
+        let m = 1 << 32;
+        let _ = det3(&[m, 0, 0], &[0, m, 0], &[0, 0, 1]);
+    }
+}
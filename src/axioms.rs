@@ -0,0 +1,376 @@
+// Axiom and theorem checks reporting the witnessing objects
+
+use crate::ck_plane::CayleyKleinPlanePrimitive;
+use crate::pg_plane::{coincident, persp, tri_dual, ProjectivePlane, ProjectivePlanePrimitive};
+use core::fmt;
+
+/// The `AxiomViolation` enum describes which axiom or theorem failed, together with the objects
+/// witnessing the failure. Checks on the dual objects report `AxiomViolation<Line, Point>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AxiomViolation<Point, Line> {
+    /// `point.incident(line)` and `line.incident(point)` disagree.
+    IncidenceNotSymmetric { point: Point, line: Line },
+    /// `pt_p.meet(pt_q)` and `pt_q.meet(pt_p)` differ.
+    MeetNotSymmetric { pt_p: Point, pt_q: Point },
+    /// The line `pt_p.meet(pt_q)` misses one of the two points.
+    MeetNotIncident {
+        pt_p: Point,
+        pt_q: Point,
+        line: Line,
+    },
+    /// `point.dot(line)` and `line.dot(point)` differ.
+    DotNotSymmetric { point: Point, line: Line },
+    /// `point.aux()` is incident with the point.
+    AuxIncident { point: Point, aux: Line },
+    /// The point `pt_p.parametrize(alpha, pt_q, beta)` is not on the line through `pt_p` and `pt_q`.
+    ParametrizeNotIncident {
+        pt_p: Point,
+        pt_q: Point,
+        point: Point,
+    },
+    /// The three cross-joins of two collinear triples are not collinear.
+    Pappus { diagonal: [Point; 3] },
+    /// The two triangles are perspective from a point but not from a line, or vice versa.
+    Desargues {
+        tri1: [Point; 3],
+        tri2: [Point; 3],
+        from_point: bool,
+    },
+    /// `point.perp().perp()` is not the point itself.
+    PerpNotInvolutive { point: Point },
+    /// The polarity maps an incident pair to a non-incident one, or vice versa.
+    PerpNotIncidencePreserving { point: Point, line: Line },
+}
+
+impl<Point: fmt::Debug, Line: fmt::Debug> fmt::Display for AxiomViolation<Point, Line> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AxiomViolation::IncidenceNotSymmetric { point, line } => {
+                write!(
+                    f,
+                    "incidence of {:?} and {:?} is not symmetric",
+                    point, line
+                )
+            }
+            AxiomViolation::MeetNotSymmetric { pt_p, pt_q } => {
+                write!(f, "meet of {:?} and {:?} is not symmetric", pt_p, pt_q)
+            }
+            AxiomViolation::MeetNotIncident { pt_p, pt_q, line } => write!(
+                f,
+                "meet {:?} of {:?} and {:?} is not incident with both",
+                line, pt_p, pt_q
+            ),
+            AxiomViolation::DotNotSymmetric { point, line } => {
+                write!(
+                    f,
+                    "dot product of {:?} and {:?} is not symmetric",
+                    point, line
+                )
+            }
+            AxiomViolation::AuxIncident { point, aux } => {
+                write!(f, "aux {:?} is incident with {:?}", aux, point)
+            }
+            AxiomViolation::ParametrizeNotIncident { pt_p, pt_q, point } => write!(
+                f,
+                "{:?} is not on the line through {:?} and {:?}",
+                point, pt_p, pt_q
+            ),
+            AxiomViolation::Pappus { diagonal } => {
+                write!(f, "Pappus line fails: {:?} are not collinear", diagonal)
+            }
+            AxiomViolation::Desargues {
+                tri1,
+                tri2,
+                from_point,
+            } => write!(
+                f,
+                "Desargues fails: {:?} and {:?} are perspective from a {} only",
+                tri1,
+                tri2,
+                if *from_point { "point" } else { "line" }
+            ),
+            AxiomViolation::PerpNotInvolutive { point } => {
+                write!(f, "perp of {:?} is not an involution", point)
+            }
+            AxiomViolation::PerpNotIncidencePreserving { point, line } => write!(
+                f,
+                "perp does not preserve the incidence of {:?} and {:?}",
+                point, line
+            ),
+        }
+    }
+}
+
+impl<Point: fmt::Debug, Line: fmt::Debug> core::error::Error for AxiomViolation<Point, Line> {}
+
+/// The function `verify_axiom` checks the incidence axioms of a projective plane on the given objects.
+///
+/// Arguments:
+///
+/// * `pt_p`: A point in the projective plane.
+/// * `pt_q`: Another point in the projective plane.
+/// * `ln_l`: A line in the projective plane.
+///
+/// Returns:
+///
+/// The function `verify_axiom` returns `Ok(())`, or the first `AxiomViolation` found.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::axioms::verify_axiom;
+/// use projgeom_rs::{PgLine, PgPoint};
+/// let pt_p = PgPoint::new([1, 3, 2]);
+/// let pt_q = PgPoint::new([-2, 1, -1]);
+/// assert_eq!(verify_axiom(&pt_p, &pt_q, &PgLine::new([2, 1, 0])), Ok(()));
+/// ```
+pub fn verify_axiom<Point, Line>(
+    pt_p: &Point,
+    pt_q: &Point,
+    ln_l: &Line,
+) -> Result<(), AxiomViolation<Point, Line>>
+where
+    Point: ProjectivePlanePrimitive<Line> + Clone,
+    Line: ProjectivePlanePrimitive<Point> + Clone,
+{
+    if pt_p.incident(ln_l) != ln_l.incident(pt_p) {
+        return Err(AxiomViolation::IncidenceNotSymmetric {
+            point: pt_p.clone(),
+            line: ln_l.clone(),
+        });
+    }
+    let ln_m = pt_p.meet(pt_q);
+    if ln_m != pt_q.meet(pt_p) {
+        return Err(AxiomViolation::MeetNotSymmetric {
+            pt_p: pt_p.clone(),
+            pt_q: pt_q.clone(),
+        });
+    }
+    if !(ln_m.incident(pt_p) && ln_m.incident(pt_q)) {
+        return Err(AxiomViolation::MeetNotIncident {
+            pt_p: pt_p.clone(),
+            pt_q: pt_q.clone(),
+            line: ln_m,
+        });
+    }
+    Ok(())
+}
+
+/// The function `verify_axiom2` checks the measurement operations `dot`, `aux` and `parametrize`
+/// on the given objects.
+///
+/// Arguments:
+///
+/// * `pt_p`: A point in the projective plane.
+/// * `pt_q`: Another point in the projective plane.
+/// * `ln_l`: A line in the projective plane.
+/// * `alpha`: The coefficient of `pt_p` in the parametrized point.
+/// * `beta`: The coefficient of `pt_q` in the parametrized point.
+///
+/// Returns:
+///
+/// The function `verify_axiom2` returns `Ok(())`, or the first `AxiomViolation` found.
+pub fn verify_axiom2<Point, Line, Value>(
+    pt_p: &Point,
+    pt_q: &Point,
+    ln_l: &Line,
+    alpha: Value,
+    beta: Value,
+) -> Result<(), AxiomViolation<Point, Line>>
+where
+    Value: Default + Eq,
+    Point: ProjectivePlane<Line, Value> + Clone,
+    Line: ProjectivePlane<Point, Value> + Clone,
+{
+    if pt_p.dot(ln_l) != ln_l.dot(pt_p) {
+        return Err(AxiomViolation::DotNotSymmetric {
+            point: pt_p.clone(),
+            line: ln_l.clone(),
+        });
+    }
+    let aux = pt_p.aux();
+    if aux.incident(pt_p) {
+        return Err(AxiomViolation::AuxIncident {
+            point: pt_p.clone(),
+            aux,
+        });
+    }
+    let pt_r = pt_p.parametrize(alpha, pt_q, beta);
+    if !pt_p.meet(pt_q).incident(&pt_r) {
+        return Err(AxiomViolation::ParametrizeNotIncident {
+            pt_p: pt_p.clone(),
+            pt_q: pt_q.clone(),
+            point: pt_r,
+        });
+    }
+    Ok(())
+}
+
+/// The function `verify_pappus` checks Pappus' theorem on two collinear triples.
+///
+/// Arguments:
+///
+/// * `coline_1`: Three points on a line.
+/// * `coline_2`: Three points on another line.
+///
+/// Returns:
+///
+/// The function `verify_pappus` returns `Ok(())`, or `AxiomViolation::Pappus` with the three
+/// cross-join points if they are not collinear.
+pub fn verify_pappus<Point, Line>(
+    coline_1: &[Point; 3],
+    coline_2: &[Point; 3],
+) -> Result<(), AxiomViolation<Point, Line>>
+where
+    Point: ProjectivePlanePrimitive<Line>,
+    Line: ProjectivePlanePrimitive<Point>,
+{
+    let [pt_a, pt_b, pt_c] = coline_1;
+    let [pt_d, pt_e, pt_f] = coline_2;
+    let pt_g = (pt_a.meet(pt_e)).meet(&pt_b.meet(pt_d));
+    let pt_h = (pt_a.meet(pt_f)).meet(&pt_c.meet(pt_d));
+    let pt_i = (pt_b.meet(pt_f)).meet(&pt_c.meet(pt_e));
+    if coincident(&pt_g, &pt_h, &pt_i) {
+        Ok(())
+    } else {
+        Err(AxiomViolation::Pappus {
+            diagonal: [pt_g, pt_h, pt_i],
+        })
+    }
+}
+
+/// The function `verify_desargue` checks Desargues' theorem: two triangles are perspective from a
+/// point exactly when they are perspective from a line.
+///
+/// Arguments:
+///
+/// * `tri1`: The vertices of the first triangle.
+/// * `tri2`: The vertices of the second triangle.
+///
+/// Returns:
+///
+/// The function `verify_desargue` returns `Ok(())`, or `AxiomViolation::Desargues` with the two
+/// triangles and which of the two perspectivities holds.
+pub fn verify_desargue<Point, Line>(
+    tri1: &[Point; 3],
+    tri2: &[Point; 3],
+) -> Result<(), AxiomViolation<Point, Line>>
+where
+    Point: ProjectivePlanePrimitive<Line> + Clone,
+    Line: ProjectivePlanePrimitive<Point>,
+{
+    let from_point = persp(tri1, tri2);
+    let from_line = persp(&tri_dual(tri1), &tri_dual(tri2));
+    if from_point == from_line {
+        Ok(())
+    } else {
+        Err(AxiomViolation::Desargues {
+            tri1: tri1.clone(),
+            tri2: tri2.clone(),
+            from_point,
+        })
+    }
+}
+
+/// The function `verify_perp` checks that `perp` is a polarity: it is an involution and `pt_p`
+/// lies on `ln_l` exactly when the pole of `ln_l` lies on the polar of `pt_p`.
+///
+/// Arguments:
+///
+/// * `pt_p`: A point in the Cayley-Klein plane.
+/// * `ln_l`: A line in the Cayley-Klein plane.
+///
+/// Returns:
+///
+/// The function `verify_perp` returns `Ok(())`, or the first `AxiomViolation` found.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::axioms::verify_perp;
+/// use projgeom_rs::{EllipticLine, EllipticPoint};
+/// let pt_p = EllipticPoint::new([1, 3, 2]);
+/// assert_eq!(verify_perp(&pt_p, &EllipticLine::new([-2, 0, 1])), Ok(()));
+/// ```
+pub fn verify_perp<Point, Line>(
+    pt_p: &Point,
+    ln_l: &Line,
+) -> Result<(), AxiomViolation<Point, Line>>
+where
+    Point: CayleyKleinPlanePrimitive<Line> + Clone,
+    Line: CayleyKleinPlanePrimitive<Point> + Clone,
+{
+    if pt_p.perp().perp() != *pt_p {
+        return Err(AxiomViolation::PerpNotInvolutive {
+            point: pt_p.clone(),
+        });
+    }
+    if pt_p.incident(ln_l) != ln_l.perp().incident(&pt_p.perp()) {
+        return Err(AxiomViolation::PerpNotIncidencePreserving {
+            point: pt_p.clone(),
+            line: ln_l.clone(),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pg_object::{PgLine, PgPoint};
+    use crate::pg_plane::Join;
+
+    /// A deliberately broken plane whose meet is not symmetric.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct Skew(i64);
+
+    impl ProjectivePlanePrimitive<Skew> for Skew {
+        fn meet(&self, rhs: &Self) -> Skew {
+            Skew(self.0 - rhs.0)
+        }
+
+        fn incident(&self, _rhs: &Skew) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn test_verify_pg() {
+        let pt_p = PgPoint::new([1, 3, 2]);
+        let pt_q = PgPoint::new([-2, 1, -1]);
+        let pt_r = PgPoint::new([2, -1, 3]);
+        let ln_l = PgLine::new([-2, 3, 1]);
+        assert_eq!(verify_axiom(&pt_p, &pt_q, &ln_l), Ok(()));
+        assert_eq!(verify_axiom(&ln_l, &pt_q.join(&pt_r), &pt_p), Ok(()));
+        assert_eq!(verify_axiom2(&pt_p, &pt_q, &ln_l, 3, 4), Ok(()));
+        let pt_s = pt_p.parametrize(2, &pt_q, 5);
+        let pt_t = pt_r.parametrize(1, &pt_q, -3);
+        assert_eq!(
+            verify_pappus::<PgPoint, PgLine>(
+                &[pt_p.clone(), pt_q.clone(), pt_s],
+                &[pt_r.clone(), pt_q.clone(), pt_t]
+            ),
+            Ok(())
+        );
+        let tri = [pt_p.clone(), pt_q.clone(), pt_r.clone()];
+        let pt_o = PgPoint::new([1, 1, 1]);
+        let tri2 = tri.clone().map(|pt| pt.parametrize(2, &pt_o, 1));
+        assert_eq!(verify_desargue::<PgPoint, PgLine>(&tri, &tri2), Ok(()));
+    }
+
+    #[test]
+    fn test_violation() {
+        let err = verify_axiom(&Skew(1), &Skew(2), &Skew(0)).unwrap_err();
+        assert_eq!(
+            err,
+            AxiomViolation::MeetNotSymmetric {
+                pt_p: Skew(1),
+                pt_q: Skew(2)
+            }
+        );
+        assert_eq!(
+            format!("{}", err),
+            "meet of Skew(1) and Skew(2) is not symmetric"
+        );
+    }
+}
@@ -0,0 +1,447 @@
+// Batch operations on many points and lines at once
+
+use crate::compact::{PgLine32, PgPoint32};
+use crate::pg_object::{normalize_homogeneous, PgLine, PgPoint};
+use crate::pg_plane::{Join, ProjectivePlane, ProjectivePlanePrimitive};
+
+/// Coordinates bounded by this value in absolute value take the fast path: every cross product
+/// and dot product of them fits into i64, so the loops run on plain i64 arithmetic without
+/// widening or overflow checks, which the compiler can vectorize.
+pub const FAST_BOUND: i64 = 1 << 30;
+
+#[inline]
+fn is_small(coord: &[i64; 3]) -> bool {
+    coord.iter().all(|x| (-FAST_BOUND..=FAST_BOUND).contains(x))
+}
+
+/// The function `meet_many` computes the lines through corresponding pairs of points.
+///
+/// Arguments:
+///
+/// * `pts_a`: The first points of the pairs.
+/// * `pts_b`: The second points of the pairs; must have the same length as `pts_a`.
+///
+/// Returns:
+///
+/// The function `meet_many` returns `pts_a[i].meet(&pts_b[i])` for every `i`.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::batch::meet_many;
+/// use projgeom_rs::{PgLine, PgPoint};
+/// let pts_a = [PgPoint::new([1, 0, 1]), PgPoint::new([0, 1, 1])];
+/// let pts_b = [PgPoint::new([0, 0, 1]), PgPoint::new([1, 1, 1])];
+/// assert_eq!(meet_many(&pts_a, &pts_b), vec![PgLine::new([0, 1, 0]), PgLine::new([0, 1, -1])]);
+/// ```
+pub fn meet_many(pts_a: &[PgPoint], pts_b: &[PgPoint]) -> Vec<PgLine> {
+    assert_eq!(pts_a.len(), pts_b.len(), "point slices differ in length");
+    let fast = pts_a.iter().chain(pts_b).all(|pt| is_small(&pt.coord));
+    if !fast {
+        return pts_a.iter().zip(pts_b).map(|(p, q)| p.join(q)).collect();
+    }
+    pts_a
+        .iter()
+        .zip(pts_b)
+        .map(|(p, q)| {
+            let ([a0, a1, a2], [b0, b1, b2]) = (p.coord, q.coord);
+            PgLine::new([a1 * b2 - a2 * b1, a2 * b0 - a0 * b2, a0 * b1 - a1 * b0])
+        })
+        .collect()
+}
+
+/// The function `incident_mask` tests many points for incidence with one line.
+///
+/// Arguments:
+///
+/// * `pts`: The points to test.
+/// * `ln`: The line.
+///
+/// Returns:
+///
+/// The function `incident_mask` returns `pts[i].incident(ln)` for every `i`.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::batch::incident_mask;
+/// use projgeom_rs::{PgLine, PgPoint};
+/// let pts = [PgPoint::new([1, 1, 1]), PgPoint::new([1, 2, 1]), PgPoint::new([3, 3, -1])];
+/// assert_eq!(incident_mask(&pts, &PgLine::new([1, -1, 0])), vec![true, false, true]);
+/// ```
+pub fn incident_mask(pts: &[PgPoint], ln: &PgLine) -> Vec<bool> {
+    let fast = is_small(&ln.coord) && pts.iter().all(|pt| is_small(&pt.coord));
+    if !fast {
+        return pts.iter().map(|pt| pt.incident(ln)).collect();
+    }
+    let [l0, l1, l2] = ln.coord;
+    pts.iter()
+        .map(|pt| {
+            let [x, y, z] = pt.coord;
+            x * l0 + y * l1 + z * l2 == 0
+        })
+        .collect()
+}
+
+/// The function `meet_many32` is `meet_many` for points with i32 coordinates, which take half
+/// the memory. The cross products are computed in i64 in a single pass that only records whether
+/// every result fits into i32; if one does not, the lines are recomputed one by one with
+/// `PgPoint32::meet`, which divides out common factors.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::batch::meet_many32;
+/// use projgeom_rs::{PgLine32, PgPoint32};
+/// let pts_a = [PgPoint32::new([1, 0, 1]), PgPoint32::new([0, 1, 1])];
+/// let pts_b = [PgPoint32::new([0, 0, 1]), PgPoint32::new([1, 1, 1])];
+/// assert_eq!(
+///     meet_many32(&pts_a, &pts_b),
+///     vec![PgLine32::new([0, 1, 0]), PgLine32::new([0, 1, -1])]
+/// );
+/// ```
+pub fn meet_many32(pts_a: &[PgPoint32], pts_b: &[PgPoint32]) -> Vec<PgLine32> {
+    assert_eq!(pts_a.len(), pts_b.len(), "point slices differ in length");
+    let mut fits = true;
+    let lines = pts_a
+        .iter()
+        .zip(pts_b)
+        .map(|(p, q)| {
+            let ([a0, a1, a2], [b0, b1, b2]) = (p.coord.map(i64::from), q.coord.map(i64::from));
+            // a wrapped difference does not fit into i32 either
+            let coord = [
+                (a1 * b2).wrapping_sub(a2 * b1),
+                (a2 * b0).wrapping_sub(a0 * b2),
+                (a0 * b1).wrapping_sub(a1 * b0),
+            ];
+            let narrowed = coord.map(|x| x as i32);
+            fits &= (0..3).fold(true, |fits, i| fits & (i64::from(narrowed[i]) == coord[i]));
+            PgLine32::new(narrowed)
+        })
+        .collect();
+    if !fits {
+        return pts_a.iter().zip(pts_b).map(|(p, q)| p.join(q)).collect();
+    }
+    lines
+}
+
+/// The function `incident_mask32` is `incident_mask` for points and a line with i32
+/// coordinates.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::batch::incident_mask32;
+/// use projgeom_rs::{PgLine32, PgPoint32};
+/// let pts = [PgPoint32::new([1, 1, 1]), PgPoint32::new([1, 2, 1]), PgPoint32::new([3, 3, -1])];
+/// assert_eq!(incident_mask32(&pts, &PgLine32::new([1, -1, 0])), vec![true, false, true]);
+/// ```
+pub fn incident_mask32(pts: &[PgPoint32], ln: &PgLine32) -> Vec<bool> {
+    let is_small32 = |coord: &[i32; 3]| is_small(&coord.map(i64::from));
+    let fast = is_small32(&ln.coord) && pts.iter().all(|pt| is_small32(&pt.coord));
+    if !fast {
+        return pts.iter().map(|pt| pt.incident(ln)).collect();
+    }
+    let [l0, l1, l2] = ln.coord.map(i64::from);
+    pts.iter()
+        .map(|pt| {
+            let [x, y, z] = pt.coord.map(i64::from);
+            x * l0 + y * l1 + z * l2 == 0
+        })
+        .collect()
+}
+
+/// The `PointSoA` struct stores many points as a structure of arrays, the x, y and z coordinates
+/// in separate vectors. Bulk operations against a fixed line or point then run over contiguous
+/// coordinate arrays, which the compiler can vectorize.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::batch::PointSoA;
+/// use projgeom_rs::{PgLine, PgPoint};
+/// let pts = vec![PgPoint::new([1, 1, 1]), PgPoint::new([2, 4, 2]), PgPoint::new([3, 3, -1])];
+/// let mut soa = PointSoA::from(pts.clone());
+/// assert_eq!(soa.len(), 3);
+/// assert_eq!(soa.dot(&PgLine::new([1, -1, 0])), vec![0, -2, 0]);
+/// soa.normalize();
+/// assert_eq!(soa.get(1).coord, [1, 2, 1]);
+/// assert_eq!(Vec::from(soa), pts);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PointSoA {
+    /// The x coordinates
+    pub x: Vec<i64>,
+    /// The y coordinates
+    pub y: Vec<i64>,
+    /// The z coordinates
+    pub z: Vec<i64>,
+}
+
+impl PointSoA {
+    /// Create an empty container.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The function `from_slices` copies the coordinate arrays into a new container.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slices differ in length.
+    pub fn from_slices(x: &[i64], y: &[i64], z: &[i64]) -> Self {
+        assert!(
+            x.len() == y.len() && y.len() == z.len(),
+            "coordinate slices differ in length"
+        );
+        Self {
+            x: x.to_vec(),
+            y: y.to_vec(),
+            z: z.to_vec(),
+        }
+    }
+
+    /// The function `from_points` copies the coordinates of the points into a new container.
+    pub fn from_points(pts: &[PgPoint]) -> Self {
+        let mut soa = Self {
+            x: Vec::with_capacity(pts.len()),
+            y: Vec::with_capacity(pts.len()),
+            z: Vec::with_capacity(pts.len()),
+        };
+        pts.iter().for_each(|pt| soa.push(pt));
+        soa
+    }
+
+    /// Return the number of points.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.x.len()
+    }
+
+    /// Check if the container holds no points.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.x.is_empty()
+    }
+
+    /// Append a point.
+    #[inline]
+    pub fn push(&mut self, pt: &PgPoint) {
+        let [x, y, z] = pt.coord;
+        self.x.push(x);
+        self.y.push(y);
+        self.z.push(z);
+    }
+
+    /// Return the `i`-th point.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is out of bounds.
+    #[inline]
+    pub fn get(&self, i: usize) -> PgPoint {
+        PgPoint::new([self.x[i], self.y[i], self.z[i]])
+    }
+
+    /// Iterate over the points.
+    pub fn iter(&self) -> impl Iterator<Item = PgPoint> + '_ {
+        (0..self.len()).map(|i| self.get(i))
+    }
+
+    /// Copy the points into a vector.
+    #[inline]
+    pub fn to_points(&self) -> Vec<PgPoint> {
+        self.iter().collect()
+    }
+
+    /// Normalize every point, see `normalize_homogeneous`.
+    pub fn normalize(&mut self) {
+        for i in 0..self.len() {
+            [self.x[i], self.y[i], self.z[i]] =
+                normalize_homogeneous(&[self.x[i], self.y[i], self.z[i]]);
+        }
+    }
+
+    /// Check if every coordinate of the points and of `coord` is bounded by `FAST_BOUND`.
+    fn is_small_with(&self, coord: &[i64; 3]) -> bool {
+        let small = |v: &[i64]| v.iter().all(|x| (-FAST_BOUND..=FAST_BOUND).contains(x));
+        is_small(coord) && small(&self.x) && small(&self.y) && small(&self.z)
+    }
+
+    /// The function `dot` computes the dot product of every point with a fixed line.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a dot product does not fit into i64.
+    pub fn dot(&self, ln: &PgLine) -> Vec<i64> {
+        if !self.is_small_with(&ln.coord) {
+            return self.iter().map(|pt| pt.dot(ln)).collect();
+        }
+        let [l0, l1, l2] = ln.coord;
+        let (x, y, z) = (&self.x, &self.y[..self.len()], &self.z[..self.len()]);
+        (0..x.len())
+            .map(|i| x[i] * l0 + y[i] * l1 + z[i] * l2)
+            .collect()
+    }
+
+    /// The function `incident_mask` tests every point for incidence with a fixed line, like the
+    /// free function `incident_mask`.
+    pub fn incident_mask(&self, ln: &PgLine) -> Vec<bool> {
+        if !self.is_small_with(&ln.coord) {
+            return self.iter().map(|pt| pt.incident(ln)).collect();
+        }
+        let [l0, l1, l2] = ln.coord;
+        let (x, y, z) = (&self.x, &self.y[..self.len()], &self.z[..self.len()]);
+        (0..x.len())
+            .map(|i| x[i] * l0 + y[i] * l1 + z[i] * l2 == 0)
+            .collect()
+    }
+
+    /// The function `meet` computes the lines through every point and a fixed point, i.e. the
+    /// cross product of their coordinates.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a coordinate of a line does not fit into i64 after dividing out common factors.
+    pub fn meet(&self, pt: &PgPoint) -> Vec<PgLine> {
+        if !self.is_small_with(&pt.coord) {
+            return self.iter().map(|pt_p| pt_p.join(pt)).collect();
+        }
+        let [b0, b1, b2] = pt.coord;
+        let (x, y, z) = (&self.x, &self.y[..self.len()], &self.z[..self.len()]);
+        (0..x.len())
+            .map(|i| {
+                PgLine::new([
+                    y[i] * b2 - z[i] * b1,
+                    z[i] * b0 - x[i] * b2,
+                    x[i] * b1 - y[i] * b0,
+                ])
+            })
+            .collect()
+    }
+}
+
+impl From<Vec<PgPoint>> for PointSoA {
+    #[inline]
+    fn from(pts: Vec<PgPoint>) -> Self {
+        Self::from_points(&pts)
+    }
+}
+
+impl From<PointSoA> for Vec<PgPoint> {
+    #[inline]
+    fn from(soa: PointSoA) -> Self {
+        soa.to_points()
+    }
+}
+
+impl FromIterator<PgPoint> for PointSoA {
+    fn from_iter<I: IntoIterator<Item = PgPoint>>(iter: I) -> Self {
+        let mut soa = Self::new();
+        iter.into_iter().for_each(|pt| soa.push(&pt));
+        soa
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_matches_scalar() {
+        let big = i64::MAX / 4;
+        let pts_a: Vec<PgPoint> = (0..50)
+            .map(|i| PgPoint::new([i - 7, 3 * i + 1, 2 - i]))
+            .collect();
+        let pts_b: Vec<PgPoint> = (0..50)
+            .map(|i| PgPoint::new([i * i - 5, -2 * i, i + 4]))
+            .collect();
+        let lines = meet_many(&pts_a, &pts_b);
+        for ((p, q), ln) in pts_a.iter().zip(&pts_b).zip(&lines) {
+            assert_eq!(ln.coord, p.join(q).coord);
+        }
+        let mask = incident_mask(&pts_a, &lines[3]);
+        let expected: Vec<bool> = pts_a.iter().map(|p| p.incident(&lines[3])).collect();
+        assert_eq!(mask, expected);
+        assert!(mask[3]);
+
+        // the slow path for large coordinates
+        let pts_c = [PgPoint::new([big, 1, 0]), PgPoint::new([1, 1, 1])];
+        let pts_d = [PgPoint::new([0, 1, 0]), PgPoint::new([2, 1, 1])];
+        assert_eq!(meet_many(&pts_c, &pts_d)[0], PgLine::new([0, 0, 1]));
+        assert_eq!(
+            incident_mask(&pts_c, &PgLine::new([0, 0, 1])),
+            vec![true, false]
+        );
+    }
+
+    #[test]
+    fn test_point_soa() {
+        let big = i64::MAX / 4;
+        let pts: Vec<PgPoint> = (0..50)
+            .map(|i| PgPoint::new([i - 7, 3 * i + 1, 2 - i]))
+            .collect();
+        let soa: PointSoA = pts.iter().cloned().collect();
+        assert_eq!(soa, PointSoA::from_points(&pts));
+        assert_eq!(soa.to_points(), pts);
+        let ln = PgLine::new([2, -1, 5]);
+        let pt = PgPoint::new([4, 1, -3]);
+        let dots: Vec<i64> = pts.iter().map(|p| p.dot(&ln)).collect();
+        assert_eq!(soa.dot(&ln), dots);
+        assert_eq!(soa.incident_mask(&ln), incident_mask(&pts, &ln));
+        let lines = soa.meet(&pt);
+        for (p, l) in pts.iter().zip(&lines) {
+            assert_eq!(l.coord, p.join(&pt).coord);
+        }
+
+        // the slow paths for large coordinates
+        let mut soa = PointSoA::from_slices(&[big, 2, 4], &[0, 2, 4], &[0, 1, 2]);
+        assert_eq!(
+            soa.incident_mask(&PgLine::new([0, 1, 0])),
+            vec![true, false, false]
+        );
+        assert_eq!(soa.dot(&PgLine::new([0, 1, -2])), vec![0, 0, 0]);
+        assert_eq!(
+            soa.meet(&PgPoint::new([0, 1, 0]))[0],
+            PgLine::new([0, 0, 1])
+        );
+        soa.normalize();
+        assert_eq!(soa.get(2).coord, [2, 2, 1]);
+        assert_eq!(soa.get(0).coord, [1, 0, 0]);
+    }
+
+    #[test]
+    fn test_batch32_matches_scalar() {
+        let m = i32::MAX;
+        let pts_a: Vec<PgPoint32> = (0..50)
+            .map(|i| PgPoint32::new([i - 7, 3 * i + 1, 2 - i]))
+            .collect();
+        let mut pts_b: Vec<PgPoint32> = (0..50)
+            .map(|i| PgPoint32::new([i * i - 5, -2 * i, i + 4]))
+            .collect();
+        let lines = meet_many32(&pts_a, &pts_b);
+        for ((p, q), ln) in pts_a.iter().zip(&pts_b).zip(&lines) {
+            assert_eq!(ln.coord, p.join(q).coord);
+            assert_eq!(
+                PgLine::from(*ln),
+                PgPoint::from(*p).join(&PgPoint::from(*q))
+            );
+        }
+        let mask = incident_mask32(&pts_a, &lines[3]);
+        let expected: Vec<bool> = pts_a.iter().map(|p| p.incident(&lines[3])).collect();
+        assert_eq!(mask, expected);
+        assert!(mask[3]);
+
+        // the slow paths for large coordinates
+        pts_b[0] = PgPoint32::new([0, m, 0]);
+        let pts_c = [PgPoint32::new([m, 0, 0]), pts_a[1]];
+        let lines = meet_many32(&pts_c, &pts_b[..2]);
+        assert_eq!(lines[0].coord, [0, 0, 1]);
+        assert_eq!(lines[1].coord, pts_a[1].join(&pts_b[1]).coord);
+        assert_eq!(
+            incident_mask32(&pts_c, &PgLine32::new([0, 1, m])),
+            vec![true, false]
+        );
+    }
+}
@@ -0,0 +1,305 @@
+// Canonical forms and stable content hashes of geometric objects
+
+use crate::conic::Conic;
+use crate::mat3::Mat3;
+use crate::pg_object::normalize_homogeneous;
+use crate::pg_object::{EllipticLine, EllipticPoint};
+use crate::pg_object::{EuclidLine, EuclidPoint};
+use crate::pg_object::{HyperbolicLine, HyperbolicPoint};
+use crate::pg_object::{MyCKLine, MyCKPoint};
+use crate::pg_object::{PerspLine, PerspPoint};
+use crate::pg_object::{PgLine, PgPoint};
+use crate::transform::{reduce, Transform};
+
+/// The 64-bit FNV-1a hash of the tag followed by the entries as little-endian bytes.
+fn fnv1a(tag: &str, entries: impl IntoIterator<Item = i64>) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+    let mut hash = OFFSET_BASIS;
+    let mut feed = |byte: u8| hash = (hash ^ u64::from(byte)).wrapping_mul(PRIME);
+    tag.bytes().for_each(&mut feed);
+    entries
+        .into_iter()
+        .flat_map(i64::to_le_bytes)
+        .for_each(&mut feed);
+    hash
+}
+
+/// The `CanonicalForm` trait gives every object a unique representative of its class of equal
+/// objects, for memoization and for comparing results between runs or implementations.
+///
+/// The canonical form of homogeneous coordinates or of a matrix divides the entries by their
+/// greatest common divisor and makes the first nonzero entry (in row-major order) positive. The
+/// content hash is the 64-bit FNV-1a hash of the bytes of `TAG` followed by the entries of the
+/// canonical form, in row-major order, as 8-byte little-endian two's complement integers. It
+/// depends on nothing else, so it is the same on every platform and in every run, and it is easy
+/// to reproduce elsewhere, e.g. in Python with `struct.pack("<3q", *coord)`.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::canonical::CanonicalForm;
+/// use projgeom_rs::{PgLine, PgPoint};
+/// let pt_p = PgPoint::new([-2, 4, -6]);
+/// assert_eq!(pt_p.canonical_form(), [1, -2, 3]);
+/// assert_eq!(pt_p.content_hash(), PgPoint::new([1, -2, 3]).content_hash());
+/// assert_eq!(pt_p.content_hash(), 0x0624_6a62_abba_3b8a);
+/// // points and lines with the same coordinates are told apart
+/// assert_ne!(pt_p.content_hash(), PgLine::new([1, -2, 3]).content_hash());
+/// ```
+pub trait CanonicalForm {
+    /// The name of the kind of object, hashed before the entries.
+    const TAG: &'static str;
+
+    /// The type of the canonical form.
+    type Form: Clone + Eq + core::hash::Hash;
+
+    /// Return the canonical form.
+    fn canonical_form(&self) -> Self::Form;
+
+    /// Return the stable content hash of the canonical form.
+    fn content_hash(&self) -> u64;
+}
+
+macro_rules! impl_canonical_form {
+    ($tag:expr, $($object:ident),+) => {$(
+        impl CanonicalForm for $object {
+            const TAG: &'static str = $tag;
+            type Form = [i64; 3];
+
+            #[inline]
+            fn canonical_form(&self) -> [i64; 3] {
+                normalize_homogeneous(&self.coord)
+            }
+
+            #[inline]
+            fn content_hash(&self) -> u64 {
+                fnv1a(Self::TAG, self.canonical_form())
+            }
+        }
+    )+};
+}
+
+impl_canonical_form!(
+    "point",
+    PgPoint,
+    EllipticPoint,
+    HyperbolicPoint,
+    MyCKPoint,
+    EuclidPoint,
+    PerspPoint
+);
+impl_canonical_form!(
+    "line",
+    PgLine,
+    EllipticLine,
+    HyperbolicLine,
+    MyCKLine,
+    EuclidLine,
+    PerspLine
+);
+
+/// Conics are identified up to a nonzero factor, unlike in `Conic`'s `PartialEq`, since `M` and
+/// `λM` have the same points.
+impl CanonicalForm for Conic {
+    const TAG: &'static str = "conic";
+    type Form = [[i64; 3]; 3];
+
+    fn canonical_form(&self) -> [[i64; 3]; 3] {
+        if self.mat == [[0; 3]; 3] {
+            return self.mat;
+        }
+        // only a matrix with coprime entries led by i64::MIN cannot be negated
+        reduce(Mat3::from(self.mat).map(i128::from)).unwrap_or(self.mat)
+    }
+
+    #[inline]
+    fn content_hash(&self) -> u64 {
+        fnv1a(Self::TAG, self.canonical_form().into_iter().flatten())
+    }
+}
+
+impl CanonicalForm for Transform {
+    const TAG: &'static str = "transform";
+    type Form = [[i64; 3]; 3];
+
+    #[inline]
+    fn canonical_form(&self) -> [[i64; 3]; 3] {
+        reduce(Mat3::from(self.mat).map(i128::from)).unwrap_or(self.mat)
+    }
+
+    #[inline]
+    fn content_hash(&self) -> u64 {
+        fnv1a(Self::TAG, self.canonical_form().into_iter().flatten())
+    }
+}
+
+/// The `Canonical` struct pairs an object with its memoized canonical form. Equality, hashing
+/// and ordering compare the cached forms, so they are plain comparisons of the entries instead
+/// of a cross product per comparison, which pays off when objects are compared or hashed many
+/// times, e.g. when deduplicating. The object itself is read through `Deref`.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::canonical::CanonicalPoint;
+/// use projgeom_rs::{Join, PgPoint, ProjectivePlanePrimitive};
+/// use std::collections::HashSet;
+/// let pts = [[1, 2, 3], [-2, -4, -6], [3, 6, 9], [1, 0, 0]].map(PgPoint::new);
+/// let distinct: HashSet<CanonicalPoint> = pts.into_iter().map(CanonicalPoint::new).collect();
+/// assert_eq!(distinct.len(), 2);
+/// let pt_p = CanonicalPoint::new(PgPoint::new([-2, 4, -6]));
+/// assert_eq!(pt_p.form(), &[1, -2, 3]);
+/// // the wrapped point keeps its coordinates and operations
+/// assert_eq!(pt_p.coord, [-2, 4, -6]);
+/// assert!(pt_p.incident(&pt_p.join(&PgPoint::new([0, 0, 1]))));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Canonical<T: CanonicalForm> {
+    object: T,
+    form: T::Form,
+}
+
+/// Point with a memoized canonical form
+pub type CanonicalPoint = Canonical<PgPoint>;
+/// Line with a memoized canonical form
+pub type CanonicalLine = Canonical<PgLine>;
+
+impl<T: CanonicalForm> Canonical<T> {
+    /// Wrap an object, computing its canonical form once.
+    #[inline]
+    pub fn new(object: T) -> Self {
+        let form = object.canonical_form();
+        Self { object, form }
+    }
+
+    /// Return the cached canonical form.
+    #[inline]
+    pub fn form(&self) -> &T::Form {
+        &self.form
+    }
+
+    /// Return the wrapped object.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.object
+    }
+}
+
+impl<T: CanonicalForm> From<T> for Canonical<T> {
+    #[inline]
+    fn from(object: T) -> Self {
+        Self::new(object)
+    }
+}
+
+impl<T: CanonicalForm> core::ops::Deref for Canonical<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.object
+    }
+}
+
+impl<T: CanonicalForm> PartialEq for Canonical<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.form == other.form
+    }
+}
+impl<T: CanonicalForm> Eq for Canonical<T> {}
+
+impl<T: CanonicalForm> core::hash::Hash for Canonical<T> {
+    #[inline]
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.form.hash(state);
+    }
+}
+
+impl<T: CanonicalForm> PartialOrd for Canonical<T>
+where
+    T::Form: Ord,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: CanonicalForm> Ord for Canonical<T>
+where
+    T::Form: Ord,
+{
+    /// Compare the canonical forms lexicographically, which agrees with the order of the
+    /// wrapped points and lines.
+    #[inline]
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.form.cmp(&other.form)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonical_form() {
+        // the empty input hashes to the offset basis
+        assert_eq!(fnv1a("", []), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(
+            PgLine::new([0, 0, -7]).canonical_form(),
+            PgLine::new([0, 0, 1]).canonical_form()
+        );
+        assert_eq!(
+            HyperbolicPoint::new([3, 0, 5]).content_hash(),
+            PgPoint::new([-6, 0, -10]).content_hash()
+        );
+
+        let circle = Conic::from_coefficients(1, 0, 1, 0, 0, -25);
+        let scaled = Conic::new(circle.mat.map(|row| row.map(|x| -3 * x)));
+        assert_ne!(circle, scaled);
+        assert_eq!(scaled.canonical_form(), [[1, 0, 0], [0, 1, 0], [0, 0, -25]]);
+        assert_eq!(circle.content_hash(), scaled.content_hash());
+        assert_ne!(
+            circle.content_hash(),
+            Conic::from_coefficients(1, 0, 1, 0, 0, -16).content_hash()
+        );
+        assert_eq!(Conic::new([[0; 3]; 3]).canonical_form(), [[0; 3]; 3]);
+
+        let trans = Transform::new([[-2, 0, 4], [0, -2, 0], [0, 0, -2]]).unwrap();
+        assert_eq!(trans.canonical_form(), [[1, 0, -2], [0, 1, 0], [0, 0, 1]]);
+        assert_ne!(trans.content_hash(), Transform::IDENTITY.content_hash());
+        // the tag keeps a transform and a conic with the same matrix apart
+        let conic = Conic::new(Transform::IDENTITY.mat);
+        assert_eq!(conic.canonical_form(), Transform::IDENTITY.canonical_form());
+        assert_ne!(conic.content_hash(), Transform::IDENTITY.content_hash());
+    }
+
+    #[test]
+    fn test_canonical_wrapper() {
+        let coords = [
+            [1, 2, 3],
+            [-2, -4, -6],
+            [0, 0, -7],
+            [0, 0, 1],
+            [3, -1, 0],
+            [0, 5, 0],
+        ];
+        for c_p in coords {
+            for c_q in coords {
+                let (pt_p, pt_q) = (PgPoint::new(c_p), PgPoint::new(c_q));
+                let (can_p, can_q) = (CanonicalPoint::new(pt_p.clone()), pt_q.clone().into());
+                assert_eq!(can_p == can_q, pt_p == pt_q);
+                assert_eq!(can_p.cmp(&can_q), pt_p.cmp(&pt_q));
+            }
+        }
+        let circle = Conic::from_coefficients(1, 0, 1, 0, 0, -25);
+        let scaled = Canonical::new(Conic::new(circle.mat.map(|row| row.map(|x| -3 * x))));
+        assert_eq!(Canonical::new(circle.clone()), scaled);
+        assert_eq!(scaled.into_inner().mat[2][2], -3 * circle.mat[2][2]);
+        let ln = CanonicalLine::new(PgLine::new([0, -2, 4]));
+        assert_eq!(ln.form(), &[0, 1, -2]);
+        assert_eq!(ln.coord, [0, -2, 4]);
+    }
+}
@@ -0,0 +1,504 @@
+// Cayley-Klein geometries with an absolute conic chosen at runtime
+
+use crate::ck_plane::{CayleyKleinPlane, CayleyKleinPlanePrimitive};
+use crate::conic::Conic;
+use crate::error::GeometryError;
+use crate::mat3::{checked_cross, checked_dot, Mat3};
+use crate::pg_object::{define_join, PgLine, PgPoint};
+use crate::pg_plane::{ProjectivePlane, ProjectivePlanePrimitive};
+use crate::transform::{reduce, Transform};
+
+/// The `CkGeometry` struct is a Cayley-Klein plane whose perp is the pole-polar correspondence of
+/// an absolute conic given at runtime, rather than the compile-time coefficients of the geometry
+/// types in `pg_object`.
+///
+/// The perp of a point `x` is its polar line `M x`, and the perp of a line `l` is its pole
+/// `adj(M) l`. For a degenerate absolute, the two maps are not inverse to each other.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::ck_geometry::CkGeometry;
+/// use projgeom_rs::conic::Conic;
+/// use projgeom_rs::{CayleyKleinPlanePrimitive, HyperbolicPoint, PgPoint};
+/// // the absolute of the hyperbolic plane is x² + y² - z² = 0
+/// let geometry = CkGeometry::from_conic(Conic::new([[1, 0, 0], [0, 1, 0], [0, 0, -1]]));
+/// let pt_p = PgPoint::new([3, -2, 5]);
+/// let ln_m = geometry.perp_point(&pt_p);
+/// assert_eq!(ln_m.coord, HyperbolicPoint::new(pt_p.coord).perp().coord);
+/// assert_eq!(geometry.perp_line(&ln_m), pt_p);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CkGeometry {
+    absolute: Conic,
+}
+
+impl CkGeometry {
+    /// Create the geometry with the given absolute conic.
+    #[inline]
+    pub fn from_conic(absolute: Conic) -> Self {
+        Self { absolute }
+    }
+
+    /// Return the absolute conic.
+    #[inline]
+    pub fn absolute(&self) -> &Conic {
+        &self.absolute
+    }
+
+    /// Return the perp (polar line) of a point.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a coordinate overflows i64.
+    #[inline]
+    pub fn perp_point(&self, pt_p: &PgPoint) -> PgLine {
+        self.absolute.polar(pt_p)
+    }
+
+    /// Return the perp (pole) of a line.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a coordinate overflows i64.
+    #[inline]
+    pub fn perp_line(&self, ln_l: &PgLine) -> PgPoint {
+        self.absolute.pole(ln_l)
+    }
+
+    /// Create a point of this geometry.
+    #[inline]
+    pub fn point(&self, coord: [i64; 3]) -> GenericCKPoint<'_> {
+        GenericCKPoint::new(coord, self)
+    }
+
+    /// Create a line of this geometry.
+    #[inline]
+    pub fn line(&self, coord: [i64; 3]) -> GenericCKLine<'_> {
+        GenericCKLine::new(coord, self)
+    }
+
+    /// The pole `adj(M) l` of a line in i128, or `NotInGeneralPosition` for a degenerate
+    /// absolute.
+    fn wide_pole(&self, ln: &[i128; 3]) -> Result<[i128; 3], GeometryError> {
+        let absolute = Mat3::from(self.absolute.mat).map(i128::from);
+        if absolute.checked_det().ok_or(GeometryError::Overflow)? == 0 {
+            return Err(GeometryError::NotInGeneralPosition);
+        }
+        absolute
+            .checked_adjugate()
+            .and_then(|adj| adj.checked_apply(ln))
+            .ok_or(GeometryError::Overflow)
+    }
+
+    /// The reflection in the line with coordinates `m`, i.e. the harmonic homology
+    /// `x ↦ (m·p) x - 2 (m·x) p` with the pole `p` of the line as center.
+    fn wide_reflection(&self, mirror: &[i128; 3]) -> Result<Transform, GeometryError> {
+        let pole = self.wide_pole(mirror)?;
+        let scale = checked_dot(mirror, &pole).ok_or(GeometryError::Overflow)?;
+        if scale == 0 {
+            return Err(GeometryError::NotInGeneralPosition);
+        }
+        let mut mat = Mat3::scalar(scale);
+        for (row, p_i) in mat.rows.iter_mut().zip(pole) {
+            for (x, m_j) in row.iter_mut().zip(mirror) {
+                *x = p_i
+                    .checked_mul(*m_j)
+                    .and_then(|y| y.checked_mul(2))
+                    .and_then(|y| x.checked_sub(y))
+                    .ok_or(GeometryError::Overflow)?;
+            }
+        }
+        Transform::new(reduce(mat)?)
+    }
+
+    /// The function `reflection` returns the reflection in a line as a transform. It fixes the
+    /// points of the mirror and its pole, and maps the absolute onto itself.
+    ///
+    /// Arguments:
+    ///
+    /// * `mirror`: The line to reflect in.
+    ///
+    /// Returns:
+    ///
+    /// The function `reflection` returns `GeometryError::NotInGeneralPosition` if the absolute is
+    /// degenerate or the mirror is tangent to it, or `GeometryError::Overflow`.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::ck_geometry::{is_isometry, CkGeometry};
+    /// use projgeom_rs::conic::Conic;
+    /// use projgeom_rs::{PgLine, PgPoint};
+    /// let geometry = CkGeometry::from_conic(Conic::new([[1, 0, 0], [0, 1, 0], [0, 0, -1]]));
+    /// let reflection = geometry.reflection(&PgLine::new([1, 0, 0])).unwrap();
+    /// assert_eq!(reflection.apply(&PgPoint::new([3, 1, 5])), PgPoint::new([-3, 1, 5]));
+    /// assert!(is_isometry(&reflection, &geometry));
+    /// ```
+    pub fn reflection(&self, mirror: &PgLine) -> Result<Transform, GeometryError> {
+        self.wide_reflection(&mirror.coord.map(i128::from))
+    }
+
+    /// The function `rotation` returns the product of the reflections in `mirror_1` and then in
+    /// `mirror_2`, which is the rotation about their meet by twice the angle between them.
+    ///
+    /// Returns:
+    ///
+    /// The function `rotation` fails like `reflection` for either mirror.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::ck_geometry::CkGeometry;
+    /// use projgeom_rs::conic::Conic;
+    /// use projgeom_rs::{PgLine, PgPoint};
+    /// let geometry = CkGeometry::from_conic(Conic::new([[1, 0, 0], [0, 1, 0], [0, 0, 1]]));
+    /// // the mirrors x = 0 and x = y meet at an eighth of a turn, giving a quarter turn
+    /// let [ln_l, ln_m] = [[1, 0, 0], [1, -1, 0]].map(PgLine::new);
+    /// let rotation = geometry.rotation(&ln_l, &ln_m).unwrap();
+    /// assert_eq!(rotation.apply(&PgPoint::new([1, 0, 2])), PgPoint::new([0, -1, 2]));
+    /// assert_eq!(rotation.apply(&PgPoint::new([0, 0, 1])), PgPoint::new([0, 0, 1]));
+    /// ```
+    pub fn rotation(
+        &self,
+        mirror_1: &PgLine,
+        mirror_2: &PgLine,
+    ) -> Result<Transform, GeometryError> {
+        self.reflection(mirror_2)?
+            .compose(&self.reflection(mirror_1)?)
+    }
+
+    /// The function `translation` returns the translation along a line that is the product of
+    /// the reflections in the perpendiculars to the line through `pt_a` and then through `pt_b`.
+    /// It maps `pt_a` to the reflection of `pt_a` in `pt_b`, i.e. it translates by twice the
+    /// distance from `pt_a` to `pt_b`, and it fixes the line and its pole.
+    ///
+    /// Arguments:
+    ///
+    /// * `axis`: The line along which to translate.
+    /// * `pt_a`: A point on the axis.
+    /// * `pt_b`: Another point on the axis.
+    ///
+    /// Returns:
+    ///
+    /// The function `translation` returns `GeometryError::NotOnLine` if a point does not lie on
+    /// the axis, `GeometryError::NotInGeneralPosition` if the absolute is degenerate or the axis
+    /// is tangent to it, or `GeometryError::Overflow`.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::ck_geometry::{is_isometry, CkGeometry};
+    /// use projgeom_rs::conic::Conic;
+    /// use projgeom_rs::{PgLine, PgPoint};
+    /// let geometry = CkGeometry::from_conic(Conic::new([[1, 0, 0], [0, 1, 0], [0, 0, -1]]));
+    /// let axis = PgLine::new([0, 1, 0]);
+    /// let [pt_a, pt_b] = [[0, 0, 1], [1, 0, 2]].map(PgPoint::new);
+    /// let translation = geometry.translation(&axis, &pt_a, &pt_b).unwrap();
+    /// assert_eq!(translation.apply(&pt_a), PgPoint::new([4, 0, 5]));
+    /// assert_eq!(translation.apply_line(&axis), axis);
+    /// assert!(is_isometry(&translation, &geometry));
+    /// ```
+    pub fn translation(
+        &self,
+        axis: &PgLine,
+        pt_a: &PgPoint,
+        pt_b: &PgPoint,
+    ) -> Result<Transform, GeometryError> {
+        if !pt_a.incident(axis) || !pt_b.incident(axis) {
+            return Err(GeometryError::NotOnLine);
+        }
+        let axis = axis.coord.map(i128::from);
+        let pole = self.wide_pole(&axis)?;
+        if checked_dot(&axis, &pole).ok_or(GeometryError::Overflow)? == 0 {
+            return Err(GeometryError::NotInGeneralPosition);
+        }
+        let reflection = |pt: &PgPoint| {
+            let perp = checked_cross(&pt.coord.map(i128::from), &pole);
+            self.wide_reflection(&perp.ok_or(GeometryError::Overflow)?)
+        };
+        reflection(pt_b)?.compose(&reflection(pt_a)?)
+    }
+}
+
+/// The function `is_isometry` checks if a transform is an isometry of a geometry, i.e. if it maps
+/// the absolute onto itself, so that it preserves the quadrances and spreads of the geometry.
+///
+/// # Panics
+///
+/// Panics if a coordinate of the image of the absolute overflows i64.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::ck_geometry::{is_isometry, CkGeometry};
+/// use projgeom_rs::conic::Conic;
+/// use projgeom_rs::transform::Transform;
+/// let geometry = CkGeometry::from_conic(Conic::new([[1, 0, 0], [0, 1, 0], [0, 0, -1]]));
+/// // the boost (x, y, z) ↦ (5x + 4z, 3y, 4x + 5z) scales x² + y² - z² by 9
+/// let boost = Transform::new([[5, 0, 4], [0, 3, 0], [4, 0, 5]]).unwrap();
+/// assert!(is_isometry(&boost, &geometry));
+/// let stretch = Transform::new([[2, 0, 0], [0, 1, 0], [0, 0, 1]]).unwrap();
+/// assert!(!is_isometry(&stretch, &geometry));
+/// ```
+pub fn is_isometry(trans: &Transform, geometry: &CkGeometry) -> bool {
+    // the identity reduces the matrix of the absolute like the image
+    let absolute = Transform::IDENTITY.apply_conic(geometry.absolute());
+    trans.apply_conic(geometry.absolute()) == absolute
+}
+
+/// Implement the Cayley-Klein plane traits for a point or line type that references a
+/// `CkGeometry`, by delegating the projective operations to `$pg` and the perp to `CkGeometry::$perp`.
+macro_rules! define_generic_ck_object {
+    ($(#[$attr:meta])* $name:ident, $dual:ident, $pg:ident, $pg_dual:ident, $perp:ident) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone)]
+        pub struct $name<'a> {
+            /// Homogeneous coordinate
+            pub coord: [i64; 3],
+            /// The geometry providing the perp
+            pub geometry: &'a CkGeometry,
+        }
+
+        impl<'a> $name<'a> {
+            #[inline]
+            pub const fn new(coord: [i64; 3], geometry: &'a CkGeometry) -> Self {
+                Self { coord, geometry }
+            }
+
+            #[inline]
+            fn from_pg(obj: $pg, geometry: &'a CkGeometry) -> Self {
+                Self::new(obj.coord, geometry)
+            }
+        }
+
+        impl From<$name<'_>> for $pg {
+            #[inline]
+            fn from(obj: $name<'_>) -> Self {
+                $pg::new(obj.coord)
+            }
+        }
+
+        /// Equality is of the points or lines of the projective plane; the geometries are not
+        /// compared.
+        impl PartialEq for $name<'_> {
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                $pg::new(self.coord) == $pg::new(other.coord)
+            }
+        }
+        impl Eq for $name<'_> {}
+
+        impl<'a> ProjectivePlanePrimitive<$dual<'a>> for $name<'a> {
+            #[inline]
+            fn incident(&self, rhs: &$dual<'a>) -> bool {
+                $pg::new(self.coord).incident(&$pg_dual::new(rhs.coord))
+            }
+
+            /// The join or meet, in the geometry of `self`.
+            #[inline]
+            fn meet(&self, rhs: &Self) -> $dual<'a> {
+                let (obj_p, obj_q) = ($pg::new(self.coord), $pg::new(rhs.coord));
+                $dual::new(ProjectivePlanePrimitive::meet(&obj_p, &obj_q).coord, self.geometry)
+            }
+        }
+
+        impl<'a> ProjectivePlane<$dual<'a>, i64> for $name<'a> {
+            #[inline]
+            fn aux(&self) -> $dual<'a> {
+                $dual::new($pg::new(self.coord).aux().coord, self.geometry)
+            }
+
+            #[inline]
+            fn dot(&self, dual: &$dual<'a>) -> i64 {
+                $pg::new(self.coord).dot(&$pg_dual::new(dual.coord))
+            }
+
+            #[inline]
+            fn parametrize(&self, lambda: i64, other: &Self, mu: i64) -> Self {
+                Self::from_pg(
+                    $pg::new(self.coord).parametrize(lambda, &$pg::new(other.coord), mu),
+                    self.geometry,
+                )
+            }
+        }
+
+        impl<'a> CayleyKleinPlanePrimitive<$dual<'a>> for $name<'a> {
+            #[inline]
+            fn perp(&self) -> $dual<'a> {
+                $dual::new(
+                    self.geometry.$perp(&$pg::new(self.coord)).coord,
+                    self.geometry,
+                )
+            }
+        }
+
+        impl<'a> CayleyKleinPlane<$dual<'a>, i64> for $name<'a> {}
+    };
+}
+
+define_generic_ck_object!(
+    /// The `GenericCKPoint` struct is a point of a `CkGeometry`, so that the generic
+    /// Cayley-Klein functions, such as `orthocenter` and `reflect`, apply to geometries chosen
+    /// at runtime.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::ck_geometry::CkGeometry;
+    /// use projgeom_rs::conic::Conic;
+    /// use projgeom_rs::{is_perpendicular, orthocenter, tri_altitude, Join, ProjectivePlanePrimitive};
+    /// let geometry = CkGeometry::from_conic(Conic::from_coefficients(2, -3, 1, 5, -4, 7));
+    /// let triangle = [[13, 23, 32], [44, -34, 2], [-2, 12, 23]].map(|c| geometry.point(c));
+    /// let [t_1, t_2, _] = tri_altitude(&triangle);
+    /// assert!(is_perpendicular(&t_1, &triangle[1].join(&triangle[2])));
+    /// assert_eq!(orthocenter(&triangle), t_1.meet(&t_2));
+    /// ```
+    GenericCKPoint, GenericCKLine, PgPoint, PgLine, perp_point
+);
+define_generic_ck_object!(
+    /// The `GenericCKLine` struct is a line of a `CkGeometry`.
+    GenericCKLine, GenericCKPoint, PgLine, PgPoint, perp_line
+);
+define_join!(impl ['a] GenericCKPoint ['a], GenericCKLine ['a]);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ck_plane::CayleyKleinPlanePrimitive;
+    use crate::pg_object::{EllipticLine, EllipticPoint, MyCKLine, MyCKPoint};
+    use crate::pg_plane::{Join, ProjectivePlanePrimitive};
+
+    #[test]
+    fn test_ck_geometry() {
+        let coords = [[13, 23, 32], [44, -34, 2], [-2, 12, 23]];
+
+        let elliptic = CkGeometry::from_conic(Conic::new([[1, 0, 0], [0, 1, 0], [0, 0, 1]]));
+        // MyCK's perp is the polarity of diag(-2, 1, -2)
+        let myck = CkGeometry::from_conic(Conic::new([[-2, 0, 0], [0, 1, 0], [0, 0, -2]]));
+        for coord in coords {
+            let (pt_p, ln_l) = (PgPoint::new(coord), PgLine::new(coord));
+            assert_eq!(
+                elliptic.perp_point(&pt_p),
+                PgLine::from(EllipticPoint::new(coord).perp())
+            );
+            assert_eq!(
+                elliptic.perp_line(&ln_l),
+                PgPoint::from(EllipticLine::new(coord).perp())
+            );
+            assert_eq!(
+                myck.perp_point(&pt_p),
+                PgLine::new(MyCKPoint::new(coord).perp().coord)
+            );
+            assert_eq!(
+                myck.perp_line(&ln_l),
+                PgPoint::new(MyCKLine::new(coord).perp().coord)
+            );
+        }
+
+        // a non-diagonal absolute: the perp is a polarity, so incidence is symmetric
+        let geometry = CkGeometry::from_conic(Conic::from_coefficients(2, -3, 1, 5, -4, 7));
+        let pt_p = PgPoint::new(coords[0]);
+        let pt_q = PgPoint::new(coords[1]);
+        assert_eq!(
+            pt_p.incident(&geometry.perp_point(&pt_q)),
+            pt_q.incident(&geometry.perp_point(&pt_p))
+        );
+        let pt_r = geometry.perp_point(&pt_p).meet(&pt_q.join(&pt_p));
+        assert!(pt_p.incident(&geometry.perp_point(&pt_r)));
+        assert_eq!(geometry.perp_line(&geometry.perp_point(&pt_q)), pt_q);
+        assert_eq!(geometry.absolute().det(), -8);
+    }
+
+    #[test]
+    fn test_generic_ck_objects() {
+        use crate::axioms::verify_perp;
+        use crate::ck_plane::{orthocenter, reflect, tri_altitude};
+
+        let geometry = CkGeometry::from_conic(Conic::from_coefficients(2, -3, 1, 5, -4, 7));
+        for coord_p in [[1, 0, 0], [0, 1, -1], [2, -1, 1], [1, 1, 1]] {
+            for coord_l in [[0, 0, 1], [1, 2, 0], [-1, 1, 2], [1, 0, -1]] {
+                let (pt_p, ln_l) = (geometry.point(coord_p), geometry.line(coord_l));
+                assert_eq!(verify_perp(&pt_p, &ln_l), Ok(()));
+                assert_eq!(verify_perp(&ln_l, &pt_p), Ok(()));
+            }
+        }
+
+        // agrees with the compile-time MyCK plane on the same absolute
+        let myck = CkGeometry::from_conic(Conic::new([[-2, 0, 0], [0, 1, 0], [0, 0, -2]]));
+        let coords = [[1, 3, 2], [-2, 1, -1], [4, -5, 3]];
+        let triangle = coords.map(|coord| myck.point(coord));
+        let expected = orthocenter(&coords.map(MyCKPoint::new));
+        assert_eq!(orthocenter(&triangle), myck.point(expected.coord));
+        let [t_1, _, _] = tri_altitude(&triangle);
+        let [s_1, _, _] = tri_altitude(&coords.map(MyCKPoint::new));
+        assert_eq!(PgLine::from(t_1.clone()), PgLine::new(s_1.coord));
+        assert_eq!(
+            PgPoint::from(reflect(&t_1, &triangle[1])),
+            PgPoint::new(reflect(&s_1, &MyCKPoint::new(coords[1])).coord)
+        );
+    }
+
+    #[test]
+    fn test_isometries() {
+        use crate::ck_plane::reflect;
+
+        let geometry = CkGeometry::from_conic(Conic::from_coefficients(2, -3, 1, 5, -4, 7));
+        let pts = [[13, 23, 32], [44, -34, 2], [-2, 12, 23]].map(PgPoint::new);
+        let [ln_l, ln_m] = [[1, 2, 0], [-1, 1, 2]].map(PgLine::new);
+        let reflection = geometry.reflection(&ln_l).unwrap();
+        assert!(reflection.is_involution());
+        assert!(is_isometry(&reflection, &geometry));
+        // agrees with the generic reflection of the Cayley-Klein plane
+        for pt_p in &pts {
+            let expected = reflect(&geometry.line(ln_l.coord), &geometry.point(pt_p.coord));
+            assert_eq!(reflection.apply(pt_p), PgPoint::from(expected));
+        }
+        assert_eq!(reflection.apply_line(&ln_l), ln_l);
+        let pole = geometry.perp_line(&ln_l);
+        assert_eq!(reflection.apply(&pole), pole);
+
+        let rotation = geometry.rotation(&ln_l, &ln_m).unwrap();
+        assert!(is_isometry(&rotation, &geometry));
+        let center = ln_l.meet(&ln_m);
+        assert_eq!(rotation.apply(&center), center);
+        assert_eq!(geometry.rotation(&ln_l, &ln_l), Ok(Transform::IDENTITY));
+
+        let axis = pts[0].join(&pts[1]);
+        let translation = geometry.translation(&axis, &pts[0], &pts[1]).unwrap();
+        assert!(is_isometry(&translation, &geometry));
+        assert_eq!(translation.apply_line(&axis), axis);
+        assert_eq!(
+            translation.apply(&pts[0]),
+            reflection_in_point(&geometry, &axis, &pts[1], &pts[0])
+        );
+        assert_eq!(
+            geometry.translation(&axis, &pts[0], &pts[2]),
+            Err(GeometryError::NotOnLine)
+        );
+
+        // the tangents of the absolute and degenerate absolutes have no reflections
+        let circle = CkGeometry::from_conic(Conic::from_coefficients(1, 0, 1, 0, 0, -1));
+        assert_eq!(
+            circle.reflection(&PgLine::new([1, 0, -1])),
+            Err(GeometryError::NotInGeneralPosition)
+        );
+        let euclid = CkGeometry::from_conic(Conic::new([[1, 0, 0], [0, 1, 0], [0, 0, 0]]));
+        assert_eq!(
+            euclid.reflection(&ln_l),
+            Err(GeometryError::NotInGeneralPosition)
+        );
+        assert!(!is_isometry(&reflection, &circle));
+    }
+
+    /// The reflection of a point of a line in another point of it, along the line.
+    fn reflection_in_point(
+        geometry: &CkGeometry,
+        axis: &PgLine,
+        center: &PgPoint,
+        pt_p: &PgPoint,
+    ) -> PgPoint {
+        let perp = center.join(&geometry.perp_line(axis));
+        geometry.reflection(&perp).unwrap().apply(pt_p)
+    }
+}
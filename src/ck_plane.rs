@@ -1,4 +1,5 @@
-use crate::pg_plane::{coincident, involution, tri_dual};
+use crate::error::GeometryError;
+use crate::pg_plane::{coincident, involution, try_tri_dual};
 use crate::pg_plane::{ProjectivePlane, ProjectivePlanePrimitive};
 
 /// The `CayleyKleinPlanePrimitive` trait is a trait that extends the `ProjectivePlanePrimitive` trait. It adds an additional
@@ -15,7 +16,7 @@ pub trait CayleyKleinPlanePrimitive<Dual>: ProjectivePlanePrimitive<Dual> {
 ///
 /// * `m_1`: A reference to an object of type Line, which represents a line or a plane in a geometric space.
 /// * `m_2`: m_2 is a reference to an object of type Line, which is a generic type parameter. The specific
-/// type of Line is not specified in the function signature.
+///   type of Line is not specified in the function signature.
 ///
 /// Returns:
 ///
@@ -55,24 +56,54 @@ where
 /// Arguments:
 ///
 /// * `triangle`: The `triangle` parameter is an array of three elements of type `Point`. Each element represents a
-/// point in a triangle.
+///   point in a triangle.
 ///
 /// Returns:
 ///
 /// The function `orthocenter` returns a value of type `Point`, which is the type parameter specified in the
 /// function signature.
+///
+/// # Panics
+///
+/// Panics if the vertices are collinear; see `try_orthocenter`.
 #[allow(dead_code)]
 #[inline]
 pub fn orthocenter<Point, Line>(triangle: &[Point; 3]) -> Point
+where
+    Point: CayleyKleinPlanePrimitive<Line>,
+    Line: CayleyKleinPlanePrimitive<Point>,
+{
+    try_orthocenter(triangle).expect("vertices of the triangle are collinear")
+}
+
+/// The function `try_orthocenter` is the non-panicking version of `orthocenter`.
+///
+/// Returns:
+///
+/// The orthocenter of the triangle, or `Err(InvalidTriangle)` if the vertices are collinear.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::{try_orthocenter, EuclidPoint, GeometryError};
+/// let triangle = [[0, 0, 1], [4, 0, 1], [0, 3, 1]].map(EuclidPoint::new);
+/// assert_eq!(try_orthocenter(&triangle), Ok(EuclidPoint::new([0, 0, 1])));
+/// let collinear = [[0, 0, 1], [1, 1, 1], [2, 2, 1]].map(EuclidPoint::new);
+/// assert_eq!(try_orthocenter(&collinear), Err(GeometryError::InvalidTriangle));
+/// ```
+#[inline]
+pub fn try_orthocenter<Point, Line>(triangle: &[Point; 3]) -> Result<Point, GeometryError>
 where
     Point: CayleyKleinPlanePrimitive<Line>,
     Line: CayleyKleinPlanePrimitive<Point>,
 {
     let [a_1, a_2, a_3] = triangle;
-    assert!(!coincident(a_1, a_2, a_3));
+    if coincident(a_1, a_2, a_3) {
+        return Err(GeometryError::InvalidTriangle);
+    }
     let t_1 = altitude(a_1, &a_2.meet(a_3));
     let t_2 = altitude(a_2, &a_3.meet(a_1));
-    t_1.meet(&t_2)
+    Ok(t_1.meet(&t_2))
 }
 
 /// The function `tri_altitude` calculates the altitudes of a triangle given its three vertices and
@@ -86,6 +117,10 @@ where
 ///
 /// The function `tri_altitude` returns an array of three elements, where each element represents the
 /// altitude of a vertex in a triangle.
+///
+/// # Panics
+///
+/// Panics if the vertices are collinear; see `try_tri_altitude`.
 #[allow(dead_code)]
 #[inline]
 pub fn tri_altitude<Point, Line>(triangle: &[Point; 3]) -> [Line; 3]
@@ -93,13 +128,145 @@ where
     Point: CayleyKleinPlanePrimitive<Line>,
     Line: CayleyKleinPlanePrimitive<Point>,
 {
-    let [l_1, l_2, l_3] = tri_dual(triangle);
+    try_tri_altitude(triangle).expect("vertices of the triangle are collinear")
+}
+
+/// The function `try_tri_altitude` is the non-panicking version of `tri_altitude`.
+///
+/// Returns:
+///
+/// The altitudes of the triangle, or `Err(InvalidTriangle)` if the vertices are collinear.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::{try_tri_altitude, EuclidPoint, GeometryError};
+/// let collinear = [[0, 0, 1], [1, 1, 1], [2, 2, 1]].map(EuclidPoint::new);
+/// assert_eq!(try_tri_altitude(&collinear), Err(GeometryError::InvalidTriangle));
+/// ```
+#[inline]
+pub fn try_tri_altitude<Point, Line>(triangle: &[Point; 3]) -> Result<[Line; 3], GeometryError>
+where
+    Point: CayleyKleinPlanePrimitive<Line>,
+    Line: CayleyKleinPlanePrimitive<Point>,
+{
+    let [l_1, l_2, l_3] = try_tri_dual(triangle)?;
     let [a_1, a_2, a_3] = triangle;
-    assert!(!coincident(a_1, a_2, a_3));
     let t_1 = altitude(a_1, &l_1);
     let t_2 = altitude(a_2, &l_2);
     let t_3 = altitude(a_3, &l_3);
-    [t_1, t_2, t_3]
+    Ok([t_1, t_2, t_3])
+}
+
+/// The function `polar_triangle` computes the polar triangle of a triangle, whose vertices are the
+/// poles of the sides: vertex `i` is the pole of the side opposite vertex `i`. In elliptic
+/// geometry this is the classical polar triangle of spherical trigonometry.
+///
+/// # Panics
+///
+/// Panics if the vertices are collinear.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::{polar_triangle, EllipticPoint};
+/// let triangle = [[1, 2, 0], [0, 1, 3], [2, 0, 1]].map(EllipticPoint::new);
+/// let polar = polar_triangle(&triangle);
+/// // the relation is symmetric
+/// assert_eq!(polar_triangle(&polar), triangle);
+/// ```
+pub fn polar_triangle<Point, Line>(triangle: &[Point; 3]) -> [Point; 3]
+where
+    Point: CayleyKleinPlanePrimitive<Line>,
+    Line: CayleyKleinPlanePrimitive<Point>,
+{
+    try_tri_dual(triangle)
+        .expect("vertices of the triangle are collinear")
+        .map(|side| side.perp())
+}
+
+/// The function `is_self_polar` checks if a triangle is self-polar, i.e. if each vertex is the
+/// pole of the opposite side, so that the triangle is its own polar triangle.
+///
+/// Returns:
+///
+/// The function `is_self_polar` returns `false` for collinear vertices.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::{is_self_polar, EllipticPoint, HyperbolicPoint};
+/// let frame = [[1, 0, 0], [0, 1, 0], [0, 0, 1]];
+/// assert!(is_self_polar(&frame.map(EllipticPoint::new)));
+/// assert!(is_self_polar(&frame.map(HyperbolicPoint::new)));
+/// assert!(!is_self_polar(&[[1, 0, 0], [0, 1, 0], [1, 1, 1]].map(EllipticPoint::new)));
+/// ```
+pub fn is_self_polar<Point, Line>(triangle: &[Point; 3]) -> bool
+where
+    Point: CayleyKleinPlanePrimitive<Line>,
+    Line: CayleyKleinPlanePrimitive<Point>,
+{
+    let [a_1, a_2, a_3] = triangle;
+    // the polar of a vertex is the opposite side if it passes through the other two vertices
+    !coincident(a_1, a_2, a_3)
+        && (0..3).all(|i| {
+            let polar = triangle[i].perp();
+            polar.incident(&triangle[(i + 1) % 3]) && polar.incident(&triangle[(i + 2) % 3])
+        })
+}
+
+/// The function `self_polar_triangle` constructs a self-polar triangle with a given vertex. The
+/// other two vertices lie on the polar of the point, and there is a one-parameter family of them;
+/// the second vertex is chosen on the line through the two given points.
+///
+/// Arguments:
+///
+/// * `pt_p`: The first vertex, not on its own polar.
+/// * `pt_q`: Another point, fixing the second vertex as the meet of the polar of `pt_p` with the
+///   line through `pt_p` and `pt_q`.
+///
+/// Returns:
+///
+/// The function `self_polar_triangle` returns the triangle, or
+/// `GeometryError::NotInGeneralPosition` if `pt_p` lies on its polar (i.e. on the absolute), if
+/// the points coincide, or if the second vertex lies on its polar.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::{is_self_polar, self_polar_triangle, EllipticPoint, GeometryError};
+/// use projgeom_rs::HyperbolicPoint;
+/// let pt_p = EllipticPoint::new([1, 2, 3]);
+/// let triangle = self_polar_triangle(&pt_p, &EllipticPoint::new([0, 0, 1])).unwrap();
+/// assert_eq!(triangle[0], pt_p);
+/// assert!(is_self_polar(&triangle));
+/// // a point on the absolute x² + y² = z² of the hyperbolic plane
+/// let ideal = HyperbolicPoint::new([3, 4, 5]);
+/// assert_eq!(
+///     self_polar_triangle(&ideal, &HyperbolicPoint::new([0, 0, 1])),
+///     Err(GeometryError::NotInGeneralPosition)
+/// );
+/// ```
+pub fn self_polar_triangle<Point, Line>(
+    pt_p: &Point,
+    pt_q: &Point,
+) -> Result<[Point; 3], GeometryError>
+where
+    Point: CayleyKleinPlanePrimitive<Line> + Clone,
+    Line: CayleyKleinPlanePrimitive<Point>,
+{
+    let polar = pt_p.perp();
+    if pt_p.incident(&polar) || pt_p == pt_q {
+        return Err(GeometryError::NotInGeneralPosition);
+    }
+    // the line through p and q is not the polar of p, so they meet in a point
+    let pt_r = polar.meet(&pt_p.meet(pt_q));
+    let polar_r = pt_r.perp();
+    if pt_r.incident(&polar_r) {
+        return Err(GeometryError::NotInGeneralPosition);
+    }
+    let pt_s = polar.meet(&polar_r);
+    Ok([pt_p.clone(), pt_r, pt_s])
 }
 
 pub trait CayleyKleinPlane<Dual, Value: Default + Eq>:
@@ -118,6 +285,10 @@ pub trait CayleyKleinPlane<Dual, Value: Default + Eq>:
 ///
 /// The function `reflect` returns a value of type `Point`, which is the same type as the input parameter
 /// `pt_p`.
+///
+/// # Panics
+///
+/// Panics if `pt_p` is the pole of `mirror`, or the pole lies on `mirror`; see `involution`.
 #[allow(dead_code)]
 #[inline]
 pub fn reflect<Point, Line, Value>(mirror: &Line, pt_p: &Point) -> Point
@@ -128,3 +299,160 @@ where
 {
     involution(&mirror.perp(), mirror, pt_p)
 }
+
+/// The `AffineCKPlane` trait is implemented by the points of Cayley-Klein planes with a fixed
+/// line at infinity, such as the Euclidean and the perspective plane, where midpoints and
+/// parallels are defined.
+pub trait AffineCKPlane<Dual, Value: Default + Eq>: CayleyKleinPlane<Dual, Value> {
+    /// Return the line at infinity.
+    fn l_inf() -> Dual;
+}
+
+/// The function `midpoint` returns the midpoint of two points, i.e. the harmonic conjugate of the
+/// meet of their join with the line at infinity.
+///
+/// Arguments:
+///
+/// * `pt_a`: The first point.
+/// * `pt_b`: The second point.
+///
+/// Returns:
+///
+/// The function `midpoint` returns a value of type `Point`.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::{midpoint, EuclidPoint};
+/// let pt_m = midpoint(&EuclidPoint::new([1, 3, 1]), &EuclidPoint::new([6, 2, 2]));
+/// assert_eq!(pt_m, EuclidPoint::new([2, 2, 1]));
+/// ```
+#[inline]
+pub fn midpoint<Point, Line, Value>(pt_a: &Point, pt_b: &Point) -> Point
+where
+    Value: Default + Eq,
+    Point: AffineCKPlane<Line, Value>,
+    Line: CayleyKleinPlane<Point, Value>,
+{
+    let l_inf = Point::l_inf();
+    pt_a.parametrize(l_inf.dot(pt_b), pt_b, l_inf.dot(pt_a))
+}
+
+/// The function `is_parallel` checks if two lines meet on the line at infinity.
+#[inline]
+pub fn is_parallel<Point, Line, Value>(ln_l: &Line, ln_m: &Line) -> bool
+where
+    Value: Default + Eq,
+    Point: AffineCKPlane<Line, Value>,
+    Line: CayleyKleinPlane<Point, Value>,
+{
+    Point::l_inf().incident(&ln_l.meet(ln_m))
+}
+
+/// The function `parallel_through` returns the line through a point parallel to a line.
+///
+/// Arguments:
+///
+/// * `pt_p`: The point, not on the line at infinity.
+/// * `ln_l`: The line, not the line at infinity.
+///
+/// Returns:
+///
+/// The function `parallel_through` returns a value of type `Line`.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::{is_parallel, parallel_through, EuclidLine, EuclidPoint};
+/// let ln_l = EuclidLine::new([1, 2, -3]);
+/// let ln_m = parallel_through(&EuclidPoint::new([1, 1, 1]), &ln_l);
+/// assert_eq!(ln_m, EuclidLine::new([1, 2, -3]));
+/// let ln_m = parallel_through(&EuclidPoint::new([0, 0, 1]), &ln_l);
+/// assert_eq!(ln_m, EuclidLine::new([1, 2, 0]));
+/// assert!(is_parallel(&ln_l, &ln_m));
+/// ```
+#[inline]
+pub fn parallel_through<Point, Line, Value>(pt_p: &Point, ln_l: &Line) -> Line
+where
+    Value: Default + Eq,
+    Point: AffineCKPlane<Line, Value>,
+    Line: CayleyKleinPlane<Point, Value>,
+{
+    ln_l.meet(&Point::l_inf()).meet(pt_p)
+}
+
+/// The `impl_cayley_klein_plane!` macro implements `CayleyKleinPlanePrimitive` and
+/// `CayleyKleinPlane` for a pair of point and line types from the given pole and polar maps.
+///
+/// With a trailing `test <module name>`, it also generates a `#[cfg(test)]` module checking on all
+/// points and lines with small coordinates that the maps form a polarity (see
+/// [`verify_perp`](crate::axioms::verify_perp)). Only non-degenerate geometries pass this check.
+///
+/// The point and line types need a `new([i64; 3])` constructor and a `coord` field, like the types
+/// in `pg_object`. For instance, the elliptic plane is defined by
+///
+/// ```text
+/// impl_cayley_klein_plane!(impl EllipticPoint, EllipticLine,
+///     |pt| EllipticLine::new(pt.coord),
+///     |ln| EllipticPoint::new(ln.coord),
+///     test elliptic_polarity);
+/// ```
+#[macro_export]
+macro_rules! impl_cayley_klein_plane {
+    (impl $point:ident, $line:ident, |$pt:ident| $polar:expr, |$ln:ident| $pole:expr) => {
+        impl $crate::ck_plane::CayleyKleinPlanePrimitive<$line> for $point {
+            #[inline]
+            fn perp(&self) -> $line {
+                let $pt = self;
+                $polar
+            }
+        }
+
+        impl $crate::ck_plane::CayleyKleinPlanePrimitive<$point> for $line {
+            #[inline]
+            fn perp(&self) -> $point {
+                let $ln = self;
+                $pole
+            }
+        }
+
+        impl $crate::ck_plane::CayleyKleinPlane<$line, i64> for $point {}
+
+        impl $crate::ck_plane::CayleyKleinPlane<$point, i64> for $line {}
+    };
+    (impl $point:ident, $line:ident, |$pt:ident| $polar:expr, |$ln:ident| $pole:expr, test $test_mod:ident) => {
+        $crate::impl_cayley_klein_plane!(impl $point, $line, |$pt| $polar, |$ln| $pole);
+
+        #[cfg(test)]
+        mod $test_mod {
+            use super::*;
+            use $crate::axioms::verify_perp;
+
+            /// All nonzero coordinate vectors with entries in `-2..=2`.
+            fn small_coords() -> Vec<[i64; 3]> {
+                let mut coords = Vec::new();
+                for x in -2..=2 {
+                    for y in -2..=2 {
+                        for z in -2..=2 {
+                            coords.push([x, y, z]);
+                        }
+                    }
+                }
+                coords.retain(|coord| *coord != [0, 0, 0]);
+                coords
+            }
+
+            #[test]
+            fn test_polarity() {
+                let coords = small_coords();
+                for coord_p in &coords {
+                    for coord_l in &coords {
+                        let (pt_p, ln_l) = ($point::new(*coord_p), $line::new(*coord_l));
+                        assert_eq!(verify_perp(&pt_p, &ln_l), Ok(()));
+                        assert_eq!(verify_perp(&ln_l, &pt_p), Ok(()));
+                    }
+                }
+            }
+        }
+    };
+}
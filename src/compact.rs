@@ -0,0 +1,227 @@
+// Compact points and lines with i32 coordinates for memory-bound batch workloads
+
+use crate::coord_ring::CoordKernel;
+use crate::error::GeometryError;
+use crate::pg_object::{cross_product_checked, plucker_operation_checked, PgLine, PgPoint};
+use crate::pg_object::{define_incidence, define_join, define_object};
+use core::hash::{Hash, Hasher};
+
+/// Narrow homogeneous coordinates to i32, dividing out their common factor if necessary
+#[inline]
+fn narrow_coord32(coord: [i64; 3]) -> Option<[i32; 3]> {
+    let g = coord
+        .iter()
+        .fold(0_u64, |g, x| num_integer::gcd(g, x.unsigned_abs()))
+        .max(1);
+    let reduced = coord.map(|x| x / g as i64);
+    reduced
+        .iter()
+        .all(|&x| x == x as i32 as i64)
+        .then(|| reduced.map(|x| x as i32))
+}
+
+/// Cross product of i32 vectors, evaluated in i64
+///
+/// Every product of two i32 fits into i64, and so does the difference of two of them, except
+/// that `2^62 - (-2^62)` wraps around to `-2^63`. A wrapped entry does not fit into i32, so
+/// when the fast path fails, the cross product is recomputed in i128 and divided by the common
+/// factor of its entries.
+///
+/// # Panics
+///
+/// Panics if the result does not fit into i32 even after dividing out the common factor.
+#[inline]
+fn cross_product32(v_a: &[i32; 3], v_b: &[i32; 3]) -> [i32; 3] {
+    let (a, b) = (v_a.map(i64::from), v_b.map(i64::from));
+    let minor = |i: usize, j: usize| (a[i] * b[j]).wrapping_sub(a[j] * b[i]);
+    let coord = [minor(1, 2), minor(2, 0), minor(0, 1)];
+    let narrowed = coord.map(|x| x as i32);
+    if (0..3).fold(true, |fits, i| fits & (i64::from(narrowed[i]) == coord[i])) {
+        narrowed
+    } else {
+        cross_product32_wide(v_a, v_b)
+    }
+}
+
+#[cold]
+#[inline(never)]
+fn cross_product32_wide(v_a: &[i32; 3], v_b: &[i32; 3]) -> [i32; 3] {
+    cross_product_checked(&v_a.map(i64::from), &v_b.map(i64::from))
+        .and_then(narrow_coord32)
+        .expect("cross product overflows i32")
+}
+
+/// Dot product of i32 vectors, evaluated in i64, or in i128 if the sum overflows
+#[inline]
+fn dot_product32(v_a: &[i32; 3], v_b: &[i32; 3]) -> i128 {
+    let [p0, p1, p2] = [0, 1, 2].map(|i| i64::from(v_a[i]) * i64::from(v_b[i]));
+    match p0.checked_add(p1).and_then(|s| s.checked_add(p2)) {
+        Some(sum) => sum as i128,
+        None => p0 as i128 + p1 as i128 + p2 as i128,
+    }
+}
+
+/// The `I32Kernel` struct is the `CoordKernel` of the compact types: products are evaluated in
+/// i64, and results are reduced by their common factor to fit into i32.
+pub(crate) struct I32Kernel;
+
+impl CoordKernel for I32Kernel {
+    type Scalar = i32;
+    type Value = i64;
+
+    /// The products fit into i64.
+    #[inline]
+    fn proportional(v_a: &[i32; 3], v_b: &[i32; 3]) -> bool {
+        let (a, b) = (v_a.map(i64::from), v_b.map(i64::from));
+        a[1] * b[2] == a[2] * b[1] && a[2] * b[0] == a[0] * b[2] && a[0] * b[1] == a[1] * b[0]
+    }
+
+    #[inline]
+    fn orthogonal(v_a: &[i32; 3], v_b: &[i32; 3]) -> bool {
+        dot_product32(v_a, v_b) == 0
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the result does not fit into i64.
+    #[inline]
+    fn dot(v_a: &[i32; 3], v_b: &[i32; 3]) -> i64 {
+        i64::try_from(dot_product32(v_a, v_b)).expect("dot product overflows i64")
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the result does not fit into i32.
+    #[inline]
+    fn cross(v_a: &[i32; 3], v_b: &[i32; 3]) -> [i32; 3] {
+        cross_product32(v_a, v_b)
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the result does not fit into i32.
+    #[inline]
+    fn plucker(lambda: i64, v_a: &[i32; 3], mu: i64, v_b: &[i32; 3]) -> [i32; 3] {
+        let (a, b) = (v_a.map(i64::from), v_b.map(i64::from));
+        plucker_operation_checked(lambda, &a, mu, &b)
+            .and_then(narrow_coord32)
+            .expect("parametrization overflows i32")
+    }
+
+    #[inline]
+    fn aux(v: &[i32; 3]) -> [i32; 3] {
+        *v
+    }
+}
+
+macro_rules! define_compact {
+    (impl $point:ident, $line:ident, $pg:ident) => {
+        define_object!(
+            /// Equality and hashing are up to a nonzero scalar factor. At 12 bytes, the object
+            /// takes half the memory of its i64 counterpart, which it converts to and from.
+            /// Operations on whole arrays are fastest with `batch::meet_many32` and
+            /// `batch::incident_mask32`. Dot products and parameters are i64, which holds every
+            /// product of two i32 coordinates.
+            #[derive(Debug, Clone, Copy)]
+            impl [] $point [], i32, I32Kernel
+        );
+        define_incidence!(impl [] $line [], $point [], i64, I32Kernel);
+
+        impl Hash for $point {
+            /// Hash the normalized coordinates, so that equal objects hash alike.
+            #[inline]
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                $pg::from(*self).normalized().coord.hash(state);
+            }
+        }
+
+        impl From<$point> for $pg {
+            #[inline]
+            fn from(obj: $point) -> Self {
+                Self::new(obj.coord.map(i64::from))
+            }
+        }
+
+        impl TryFrom<$pg> for $point {
+            type Error = GeometryError;
+
+            /// Narrow the coordinates, dividing out their common factor if necessary. Fails with
+            /// `GeometryError::Overflow` if they still do not fit into i32.
+            #[inline]
+            fn try_from(obj: $pg) -> Result<Self, Self::Error> {
+                narrow_coord32(obj.coord)
+                    .map(Self::new)
+                    .ok_or(GeometryError::Overflow)
+            }
+        }
+    };
+}
+
+define_compact!(impl PgPoint32, PgLine32, PgPoint);
+define_compact!(impl PgLine32, PgPoint32, PgLine);
+define_join!(impl [] PgPoint32 [], PgLine32 []);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pg_plane::{check_axiom, check_desargue, check_pappus, coincident, harm_conj, Join};
+    use crate::pg_plane::{ProjectivePlane, ProjectivePlanePrimitive};
+
+    #[test]
+    fn test_compact() {
+        assert_eq!(core::mem::size_of::<PgPoint32>(), 12);
+        assert_eq!(core::mem::size_of::<PgPoint>(), 24);
+
+        let pt_p = PgPoint32::new([1, 3, 2]);
+        let pt_q = PgPoint32::new([-2, 1, -1]);
+        let ln_l = pt_p.join(&pt_q);
+        assert!(pt_p.incident(&ln_l) && pt_q.incident(&ln_l));
+        assert_eq!(
+            PgLine::from(ln_l),
+            PgPoint::from(pt_p).join(&PgPoint::from(pt_q))
+        );
+        assert_eq!(pt_p, PgPoint32::new([-2, -6, -4]));
+        assert_ne!(pt_p, pt_q);
+        check_axiom(&pt_p, &pt_q, &ln_l);
+
+        let pt_r = pt_p.parametrize(3, &pt_q, 4);
+        assert!(coincident(&pt_p, &pt_q, &pt_r));
+        let pt_s = harm_conj(&pt_p, &pt_q, &pt_r);
+        assert_eq!(
+            PgPoint::from(pt_s),
+            harm_conj(&pt_p.into(), &pt_q.into(), &pt_r.into())
+        );
+
+        let tri1 = [[0, 0, 1], [4, 0, 1], [0, 4, 1]].map(PgPoint32::new);
+        let tri2 = [[0, 0, 2], [8, 0, 1], [0, 8, 1]].map(PgPoint32::new);
+        assert!(check_desargue(&tri1, &tri2));
+        let coline_1 = [[0, 0, 1], [1, 0, 1], [3, 0, 1]].map(PgPoint32::new);
+        let coline_2 = [[0, 1, 1], [2, 1, 1], [5, 1, 1]].map(PgPoint32::new);
+        assert!(check_pappus(&coline_1, &coline_2));
+
+        // results are reduced by their common factor to fit into i32
+        let m = i32::MAX;
+        let ln_m = PgPoint32::new([m, 0, 0]).join(&PgPoint32::new([0, m, 0]));
+        assert_eq!(ln_m.coord, [0, 0, 1]);
+        let big = PgPoint32::new([m, m - 1, 1]);
+        assert!(big.incident(&PgLine32::new([1, -1, -1])));
+        assert!(!big.incident(&PgLine32::new([m, m, m])));
+        assert_eq!(big.dot(&PgLine32::new([1, 1, 0])), 2 * m as i64 - 1);
+
+        assert_eq!(
+            PgPoint32::try_from(PgPoint::new([1 << 40, 1 << 41, 0])),
+            Ok(PgPoint32::new([1, 2, 0]))
+        );
+        assert_eq!(
+            PgLine32::try_from(PgLine::new([1 << 40, 1, 0])),
+            Err(GeometryError::Overflow)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "cross product overflows i32")]
+    fn test_compact_overflow() {
+        let m = i32::MAX;
+        let _ = PgPoint32::new([m, 1, 0]).join(&PgPoint32::new([1, m, 1]));
+    }
+}
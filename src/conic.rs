@@ -0,0 +1,1500 @@
+// Conics given by symmetric matrices
+
+use crate::error::GeometryError;
+use crate::mat3::{checked_cross, checked_dot, Mat3};
+use crate::pg_object::{cross_product, dot_product, PgLine, PgPoint};
+use crate::pg_plane::{Join, ProjectivePlanePrimitive};
+use crate::transform::{reduce, Transform};
+use fractions::Fraction;
+use num_integer::Integer;
+
+/// The `ConicType` enum is the affine classification of a conic, i.e. relative to the line at
+/// infinity `z = 0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConicType {
+    /// A real ellipse (including circles).
+    Ellipse,
+    /// An ellipse without real points, e.g. `x² + y² + z² = 0`.
+    ImaginaryEllipse,
+    Parabola,
+    Hyperbola,
+    /// The matrix is singular: a pair of lines or a double line.
+    Degenerate,
+}
+
+/// The `Conic` struct is the point conic `xᵀ M x = 0` for a symmetric integer matrix `M`.
+///
+/// Equality is of the matrices, not up to scale.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Conic {
+    /// Symmetric matrix
+    pub mat: [[i64; 3]; 3],
+}
+
+impl Conic {
+    /// Create a conic from a symmetric matrix.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix is not symmetric.
+    #[inline]
+    pub fn new(mat: [[i64; 3]; 3]) -> Self {
+        assert!(
+            mat[0][1] == mat[1][0] && mat[0][2] == mat[2][0] && mat[1][2] == mat[2][1],
+            "conic matrix must be symmetric"
+        );
+        Self { mat }
+    }
+
+    /// The function `from_coefficients` creates the conic
+    /// `a x² + b xy + c y² + d xz + e yz + f z² = 0`. The matrix stored is twice the matrix of the
+    /// quadratic form, so that it has integer entries.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::conic::Conic;
+    /// use projgeom_rs::PgPoint;
+    /// // the unit circle
+    /// let circle = Conic::from_coefficients(1, 0, 1, 0, 0, -1);
+    /// assert!(circle.contains(&PgPoint::new([3, 4, 5])));
+    /// assert!(!circle.contains(&PgPoint::new([1, 1, 1])));
+    /// ```
+    #[inline]
+    pub fn from_coefficients(a: i64, b: i64, c: i64, d: i64, e: i64, f: i64) -> Self {
+        Self::new([[2 * a, b, d], [b, 2 * c, e], [d, e, 2 * f]])
+    }
+
+    /// The function `through_points` returns the conic through five points, no three of which
+    /// are collinear. The line pairs `AB, CD` and `AC, BD` are degenerate conics through the first
+    /// four points, and the member of their pencil through the fifth point `E` is
+    /// `(AC·E)(BD·E) [AB, CD] - (AB·E)(CD·E) [AC, BD]`, where `[l, m] = l mᵀ + m lᵀ`.
+    ///
+    /// Returns:
+    ///
+    /// The nondegenerate conic with reduced matrix, `GeometryError::NotInGeneralPosition` if
+    /// three of the points are collinear, or `GeometryError::Overflow`.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::conic::Conic;
+    /// use projgeom_rs::{GeometryError, PgPoint};
+    /// let points = [[5, 0, 1], [3, 4, 1], [0, 5, 1], [-4, 3, 1], [0, -5, 1]].map(PgPoint::new);
+    /// let circle = Conic::through_points(&points).unwrap();
+    /// assert_eq!(circle, Conic::new([[1, 0, 0], [0, 1, 0], [0, 0, -25]]));
+    /// let collinear = [[0, 0, 1], [1, 1, 1], [2, 2, 1], [1, 0, 1], [0, 1, 1]].map(PgPoint::new);
+    /// assert_eq!(Conic::through_points(&collinear), Err(GeometryError::NotInGeneralPosition));
+    /// ```
+    pub fn through_points(points: &[PgPoint; 5]) -> Result<Conic, GeometryError> {
+        let wide = points.clone().map(|pt| pt.coord.map(i128::from));
+        for i in 0..5 {
+            for j in i + 1..5 {
+                for k in j + 1..5 {
+                    let normal =
+                        checked_cross(&wide[j], &wide[k]).ok_or(GeometryError::Overflow)?;
+                    if checked_dot(&wide[i], &normal).ok_or(GeometryError::Overflow)? == 0 {
+                        return Err(GeometryError::NotInGeneralPosition);
+                    }
+                }
+            }
+        }
+        let [pt_a, pt_b, pt_c, pt_d, pt_e] = &wide;
+        let join = |v_a, v_b| checked_cross(v_a, v_b).ok_or(GeometryError::Overflow);
+        let (ln_ab, ln_cd) = (join(pt_a, pt_b)?, join(pt_c, pt_d)?);
+        let (ln_ac, ln_bd) = (join(pt_a, pt_c)?, join(pt_b, pt_d)?);
+        // the value of the line pair `l, m` at `E`, up to the factor 2
+        let value = |ln_l: &[i128; 3], ln_m: &[i128; 3]| {
+            checked_dot(ln_l, pt_e)?.checked_mul(checked_dot(ln_m, pt_e)?)
+        };
+        let (value_1, value_2) = (value(&ln_ab, &ln_cd), value(&ln_ac, &ln_bd));
+        let (value_1, value_2) = value_1.zip(value_2).ok_or(GeometryError::Overflow)?;
+        let mut mat = Mat3::zero();
+        for i in 0..3 {
+            for j in 0..3 {
+                let pair = |ln_l: &[i128; 3], ln_m: &[i128; 3]| {
+                    ln_l[i]
+                        .checked_mul(ln_m[j])?
+                        .checked_add(ln_l[j].checked_mul(ln_m[i])?)
+                };
+                mat.rows[i][j] = pair(&ln_ab, &ln_cd)
+                    .and_then(|x| x.checked_mul(value_2))
+                    .zip(pair(&ln_ac, &ln_bd).and_then(|x| x.checked_mul(value_1)))
+                    .and_then(|(x, y)| x.checked_sub(y))
+                    .ok_or(GeometryError::Overflow)?;
+            }
+        }
+        Ok(Conic::new(reduce(mat)?))
+    }
+
+    /// Return the matrix widened to i128.
+    #[inline]
+    fn wide(&self) -> Mat3<i128> {
+        Mat3::from(self.mat).map(i128::from)
+    }
+
+    /// Return `M x` for the coordinate vector `x`, computed exactly.
+    #[inline]
+    fn apply(&self, coord: &[i64; 3]) -> [i128; 3] {
+        self.wide().apply(&coord.map(i128::from))
+    }
+
+    /// Return the value of the quadratic form `xᵀ M x` at the point.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value overflows i128.
+    #[inline]
+    pub fn eval(&self, pt: &PgPoint) -> i128 {
+        let m_x = self.apply(&pt.coord);
+        (0..3).fold(0_i128, |sum, i| {
+            m_x[i]
+                .checked_mul(pt.coord[i] as i128)
+                .and_then(|v| sum.checked_add(v))
+                .expect("conic value overflows i128")
+        })
+    }
+
+    /// Check if the point lies on the conic.
+    #[inline]
+    pub fn contains(&self, pt: &PgPoint) -> bool {
+        self.eval(pt) == 0
+    }
+
+    /// The function `polar` returns the polar line `M x` of a point. For a point on the conic, this
+    /// is the tangent line at the point.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::conic::Conic;
+    /// use projgeom_rs::{PgLine, PgPoint};
+    /// let circle = Conic::from_coefficients(1, 0, 1, 0, 0, -1);
+    /// assert_eq!(circle.polar(&PgPoint::new([1, 0, 1])), PgLine::new([1, 0, -1]));
+    /// ```
+    #[inline]
+    pub fn polar(&self, pt: &PgPoint) -> PgLine {
+        PgLine::new(
+            self.apply(&pt.coord)
+                .map(|x| i64::try_from(x).expect("coordinate overflows i64")),
+        )
+    }
+
+    /// Return `adj(M) l` for the coordinate vector `l`, or `None` on overflow. The adjugate of a
+    /// symmetric matrix is its cofactor matrix.
+    fn apply_adjugate(&self, coord: &[i64; 3]) -> Option<[i128; 3]> {
+        self.wide()
+            .checked_adjugate()?
+            .checked_apply(&coord.map(i128::from))
+    }
+
+    /// The function `pole` returns the pole `adj(M) l` of a line. For a nondegenerate conic, this
+    /// inverts `polar`, and the pole of a tangent line is its point of contact.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::conic::Conic;
+    /// use projgeom_rs::{PgLine, PgPoint};
+    /// let circle = Conic::from_coefficients(1, 0, 1, 0, 0, -1);
+    /// let pt_p = PgPoint::new([2, 1, 3]);
+    /// assert_eq!(circle.pole(&circle.polar(&pt_p)), pt_p);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if a coordinate of the pole overflows i64.
+    #[inline]
+    pub fn pole(&self, ln: &PgLine) -> PgPoint {
+        PgPoint::new(
+            self.apply_adjugate(&ln.coord)
+                .and_then(|coord| {
+                    let [x, y, z] = coord.map(i64::try_from);
+                    Some([x.ok()?, y.ok()?, z.ok()?])
+                })
+                .expect("coordinate overflows i64"),
+        )
+    }
+
+    /// The function `is_tangent` checks if a line is tangent to the conic, i.e. if it lies on the
+    /// dual conic `lᵀ adj(M) l = 0`. For a degenerate conic, this holds for the lines through a
+    /// singular point.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::conic::Conic;
+    /// use projgeom_rs::PgLine;
+    /// let circle = Conic::from_coefficients(1, 0, 1, 0, 0, -1);
+    /// assert!(circle.is_tangent(&PgLine::new([3, 4, -5])));
+    /// assert!(!circle.is_tangent(&PgLine::new([1, 1, -1])));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if an intermediate result overflows i128.
+    pub fn is_tangent(&self, ln: &PgLine) -> bool {
+        self.apply_adjugate(&ln.coord)
+            .and_then(|adj_l| {
+                (0..3).try_fold(0_i128, |sum, i| {
+                    adj_l[i]
+                        .checked_mul(ln.coord[i] as i128)
+                        .and_then(|v| sum.checked_add(v))
+                })
+            })
+            .expect("tangency test overflows i128")
+            == 0
+    }
+
+    /// The function `are_conjugate_points` checks if two points are conjugate with respect to the
+    /// conic, i.e. if `pᵀ M q = 0`, so that each lies on the polar of the other. A point is
+    /// self-conjugate exactly when it lies on the conic.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::conic::Conic;
+    /// use projgeom_rs::PgPoint;
+    /// let circle = Conic::from_coefficients(1, 0, 1, 0, 0, -1);
+    /// // the polar of (2, 0) is x = 1/2
+    /// let pt_p = PgPoint::new([2, 0, 1]);
+    /// assert!(circle.are_conjugate_points(&pt_p, &PgPoint::new([1, 5, 2])));
+    /// assert!(!circle.are_conjugate_points(&pt_p, &PgPoint::new([1, 5, 1])));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value overflows i128.
+    pub fn are_conjugate_points(&self, pt_p: &PgPoint, pt_q: &PgPoint) -> bool {
+        checked_dot(&self.apply(&pt_p.coord), &pt_q.coord.map(i128::from))
+            .expect("conjugacy test overflows i128")
+            == 0
+    }
+
+    /// The function `are_conjugate_lines` checks if two lines are conjugate with respect to the
+    /// conic, i.e. if `lᵀ adj(M) m = 0`, so that each passes through the pole of the other. This
+    /// is conjugacy of points with respect to the dual conic; a line is self-conjugate exactly
+    /// when it is tangent to the conic.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::conic::Conic;
+    /// use projgeom_rs::PgLine;
+    /// let circle = Conic::from_coefficients(1, 0, 1, 0, 0, -1);
+    /// // perpendicular diameters are conjugate
+    /// assert!(circle.are_conjugate_lines(&PgLine::new([1, 2, 0]), &PgLine::new([2, -1, 0])));
+    /// assert!(!circle.are_conjugate_lines(&PgLine::new([1, 2, 0]), &PgLine::new([2, 1, 0])));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if an intermediate result overflows i128.
+    pub fn are_conjugate_lines(&self, ln_l: &PgLine, ln_m: &PgLine) -> bool {
+        self.apply_adjugate(&ln_l.coord)
+            .and_then(|adj_l| checked_dot(&adj_l, &ln_m.coord.map(i128::from)))
+            .expect("conjugacy test overflows i128")
+            == 0
+    }
+
+    /// The function `conjugate_point_on_line` returns the unique point of a line that is conjugate
+    /// to a given point, i.e. the meet of the line with the polar of the point.
+    ///
+    /// Returns:
+    ///
+    /// The function `conjugate_point_on_line` returns `GeometryError::NotInGeneralPosition` if
+    /// every point of the line is conjugate to the point, i.e. if the line is its polar or the
+    /// point is a singular point of a degenerate conic, or `GeometryError::Overflow`.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::conic::Conic;
+    /// use projgeom_rs::{GeometryError, PgLine, PgPoint};
+    /// let circle = Conic::from_coefficients(1, 0, 1, 0, 0, -1);
+    /// let pt_p = PgPoint::new([2, 0, 1]);
+    /// let pt_q = circle.conjugate_point_on_line(&pt_p, &PgLine::new([0, 1, -1]));
+    /// assert_eq!(pt_q, Ok(PgPoint::new([1, 2, 2])));
+    /// assert_eq!(
+    ///     circle.conjugate_point_on_line(&pt_p, &circle.polar(&pt_p)),
+    ///     Err(GeometryError::NotInGeneralPosition)
+    /// );
+    /// ```
+    pub fn conjugate_point_on_line(
+        &self,
+        pt: &PgPoint,
+        ln: &PgLine,
+    ) -> Result<PgPoint, GeometryError> {
+        let polar = self
+            .wide()
+            .checked_apply(&pt.coord.map(i128::from))
+            .ok_or(GeometryError::Overflow)?;
+        let coord =
+            checked_cross(&polar, &ln.coord.map(i128::from)).ok_or(GeometryError::Overflow)?;
+        if coord == [0, 0, 0] {
+            return Err(GeometryError::NotInGeneralPosition);
+        }
+        narrow_reduced(coord)
+            .map(PgPoint::new)
+            .ok_or(GeometryError::Overflow)
+    }
+
+    /// The function `self_polar_triangle` constructs a triangle `P Q R` that is self-polar with
+    /// respect to the conic, i.e. each vertex is the pole of the opposite side, starting from the
+    /// vertex `P`. The vertex `Q` is a point of the polar of `P` off the conic, and `R` is the
+    /// point of that polar conjugate to `Q`.
+    ///
+    /// Returns:
+    ///
+    /// The function `self_polar_triangle` returns `GeometryError::NotInGeneralPosition` if the
+    /// conic is degenerate or the point lies on it, or `GeometryError::Overflow`.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::conic::Conic;
+    /// use projgeom_rs::{Join, PgPoint};
+    /// let circle = Conic::from_coefficients(1, 0, 1, 0, 0, -1);
+    /// let [pt_p, pt_q, pt_r] = circle.self_polar_triangle(&PgPoint::new([2, 1, 1])).unwrap();
+    /// assert_eq!(circle.polar(&pt_p), pt_q.join(&pt_r));
+    /// assert_eq!(circle.polar(&pt_q), pt_r.join(&pt_p));
+    /// assert_eq!(circle.polar(&pt_r), pt_p.join(&pt_q));
+    /// assert!(circle.self_polar_triangle(&PgPoint::new([3, 4, 5])).is_err());
+    /// ```
+    pub fn self_polar_triangle(&self, pt: &PgPoint) -> Result<[PgPoint; 3], GeometryError> {
+        if self.is_degenerate() || self.contains(pt) {
+            return Err(GeometryError::NotInGeneralPosition);
+        }
+        let polar = self
+            .wide()
+            .checked_apply(&pt.coord.map(i128::from))
+            .ok_or(GeometryError::Overflow)?;
+        let ln = PgLine::new(narrow_reduced(polar).ok_or(GeometryError::Overflow)?);
+        // the polar meets the sides of the coordinate triangle in at least two distinct points,
+        // and at most two points of the polar lie on the conic
+        let mut sides = [[1, 0, 0], [0, 1, 0], [0, 0, 1]]
+            .map(|side| PgPoint::new(cross_product(&ln.coord, &side)))
+            .into_iter()
+            .filter(|pt| pt.coord != [0, 0, 0]);
+        let pt_a = sides.next().expect("a line meets two sides");
+        let pt_b = sides
+            .find(|pt| *pt != pt_a)
+            .expect("a line meets two sides in distinct points");
+        let sum = [0, 1, 2].map(|i| i128::from(pt_a.coord[i]) + i128::from(pt_b.coord[i]));
+        let pt_c = PgPoint::new(narrow_reduced(sum).ok_or(GeometryError::Overflow)?);
+        let pt_q = [pt_a, pt_b, pt_c]
+            .into_iter()
+            .find(|pt| !self.contains(pt))
+            .expect("a line meets a conic in at most two points");
+        let pt_r = self.conjugate_point_on_line(&pt_q, &ln)?;
+        Ok([pt.clone(), pt_q, pt_r])
+    }
+
+    /// The function `dual` returns the dual conic `adj(M)`, divided by the gcd of its entries.
+    /// Its points, read as lines, are the tangents of the conic; for a nondegenerate conic, the
+    /// dual of the dual is the conic up to scale.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::conic::Conic;
+    /// use projgeom_rs::{PgLine, PgPoint};
+    /// let ellipse = Conic::from_coefficients(1, 0, 4, 0, 0, -4);
+    /// let dual = ellipse.dual();
+    /// assert_eq!(dual, Conic::new([[-4, 0, 0], [0, -1, 0], [0, 0, 1]]));
+    /// // the tangent x = 2 of the ellipse is a point of the dual conic
+    /// assert!(ellipse.is_tangent(&PgLine::new([1, 0, -2])));
+    /// assert!(dual.contains(&PgPoint::new([1, 0, -2])));
+    /// // x² + 4y² = 4 again, up to scale
+    /// assert_eq!(dual.dual(), Conic::new([[-1, 0, 0], [0, -4, 0], [0, 0, 4]]));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if an entry overflows i64 after dividing out the common factor.
+    pub fn dual(&self) -> Conic {
+        let adj = self
+            .wide()
+            .checked_adjugate()
+            .expect("adjugate overflows i128");
+        let common = adj.rows.iter().flatten().fold(0_i128, |g, x| g.gcd(x));
+        let rows = adj
+            .rows
+            .map(|row| row.map(|x| if common == 0 { x } else { x / common }))
+            .map(|row| row.map(i64::try_from));
+        let mat = rows.map(|row| row.map(|x| x.expect("coordinate overflows i64")));
+        Conic { mat }
+    }
+
+    /// The function `transformed` returns the image of the conic under a projective transform
+    /// `x ↦ T x`, i.e. the conic `(T⁻¹)ᵀ Q T⁻¹`, computed exactly with the adjugate of `T` in place
+    /// of the inverse and reduced as in `Transform::apply_conic`. Its points are the images of the
+    /// points of the conic, and polars map to polars.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an entry of the image overflows i64.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::conic::Conic;
+    /// use projgeom_rs::transform::Transform;
+    /// use projgeom_rs::PgPoint;
+    /// // the unit circle moved by (2, -1) and stretched by 3 in x
+    /// let circle = Conic::new([[1, 0, 0], [0, 1, 0], [0, 0, -1]]);
+    /// let trans = Transform::from_affine_parts([[3, 0], [0, 1]], [2, -1]).unwrap();
+    /// let ellipse = circle.transformed(&trans);
+    /// // (x - 2)² + 9 (y + 1)² = 9
+    /// assert_eq!(ellipse, Conic::new([[1, 0, -2], [0, 9, 9], [-2, 9, 4]]));
+    /// let pt_p = PgPoint::new([3, 4, 5]);
+    /// assert!(ellipse.contains(&trans.apply(&pt_p)));
+    /// // the inverse moves it back
+    /// assert_eq!(ellipse.transformed(&trans.inverse().unwrap()), circle);
+    /// ```
+    #[inline]
+    pub fn transformed(&self, trans: &Transform) -> Conic {
+        trans.apply_conic(self)
+    }
+
+    /// The function `signature` returns the numbers of positive and of negative eigenvalues of the
+    /// matrix. Up to the order, which changes with the sign of the matrix, it is `(2, 1)` for a
+    /// real nondegenerate conic and `(3, 0)` for an imaginary one; degenerate conics have a zero
+    /// eigenvalue. The counts are the sign changes of the characteristic polynomial, which are
+    /// exact as its roots are real.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::conic::Conic;
+    /// assert_eq!(Conic::from_coefficients(1, 0, 1, 0, 0, -1).signature(), (2, 1));
+    /// assert_eq!(Conic::from_coefficients(-1, 0, 0, 0, 1, 0).signature(), (1, 2));
+    /// assert_eq!(Conic::from_coefficients(1, 0, 1, 0, 0, 1).signature(), (3, 0));
+    /// assert_eq!(Conic::from_coefficients(1, 0, -1, 0, 0, 0).signature(), (1, 1));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if a coefficient of the characteristic polynomial overflows i128.
+    pub fn signature(&self) -> (usize, usize) {
+        let [det, c1, tr] = self
+            .wide()
+            .checked_char_poly()
+            .expect("characteristic polynomial overflows i128");
+        let changes = |coefs: [i128; 4]| {
+            let mut signs = coefs.into_iter().filter(|c| *c != 0).map(i128::signum);
+            let first = signs.next().unwrap_or(0);
+            signs
+                .fold((0, first), |(count, last), sign| {
+                    (count + usize::from(sign != last), sign)
+                })
+                .0
+        };
+        // t³ - tr t² + c1 t - det, and the same at -t
+        (changes([1, -tr, c1, -det]), changes([1, tr, c1, det]))
+    }
+
+    /// The function `has_rational_point` decides by Legendre's theorem whether the conic has a
+    /// rational point. Over the rationals, the matrix is congruent to a diagonal one; the conic has
+    /// a rational point if and only if it has real points and the Hilbert symbols of the diagonal
+    /// form are 1 at every odd prime dividing its entries. The symbol at 2 then follows from the
+    /// product formula. A degenerate conic always has a rational point, e.g. its singular point.
+    ///
+    /// Returns:
+    ///
+    /// The function `has_rational_point` returns whether there is a rational point, or `None` if
+    /// the answer depends on a prime factor of the diagonal form beyond the reach of trial
+    /// division, or if an intermediate result overflows i128.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::conic::Conic;
+    /// assert_eq!(Conic::from_coefficients(1, 0, 1, 0, 0, -1).has_rational_point(), Some(true));
+    /// assert_eq!(Conic::from_coefficients(1, 0, 1, 0, 0, -3).has_rational_point(), Some(false));
+    /// assert_eq!(Conic::from_coefficients(1, 0, 1, 0, 0, 1).has_rational_point(), Some(false));
+    /// // 6 is not a sum of two rational squares, 5 = 1² + 2² is
+    /// assert_eq!(Conic::from_coefficients(1, 0, 1, 0, 0, -6).has_rational_point(), Some(false));
+    /// assert_eq!(Conic::from_coefficients(1, 0, 1, 0, 0, -5).has_rational_point(), Some(true));
+    /// ```
+    pub fn has_rational_point(&self) -> Option<bool> {
+        let det = self.wide().checked_det()?;
+        if det == 0 {
+            return Some(true);
+        }
+        // vectors v_1, v_2 with q(v_1) != 0 spanning a plane on which the form is nondegenerate,
+        // i.e. with Gram determinant d_2 != 0; one of the coordinate vectors or their sums will do
+        let vectors = [
+            [1, 0, 0],
+            [0, 1, 0],
+            [0, 0, 1],
+            [1, 1, 0],
+            [1, 0, 1],
+            [0, 1, 1],
+        ];
+        let (d_1, d_2) = vectors.iter().find_map(|v_1| {
+            let d_1 = self.bilinear(v_1, v_1).filter(|d_1| *d_1 != 0)?;
+            vectors.iter().find_map(|v_2| {
+                let (b_12, b_22) = (self.bilinear(v_1, v_2)?, self.bilinear(v_2, v_2)?);
+                let d_2 = d_1
+                    .checked_mul(b_22)?
+                    .checked_sub(b_12.checked_mul(b_12)?)?;
+                (d_2 != 0).then_some((d_1, d_2))
+            })
+        })?;
+        // the form is d_1 x² + d_2 / d_1 y² + det / d_2 z², which is definite without real points
+        if d_2 > 0 && d_1.signum() == det.signum() {
+            return Some(false);
+        }
+        // up to squares, the form is a x² + b y² + c z² with a = d_1, b = d_1 d_2, c = d_2 det,
+        // and the Hilbert symbol (-ac, -bc) equals (-d_1 d_2 det, -d_1 det)
+        let (x, y) = ([-1, d_1, d_2, det], [-1, d_1, det]);
+        let factors = [d_1, d_2, det].map(i128::unsigned_abs);
+        let (mut solvable, mut complete) = (true, true);
+        for (k, n) in factors.iter().enumerate() {
+            complete &= for_each_odd_prime_factor(*n, |p| {
+                // each prime once, at the first of the factors it divides
+                if factors[..k].iter().all(|m| m % p != 0) {
+                    solvable &= hilbert_symbol(&x, &y, p) == 1;
+                }
+            });
+        }
+        if solvable && !complete {
+            None
+        } else {
+            Some(solvable)
+        }
+    }
+
+    /// Search the lines of small height for one meeting the conic in rational points.
+    fn find_rational_point(&self) -> Result<Option<PgPoint>, GeometryError> {
+        const MAX_HEIGHT: i64 = 16;
+        for height in 1..=MAX_HEIGHT {
+            let range = -height..=height;
+            for a in range.clone() {
+                for b in range.clone() {
+                    for c in range.clone() {
+                        if a.abs().max(b.abs()).max(c.abs()) != height {
+                            continue;
+                        }
+                        if let Some([pt, _]) = self.intersect_line(&PgLine::new([a, b, c]))? {
+                            return Ok(Some(pt));
+                        }
+                    }
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// The function `normal_form` reduces a real nondegenerate conic by a projective transform
+    /// over the rationals to the unit circle `x² + y² - z² = 0`. The transform maps a rational
+    /// point `A`, two further points `B`, `C` and the pole of `AB` of the conic to `(1, 0)`,
+    /// `(-1, 0)`, `(0, 1)` and the point at infinity of the y-axis. (It is not named
+    /// `canonical_form`, which `CanonicalForm` gives every conic as the reduced matrix.)
+    ///
+    /// Returns:
+    ///
+    /// The function `normal_form` returns the unit circle and the transform `T` with
+    /// `self.transformed(&T)` equal to it. It returns `GeometryError::NotInGeneralPosition` for a
+    /// degenerate conic, and `GeometryError::Irrational` if the conic has no rational points,
+    /// either because it has no real points (see `signature`) or by `has_rational_point`; such a
+    /// conic, e.g. `x² + y² = 3 z²`, is not equivalent to the unit circle over the rationals. It
+    /// returns `GeometryError::NotFound` if the search among the lines of small height found no
+    /// rational point, and `GeometryError::Overflow` if an intermediate result does not fit.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::conic::Conic;
+    /// use projgeom_rs::{GeometryError, PgPoint};
+    /// let parabola = Conic::from_coefficients(1, 0, 0, 0, -1, 0);
+    /// let (circle, trans) = parabola.normal_form().unwrap();
+    /// assert_eq!(circle, Conic::new([[1, 0, 0], [0, 1, 0], [0, 0, -1]]));
+    /// assert_eq!(parabola.transformed(&trans), circle);
+    /// assert!(circle.contains(&trans.apply(&PgPoint::new([3, 9, 1]))));
+    /// let no_points = Conic::from_coefficients(1, 0, 1, 0, 0, -3);
+    /// assert_eq!(no_points.normal_form(), Err(GeometryError::Irrational));
+    /// ```
+    pub fn normal_form(&self) -> Result<(Conic, Transform), GeometryError> {
+        if self.is_degenerate() {
+            return Err(GeometryError::NotInGeneralPosition);
+        }
+        if matches!(self.signature(), (3, 0) | (0, 3)) || self.has_rational_point() == Some(false) {
+            return Err(GeometryError::Irrational);
+        }
+        let pt_a = self.find_rational_point()?.ok_or(GeometryError::NotFound)?;
+        // the points of the parameters of height 1 are distinct from each other
+        let mut others = self.rational_points(&pt_a, 1).filter(|pt| *pt != pt_a);
+        let (Some(pt_b), Some(pt_c)) = (others.next(), others.next()) else {
+            unreachable!("a nondegenerate conic has four points of parameters of height 1");
+        };
+        let pole = self.pole(&pt_a.join(&pt_b));
+        let src = [pt_a, pt_b, pole, pt_c];
+        let dst = [[1, 0, 1], [-1, 0, 1], [0, 1, 0], [0, 1, 1]].map(PgPoint::new);
+        let trans = Transform::from_quadrangles(&src, &dst)?;
+        // the exact images are the affine points (±1, 0), (0, 1), the point at infinity of the
+        // y-axis, and the tangent x = 1 at the image of `A`
+        let exact = |coord: [i64; 3]| Ok(coord.map(|x| Fraction::new(x, 1)));
+        debug_assert!(src
+            .iter()
+            .zip(dst)
+            .all(|(pt, frame)| trans.try_apply_point_fraction(pt) == exact(frame.coord)));
+        debug_assert_eq!(
+            trans.try_apply_line_fraction(&self.polar(&src[0])),
+            exact([-1, 0, 1])
+        );
+        let circle = trans.try_apply_conic(self)?;
+        debug_assert_eq!(circle.mat, [[1, 0, 0], [0, 1, 0], [0, 0, -1]]);
+        Ok((circle, trans))
+    }
+
+    /// Return the determinant of the matrix.
+    #[inline]
+    pub fn det(&self) -> i128 {
+        self.wide()
+            .checked_det()
+            .expect("determinant overflows i128")
+    }
+
+    /// Check if the matrix is singular.
+    #[inline]
+    pub fn is_degenerate(&self) -> bool {
+        self.det() == 0
+    }
+
+    /// The function `conic_type` classifies the conic by its intersection with the line at
+    /// infinity.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::conic::{Conic, ConicType};
+    /// assert_eq!(Conic::from_coefficients(1, 0, 1, 0, 0, -1).conic_type(), ConicType::Ellipse);
+    /// assert_eq!(Conic::from_coefficients(1, 0, 0, 0, -1, 0).conic_type(), ConicType::Parabola);
+    /// assert_eq!(Conic::from_coefficients(0, 1, 0, 0, 0, -1).conic_type(), ConicType::Hyperbola);
+    /// ```
+    pub fn conic_type(&self) -> ConicType {
+        let det = self.det();
+        if det == 0 {
+            return ConicType::Degenerate;
+        }
+        let [[m00, m01, _], [_, m11, _], _] = self.mat.map(|row| row.map(|x| x as i128));
+        match (m00 * m11 - m01 * m01).signum() {
+            1 if m00.signum() * det.signum() > 0 => ConicType::ImaginaryEllipse,
+            1 => ConicType::Ellipse,
+            0 => ConicType::Parabola,
+            _ => ConicType::Hyperbola,
+        }
+    }
+
+    /// The function `center` returns the center of the conic, i.e. the pole of the line at
+    /// infinity, which bisects every chord through it. For a pair of intersecting lines, this is
+    /// their meet.
+    ///
+    /// Returns:
+    ///
+    /// The function `center` returns `None` if there is no finite center, e.g. for a parabola, whose
+    /// pole of the line at infinity is the ideal point of its axis.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a coordinate overflows i64.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::conic::Conic;
+    /// use projgeom_rs::PgPoint;
+    /// // the hyperbola (x - 1)(y - 2) = 1
+    /// let hyperbola = Conic::from_coefficients(0, 1, 0, -2, -1, 1);
+    /// assert_eq!(hyperbola.center(), Some(PgPoint::new([1, 2, 1])));
+    /// let parabola = Conic::from_coefficients(1, 0, 0, 0, -1, 0);
+    /// assert_eq!(parabola.center(), None);
+    /// ```
+    pub fn center(&self) -> Option<PgPoint> {
+        let coord = self
+            .apply_adjugate(&[0, 0, 1])
+            .expect("coordinate overflows i128");
+        if coord[2] == 0 {
+            return None;
+        }
+        Some(PgPoint::new(
+            narrow_reduced(coord).expect("coordinate overflows i64"),
+        ))
+    }
+
+    /// Return the polar line of a point given by i128 coordinates.
+    #[cfg(feature = "std")]
+    fn wide_polar(&self, coord: [i128; 3]) -> PgLine {
+        self.wide()
+            .checked_apply(&reduce_wide(coord))
+            .and_then(narrow_reduced)
+            .map(PgLine::new)
+            .expect("coordinate overflows i64")
+    }
+
+    /// The function `asymptotes` returns the asymptotes of a hyperbola, i.e. the tangents at its
+    /// two points at infinity.
+    ///
+    /// Returns:
+    ///
+    /// The function `asymptotes` returns the two asymptotes, or none if the conic is not a
+    /// hyperbola or its asymptotes are not rational.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a coordinate overflows i64.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::conic::Conic;
+    /// use projgeom_rs::PgLine;
+    /// let hyperbola = Conic::from_coefficients(0, 1, 0, -2, -1, 1);
+    /// assert_eq!(
+    ///     hyperbola.asymptotes(),
+    ///     vec![PgLine::new([0, 1, -2]), PgLine::new([1, 0, -1])]
+    /// );
+    /// // the asymptotes of x² - 2y² = 1 have the irrational slopes ±1/√2
+    /// assert!(Conic::from_coefficients(1, 0, -2, 0, 0, -1).asymptotes().is_empty());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn asymptotes(&self) -> Vec<PgLine> {
+        if self.conic_type() != ConicType::Hyperbola {
+            return vec![];
+        }
+        // the points at infinity solve m00 x² + 2 m01 xy + m11 y² = 0
+        let [[m00, m01, _], [_, m11, _], _] = self.wide().rows;
+        let disc = m01 * m01 - m00 * m11;
+        let root = disc.isqrt();
+        if root * root != disc {
+            return vec![];
+        }
+        let ideal_points = if m00 != 0 {
+            [[root - m01, m00, 0], [-root - m01, m00, 0]]
+        } else {
+            [[1, 0, 0], [m11, -2 * m01, 0]]
+        };
+        ideal_points
+            .into_iter()
+            .map(|coord| self.wide_polar(coord))
+            .collect()
+    }
+
+    /// The function `axes` returns the axes of symmetry of the conic, i.e. the diameters
+    /// perpendicular to their conjugate chords. These are the polars of the points at infinity in
+    /// the principal directions, the eigenvectors of the quadratic part.
+    ///
+    /// Returns:
+    ///
+    /// The function `axes` returns the two axes of an ellipse or a hyperbola and the single axis of
+    /// a parabola. It returns none for a circle, for which every diameter is an axis, for a
+    /// degenerate conic, or if the principal directions are not rational.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an intermediate result overflows.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::conic::Conic;
+    /// use projgeom_rs::PgLine;
+    /// let hyperbola = Conic::from_coefficients(0, 1, 0, -2, -1, 1);
+    /// assert_eq!(
+    ///     hyperbola.axes(),
+    ///     vec![PgLine::new([1, 1, -3]), PgLine::new([1, -1, 1])]
+    /// );
+    /// let parabola = Conic::from_coefficients(1, 0, 0, 0, -1, 0);
+    /// assert_eq!(parabola.axes(), vec![PgLine::new([1, 0, 0])]);
+    /// assert!(Conic::from_coefficients(1, 0, 1, 0, 0, -1).axes().is_empty());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn axes(&self) -> Vec<PgLine> {
+        if self.is_degenerate() {
+            return vec![];
+        }
+        let [[m00, m01, _], [_, m11, _], _] = self.wide().rows;
+        let diff = m00 - m11;
+        let disc = diff
+            .checked_mul(diff)
+            .zip(m01.checked_mul(m01 * 4))
+            .and_then(|(p, q)| p.checked_add(q))
+            .expect("discriminant overflows i128");
+        let root = disc.isqrt();
+        if root == 0 || root * root != disc {
+            return vec![];
+        }
+        let directions = if m01 == 0 {
+            [[1, 0, 0], [0, 1, 0]]
+        } else {
+            [[2 * m01, root - diff, 0], [2 * m01, -root - diff, 0]]
+        };
+        // the polar of the direction of a parabola's axis is the line at infinity
+        directions
+            .into_iter()
+            .map(|coord| self.wide_polar(coord))
+            .filter(|ln| ln.coord[..2] != [0, 0])
+            .collect()
+    }
+
+    /// Value of the bilinear form `uᵀ M v`, or `None` on overflow.
+    fn bilinear(&self, v_u: &[i128; 3], v_v: &[i128; 3]) -> Option<i128> {
+        checked_dot(v_u, &self.wide().checked_apply(v_v)?)
+    }
+
+    /// The function `intersect_line` returns the two points where a line meets the conic, the same
+    /// point twice for a tangent, or `None` if the points are not real or not rational.
+    ///
+    /// Returns:
+    ///
+    /// The function `intersect_line` returns the points, `GeometryError::NotInGeneralPosition` if
+    /// the line is a component of the conic, or `GeometryError::Overflow` if an intermediate result
+    /// does not fit.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::conic::Conic;
+    /// use projgeom_rs::{PgLine, PgPoint};
+    /// let parabola = Conic::from_coefficients(1, 0, 0, 0, -1, 0);
+    /// let points = parabola.intersect_line(&PgLine::new([1, 1, -6])).unwrap();
+    /// assert_eq!(points, Some([[2, 4, 1], [-3, 9, 1]].map(PgPoint::new)));
+    /// assert_eq!(parabola.intersect_line(&PgLine::new([0, 1, 1])), Ok(None));
+    /// ```
+    pub fn intersect_line(&self, ln: &PgLine) -> Result<Option<[PgPoint; 2]>, GeometryError> {
+        // two distinct points of the line
+        let [l_a, l_b, l_c] = ln.coord.map(i128::from);
+        let candidates = [[l_b, -l_a, 0], [-l_c, 0, l_a], [0, l_c, -l_b]];
+        let independent = |u: &[i128; 3], v: &[i128; 3]| {
+            (0..3).any(|i| u[i] * v[(i + 1) % 3] != u[(i + 1) % 3] * v[i])
+        };
+        let Some(v_p) = candidates.iter().find(|v| **v != [0, 0, 0]) else {
+            return Err(GeometryError::NotInGeneralPosition);
+        };
+        let Some(v_q) = candidates.iter().find(|v| independent(v_p, v)) else {
+            return Err(GeometryError::NotInGeneralPosition);
+        };
+        let (a, b, c) = match (
+            self.bilinear(v_p, v_p),
+            self.bilinear(v_p, v_q),
+            self.bilinear(v_q, v_q),
+        ) {
+            (Some(a), Some(b), Some(c)) => (a, b, c),
+            _ => return Err(GeometryError::Overflow),
+        };
+        if (a, b, c) == (0, 0, 0) {
+            return Err(GeometryError::NotInGeneralPosition);
+        }
+        // s p + t q is on the conic when a s² + 2 b s t + c t² = 0
+        let combine = |s: i128, t: i128| {
+            let mut vec = [0_i128; 3];
+            for (x, (p, q)) in vec.iter_mut().zip(v_p.iter().zip(v_q)) {
+                *x = s
+                    .checked_mul(*p)
+                    .zip(t.checked_mul(*q))
+                    .and_then(|(u, v)| u.checked_add(v))
+                    .ok_or(GeometryError::Overflow)?;
+            }
+            narrow_reduced(vec)
+                .map(PgPoint::new)
+                .ok_or(GeometryError::Overflow)
+        };
+        if a == 0 {
+            return Ok(Some([combine(1, 0)?, combine(-c, 2 * b)?]));
+        }
+        let disc = b
+            .checked_mul(b)
+            .zip(a.checked_mul(c))
+            .and_then(|(u, v)| u.checked_sub(v))
+            .ok_or(GeometryError::Overflow)?;
+        if disc < 0 {
+            return Ok(None);
+        }
+        let root = disc.isqrt();
+        if root * root != disc {
+            return Ok(None);
+        }
+        Ok(Some([combine(root - b, a)?, combine(-root - b, a)?]))
+    }
+
+    /// Return the second intersection of the conic with the line through its point `base` and
+    /// the point `numer pt_q + denom pt_p` of the frame `[pt_p, pt_q]`.
+    fn second_intersection(
+        &self,
+        base: &[i64; 3],
+        [pt_p, pt_q]: &[[i64; 3]; 2],
+        numer: i64,
+        denom: i64,
+    ) -> PgPoint {
+        // with q(base) = 0, the point 2 B(base, dir) dir - q(dir) base is on the conic
+        let base = base.map(i128::from);
+        let dir =
+            [0, 1, 2].map(|i| denom as i128 * pt_p[i] as i128 + numer as i128 * pt_q[i] as i128);
+        self.wide()
+            .checked_apply(&dir)
+            .and_then(|m_dir| {
+                let b_2 = checked_dot(&base, &m_dir)?.checked_mul(2)?;
+                let q_dir = checked_dot(&dir, &m_dir)?;
+                let mut coord = [0_i128; 3];
+                for (x, (d, o)) in coord.iter_mut().zip(dir.iter().zip(&base)) {
+                    *x = b_2.checked_mul(*d)?.checked_sub(q_dir.checked_mul(*o)?)?;
+                }
+                narrow_reduced(coord)
+            })
+            .map(PgPoint::new)
+            .expect("coordinate overflows i64")
+    }
+
+    /// The function `rational_parametrization` returns the stereographic projection from a point of
+    /// the conic: the parameter `t` gives the second intersection of the conic with the line through
+    /// `base_point` and the point `t` of a fixed line avoiding it. Every rational point of a
+    /// nondegenerate conic is the image of exactly one parameter, or of `∞`, see `rational_points`.
+    ///
+    /// Arguments:
+    ///
+    /// * `base_point`: A point of the conic, the image of the parameter of the tangent at it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `base_point` is not on the conic, or the returned function panics if a
+    /// coordinate overflows i64.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use fractions::Fraction;
+    /// use projgeom_rs::conic::Conic;
+    /// use projgeom_rs::PgPoint;
+    /// let circle = Conic::from_coefficients(1, 0, 1, 0, 0, -1);
+    /// let param = circle.rational_parametrization(&PgPoint::new([-1, 0, 1]));
+    /// let pt = param(Fraction::new(1, 2));
+    /// assert!(circle.contains(&pt));
+    /// assert_eq!(pt, PgPoint::new([15, -8, 17]));
+    /// ```
+    pub fn rational_parametrization(
+        &self,
+        base_point: &PgPoint,
+    ) -> impl Fn(Fraction<i64>) -> PgPoint {
+        assert!(self.contains(base_point), "base point is not on the conic");
+        let (conic, base) = (self.clone(), base_point.coord);
+        let frame = stereographic_frame(&base);
+        move |t| conic.second_intersection(&base, &frame, t.numer, t.denom)
+    }
+
+    /// The function `rational_points` iterates over the rational points of the conic given by the
+    /// parameters of `rational_parametrization` of height at most `height`, i.e. the point of `∞`
+    /// and those of the fractions `n / d` with `|n|, d <= height`. For a nondegenerate conic, the
+    /// points are distinct.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `base_point` is not on the conic, or iterating panics if a coordinate overflows
+    /// i64.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::conic::Conic;
+    /// use projgeom_rs::PgPoint;
+    /// let circle = Conic::from_coefficients(1, 0, 1, 0, 0, -1);
+    /// let base = PgPoint::new([-1, 0, 1]);
+    /// // the parameters ∞, -1, 0 and 1
+    /// assert_eq!(circle.rational_points(&base, 1).count(), 4);
+    /// assert!(circle.rational_points(&base, 5).all(|pt| circle.contains(&pt)));
+    /// ```
+    pub fn rational_points(
+        &self,
+        base_point: &PgPoint,
+        height: i64,
+    ) -> impl Iterator<Item = PgPoint> + '_ {
+        assert!(self.contains(base_point), "base point is not on the conic");
+        let base = base_point.coord;
+        let frame = stereographic_frame(&base);
+        let fractions = (1..=height).flat_map(move |denom| {
+            (-height..=height)
+                .filter(move |numer| numer.gcd(&denom) == 1)
+                .map(move |numer| (numer, denom))
+        });
+        core::iter::once((1, 0))
+            .chain(fractions)
+            .map(move |(numer, denom)| self.second_intersection(&base, &frame, numer, denom))
+    }
+}
+
+/// Two points spanning the line with the same coordinates as the nonzero vector `base`, which
+/// does not pass through the point `base`.
+fn stereographic_frame(base: &[i64; 3]) -> [[i64; 3]; 2] {
+    let k = (0..3).find(|&k| base[k] != 0).expect("base point is zero");
+    let unit = |i: usize| {
+        let mut vec = [0; 3];
+        vec[i] = 1;
+        vec
+    };
+    [
+        cross_product(base, &unit((k + 1) % 3)),
+        cross_product(base, &unit((k + 2) % 3)),
+    ]
+}
+
+/// Call `visit` with each odd prime factor of the nonzero `n` found by trial division, and return
+/// whether the factorization is complete, i.e. the cofactor left is 1 or a prime.
+fn for_each_odd_prime_factor(mut n: u128, mut visit: impl FnMut(u128)) -> bool {
+    const BOUND: u128 = 1 << 20;
+    n >>= n.trailing_zeros();
+    let mut p = 3;
+    while p < BOUND && p * p <= n {
+        if n.is_multiple_of(p) {
+            visit(p);
+            while n.is_multiple_of(p) {
+                n /= p;
+            }
+        }
+        p += 2;
+    }
+    if n > 1 && p * p > n {
+        visit(n);
+    }
+    p * p > n
+}
+
+/// The Hilbert symbol `(x, y)` at the odd prime `p`, of the products `x` and `y` of the entries of
+/// the slices, which are nonzero.
+fn hilbert_symbol(x: &[i128], y: &[i128], p: u128) -> i8 {
+    // the valuation of the product and its unit part modulo p
+    let split = |factors: &[i128]| {
+        factors.iter().fold((0_u32, 1_u128), |(val, unit), f| {
+            let (mut m, mut v) = (f.unsigned_abs(), 0);
+            while m.is_multiple_of(p) {
+                m /= p;
+                v += 1;
+            }
+            let residue = if *f < 0 { p - m % p } else { m % p };
+            (val + v, unit * residue % p)
+        })
+    };
+    let legendre = |a: u128| {
+        let (mut base, mut exp, mut power) = (a, (p - 1) / 2, 1);
+        while exp > 0 {
+            if exp & 1 == 1 {
+                power = power * base % p;
+            }
+            base = base * base % p;
+            exp >>= 1;
+        }
+        if power == 1 {
+            1
+        } else {
+            -1
+        }
+    };
+    let ((alpha, u), (beta, v)) = (split(x), split(y));
+    let mut symbol = if alpha % 2 == 1 && beta % 2 == 1 && p % 4 == 3 {
+        -1
+    } else {
+        1
+    };
+    if beta % 2 == 1 {
+        symbol *= legendre(u);
+    }
+    if alpha % 2 == 1 {
+        symbol *= legendre(v);
+    }
+    symbol
+}
+
+/// Divide the entries by their gcd.
+fn reduce_wide(coord: [i128; 3]) -> [i128; 3] {
+    match coord.iter().fold(0_i128, |g, x| g.gcd(x)) {
+        0 => coord,
+        common => coord.map(|x| x / common),
+    }
+}
+
+/// Divide the entries by their gcd and narrow to i64, or `None` on overflow.
+fn narrow_reduced(coord: [i128; 3]) -> Option<[i64; 3]> {
+    let [x, y, z] = reduce_wide(coord).map(i64::try_from);
+    Some([x.ok()?, y.ok()?, z.ok()?])
+}
+
+/// Return the meets of the opposite sides of a hexagon, given by its vertices.
+pub(crate) fn opposite_meets<Point, Line>(hexagon: &[Point; 6]) -> [Point; 3]
+where
+    Point: ProjectivePlanePrimitive<Line>,
+    Line: ProjectivePlanePrimitive<Point>,
+{
+    let sides: [Line; 6] = core::array::from_fn(|i| hexagon[i].meet(&hexagon[(i + 1) % 6]));
+    core::array::from_fn(|i| sides[i].meet(&sides[i + 3]))
+}
+
+/// Return the common join of three points given by coordinates, or an error if a point is
+/// undefined (zero) or the points do not determine a common line.
+fn common_join(coords: [[i64; 3]; 3]) -> Result<[i64; 3], GeometryError> {
+    let [v_a, v_b, v_c] = coords;
+    if coords.contains(&[0, 0, 0]) {
+        return Err(GeometryError::NotInGeneralPosition);
+    }
+    let join = match cross_product(&v_a, &v_b) {
+        [0, 0, 0] => cross_product(&v_a, &v_c),
+        join => join,
+    };
+    if join == [0, 0, 0] || dot_product(&join, &v_c) != 0 {
+        return Err(GeometryError::NotInGeneralPosition);
+    }
+    Ok(join)
+}
+
+/// The function `pascal_line` returns the Pascal line of a hexagon inscribed in a conic: the line
+/// through the three meets of opposite sides.
+///
+/// Arguments:
+///
+/// * `conic`: The conic.
+/// * `hexagon`: The six vertices of the hexagon, in order.
+///
+/// Returns:
+///
+/// The function `pascal_line` returns the Pascal line, or `GeometryError::NotOnConic` if a vertex
+/// is not on the conic, or `GeometryError::NotInGeneralPosition` if the vertices are not distinct
+/// enough for the line to be determined.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::conic::{pascal_line, Conic};
+/// use projgeom_rs::{PgLine, PgPoint};
+/// // the parabola y = x²
+/// let parabola = Conic::from_coefficients(1, 0, 0, 0, -1, 0);
+/// let hexagon = [-2, 0, 1, 2, -1, 3].map(|x| PgPoint::new([x, x * x, 1]));
+/// let pascal = pascal_line(&parabola, &hexagon).unwrap();
+/// assert_eq!(pascal, PgLine::new([13, -7, 18]));
+/// ```
+pub fn pascal_line(conic: &Conic, hexagon: &[PgPoint; 6]) -> Result<PgLine, GeometryError> {
+    if !hexagon.iter().all(|pt| conic.contains(pt)) {
+        return Err(GeometryError::NotOnConic);
+    }
+    let meets = opposite_meets::<PgPoint, PgLine>(hexagon).map(|pt| pt.coord);
+    Ok(PgLine::new(common_join(meets)?))
+}
+
+/// The function `brianchon_point` returns the Brianchon point of a hexagon circumscribed about a
+/// conic: the point on the three diagonals joining opposite vertices. This is the dual of
+/// `pascal_line`.
+///
+/// Arguments:
+///
+/// * `conic`: The conic.
+/// * `sides`: The six sides of the hexagon, in order, all tangent to the conic.
+///
+/// Returns:
+///
+/// The function `brianchon_point` returns the Brianchon point, or `GeometryError::NotTangent` if a
+/// side is not tangent to the conic, or `GeometryError::NotInGeneralPosition` if the sides are not
+/// distinct enough for the point to be determined.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::conic::{brianchon_point, Conic};
+/// use projgeom_rs::PgPoint;
+/// let parabola = Conic::from_coefficients(1, 0, 0, 0, -1, 0);
+/// let sides = [-2, 0, 1, 2, -1, 3].map(|x| parabola.polar(&PgPoint::new([x, x * x, 1])));
+/// let brianchon = brianchon_point(&parabola, &sides).unwrap();
+/// assert!(!parabola.contains(&brianchon));
+/// ```
+pub fn brianchon_point(conic: &Conic, sides: &[PgLine; 6]) -> Result<PgPoint, GeometryError> {
+    if !sides.iter().all(|ln| conic.is_tangent(ln)) {
+        return Err(GeometryError::NotTangent);
+    }
+    let diagonals = opposite_meets::<PgLine, PgPoint>(sides).map(|ln| ln.coord);
+    Ok(PgPoint::new(common_join(diagonals)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pg_object::RatPoint;
+    use crate::pg_plane::{ProjectivePlane, ProjectivePlanePrimitive};
+
+    #[test]
+    fn test_conic() {
+        let conic = Conic::from_coefficients(2, -3, 1, 5, -4, 7);
+        assert_eq!(conic.mat, [[4, -3, 5], [-3, 2, -4], [5, -4, 14]]);
+        assert_eq!(conic.det(), -8);
+        assert_eq!(conic.conic_type(), ConicType::Hyperbola);
+        assert_eq!(
+            Conic::from_coefficients(1, 0, 1, 0, 0, 1).conic_type(),
+            ConicType::ImaginaryEllipse
+        );
+        // x² - y² = 0 is a pair of lines
+        let pair = Conic::from_coefficients(1, 0, -1, 0, 0, 0);
+        assert_eq!(pair.conic_type(), ConicType::Degenerate);
+        assert!(pair.contains(&PgPoint::new([2, -2, 7])));
+    }
+
+    #[test]
+    fn test_through_points() {
+        use crate::canonical::CanonicalForm;
+        let conic = Conic::from_coefficients(2, -3, 1, 5, -4, 7);
+        let base = PgPoint::new([1, 2, 0]);
+        assert!(conic.contains(&base));
+        let param = conic.rational_parametrization(&base);
+        let points = [-2, -1, 1, 2, 3].map(|t| param(Fraction::new(t, 1)));
+        let through = Conic::through_points(&points).unwrap();
+        assert_eq!(through.canonical_form(), conic.canonical_form());
+        let [pt_a, pt_b, pt_c, pt_d, _] = points.clone();
+        assert_eq!(
+            Conic::through_points(&[pt_a, pt_b, pt_c, pt_d, base]),
+            Ok(through)
+        );
+        // four points and a point on the line through two of them
+        let [pt_a, pt_b, pt_c, pt_d, _] = points;
+        let pt_e = pt_a.parametrize(2, &pt_b, -1);
+        assert_eq!(
+            Conic::through_points(&[pt_a, pt_b, pt_c, pt_d, pt_e]),
+            Err(GeometryError::NotInGeneralPosition)
+        );
+    }
+
+    #[test]
+    fn test_tangent() {
+        // the parabola y = x², i.e. x² - yz = 0
+        let parabola = Conic::from_coefficients(1, 0, 0, 0, -1, 0);
+        let pt_p = PgPoint::new([3, 9, 1]);
+        assert!(parabola.contains(&pt_p));
+        let tangent = parabola.polar(&pt_p);
+        assert!(tangent.incident(&pt_p));
+        // y = 6x - 9
+        assert_eq!(tangent, PgLine::new([6, -1, -9]));
+        // the tangent at the ideal point [0 : 1 : 0] is the line at infinity
+        assert_eq!(
+            parabola.polar(&PgPoint::new([0, 1, 0])),
+            PgLine::new([0, 0, 1])
+        );
+        assert!(parabola.is_tangent(&tangent));
+        assert!(parabola.is_tangent(&PgLine::new([0, 0, 1])));
+        assert!(!parabola.is_tangent(&PgLine::new([0, 1, -1])));
+        assert_eq!(parabola.pole(&tangent), pt_p);
+        assert_eq!(
+            parabola.pole(&PgLine::new([0, 1, -1])),
+            PgPoint::new([0, -1, 1])
+        );
+    }
+
+    #[test]
+    fn test_conjugates() {
+        let ellipse = Conic::from_coefficients(1, 0, 4, 0, 0, -4);
+        let pt_p = PgPoint::new([3, 1, 1]);
+        let ln_l = PgLine::new([1, -1, 0]);
+        let pt_q = ellipse.conjugate_point_on_line(&pt_p, &ln_l).unwrap();
+        assert!(pt_q.incident(&ln_l));
+        assert!(ellipse.are_conjugate_points(&pt_p, &pt_q));
+        assert!(ellipse.are_conjugate_points(&pt_q, &pt_p));
+        // the polars of conjugate points are conjugate lines
+        let [ln_p, ln_q] = [&pt_p, &pt_q].map(|pt| ellipse.polar(pt));
+        assert!(ellipse.are_conjugate_lines(&ln_p, &ln_q));
+        assert!(!ellipse.are_conjugate_lines(&ln_p, &ln_l));
+        // points on the conic and tangents are self-conjugate
+        let pt_r = PgPoint::new([2, 0, 1]);
+        assert!(ellipse.are_conjugate_points(&pt_r, &pt_r));
+        let tangent = ellipse.polar(&pt_r);
+        assert!(ellipse.are_conjugate_lines(&tangent, &tangent));
+        // the vertex of a line pair is conjugate to every point
+        let pair = Conic::from_coefficients(1, 0, -1, 0, 0, 0);
+        assert_eq!(
+            pair.conjugate_point_on_line(&PgPoint::new([0, 0, 1]), &ln_l),
+            Err(GeometryError::NotInGeneralPosition)
+        );
+    }
+
+    #[test]
+    fn test_transformed() {
+        let conic = Conic::from_coefficients(2, -3, 1, 5, -4, 7);
+        let trans = Transform::new([[3, -1, 2], [1, 4, 0], [-2, 1, 5]]).unwrap();
+        let image = conic.transformed(&trans);
+        assert_eq!(image.is_degenerate(), conic.is_degenerate());
+        // incidence, polarity and tangency are preserved
+        let base = PgPoint::new([1, 2, 0]);
+        let pt_p = PgPoint::new([2, -1, 3]);
+        assert!(image.contains(&trans.apply(&base)));
+        assert_eq!(
+            image.polar(&trans.apply(&pt_p)),
+            trans.apply_line(&conic.polar(&pt_p))
+        );
+        assert!(image.is_tangent(&trans.apply_line(&conic.polar(&base))));
+        let back = image.transformed(&trans.inverse().unwrap());
+        assert_eq!(back, conic);
+        assert_eq!(conic.transformed(&Transform::IDENTITY), conic);
+
+        // the conic through the exact images of five points is the image conic
+        let circle = Conic::new([[1, 0, 0], [0, 1, 0], [0, 0, -25]]);
+        let points = [[5, 0, 1], [3, 4, 1], [0, 5, 1], [-4, 3, 1], [0, -5, 1]].map(PgPoint::new);
+        let images = points.map(|pt| {
+            let coord = trans.try_apply_point_fraction(&pt).unwrap();
+            PgPoint::try_from(RatPoint::new(coord)).unwrap()
+        });
+        assert_eq!(
+            Conic::through_points(&images),
+            Ok(circle.transformed(&trans))
+        );
+    }
+
+    #[test]
+    fn test_normal_form() {
+        use crate::canonical::CanonicalForm;
+        let unit = Conic::new([[1, 0, 0], [0, 1, 0], [0, 0, -1]]);
+        for conic in [
+            Conic::from_coefficients(2, -3, 1, 5, -4, 7),
+            Conic::from_coefficients(5, 4, 8, -14, -20, -19),
+            Conic::from_coefficients(0, 1, 0, -2, -1, 1),
+            Conic::from_coefficients(-1, 0, -1, 0, 0, 25),
+        ] {
+            let (circle, trans) = conic.normal_form().unwrap();
+            assert_eq!(circle, unit);
+            assert_eq!(conic.transformed(&trans), unit);
+            let back = unit.transformed(&trans.inverse().unwrap());
+            assert_eq!(back.canonical_form(), conic.canonical_form());
+        }
+        assert_eq!(
+            Conic::from_coefficients(1, 0, 1, 0, 0, 1).normal_form(),
+            Err(GeometryError::Irrational)
+        );
+        // x² + y² = 3 z² in other coordinates has no rational point
+        let trans = Transform::new([[2, 1, 0], [1, 1, 0], [0, 3, 1]]).unwrap();
+        let no_points = Conic::from_coefficients(1, 0, 1, 0, 0, -3).transformed(&trans);
+        assert_eq!(no_points.has_rational_point(), Some(false));
+        assert_eq!(no_points.normal_form(), Err(GeometryError::Irrational));
+        // the prime 10⁹ + 9 is a sum of two squares, but not of two small ones
+        let far_points = Conic::from_coefficients(1, 0, 1, 0, 0, -1_000_000_009);
+        assert_eq!(far_points.has_rational_point(), Some(true));
+        assert_eq!(far_points.normal_form(), Err(GeometryError::NotFound));
+        // the product of two primes beyond trial division
+        let product = 2_147_483_647 * 2_147_483_629;
+        let undecided = Conic::from_coefficients(1, 0, 1, 0, 0, -product);
+        assert_eq!(undecided.has_rational_point(), None);
+        assert_eq!(
+            Conic::from_coefficients(1, 0, -1, 0, 0, 0).normal_form(),
+            Err(GeometryError::NotInGeneralPosition)
+        );
+    }
+
+    #[test]
+    fn test_center_axes_asymptotes() {
+        // the ellipse 5(x - 1)² + 4(x - 1)(y - 1) + 8(y - 1)² = 36 with principal directions
+        // [2, -1] and [1, 2]
+        let ellipse = Conic::from_coefficients(5, 4, 8, -14, -20, -19);
+        assert_eq!(ellipse.conic_type(), ConicType::Ellipse);
+        let center = ellipse.center().unwrap();
+        assert_eq!(center, PgPoint::new([1, 1, 1]));
+        let axes = ellipse.axes();
+        assert_eq!(axes.len(), 2);
+        assert!(axes.contains(&PgLine::new([1, 2, -3])));
+        assert!(axes.contains(&PgLine::new([2, -1, -1])));
+        assert!(axes.iter().all(|ln| ln.incident(&center)));
+        assert!(ellipse.asymptotes().is_empty());
+
+        // a hyperbola with rational asymptotes through its center
+        let hyperbola = Conic::from_coefficients(0, 1, 0, -2, -1, 1);
+        let center = hyperbola.center().unwrap();
+        for asymptote in hyperbola.asymptotes() {
+            assert!(asymptote.incident(&center));
+            assert!(hyperbola.is_tangent(&asymptote));
+        }
+        // x² - 4y² = 4 has the asymptotes x = ±2y
+        let hyperbola = Conic::from_coefficients(1, 0, -4, 0, 0, -4);
+        assert_eq!(
+            hyperbola.asymptotes(),
+            vec![PgLine::new([1, -2, 0]), PgLine::new([1, 2, 0])]
+        );
+        assert_eq!(
+            hyperbola.axes(),
+            vec![PgLine::new([1, 0, 0]), PgLine::new([0, 1, 0])]
+        );
+
+        // the parabola x = (y - 3)², with the axis y = 3 and no center
+        let parabola = Conic::from_coefficients(0, 0, 1, -1, -6, 9);
+        assert_eq!(parabola.conic_type(), ConicType::Parabola);
+        assert_eq!(parabola.center(), None);
+        assert_eq!(parabola.axes(), vec![PgLine::new([0, 1, -3])]);
+        assert!(parabola.asymptotes().is_empty());
+
+        // a pair of lines meets in its center, but has no axes
+        let pair = Conic::from_coefficients(1, 0, -1, -2, 0, 1);
+        assert_eq!(pair.center(), Some(PgPoint::new([1, 0, 1])));
+        assert!(pair.axes().is_empty());
+    }
+
+    #[test]
+    fn test_rational_parametrization() {
+        let conic = Conic::from_coefficients(2, -3, 1, 5, -4, 7);
+        let base = PgPoint::new([1, 1, 0]);
+        let param = conic.rational_parametrization(&base);
+        let points: Vec<PgPoint> = conic.rational_points(&base, 3).collect();
+        // ∞ and the fractions n / d with |n| <= 3, d <= 3 in lowest terms
+        assert_eq!(points.len(), 1 + 7 + 4 + 4);
+        for (i, pt) in points.iter().enumerate() {
+            assert!(conic.contains(pt));
+            assert!(points[i + 1..].iter().all(|other| other != pt));
+        }
+        // the base point belongs to the tangent at it
+        assert!(points.contains(&base));
+        assert_eq!(points[1], param(Fraction::from(-3)));
+        assert_eq!(points[15], param(Fraction::new(2, 3)));
+        // pascal_line needs six points on the conic
+        let hexagon: [PgPoint; 6] = core::array::from_fn(|i| points[2 * i + 1].clone());
+        assert!(pascal_line(&conic, &hexagon).is_ok());
+    }
+
+    #[test]
+    fn test_pascal_brianchon() {
+        let conic = Conic::from_coefficients(2, -3, 1, 5, -4, 7);
+        // rational points of the conic, by the lines through [1 : 1 : 0] on it
+        let pt_o = PgPoint::new([1, 1, 0]);
+        assert!(conic.contains(&pt_o));
+        let pt_a = PgPoint::new([0, 0, 1]);
+        assert!(!conic.contains(&pt_a));
+        let mut points = [1, 2, -1, 3, -2, 5].map(|t| {
+            // second intersection of the line through pt_o and [t : 0 : 1]
+            let pt_d = PgPoint::new([t, 0, 1]);
+            let (q_dd, q_od) = (conic.eval(&pt_d), conic.polar(&pt_o).coord);
+            let b_od = dot_product(&q_od, &pt_d.coord) as i128;
+            // x = 2 b_od pt_d - q_dd pt_o, since q(pt_o) = 0
+            let coord =
+                [0, 1, 2].map(|i| 2 * b_od * pt_d.coord[i] as i128 - q_dd * pt_o.coord[i] as i128);
+            PgPoint::new(coord.map(|x| i64::try_from(x).unwrap())).normalized()
+        });
+        assert!(points.iter().all(|pt| conic.contains(pt)));
+        let pascal = pascal_line(&conic, &points).unwrap();
+        // the Pascal line does not depend on where the hexagon starts
+        points.rotate_left(2);
+        assert_eq!(pascal_line(&conic, &points), Ok(pascal.clone()));
+
+        // the tangents at the vertices form a hexagon whose Brianchon point is the pole of the
+        // Pascal line
+        let sides = points.clone().map(|pt| conic.polar(&pt));
+        let brianchon = brianchon_point(&conic, &sides).unwrap();
+        assert_eq!(conic.polar(&brianchon), pascal);
+        for i in 0..3 {
+            let vertex_1 = sides[i].meet(&sides[(i + 1) % 6]);
+            let vertex_2 = sides[i + 3].meet(&sides[(i + 4) % 6]);
+            assert!(brianchon.incident(&vertex_1.join(&vertex_2)));
+        }
+
+        let mut off = points.clone();
+        off[4] = PgPoint::new([1, 2, 3]);
+        assert_eq!(pascal_line(&conic, &off), Err(GeometryError::NotOnConic));
+        let mut repeated = points.clone();
+        repeated[1] = repeated[0].clone();
+        assert_eq!(
+            pascal_line(&conic, &repeated),
+            Err(GeometryError::NotInGeneralPosition)
+        );
+        let mut secant = sides.clone();
+        secant[2] = points[0].join(&points[1]);
+        assert_eq!(
+            brianchon_point(&conic, &secant),
+            Err(GeometryError::NotTangent)
+        );
+    }
+}
@@ -0,0 +1,504 @@
+// Pencils of conics
+
+use crate::conic::Conic;
+use crate::error::GeometryError;
+use crate::mat3::{checked_dot, integer_roots, Mat3};
+use crate::pg_object::{PgLine, PgPoint};
+use crate::pg_plane::Join;
+use num_integer::Integer;
+
+/// The `ConicPencil` struct is the pencil of conics `lambda A + mu B` spanned by two conics. Its
+/// members are the conics through the (generically four) common points of `A` and `B`, the base
+/// points of the pencil.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::conic::Conic;
+/// use projgeom_rs::conic_pencil::ConicPencil;
+/// use projgeom_rs::PgPoint;
+/// // x² + y² = 25 and x² + 4y² = 73 meet in the points (±3, ±4)
+/// let pencil = ConicPencil::new(
+///     Conic::from_coefficients(1, 0, 1, 0, 0, -25),
+///     Conic::from_coefficients(1, 0, 4, 0, 0, -73),
+/// );
+/// assert!(pencil.member(2, -3).contains(&PgPoint::new([3, -4, 1])));
+/// assert_eq!(pencil.degenerate_members().len(), 3);
+/// assert_eq!(pencil.base_points().unwrap().len(), 4);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ConicPencil {
+    conic_a: Conic,
+    conic_b: Conic,
+}
+
+impl ConicPencil {
+    /// Create the pencil spanned by two conics.
+    #[inline]
+    pub fn new(conic_a: Conic, conic_b: Conic) -> Self {
+        Self { conic_a, conic_b }
+    }
+
+    /// The function `member` returns the conic `lambda A + mu B` of the pencil.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an entry overflows i64.
+    pub fn member(&self, lambda: i64, mu: i64) -> Conic {
+        let mut mat = [[0_i64; 3]; 3];
+        for (i, row) in mat.iter_mut().enumerate() {
+            for (j, x) in row.iter_mut().enumerate() {
+                *x = lambda
+                    .checked_mul(self.conic_a.mat[i][j])
+                    .zip(mu.checked_mul(self.conic_b.mat[i][j]))
+                    .and_then(|(p, q)| p.checked_add(q))
+                    .expect("coefficient overflows i64");
+            }
+        }
+        Conic::new(mat)
+    }
+
+    /// Coefficients `[c0, c1, c2, c3]` of the binary cubic
+    /// `det(lambda A + mu B) = c0 lambda³ + c1 lambda² mu + c2 lambda mu² + c3 mu³`.
+    fn det_form(&self) -> Result<[i128; 4], GeometryError> {
+        let wide_a = Mat3::from(self.conic_a.mat).map(i128::from);
+        let wide_b = Mat3::from(self.conic_b.mat).map(i128::from);
+        // the mixed coefficients are tr(adj(A) B) and tr(A adj(B)), i.e. the sums of the products
+        // of the entries with the cofactors of the other matrix
+        let mixed = |mat: &Mat3<i128>, other: &Mat3<i128>| {
+            let cof = mat.checked_cofactors()?;
+            (0..3).try_fold(0_i128, |sum, i| {
+                sum.checked_add(checked_dot(&cof.rows[i], &other.rows[i])?)
+            })
+        };
+        match (
+            wide_a.checked_det(),
+            mixed(&wide_a, &wide_b),
+            mixed(&wide_b, &wide_a),
+            wide_b.checked_det(),
+        ) {
+            (Some(c0), Some(c1), Some(c2), Some(c3)) => Ok([c0, c1, c2, c3]),
+            _ => Err(GeometryError::Overflow),
+        }
+    }
+
+    /// The parameters `[lambda, mu]` of the distinct degenerate members with rational parameters,
+    /// in lowest terms with the first nonzero entry positive.
+    fn degenerate_parameters(&self) -> Result<Vec<[i64; 2]>, GeometryError> {
+        let [c0, c1, c2, c3] = self.det_form()?;
+        let mut params: Vec<[i128; 2]> = Vec::new();
+        if c0 != 0 {
+            // with lambda / mu = s / c0, the cubic becomes s³ + c1 s² + c0 c2 s + c0² c3, which is
+            // monic, so that its rational roots are integers
+            let c0_c2 = c0.checked_mul(c2);
+            let c0_c0_c3 = c0.checked_mul(c0).and_then(|x| x.checked_mul(c3));
+            let (Some(c0_c2), Some(c0_c0_c3)) = (c0_c2, c0_c0_c3) else {
+                return Err(GeometryError::Overflow);
+            };
+            for root in integer_roots(&[-c0_c0_c3, c0_c2, -c1]) {
+                params.push([root, c0]);
+            }
+        } else {
+            // A is degenerate, and the other members solve c1 lambda² + c2 lambda mu + c3 mu² = 0
+            params.push([1, 0]);
+            if c1 != 0 {
+                let disc = c2
+                    .checked_mul(c2)
+                    .zip(c1.checked_mul(c3).and_then(|x| x.checked_mul(4)))
+                    .and_then(|(p, q)| p.checked_sub(q))
+                    .ok_or(GeometryError::Overflow)?;
+                let root = if disc < 0 { -1 } else { disc.isqrt() };
+                if root >= 0 && root.checked_mul(root) == Some(disc) {
+                    let (Some(lambda_1), Some(lambda_2), Some(mu)) = (
+                        root.checked_sub(c2),
+                        (-root).checked_sub(c2),
+                        c1.checked_mul(2),
+                    ) else {
+                        return Err(GeometryError::Overflow);
+                    };
+                    params.push([lambda_1, mu]);
+                    params.push([lambda_2, mu]);
+                }
+            } else if c2 != 0 {
+                params.push([-c3, c2]);
+            } else if c3 == 0 {
+                // every member is degenerate
+                return Ok(vec![]);
+            }
+        }
+        let mut result: Vec<[i64; 2]> = Vec::new();
+        for [lambda, mu] in params {
+            let common = lambda.gcd(&mu);
+            let common = if lambda < 0 || (lambda == 0 && mu < 0) {
+                -common
+            } else {
+                common
+            };
+            let param = [lambda / common, mu / common].map(i64::try_from);
+            let [Ok(lambda), Ok(mu)] = param else {
+                return Err(GeometryError::Overflow);
+            };
+            if !result.contains(&[lambda, mu]) {
+                result.push([lambda, mu]);
+            }
+        }
+        Ok(result)
+    }
+
+    /// The function `degenerate_members` returns the distinct degenerate members, i.e. the line
+    /// pairs through the base points, found by the rational roots of the cubic
+    /// `det(lambda A + mu B) = 0`. Members of irrational parameters are not reported.
+    ///
+    /// Returns:
+    ///
+    /// The function `degenerate_members` returns the members in the order of their parameters, or
+    /// none if every member is degenerate.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an intermediate result overflows.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::conic::Conic;
+    /// use projgeom_rs::conic_pencil::ConicPencil;
+    /// let pencil = ConicPencil::new(
+    ///     Conic::from_coefficients(1, 0, 1, 0, 0, -25),
+    ///     Conic::from_coefficients(1, 0, 4, 0, 0, -73),
+    /// );
+    /// assert!(pencil.degenerate_members().iter().all(|conic| conic.is_degenerate()));
+    /// // 3y² = 48, i.e. the pair of lines y = ±4
+    /// assert!(pencil.degenerate_members().contains(&pencil.member(1, -1)));
+    /// ```
+    pub fn degenerate_members(&self) -> Vec<Conic> {
+        self.degenerate_parameters()
+            .expect("coordinate overflow")
+            .into_iter()
+            .map(|[lambda, mu]| self.member(lambda, mu))
+            .collect()
+    }
+
+    /// The function `base_points` returns the real rational base points of the pencil, i.e. the
+    /// common points of its members. They are found by splitting a degenerate member into two
+    /// rational lines and meeting them with another member.
+    ///
+    /// Returns:
+    ///
+    /// The function `base_points` returns the distinct base points, `GeometryError::Irrational` if
+    /// no degenerate member splits into rational lines, `GeometryError::NotInGeneralPosition` if
+    /// every member is degenerate, e.g. if the conics share a line, or `GeometryError::Overflow`.
+    /// Base points that are not real or not rational are not reported.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::conic::Conic;
+    /// use projgeom_rs::conic_pencil::ConicPencil;
+    /// use projgeom_rs::PgPoint;
+    /// // the parabolas y = x² and x = y² meet in (0, 0) and (1, 1), and twice at infinity
+    /// let pencil = ConicPencil::new(
+    ///     Conic::from_coefficients(1, 0, 0, 0, -1, 0),
+    ///     Conic::from_coefficients(0, 0, 1, -1, 0, 0),
+    /// );
+    /// let points = pencil.base_points().unwrap();
+    /// assert!(points.contains(&PgPoint::new([0, 0, 1])));
+    /// assert!(points.contains(&PgPoint::new([1, 1, 1])));
+    /// ```
+    pub fn base_points(&self) -> Result<Vec<PgPoint>, GeometryError> {
+        let params = self.degenerate_parameters()?;
+        if params.is_empty() {
+            return Err(GeometryError::NotInGeneralPosition);
+        }
+        for [lambda, mu] in params {
+            let Some(lines) = split_lines(&self.member(lambda, mu))? else {
+                continue;
+            };
+            // any other member meets the line pair in the base points
+            let other = if mu == 0 {
+                &self.conic_b
+            } else {
+                &self.conic_a
+            };
+            let mut points: Vec<PgPoint> = Vec::new();
+            for ln in lines {
+                for pt in other.intersect_line(&ln)?.into_iter().flatten() {
+                    if !points.contains(&pt) {
+                        points.push(pt);
+                    }
+                }
+            }
+            return Ok(points);
+        }
+        Err(GeometryError::Irrational)
+    }
+
+    /// The function `self_polar_triangle` returns the common self-polar triangle of the conics
+    /// of the pencil. Its vertices are the singular points of the three degenerate members, i.e.
+    /// the diagonal points of the quadrangle of base points, and each vertex has the same polar,
+    /// the opposite side, with respect to every member.
+    ///
+    /// Returns:
+    ///
+    /// The function `self_polar_triangle` returns the vertices in the order of the parameters of
+    /// the degenerate members. It returns `GeometryError::NotInGeneralPosition` if the pencil has
+    /// fewer than three distinct degenerate members, e.g. if the conics touch,
+    /// `GeometryError::Irrational` if a degenerate member is not real or not rational, or
+    /// `GeometryError::Overflow`.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::conic::Conic;
+    /// use projgeom_rs::conic_pencil::ConicPencil;
+    /// use projgeom_rs::PgPoint;
+    /// let pencil = ConicPencil::new(
+    ///     Conic::from_coefficients(1, 0, 1, 0, 0, -25),
+    ///     Conic::from_coefficients(1, 0, 4, 0, 0, -73),
+    /// );
+    /// let triangle = pencil.self_polar_triangle().unwrap();
+    /// assert!(triangle.contains(&PgPoint::new([0, 0, 1])));
+    /// assert!(triangle.contains(&PgPoint::new([1, 0, 0])));
+    /// assert!(triangle.contains(&PgPoint::new([0, 1, 0])));
+    /// ```
+    pub fn self_polar_triangle(&self) -> Result<[PgPoint; 3], GeometryError> {
+        let [c0, c1, c2, _] = self.det_form()?;
+        let params = self.degenerate_parameters()?;
+        // a repeated root of the binary cubic det(lambda A + mu B) is rational, and so is then the
+        // third root; fewer than three rational roots mean a repeated root, unless the others are
+        // irrational
+        let [param_1, param_2, param_3] = params[..] else {
+            let repeated = match params[..] {
+                [] => c0 == 0,
+                [[lambda, mu]] if c0 != 0 => {
+                    // the root s = c0 lambda / mu of s³ + c1 s² + c0 c2 s + c0² c3 is triple when
+                    // the sum of the roots is 3 s and the sum of their products 3 s²
+                    let root = i128::from(lambda) * (c0 / i128::from(mu));
+                    let square = root.checked_mul(root).and_then(|x| x.checked_mul(3));
+                    root.checked_mul(-3) == Some(c1) && square == c0.checked_mul(c2)
+                }
+                // with c0 = 0, [1, 0] is a root, and a double one when c1 = 0
+                [_] => c1 == 0,
+                _ => true,
+            };
+            return Err(if repeated {
+                GeometryError::NotInGeneralPosition
+            } else {
+                GeometryError::Irrational
+            });
+        };
+        // a double line as degenerate member has no singular point
+        let vertex = |[lambda, mu]: [i64; 2]| {
+            singular_point(&self.member(lambda, mu))?.ok_or(GeometryError::NotInGeneralPosition)
+        };
+        Ok([vertex(param_1)?, vertex(param_2)?, vertex(param_3)?])
+    }
+}
+
+/// Return the singular point of a line pair, or `None` for a double line.
+fn singular_point(conic: &Conic) -> Result<Option<PgPoint>, GeometryError> {
+    let wide = Mat3::from(conic.mat).map(i128::from);
+    let cof = wide.checked_cofactors().ok_or(GeometryError::Overflow)?;
+    // the adjugate of a line pair is a multiple of s sᵀ for its singular point s
+    let Some(row) = cof.rows.iter().find(|row| **row != [0, 0, 0]) else {
+        return Ok(None);
+    };
+    let common = row.iter().fold(0_i128, |g, x| g.gcd(x));
+    let [x, y, z] = row.map(|x| i64::try_from(x / common));
+    let (Ok(x), Ok(y), Ok(z)) = (x, y, z) else {
+        return Err(GeometryError::Overflow);
+    };
+    Ok(Some(PgPoint::new([x, y, z])))
+}
+
+/// Split a degenerate conic into its lines, or return `None` if they are not real and rational.
+fn split_lines(conic: &Conic) -> Result<Option<Vec<PgLine>>, GeometryError> {
+    let Some(singular) = singular_point(conic)? else {
+        // a double line, given by any nonzero row
+        return Ok(conic
+            .mat
+            .iter()
+            .find(|row| **row != [0, 0, 0])
+            .map(|row| vec![PgLine::new(*row)]));
+    };
+    // the line with the coordinates of the singular point avoids it, and meets the pair in one
+    // point of each line
+    match conic.intersect_line(&PgLine::new(singular.coord))? {
+        Some([pt_p, pt_q]) => Ok(Some(vec![singular.join(&pt_p), singular.join(&pt_q)])),
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pg_plane::ProjectivePlanePrimitive;
+
+    #[test]
+    fn test_conic_pencil() {
+        // the pencil through the vertices of a quadrangle, spanned by two pairs of opposite sides
+        let quadrangle = [[1, 2, 1], [-3, 1, 1], [2, -1, 1], [0, 5, 2]].map(PgPoint::new);
+        let pair = |i: usize, j: usize, k: usize, l: usize| {
+            let [l_1, l_2] = [
+                quadrangle[i].join(&quadrangle[j]).coord,
+                quadrangle[k].join(&quadrangle[l]).coord,
+            ];
+            let mut mat = [[0_i64; 3]; 3];
+            for (r, row) in mat.iter_mut().enumerate() {
+                for (c, x) in row.iter_mut().enumerate() {
+                    *x = l_1[r] * l_2[c] + l_2[r] * l_1[c];
+                }
+            }
+            Conic::new(mat)
+        };
+        let (pair_a, pair_b) = (pair(0, 1, 2, 3), pair(0, 2, 1, 3));
+        let pencil = ConicPencil::new(pair_a.clone(), pair_b.clone());
+        let conic = pencil.member(2, -1);
+        assert!(!conic.is_degenerate());
+        assert!(quadrangle.iter().all(|pt| conic.contains(pt)));
+
+        // the same pencil, spanned by two nondegenerate members
+        let pencil = ConicPencil::new(conic, pencil.member(1, 3));
+        let members = pencil.degenerate_members();
+        assert_eq!(members.len(), 3);
+        for member in &members {
+            assert!(member.is_degenerate());
+            assert!(quadrangle.iter().all(|pt| member.contains(pt)));
+        }
+        let points = pencil.base_points().unwrap();
+        assert_eq!(points.len(), 4);
+        assert!(quadrangle.iter().all(|pt| points.contains(pt)));
+
+        // concentric circles meet only in the circular points at infinity
+        let pencil = ConicPencil::new(
+            Conic::from_coefficients(1, 0, 1, 0, 0, -1),
+            Conic::from_coefficients(1, 0, 1, 0, 0, -4),
+        );
+        assert_eq!(pencil.degenerate_members().len(), 2);
+        assert_eq!(pencil.base_points(), Ok(vec![]));
+
+        // a degenerate generator, and a pencil of line pairs only
+        let pencil = ConicPencil::new(pair_a.clone(), pair_b.clone());
+        assert!(pencil.degenerate_members().contains(&pair_a));
+        assert_eq!(pencil.base_points().unwrap().len(), 4);
+        let pencil = ConicPencil::new(pair_a.clone(), pair_a);
+        assert!(pencil.degenerate_members().is_empty());
+        assert_eq!(
+            pencil.base_points(),
+            Err(GeometryError::NotInGeneralPosition)
+        );
+
+        // x² + y² = 3 and x² - y² = 1 meet in (±√2, ±1)
+        let pencil = ConicPencil::new(
+            Conic::from_coefficients(1, 0, 1, 0, 0, -3),
+            Conic::from_coefficients(1, 0, -1, 0, 0, -1),
+        );
+        assert_eq!(pencil.degenerate_members().len(), 3);
+        assert_eq!(pencil.base_points(), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_self_polar_triangle() {
+        // the conics through the quadrangle (1, 2), (-3, 1), (2, -1), (0, 5/2)
+        let quadrangle = [[1, 2, 1], [-3, 1, 1], [2, -1, 1], [0, 5, 2]].map(PgPoint::new);
+        let conic_a = Conic::through_points(&[
+            quadrangle[0].clone(),
+            quadrangle[1].clone(),
+            quadrangle[2].clone(),
+            quadrangle[3].clone(),
+            PgPoint::new([1, 0, 0]),
+        ])
+        .unwrap();
+        let conic_b = Conic::through_points(&[
+            quadrangle[0].clone(),
+            quadrangle[1].clone(),
+            quadrangle[2].clone(),
+            quadrangle[3].clone(),
+            PgPoint::new([0, 1, 0]),
+        ])
+        .unwrap();
+        let pencil = ConicPencil::new(conic_a.clone(), conic_b.clone());
+        let triangle = pencil.self_polar_triangle().unwrap();
+        // the diagonal points of the quadrangle
+        let diagonal = |i: usize, j: usize, k: usize, l: usize| {
+            quadrangle[i]
+                .join(&quadrangle[j])
+                .meet(&quadrangle[k].join(&quadrangle[l]))
+        };
+        for pt in [
+            diagonal(0, 1, 2, 3),
+            diagonal(0, 2, 1, 3),
+            diagonal(0, 3, 1, 2),
+        ] {
+            assert!(triangle.contains(&pt));
+        }
+        for conic in [&conic_a, &conic_b, &pencil.member(3, -2)] {
+            for i in 0..3 {
+                let side = triangle[(i + 1) % 3].join(&triangle[(i + 2) % 3]);
+                assert_eq!(conic.polar(&triangle[i]), side);
+            }
+        }
+
+        // degenerate members with large parameters
+        let pencil = ConicPencil::new(
+            Conic::new([[1, 0, 0], [0, 1, 0], [0, 0, 1]]),
+            Conic::new([
+                [-1000000007, 0, 0],
+                [0, -1000000009, 0],
+                [0, 0, -1000000011],
+            ]),
+        );
+        assert_eq!(pencil.degenerate_members().len(), 3);
+        for diagonal in [
+            [-1000003, -1000005, -1000007],
+            [-100000007, -100000009, -100000011],
+        ] {
+            let mut mat = [[0; 3]; 3];
+            for (i, x) in diagonal.into_iter().enumerate() {
+                mat[i][i] = x;
+            }
+            let pencil = ConicPencil::new(
+                Conic::new([[1, 0, 0], [0, 1, 0], [0, 0, 1]]),
+                Conic::new(mat),
+            );
+            let triangle = pencil.self_polar_triangle().unwrap();
+            for pt in [[1, 0, 0], [0, 1, 0], [0, 0, 1]].map(PgPoint::new) {
+                assert!(triangle.contains(&pt));
+            }
+        }
+
+        // concentric circles touch twice at the circular points, and x² + y² = 3 and
+        // x² - y² = 1 have the degenerate members x² = 2 and y² = 1 with irrational lines, but a
+        // rational self-polar triangle
+        let pencil = ConicPencil::new(
+            Conic::from_coefficients(1, 0, 1, 0, 0, -1),
+            Conic::from_coefficients(1, 0, 1, 0, 0, -4),
+        );
+        assert_eq!(
+            pencil.self_polar_triangle(),
+            Err(GeometryError::NotInGeneralPosition)
+        );
+        let pencil = ConicPencil::new(
+            Conic::from_coefficients(1, 0, 1, 0, 0, -3),
+            Conic::from_coefficients(1, 0, -1, 0, 0, -1),
+        );
+        assert!(pencil.self_polar_triangle().is_ok());
+
+        // the eigenvalues 1 and (1 ± √5) / 2 of B give one rational and two irrational degenerate
+        // members, and the parabola y² = xz and the double line x² = 0 have four-point contact
+        let pencil = ConicPencil::new(
+            Conic::new([[1, 0, 0], [0, 1, 0], [0, 0, 1]]),
+            Conic::new([[0, 1, 0], [1, 1, 0], [0, 0, 1]]),
+        );
+        assert_eq!(pencil.degenerate_members().len(), 1);
+        assert_eq!(pencil.self_polar_triangle(), Err(GeometryError::Irrational));
+        let pencil = ConicPencil::new(
+            Conic::new([[0, 0, -1], [0, 2, 0], [-1, 0, 0]]),
+            Conic::new([[1, 0, 0], [0, 0, 0], [0, 0, 0]]),
+        );
+        assert_eq!(
+            pencil.self_polar_triangle(),
+            Err(GeometryError::NotInGeneralPosition)
+        );
+    }
+}
@@ -0,0 +1,312 @@
+// Straightedge constructions declared once and evaluated for different inputs
+
+use crate::error::GeometryError;
+use crate::pg_plane::{try_harm_conj, ProjectivePlane};
+use core::marker::PhantomData;
+use core::ops::Index;
+
+/// Handle of a point of a `Construction`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PointId(usize);
+
+/// Handle of a line of a `Construction`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LineId(usize);
+
+/// A step of a construction. Steps refer only to objects of earlier steps, so evaluating them in
+/// order respects the dependencies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    FreePoint,
+    FreeLine,
+    Join(PointId, PointId),
+    Meet(LineId, LineId),
+    HarmConj(PointId, PointId, PointId),
+}
+
+/// The `Construction` struct records a straightedge construction symbolically: free points and
+/// lines, and the objects derived from them by joins, meets and harmonic conjugates. Evaluating
+/// it for other coordinates of the free objects re-runs every step, as when a point is dragged in
+/// dynamic geometry software.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::construction::Construction;
+/// use projgeom_rs::{PgLine, PgPoint, ProjectivePlanePrimitive};
+/// // the diagonal triangle of a quadrangle
+/// let mut cons = Construction::new();
+/// let [a, b, c, d] = [(); 4].map(|_| cons.free_point());
+/// let (ab, cd) = (cons.join(a, b), cons.join(c, d));
+/// let (ac, bd) = (cons.join(a, c), cons.join(b, d));
+/// let (p, q) = (cons.meet(ab, cd), cons.meet(ac, bd));
+///
+/// let quad = [[0, 0, 1], [1, 0, 1], [1, 1, 1], [0, 1, 1]].map(PgPoint::new);
+/// let values = cons.evaluate::<PgLine, i64>(&quad, &[]).unwrap();
+/// assert_eq!(values[p], PgPoint::new([1, 0, 0]));
+///
+/// // drag the third vertex and evaluate again
+/// let quad = [[0, 0, 1], [1, 0, 1], [2, 3, 1], [0, 1, 1]].map(PgPoint::new);
+/// let values = cons.evaluate::<PgLine, i64>(&quad, &[]).unwrap();
+/// assert!(values[p].incident(&values[ab]) && values[q].incident(&values[bd]));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Construction<Point> {
+    steps: Vec<Step>,
+    num_points: usize,
+    num_lines: usize,
+    _marker: PhantomData<Point>,
+}
+
+impl<Point> Default for Construction<Point> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Point> Construction<Point> {
+    /// Create an empty construction.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            steps: Vec::new(),
+            num_points: 0,
+            num_lines: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    fn push_point(&mut self, step: Step) -> PointId {
+        self.steps.push(step);
+        self.num_points += 1;
+        PointId(self.num_points - 1)
+    }
+
+    fn push_line(&mut self, step: Step) -> LineId {
+        self.steps.push(step);
+        self.num_lines += 1;
+        LineId(self.num_lines - 1)
+    }
+
+    /// Declare a free point. Free points take their coordinates from the input of `evaluate`, in
+    /// the order of declaration.
+    #[inline]
+    pub fn free_point(&mut self) -> PointId {
+        self.push_point(Step::FreePoint)
+    }
+
+    /// Declare a free line. Free lines take their coordinates from the input of `evaluate`, in
+    /// the order of declaration.
+    #[inline]
+    pub fn free_line(&mut self) -> LineId {
+        self.push_line(Step::FreeLine)
+    }
+
+    /// Declare the line through two points.
+    #[inline]
+    pub fn join(&mut self, pt_a: PointId, pt_b: PointId) -> LineId {
+        self.push_line(Step::Join(pt_a, pt_b))
+    }
+
+    /// Declare the point where two lines meet.
+    #[inline]
+    pub fn meet(&mut self, ln_l: LineId, ln_m: LineId) -> PointId {
+        self.push_point(Step::Meet(ln_l, ln_m))
+    }
+
+    /// Declare the harmonic conjugate of `pt_c` with respect to `pt_a` and `pt_b`.
+    #[inline]
+    pub fn harm_conj(&mut self, pt_a: PointId, pt_b: PointId, pt_c: PointId) -> PointId {
+        self.push_point(Step::HarmConj(pt_a, pt_b, pt_c))
+    }
+
+    /// Return the number of free points and free lines the construction takes as input.
+    pub fn num_free(&self) -> (usize, usize) {
+        let count = |kind: Step| self.steps.iter().filter(|&&step| step == kind).count();
+        (count(Step::FreePoint), count(Step::FreeLine))
+    }
+
+    /// Evaluate every step of the construction for the given free points and lines.
+    ///
+    /// Arguments:
+    ///
+    /// * `free_points`: The coordinates of the free points, in the order of declaration.
+    /// * `free_lines`: The coordinates of the free lines, in the order of declaration.
+    ///
+    /// Returns:
+    ///
+    /// The `Evaluation` holding every point and line of the construction, indexed by their
+    /// handles, or `Err(NotInGeneralPosition)` if a step joins coincident points or meets
+    /// coincident lines, or `Err(NotCollinear)` if a harmonic conjugate is taken of a point that
+    /// is not on the line of the other two.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the numbers of free points and lines differ from those declared, or if a
+    /// coordinate overflows.
+    pub fn evaluate<Line, Value>(
+        &self,
+        free_points: &[Point],
+        free_lines: &[Line],
+    ) -> Result<Evaluation<Point, Line>, GeometryError>
+    where
+        Value: Default + Eq,
+        Point: ProjectivePlane<Line, Value> + Clone,
+        Line: ProjectivePlane<Point, Value> + Clone,
+    {
+        assert_eq!(
+            (free_points.len(), free_lines.len()),
+            self.num_free(),
+            "wrong number of free points or lines"
+        );
+        let mut points = Vec::with_capacity(self.num_points);
+        let mut lines = Vec::with_capacity(self.num_lines);
+        let (mut next_point, mut next_line) = (free_points.iter(), free_lines.iter());
+        for step in &self.steps {
+            match *step {
+                Step::FreePoint => points.push(next_point.next().unwrap().clone()),
+                Step::FreeLine => lines.push(next_line.next().unwrap().clone()),
+                Step::Join(PointId(a), PointId(b)) => {
+                    if points[a] == points[b] {
+                        return Err(GeometryError::NotInGeneralPosition);
+                    }
+                    lines.push(points[a].meet(&points[b]));
+                }
+                Step::Meet(LineId(l), LineId(m)) => {
+                    if lines[l] == lines[m] {
+                        return Err(GeometryError::NotInGeneralPosition);
+                    }
+                    points.push(lines[l].meet(&lines[m]));
+                }
+                Step::HarmConj(PointId(a), PointId(b), PointId(c)) => {
+                    let (pt_a, pt_b, pt_c) = (&points[a], &points[b], &points[c]);
+                    if pt_a == pt_b || pt_a == pt_c || pt_b == pt_c {
+                        return Err(GeometryError::NotInGeneralPosition);
+                    }
+                    points.push(try_harm_conj(pt_a, pt_b, pt_c)?);
+                }
+            }
+        }
+        Ok(Evaluation { points, lines })
+    }
+}
+
+/// The `Evaluation` struct holds the points and lines of an evaluated `Construction`, indexed by
+/// their handles.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Evaluation<Point, Line> {
+    points: Vec<Point>,
+    lines: Vec<Line>,
+}
+
+impl<Point, Line> Evaluation<Point, Line> {
+    /// Return all points, in the order of declaration.
+    #[inline]
+    pub fn points(&self) -> &[Point] {
+        &self.points
+    }
+
+    /// Return all lines, in the order of declaration.
+    #[inline]
+    pub fn lines(&self) -> &[Line] {
+        &self.lines
+    }
+}
+
+impl<Point, Line> Index<PointId> for Evaluation<Point, Line> {
+    type Output = Point;
+
+    #[inline]
+    fn index(&self, PointId(i): PointId) -> &Point {
+        &self.points[i]
+    }
+}
+
+impl<Point, Line> Index<LineId> for Evaluation<Point, Line> {
+    type Output = Line;
+
+    #[inline]
+    fn index(&self, LineId(i): LineId) -> &Line {
+        &self.lines[i]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pg_object::{EuclidLine, EuclidPoint, PgLine, PgPoint};
+    use crate::pg_plane::{check_pappus, coincident};
+
+    #[test]
+    fn test_construction() {
+        // Pappus' configuration on two free lines
+        let mut cons = Construction::new();
+        let [a, b, c, d, e, f] = [(); 6].map(|_| cons.free_point());
+        let [ae, bd, af, cd, bf, ce] =
+            [(a, e), (b, d), (a, f), (c, d), (b, f), (c, e)].map(|(p, q)| cons.join(p, q));
+        let (g, h, i) = (cons.meet(ae, bd), cons.meet(af, cd), cons.meet(bf, ce));
+        assert_eq!(cons.num_free(), (6, 0));
+
+        for shift in [0, 1, 5] {
+            let coords = [
+                [0, 0, 1],
+                [1, shift, 1],
+                [2, 2 * shift, 1],
+                [0, 3, 1],
+                [4, 3, 1],
+                [-1, 3, 1],
+            ];
+            let values = cons
+                .evaluate::<PgLine, i64>(&coords.map(PgPoint::new), &[])
+                .unwrap();
+            assert!(coincident(&values[g], &values[h], &values[i]));
+            let points = values.points();
+            assert!(check_pappus(
+                &[points[0].clone(), points[1].clone(), points[2].clone()],
+                &[points[3].clone(), points[4].clone(), points[5].clone()],
+            ));
+            assert_eq!(points.len(), 9);
+            assert_eq!(values.lines().len(), 6);
+        }
+
+        // dragging a point onto another degenerates the join
+        let coords = [
+            [0, 0, 1],
+            [1, 0, 1],
+            [2, 0, 1],
+            [0, 3, 1],
+            [0, 0, 2],
+            [-1, 3, 1],
+        ];
+        assert_eq!(
+            cons.evaluate::<PgLine, i64>(&coords.map(PgPoint::new), &[]),
+            Err(GeometryError::NotInGeneralPosition)
+        );
+
+        // the midpoint as the harmonic conjugate of the point at infinity, with a free line
+        let mut cons = Construction::new();
+        let (a, b, l_inf) = (cons.free_point(), cons.free_point(), cons.free_line());
+        let ab = cons.join(a, b);
+        let ideal = cons.meet(ab, l_inf);
+        let mid = cons.harm_conj(a, b, ideal);
+        let l_inf_value = EuclidLine::new([0, 0, 1]);
+        let values = cons
+            .evaluate(
+                &[EuclidPoint::new([0, 0, 1]), EuclidPoint::new([4, 2, 1])],
+                core::slice::from_ref(&l_inf_value),
+            )
+            .unwrap();
+        assert_eq!(values[mid], EuclidPoint::new([2, 1, 1]));
+        assert_eq!(values[l_inf], l_inf_value);
+
+        let mut cons = Construction::new();
+        let [a, b, c] = [(); 3].map(|_| cons.free_point());
+        cons.harm_conj(a, b, c);
+        let triangle = [[0, 0, 1], [1, 0, 1], [0, 1, 1]].map(PgPoint::new);
+        assert_eq!(
+            cons.evaluate::<PgLine, i64>(&triangle, &[]),
+            Err(GeometryError::NotCollinear)
+        );
+    }
+}
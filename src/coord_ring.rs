@@ -0,0 +1,239 @@
+// Coordinate rings for homogeneous coordinates
+
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::{Add, Mul, Neg, Sub};
+
+/// The `CoordRing` trait describes a commutative ring that can serve as the coordinate ring of
+/// the projective plane. `Default::default()` must be the zero of the ring.
+///
+/// It is implemented for every type with the required operations, e.g. `i64`, `i128`,
+/// `Fraction<i64>` and `Zp<P>`.
+pub trait CoordRing:
+    Clone
+    + Default
+    + Eq
+    + fmt::Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+{
+    /// Return the zero of the ring.
+    #[inline]
+    fn zero() -> Self {
+        Self::default()
+    }
+
+    /// Check if the element is the zero of the ring.
+    #[inline]
+    fn is_zero(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl<T> CoordRing for T where
+    T: Clone
+        + Default
+        + Eq
+        + fmt::Debug
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Neg<Output = T>
+{
+}
+
+/// The function `ring_dot` calculates the dot product of two vectors over a `CoordRing`.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::coord_ring::{ring_dot, Zp};
+/// let v_a = [Zp::<5>::new(1), Zp::new(2), Zp::new(3)];
+/// let v_b = [Zp::<5>::new(4), Zp::new(4), Zp::new(1)];
+/// assert_eq!(ring_dot(&v_a, &v_b), Zp::new(0));
+/// ```
+#[inline]
+pub fn ring_dot<T: CoordRing>(v_a: &[T; 3], v_b: &[T; 3]) -> T {
+    v_a[0].clone() * v_b[0].clone()
+        + v_a[1].clone() * v_b[1].clone()
+        + v_a[2].clone() * v_b[2].clone()
+}
+
+/// The function `ring_cross` calculates the cross product of two vectors over a `CoordRing`.
+#[inline]
+pub fn ring_cross<T: CoordRing>(v_a: &[T; 3], v_b: &[T; 3]) -> [T; 3] {
+    let cross2 =
+        |i: usize, j: usize| v_a[i].clone() * v_b[j].clone() - v_a[j].clone() * v_b[i].clone();
+    [cross2(1, 2), cross2(2, 0), cross2(0, 1)]
+}
+
+/// The function `ring_plucker` calculates `lambda * v_a + mu * v_b` over a `CoordRing`.
+#[inline]
+pub fn ring_plucker<T: CoordRing>(lambda: T, v_a: &[T; 3], mu: T, v_b: &[T; 3]) -> [T; 3] {
+    [0, 1, 2].map(|i| lambda.clone() * v_a[i].clone() + mu.clone() * v_b[i].clone())
+}
+
+/// The `CoordKernel` trait supplies the coordinate arithmetic of the point and line types
+/// generated in `pg_object`, so that their equality, incidence, meet and parametrization are
+/// written once for every coordinate type. `Scalar` is the type of the coordinates and `Value`
+/// the type of dot products and parameters.
+pub(crate) trait CoordKernel {
+    type Scalar;
+    type Value;
+
+    /// Check if two coordinate vectors are proportional, i.e. represent the same object.
+    fn proportional(v_a: &[Self::Scalar; 3], v_b: &[Self::Scalar; 3]) -> bool;
+
+    /// Check if the dot product of two coordinate vectors vanishes.
+    fn orthogonal(v_a: &[Self::Scalar; 3], v_b: &[Self::Scalar; 3]) -> bool;
+
+    /// Return the dot product of two coordinate vectors.
+    fn dot(v_a: &[Self::Scalar; 3], v_b: &[Self::Scalar; 3]) -> Self::Value;
+
+    /// Return the cross product of two coordinate vectors.
+    fn cross(v_a: &[Self::Scalar; 3], v_b: &[Self::Scalar; 3]) -> [Self::Scalar; 3];
+
+    /// Return `lambda * v_a + mu * v_b`.
+    fn plucker(
+        lambda: Self::Value,
+        v_a: &[Self::Scalar; 3],
+        mu: Self::Value,
+        v_b: &[Self::Scalar; 3],
+    ) -> [Self::Scalar; 3];
+
+    /// Return coordinates whose dot product with `v` does not vanish.
+    fn aux(v: &[Self::Scalar; 3]) -> [Self::Scalar; 3];
+}
+
+/// The `RingKernel` struct is the `CoordKernel` of an arbitrary `CoordRing`.
+pub(crate) struct RingKernel<T>(PhantomData<T>);
+
+impl<T: CoordRing> CoordKernel for RingKernel<T> {
+    type Scalar = T;
+    type Value = T;
+
+    #[inline]
+    fn proportional(v_a: &[T; 3], v_b: &[T; 3]) -> bool {
+        ring_cross(v_a, v_b).iter().all(CoordRing::is_zero)
+    }
+
+    #[inline]
+    fn orthogonal(v_a: &[T; 3], v_b: &[T; 3]) -> bool {
+        ring_dot(v_a, v_b).is_zero()
+    }
+
+    #[inline]
+    fn dot(v_a: &[T; 3], v_b: &[T; 3]) -> T {
+        ring_dot(v_a, v_b)
+    }
+
+    #[inline]
+    fn cross(v_a: &[T; 3], v_b: &[T; 3]) -> [T; 3] {
+        ring_cross(v_a, v_b)
+    }
+
+    #[inline]
+    fn plucker(lambda: T, v_a: &[T; 3], mu: T, v_b: &[T; 3]) -> [T; 3] {
+        ring_plucker(lambda, v_a, mu, v_b)
+    }
+
+    /// The coordinate line through the first nonzero coordinate, since over a finite field a
+    /// vector may be orthogonal to itself.
+    #[inline]
+    fn aux(v: &[T; 3]) -> [T; 3] {
+        let k = v.iter().position(|x| !x.is_zero()).unwrap_or(0);
+        let mut coord = [T::zero(), T::zero(), T::zero()];
+        coord[k] = v[k].clone();
+        coord
+    }
+}
+
+/// The `Zp` struct is an element of the prime field GF(P), stored as its least nonnegative
+/// residue. `P` is expected to be prime; only then are lines through distinct points unique.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Zp<const P: u64>(u64);
+
+impl<const P: u64> Zp<P> {
+    /// Create the residue class of `value` modulo `P`.
+    #[inline]
+    pub const fn new(value: i64) -> Self {
+        Self((value as i128).rem_euclid(P as i128) as u64)
+    }
+
+    /// Return the least nonnegative residue.
+    #[inline]
+    pub const fn value(&self) -> u64 {
+        self.0
+    }
+}
+
+impl<const P: u64> Add for Zp<P> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Self(((self.0 as u128 + rhs.0 as u128) % P as u128) as u64)
+    }
+}
+
+impl<const P: u64> Sub for Zp<P> {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        Self(((self.0 as u128 + P as u128 - rhs.0 as u128) % P as u128) as u64)
+    }
+}
+
+impl<const P: u64> Mul for Zp<P> {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        Self(((self.0 as u128 * rhs.0 as u128) % P as u128) as u64)
+    }
+}
+
+impl<const P: u64> Neg for Zp<P> {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Self((P - self.0) % P)
+    }
+}
+
+impl<const P: u64> fmt::Display for Zp<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zp() {
+        let a = Zp::<7>::new(-3);
+        assert_eq!(a.value(), 4);
+        assert_eq!(a + Zp::new(5), Zp::new(2));
+        assert_eq!(a - Zp::new(5), Zp::new(6));
+        assert_eq!(a * Zp::new(5), Zp::new(6));
+        assert_eq!(-a, Zp::new(3));
+        assert_eq!(-Zp::<7>::zero(), Zp::zero());
+        assert!(Zp::<7>::new(14).is_zero());
+    }
+
+    #[test]
+    fn test_ring_kernels() {
+        let v_a = [1_i64, 3, 2];
+        let v_b = [-2_i64, 1, -1];
+        let v_c = ring_cross(&v_a, &v_b);
+        assert_eq!(ring_dot(&v_a, &v_c), 0);
+        assert_eq!(ring_dot(&v_b, &v_c), 0);
+        assert_eq!(ring_plucker(2, &v_a, 3, &v_b), [-4, 9, 1]);
+    }
+}
@@ -0,0 +1,114 @@
+// Duality: mapping configurations of points and lines to their dual configurations
+
+use crate::conic::Conic;
+use crate::pg_object::{PgLine, PgPoint};
+
+/// The `Dualize` trait maps an object of the projective plane to its dual under the standard
+/// polarity, which reads the coordinates of a point as those of a line and vice versa. Incidence
+/// is preserved, `p.incident(l)` if and only if `l.dualize().incident(&p.dualize())`, so an
+/// algorithm written for points can be applied to lines by dualizing its input and output.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::duality::Dualize;
+/// use projgeom_rs::{tri_dual, Join, PgLine, PgPoint, ProjectivePlanePrimitive};
+/// // the dual of a triangle is a trilateral, whose sides are the duals of the vertices
+/// let triangle = [[1, 0, 1], [0, 2, 1], [-1, -1, 1]].map(PgPoint::new);
+/// let trilateral: [PgLine; 3] = triangle.dualize();
+/// // the dual of the join of two vertices is the meet of the dual sides
+/// let side = triangle[0].join(&triangle[1]);
+/// assert_eq!(side.dualize(), trilateral[0].meet(&trilateral[1]));
+/// assert_eq!(trilateral.dualize(), triangle);
+/// // the vertices of the trilateral are the duals of the sides of the triangle
+/// let sides: [PgLine; 3] = tri_dual(&triangle);
+/// assert_eq!(tri_dual(&trilateral), sides.dualize());
+/// ```
+pub trait Dualize {
+    type Dual;
+
+    /// Return the dual object.
+    fn dualize(&self) -> Self::Dual;
+}
+
+impl Dualize for PgPoint {
+    type Dual = PgLine;
+
+    #[inline]
+    fn dualize(&self) -> PgLine {
+        PgLine::new(self.coord)
+    }
+}
+
+impl Dualize for PgLine {
+    type Dual = PgPoint;
+
+    #[inline]
+    fn dualize(&self) -> PgPoint {
+        PgPoint::new(self.coord)
+    }
+}
+
+/// A configuration given as an array, e.g. a triangle, dualizes elementwise.
+impl<T: Dualize, const N: usize> Dualize for [T; N] {
+    type Dual = [T::Dual; N];
+
+    #[inline]
+    fn dualize(&self) -> Self::Dual {
+        core::array::from_fn(|i| self[i].dualize())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Dualize> Dualize for Vec<T> {
+    type Dual = Vec<T::Dual>;
+
+    #[inline]
+    fn dualize(&self) -> Self::Dual {
+        self.iter().map(Dualize::dualize).collect()
+    }
+}
+
+/// The dual of a point conic is its line conic of tangents, read as a point conic; see
+/// `Conic::dual`.
+impl Dualize for Conic {
+    type Dual = Conic;
+
+    #[inline]
+    fn dualize(&self) -> Conic {
+        self.dual()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pg_plane::{check_desargue, persp, ProjectivePlanePrimitive};
+
+    #[test]
+    fn test_dualize() {
+        let pt_p = PgPoint::new([1, 3, 2]);
+        let ln_l = PgLine::new([-2, 0, 1]);
+        assert!(pt_p.incident(&ln_l));
+        assert!(ln_l.dualize().incident(&pt_p.dualize()));
+
+        // Desargues' theorem is self-dual
+        let tri1 = [[0, 0, 1], [4, 0, 1], [0, 4, 1]].map(PgPoint::new);
+        let tri2 = [[0, 0, 2], [8, 0, 1], [0, 8, 1]].map(PgPoint::new);
+        assert!(persp(&tri1, &tri2));
+        assert!(persp(&tri1.dualize(), &tri2.dualize()));
+        assert!(check_desargue(&tri1.dualize(), &tri2.dualize()));
+
+        // the duals of the points of a conic are tangents of the dual conic
+        let circle = Conic::from_coefficients(1, 0, 1, 0, 0, -25);
+        let dual = circle.dualize();
+        for coord in [[3, 4, 1], [-5, 0, 1], [0, 5, 1], [4, -3, 1]] {
+            let pt = PgPoint::new(coord);
+            assert!(circle.contains(&pt));
+            assert!(dual.is_tangent(&pt.dualize()));
+            assert!(circle.is_tangent(&circle.polar(&pt)));
+        }
+        let points = vec![pt_p.clone(), PgPoint::new([0, 0, 1])];
+        assert_eq!(points.dualize().dualize(), points);
+    }
+}
@@ -0,0 +1,73 @@
+use core::fmt;
+
+/// The `GeometryError` enum lists the ways in which an exact geometric computation can fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeometryError {
+    /// A point at infinity was given where an affine point is required.
+    PointAtInfinity,
+    /// The line at infinity was given where a line with a direction is required.
+    LineAtInfinity,
+    /// The three vertices of a triangle are collinear.
+    InvalidTriangle,
+    /// An intermediate result does not fit into the integer type used for coordinates.
+    Overflow,
+    /// The exact result is irrational, e.g. a side length that is not rational.
+    Irrational,
+    /// The matrix of a projective transform is singular.
+    SingularTransform,
+    /// Three of four points that should form a projective frame are collinear.
+    NotInGeneralPosition,
+    /// The point correspondences differ in length or are too few to determine a transform.
+    InvalidCorrespondences,
+    /// No projective transform maps the source points to the target points.
+    NoHomography,
+    /// A point that should lie on a line does not.
+    NotOnLine,
+    /// Three points that should be collinear are not.
+    NotCollinear,
+    /// A point that should lie on a conic does not.
+    NotOnConic,
+    /// A line that should be tangent to a conic is not.
+    NotTangent,
+    /// A group or orbit has more elements than the given bound, or is infinite.
+    SizeLimitExceeded,
+    /// A fraction with a zero denominator, or a float that is not finite, was given where a finite
+    /// rational is required.
+    ZeroDenominator,
+    /// A bounded search ended without a result, although one may exist.
+    NotFound,
+}
+
+impl fmt::Display for GeometryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GeometryError::PointAtInfinity => {
+                write!(f, "point at infinity has no affine coordinates")
+            }
+            GeometryError::LineAtInfinity => write!(f, "line at infinity has no direction"),
+            GeometryError::InvalidTriangle => write!(f, "vertices of the triangle are collinear"),
+            GeometryError::Overflow => write!(f, "arithmetic overflow in exact computation"),
+            GeometryError::Irrational => write!(f, "exact result is not rational"),
+            GeometryError::SingularTransform => write!(f, "matrix of the transform is singular"),
+            GeometryError::NotInGeneralPosition => write!(f, "points are not in general position"),
+            GeometryError::InvalidCorrespondences => {
+                write!(f, "too few or unmatched point correspondences")
+            }
+            GeometryError::NoHomography => {
+                write!(
+                    f,
+                    "no projective transform maps the source points to the targets"
+                )
+            }
+            GeometryError::NotOnLine => write!(f, "point does not lie on the line"),
+            GeometryError::NotCollinear => write!(f, "points are not collinear"),
+            GeometryError::NotOnConic => write!(f, "point does not lie on the conic"),
+            GeometryError::NotTangent => write!(f, "line is not tangent to the conic"),
+            GeometryError::SizeLimitExceeded => write!(f, "size limit exceeded"),
+            GeometryError::ZeroDenominator => write!(f, "fraction has a zero denominator"),
+            GeometryError::NotFound => write!(f, "no result found within the search bound"),
+        }
+    }
+}
+
+impl core::error::Error for GeometryError {}
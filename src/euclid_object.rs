@@ -1,14 +1,19 @@
 // Euclidean Geometry
 
-use crate::ck_plane::{CayleyKleinPlane, CayleyKleinPlanePrimitive};
-use crate::pg_object::{EuclidLine, EuclidPoint};
-use crate::pg_plane::{coincident, tri_dual, ProjectivePlane, ProjectivePlanePrimitive};
+use crate::ck_plane::{midpoint, AffineCKPlane, CayleyKleinPlane, CayleyKleinPlanePrimitive};
+use crate::conic::Conic;
+use crate::error::GeometryError;
+use crate::pg_object::{det3_checked, EuclidLine, EuclidPoint, PgLine, PgPoint};
+use crate::pg_plane::{coincident, involution, tri_dual, Join, ProjectivePlanePrimitive};
+use crate::predicates::{narrow, squared_distance};
+use fractions::Fraction;
+use num_integer::Integer;
 // use crate::pg_object::{plucker_operation, dot_product};
 use crate::pg_object::dot1;
 
 // static I_RE: EuclidPoint = EuclidPoint { coord: [0, 1, 1] };
 // static I_IM: EuclidPoint = EuclidPoint { coord: [1, 0, 0] };
-static L_INF: EuclidLine = EuclidLine { coord: [0, 0, 1] };
+static L_INF: EuclidLine = EuclidPoint::new([1, 0, 0]).meet_const(&EuclidPoint::new([0, 1, 0]));
 
 /// This code is implementing the `perp` method for the `CayleyKleinPlanePrimitive` trait for the `EuclidPoint`
 /// struct. The `perp` method returns a perpendicular line to the given point. In this implementation,
@@ -35,6 +40,13 @@ impl CayleyKleinPlane<EuclidLine, i64> for EuclidPoint {}
 
 impl CayleyKleinPlane<EuclidPoint, i64> for EuclidLine {}
 
+impl AffineCKPlane<EuclidLine, i64> for EuclidPoint {
+    #[inline]
+    fn l_inf() -> EuclidLine {
+        L_INF.clone()
+    }
+}
+
 impl EuclidLine {
     /// The function checks if two EuclidLine objects are parallel.
     ///
@@ -65,6 +77,44 @@ impl EuclidLine {
         dot1(&self.coord, &other.coord) == 0
     }
 
+    /// The `intersect` function returns the affine point where two lines meet.
+    ///
+    /// Arguments:
+    ///
+    /// * `other`: `other` is a reference to an `EuclidLine` object.
+    ///
+    /// Returns:
+    ///
+    /// The point of intersection, or `None` if the lines are parallel or coincide, where `meet`
+    /// would return a point at infinity (or the zero vector).
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::{EuclidLine, EuclidPoint};
+    /// let ln_l = EuclidLine::new([1, -1, 0]);
+    /// let ln_m = EuclidLine::new([1, 1, -4]);
+    /// assert_eq!(ln_l.intersect(&ln_m), Some(EuclidPoint::new([2, 2, 1])));
+    /// let ln_n = EuclidLine::new([2, -2, 3]);
+    /// assert_eq!(ln_l.intersect(&ln_n), None);
+    /// assert_eq!(ln_l.meet_projective(&ln_n), EuclidPoint::new([1, 1, 0]));
+    /// ```
+    #[inline]
+    pub fn intersect(&self, other: &EuclidLine) -> Option<EuclidPoint> {
+        if self.is_parallel(other) {
+            None
+        } else {
+            Some(self.meet(other))
+        }
+    }
+
+    /// The `meet_projective` function returns the meet of two lines in the projective plane, which
+    /// is a point at infinity for parallel lines. It is the same as `meet`.
+    #[inline]
+    pub fn meet_projective(&self, other: &EuclidLine) -> EuclidPoint {
+        self.meet(other)
+    }
+
     /// The `altitude` function calculates the perpendicular line from a given point to a line.
     ///
     /// Arguments:
@@ -85,7 +135,7 @@ impl EuclidLine {
     /// The `altitude` function returns an `EuclidLine` object.
     #[inline]
     pub fn altitude(&self, pt_a: &EuclidPoint) -> EuclidLine {
-        self.perp().meet(pt_a)
+        self.perp().join(pt_a)
     }
 }
 
@@ -101,7 +151,7 @@ impl EuclidPoint {
     /// The `midpoint` function returns an instance of the `EuclidPoint` struct.
     #[inline]
     pub fn midpoint(&self, other: &EuclidPoint) -> EuclidPoint {
-        EuclidPoint::parametrize(self, other.coord[2], other, self.coord[2])
+        midpoint(self, other)
     }
 }
 
@@ -110,7 +160,7 @@ impl EuclidPoint {
 /// Arguments:
 ///
 /// * `triangle`: The `triangle` parameter is an array of `EuclidPoint` structs representing the three vertices of
-/// a triangle.
+///   a triangle.
 ///
 /// Returns:
 ///
@@ -140,7 +190,482 @@ pub fn tri_altitude(triangle: &[EuclidPoint; 3]) -> [EuclidLine; 3] {
 pub fn orthocenter(triangle: &[EuclidPoint; 3]) -> EuclidPoint {
     let [a_1, a_2, a_3] = triangle;
     assert!(!coincident(a_1, a_2, a_3));
-    let t_1 = a_2.meet(a_3).altitude(a_1);
-    let t_2 = a_3.meet(a_1).altitude(a_2);
+    let t_1 = a_2.join(a_3).altitude(a_1);
+    let t_2 = a_3.join(a_1).altitude(a_2);
     t_1.meet(&t_2)
 }
+
+/// The function `quadrance` returns the quadrance of two affine points, i.e. the square of their
+/// distance, as an exact fraction.
+///
+/// Arguments:
+///
+/// * `pt_p`: The first point.
+/// * `pt_q`: The second point.
+///
+/// Returns:
+///
+/// The function `quadrance` returns the quadrance, `GeometryError::PointAtInfinity`, or
+/// `GeometryError::Overflow` if the result does not fit.
+///
+/// Examples:
+///
+/// ```rust
+/// use fractions::Fraction;
+/// use projgeom_rs::euclid_object::quadrance;
+/// use projgeom_rs::EuclidPoint;
+/// let pt_p = EuclidPoint::new([1, 1, 2]);
+/// let pt_q = EuclidPoint::new([2, 3, 1]);
+/// assert_eq!(quadrance(&pt_p, &pt_q), Ok(Fraction::new(17, 2)));
+/// ```
+#[inline]
+pub fn quadrance(pt_p: &EuclidPoint, pt_q: &EuclidPoint) -> Result<Fraction<i64>, GeometryError> {
+    squared_distance(pt_p, pt_q)
+}
+
+/// The function `spread` returns the spread of two lines, i.e. the square of the sine of their
+/// angle, as an exact fraction. Parallel lines have spread 0 and perpendicular lines spread 1.
+///
+/// Arguments:
+///
+/// * `ln_l`: The first line.
+/// * `ln_m`: The second line.
+///
+/// Returns:
+///
+/// The function `spread` returns the spread, `GeometryError::LineAtInfinity`, or
+/// `GeometryError::Overflow` if the result does not fit.
+///
+/// Examples:
+///
+/// ```rust
+/// use fractions::Fraction;
+/// use projgeom_rs::euclid_object::spread;
+/// use projgeom_rs::EuclidLine;
+/// // y = 0 and y = x
+/// let ln_l = EuclidLine::new([0, 1, 0]);
+/// let ln_m = EuclidLine::new([1, -1, 0]);
+/// assert_eq!(spread(&ln_l, &ln_m), Ok(Fraction::new(1, 2)));
+/// ```
+pub fn spread(ln_l: &EuclidLine, ln_m: &EuclidLine) -> Result<Fraction<i64>, GeometryError> {
+    let [l_a, l_b, _] = ln_l.coord.map(i128::from);
+    let [m_a, m_b, _] = ln_m.coord.map(i128::from);
+    if (l_a, l_b) == (0, 0) || (m_a, m_b) == (0, 0) {
+        return Err(GeometryError::LineAtInfinity);
+    }
+    let cross = (l_a * m_b)
+        .checked_sub(l_b * m_a)
+        .ok_or(GeometryError::Overflow)?;
+    let norm = |a: i128, b: i128| (a * a).checked_add(b * b);
+    let numer = cross.checked_mul(cross).ok_or(GeometryError::Overflow)?;
+    let denom = norm(l_a, l_b)
+        .zip(norm(m_a, m_b))
+        .and_then(|(n_l, n_m)| n_l.checked_mul(n_m))
+        .ok_or(GeometryError::Overflow)?;
+    narrow(numer, denom)
+}
+
+/// The function `quadrea` returns the quadrea of a triangle, i.e. sixteen times the square of its
+/// area, as an exact fraction. It is related to the quadrances by Archimedes' formula
+/// `(Q_1 + Q_2 + Q_3)² = 2 (Q_1² + Q_2² + Q_3²) + A`, and to the spreads by `A = 4 Q_1 Q_2 s_3`.
+/// Collinear points have quadrea 0.
+///
+/// Arguments:
+///
+/// * `triangle`: The vertices of the triangle.
+///
+/// Returns:
+///
+/// The function `quadrea` returns the quadrea, `GeometryError::PointAtInfinity`, or
+/// `GeometryError::Overflow` if the result does not fit.
+///
+/// Examples:
+///
+/// ```rust
+/// use fractions::Fraction;
+/// use projgeom_rs::euclid_object::quadrea;
+/// use projgeom_rs::EuclidPoint;
+/// // the 3-4-5 triangle has area 6
+/// let triangle = [[0, 0, 1], [4, 0, 1], [0, 3, 1]].map(EuclidPoint::new);
+/// assert_eq!(quadrea(&triangle), Ok(Fraction::from(576)));
+/// let triangle = [[0, 0, 1], [1, 0, 2], [0, 1, 1]].map(EuclidPoint::new);
+/// assert_eq!(quadrea(&triangle), Ok(Fraction::from(1)));
+/// ```
+pub fn quadrea(triangle: &[EuclidPoint; 3]) -> Result<Fraction<i64>, GeometryError> {
+    if triangle.iter().any(|pt| pt.coord[2] == 0) {
+        return Err(GeometryError::PointAtInfinity);
+    }
+    // twice the signed area is det(a_1, a_2, a_3) / (z_1 z_2 z_3)
+    let [a_1, a_2, a_3] = triangle.each_ref().map(|pt| &pt.coord);
+    let det = det3_checked(a_1, a_2, a_3).ok_or(GeometryError::Overflow)?;
+    let scale = triangle
+        .iter()
+        .try_fold(1_i128, |v, pt| v.checked_mul(i128::from(pt.coord[2])))
+        .ok_or(GeometryError::Overflow)?;
+    let numer = det
+        .checked_mul(det)
+        .and_then(|v| v.checked_mul(4))
+        .ok_or(GeometryError::Overflow)?;
+    let denom = scale.checked_mul(scale).ok_or(GeometryError::Overflow)?;
+    narrow(numer, denom)
+}
+
+/// The function `reflect_line` reflects a line in a mirror line. The reflection is the harmonic
+/// homology with axis `mirror` and center `mirror.perp()`, so the dual of `involution` applies.
+///
+/// Arguments:
+///
+/// * `mirror`: The mirror line, not the line at infinity.
+/// * `ln_l`: The line to reflect.
+///
+/// Returns:
+///
+/// The function `reflect_line` returns the reflected line.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::euclid_object::reflect_line;
+/// use projgeom_rs::EuclidLine;
+/// // reflecting y = 2x in y = x gives x = 2y
+/// let mirror = EuclidLine::new([1, -1, 0]);
+/// assert_eq!(reflect_line(&mirror, &EuclidLine::new([2, -1, 0])), EuclidLine::new([1, -2, 0]));
+/// ```
+pub fn reflect_line(mirror: &EuclidLine, ln_l: &EuclidLine) -> EuclidLine {
+    assert!(
+        mirror.coord[..2] != [0, 0],
+        "cannot reflect in the line at infinity"
+    );
+    if ln_l == mirror {
+        return ln_l.clone();
+    }
+    involution(mirror, &mirror.perp(), ln_l).normalized()
+}
+
+/// The function `angle_bisectors` returns the two bisectors of the angles between two lines,
+/// `l_1 / |n_1| + l_2 / |n_2|` and `l_1 / |n_1| - l_2 / |n_2|` for the normals `n_1`, `n_2`. They
+/// have integer coordinates when the product of the quadrances of the normals is a square. For
+/// parallel lines, the first or the second is the line at infinity.
+///
+/// Arguments:
+///
+/// * `ln_1`: The first line.
+/// * `ln_2`: The second line.
+///
+/// Returns:
+///
+/// The function `angle_bisectors` returns the two bisectors, or `None` if they are not exact, the
+/// lines are equal, or one of them is the line at infinity.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::euclid_object::angle_bisectors;
+/// use projgeom_rs::EuclidLine;
+/// // the axes x = 0 and y = 0 are bisected by y = x and y = -x
+/// let (bisector_1, bisector_2) =
+///     angle_bisectors(&EuclidLine::new([1, 0, 0]), &EuclidLine::new([0, 1, 0])).unwrap();
+/// assert_eq!(bisector_1, EuclidLine::new([1, 1, 0]));
+/// assert_eq!(bisector_2, EuclidLine::new([1, -1, 0]));
+/// // the normals (1, 1) and (1, 0) have no rational angle bisector
+/// assert!(angle_bisectors(&EuclidLine::new([1, 1, 0]), &EuclidLine::new([1, 0, 0])).is_none());
+/// ```
+///
+/// # Panics
+///
+/// Panics if an intermediate result overflows.
+pub fn angle_bisectors(ln_1: &EuclidLine, ln_2: &EuclidLine) -> Option<(EuclidLine, EuclidLine)> {
+    if ln_1 == ln_2 {
+        return None;
+    }
+    let norm = |ln: &EuclidLine| {
+        let [a, b, _] = ln.coord.map(i128::from);
+        (a * a)
+            .checked_add(b * b)
+            .expect("quadrance overflows i128")
+    };
+    let (norm_1, norm_2) = (norm(ln_1), norm(ln_2));
+    if norm_1 == 0 || norm_2 == 0 {
+        return None;
+    }
+    let product = norm_1
+        .checked_mul(norm_2)
+        .expect("quadrance overflows i128");
+    let root = product.isqrt();
+    if root * root != product {
+        return None;
+    }
+    // multiplied by |n_1| |n_2|: |n_2|² l_1 ± |n_1| |n_2| l_2
+    let bisector = |sign: i128| {
+        let mut vec = [0_i128; 3];
+        for (x, (u_1, u_2)) in vec.iter_mut().zip(ln_1.coord.iter().zip(&ln_2.coord)) {
+            *x = norm_2
+                .checked_mul(*u_1 as i128)
+                .zip((sign * root).checked_mul(*u_2 as i128))
+                .and_then(|(v_1, v_2)| v_1.checked_add(v_2))
+                .expect("coordinate overflows i128");
+        }
+        reduced_point(vec)
+            .map(|pt| EuclidLine::new(pt.coord))
+            .expect("coordinate overflows i64")
+    };
+    Some((bisector(1), bisector(-1)))
+}
+
+/// Value of the bilinear form `pᵀ M q` in i128, or `None` on overflow.
+fn bilinear(mat: &[[i64; 3]; 3], v_p: &[i128; 3], v_q: &[i128; 3]) -> Option<i128> {
+    let mut sum = 0_i128;
+    for (row, p) in mat.iter().zip(v_p) {
+        for (m, q) in row.iter().zip(v_q) {
+            sum = sum.checked_add(p.checked_mul(*m as i128)?.checked_mul(*q)?)?;
+        }
+    }
+    Some(sum)
+}
+
+/// Divide a nonzero vector by the gcd of its entries, with the sign of its last nonzero entry,
+/// and narrow it to an `EuclidPoint`.
+fn reduced_point(vec: [i128; 3]) -> Result<EuclidPoint, GeometryError> {
+    let common = vec.iter().fold(0_i128, |g, x| g.gcd(x));
+    let common = match vec.iter().rev().find(|x| **x != 0) {
+        Some(last) if *last < 0 => -common,
+        _ => common,
+    };
+    let [x, y, z] = vec.map(|x| i64::try_from(x / common).map_err(|_| GeometryError::Overflow));
+    Ok(EuclidPoint::new([x?, y?, z?]))
+}
+
+/// The `EuclidCircle` struct is a circle of the Euclidean plane, stored as the conic
+/// `A (x² + y²) + 2 D xz + 2 E yz + F z² = 0` with `A > 0` and coprime coefficients, so that equal
+/// circles have equal representations.
+///
+/// A circle of quadrance 0 is a single real point, and one of negative quadrance has no real
+/// points.
+///
+/// Examples:
+///
+/// ```rust
+/// use fractions::Fraction;
+/// use projgeom_rs::euclid_object::EuclidCircle;
+/// use projgeom_rs::EuclidPoint;
+/// let triangle = [[1, 0, 1], [0, 1, 1], [-1, 0, 1]].map(EuclidPoint::new);
+/// let circle = EuclidCircle::through_three_points(&triangle).unwrap();
+/// assert_eq!(circle.center(), EuclidPoint::new([0, 0, 1]));
+/// assert_eq!(circle.quadrance(), Ok(Fraction::from(1)));
+/// assert!(circle.contains(&EuclidPoint::new([3, 4, 5])));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EuclidCircle {
+    conic: Conic,
+}
+
+impl EuclidCircle {
+    /// Create the circle from its four coefficients, normalized.
+    fn from_coefficients(coeffs: [i128; 4]) -> Result<Self, GeometryError> {
+        let common = coeffs.iter().fold(0_i128, |g, x| g.gcd(x));
+        let common = if coeffs[0] < 0 { -common } else { common };
+        let [a, d, e, f] =
+            coeffs.map(|x| i64::try_from(x / common).map_err(|_| GeometryError::Overflow));
+        let (a, d, e, f) = (a?, d?, e?, f?);
+        Ok(Self {
+            conic: Conic::new([[a, 0, d], [0, a, e], [d, e, f]]),
+        })
+    }
+
+    /// The function `new` creates the circle with the given center and quadrance (squared
+    /// radius).
+    ///
+    /// Returns:
+    ///
+    /// The function `new` returns the circle, `GeometryError::PointAtInfinity` if the center is an
+    /// ideal point, or `GeometryError::Overflow` if a coefficient does not fit.
+    pub fn new(center: &EuclidPoint, quadrance: Fraction<i64>) -> Result<Self, GeometryError> {
+        let [c_x, c_y, c_z] = center.coord.map(i128::from);
+        if c_z == 0 {
+            return Err(GeometryError::PointAtInfinity);
+        }
+        let (numer, denom) = (quadrance.numer as i128, quadrance.denom as i128);
+        // denom ((c_z x - c_x z)² + (c_z y - c_y z)²) = numer c_z² z²
+        let coeffs = || {
+            let a = denom.checked_mul(c_z * c_z)?;
+            let d = denom.checked_mul(c_z * c_x)?.checked_neg()?;
+            let e = denom.checked_mul(c_z * c_y)?.checked_neg()?;
+            let f = (c_x * c_x)
+                .checked_add(c_y * c_y)?
+                .checked_mul(denom)?
+                .checked_sub(numer.checked_mul(c_z * c_z)?)?;
+            Some([a, d, e, f])
+        };
+        Self::from_coefficients(coeffs().ok_or(GeometryError::Overflow)?)
+    }
+
+    /// The function `through_three_points` creates the circumcircle of a triangle, whose center
+    /// is the meet of two perpendicular bisectors.
+    ///
+    /// Returns:
+    ///
+    /// The function `through_three_points` returns the circle, `GeometryError::PointAtInfinity`,
+    /// `GeometryError::InvalidTriangle` if the points are collinear, or `GeometryError::Overflow`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a coordinate of the center overflows i64.
+    pub fn through_three_points(triangle: &[EuclidPoint; 3]) -> Result<Self, GeometryError> {
+        if triangle.iter().any(|pt| pt.coord[2] == 0) {
+            return Err(GeometryError::PointAtInfinity);
+        }
+        let [a_1, a_2, a_3] = triangle;
+        if coincident(a_1, a_2, a_3) {
+            return Err(GeometryError::InvalidTriangle);
+        }
+        let bisector_3 = a_1.join(a_2).altitude(&a_1.midpoint(a_2));
+        let bisector_2 = a_1.join(a_3).altitude(&a_1.midpoint(a_3));
+        let center = bisector_3.meet(&bisector_2).normalized();
+        Self::new(&center, quadrance(&center, a_1)?)
+    }
+
+    /// Return the circle as a conic.
+    #[inline]
+    pub fn conic(&self) -> &Conic {
+        &self.conic
+    }
+
+    /// Return the center of the circle.
+    #[inline]
+    pub fn center(&self) -> EuclidPoint {
+        let [[a, _, d], [_, _, e], _] = self.conic.mat;
+        EuclidPoint::new([-d, -e, a])
+    }
+
+    /// The function `quadrance` returns the quadrance (squared radius) of the circle, or
+    /// `GeometryError::Overflow` if it does not fit.
+    pub fn quadrance(&self) -> Result<Fraction<i64>, GeometryError> {
+        let [[a, _, d], [_, _, e], [_, _, f]] = self.conic.mat.map(|row| row.map(i128::from));
+        let numer = (d * d)
+            .checked_add(e * e)
+            .and_then(|v| v.checked_sub(a * f))
+            .ok_or(GeometryError::Overflow)?;
+        narrow(numer, a * a)
+    }
+
+    /// Check if the point lies on the circle.
+    #[inline]
+    pub fn contains(&self, pt: &EuclidPoint) -> bool {
+        self.conic.contains(&PgPoint::new(pt.coord))
+    }
+
+    /// The function `tangent_at` returns the tangent line at a point of the circle, or
+    /// `GeometryError::NotOnConic` if the point is not on the circle.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a coordinate of the tangent overflows i64.
+    pub fn tangent_at(&self, pt: &EuclidPoint) -> Result<EuclidLine, GeometryError> {
+        if !self.contains(pt) {
+            return Err(GeometryError::NotOnConic);
+        }
+        Ok(EuclidLine::new(
+            self.conic.polar(&PgPoint::new(pt.coord)).coord,
+        ))
+    }
+
+    /// The function `power_of_point` returns the power of a point with respect to the circle:
+    /// the quadrance to the center minus the quadrance of the circle. It is negative inside the
+    /// circle, zero on it, and positive outside.
+    ///
+    /// Returns:
+    ///
+    /// The function `power_of_point` returns the power, `GeometryError::PointAtInfinity`, or
+    /// `GeometryError::Overflow`.
+    pub fn power_of_point(&self, pt: &EuclidPoint) -> Result<Fraction<i64>, GeometryError> {
+        let v_p = pt.coord.map(i128::from);
+        if v_p[2] == 0 {
+            return Err(GeometryError::PointAtInfinity);
+        }
+        let numer = bilinear(&self.conic.mat, &v_p, &v_p).ok_or(GeometryError::Overflow)?;
+        let denom = (v_p[2] * v_p[2])
+            .checked_mul(self.conic.mat[0][0] as i128)
+            .ok_or(GeometryError::Overflow)?;
+        narrow(numer, denom)
+    }
+
+    /// The function `radical_axis` returns the radical axis of two circles, the line of points
+    /// with equal power with respect to both. For concentric circles, this is the line at
+    /// infinity.
+    ///
+    /// Returns:
+    ///
+    /// The function `radical_axis` returns the line, `GeometryError::NotInGeneralPosition` for
+    /// equal circles, or `GeometryError::Overflow`.
+    pub fn radical_axis(&self, other: &EuclidCircle) -> Result<EuclidLine, GeometryError> {
+        let [[a_1, _, d_1], [_, _, e_1], [_, _, f_1]] = self.conic.mat.map(|r| r.map(i128::from));
+        let [[a_2, _, d_2], [_, _, e_2], [_, _, f_2]] = other.conic.mat.map(|r| r.map(i128::from));
+        // a_2 C_1 - a_1 C_2 is free of x² + y², with the coefficients 2 D and 2 E of xz and yz
+        let combine = |u_1: i128, u_2: i128, scale: i128| {
+            (a_2 * u_1)
+                .checked_sub(a_1 * u_2)
+                .and_then(|u| u.checked_mul(scale))
+                .ok_or(GeometryError::Overflow)
+        };
+        let axis = [
+            combine(d_1, d_2, 2)?,
+            combine(e_1, e_2, 2)?,
+            combine(f_1, f_2, 1)?,
+        ];
+        if axis == [0, 0, 0] {
+            return Err(GeometryError::NotInGeneralPosition);
+        }
+        reduced_point(axis).map(|pt| EuclidLine::new(pt.coord))
+    }
+
+    /// The function `intersect_line` returns the two points where a line meets the circle, the
+    /// same point twice for a tangent, or `None` if the points are not real or not rational.
+    ///
+    /// Returns:
+    ///
+    /// The function `intersect_line` returns the points, or `GeometryError::Overflow` if an
+    /// intermediate result does not fit.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use fractions::Fraction;
+    /// use projgeom_rs::euclid_object::EuclidCircle;
+    /// use projgeom_rs::{EuclidLine, EuclidPoint};
+    /// let circle = EuclidCircle::new(&EuclidPoint::new([0, 0, 1]), Fraction::from(25)).unwrap();
+    /// let points = circle.intersect_line(&EuclidLine::new([0, 1, -4])).unwrap();
+    /// assert_eq!(points, Some([[-3, 4, 1], [3, 4, 1]].map(EuclidPoint::new)));
+    /// assert_eq!(circle.intersect_line(&EuclidLine::new([1, 1, -2])), Ok(None));
+    /// ```
+    pub fn intersect_line(
+        &self,
+        ln: &EuclidLine,
+    ) -> Result<Option<[EuclidPoint; 2]>, GeometryError> {
+        match self.conic.intersect_line(&PgLine::new(ln.coord))? {
+            Some([pt_p, pt_q]) => Ok(Some([
+                reduced_point(pt_p.coord.map(i128::from))?,
+                reduced_point(pt_q.coord.map(i128::from))?,
+            ])),
+            None => Ok(None),
+        }
+    }
+
+    /// The function `intersect_circle` returns the two points where two circles meet, the same
+    /// point twice if they touch, or `None` if the points are not real or not rational.
+    ///
+    /// Returns:
+    ///
+    /// The function `intersect_circle` returns the points, `GeometryError::NotInGeneralPosition`
+    /// for equal circles, or `GeometryError::Overflow`.
+    pub fn intersect_circle(
+        &self,
+        other: &EuclidCircle,
+    ) -> Result<Option<[EuclidPoint; 2]>, GeometryError> {
+        let axis = self.radical_axis(other)?;
+        if axis.coord[..2] == [0, 0] {
+            // concentric: the circles only share the circular points at infinity
+            return Ok(None);
+        }
+        self.intersect_line(&axis)
+    }
+}
@@ -0,0 +1,723 @@
+// C interface for the projective, Euclidean, hyperbolic and elliptic planes, transforms and conics
+
+use crate::ck_plane::{midpoint, reflect, CayleyKleinPlanePrimitive};
+use crate::conic::Conic;
+use crate::error::GeometryError;
+use crate::pg_object::{
+    cross_product_checked, EllipticLine, EllipticPoint, EuclidLine, EuclidPoint, HyperbolicLine,
+    HyperbolicPoint, PgLine, PgPoint,
+};
+use crate::pg_plane::{try_harm_conj, Join, ProjectivePlanePrimitive};
+use crate::transform::Transform;
+use std::cell::Cell;
+use std::panic::{catch_unwind, UnwindSafe};
+
+/// The homogeneous coordinates of a point, in the geometry of the function it is passed to.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FfiPoint {
+    pub coord: [i64; 3],
+}
+
+/// The homogeneous coordinates of a line, in the geometry of the function it is passed to.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FfiLine {
+    pub coord: [i64; 3],
+}
+
+/// The matrix of a projective transform, by rows. The functions returning a transform reduce it
+/// as `Transform` does; the matrix of an argument is checked to be nonsingular.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FfiTransform {
+    pub mat: [[i64; 3]; 3],
+}
+
+/// The symmetric matrix of a conic, by rows, as in `Conic`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FfiConic {
+    pub mat: [[i64; 3]; 3],
+}
+
+/// The `FfiError` enum is the error code of a failed call through the C interface: every
+/// function returning `false` records it for the calling thread, to be read by
+/// `pg_get_last_error`. The codes after `Panic` are those of `GeometryError`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FfiError {
+    /// No call on this thread has failed since the last `pg_clear_last_error`.
+    Ok = 0,
+    /// A pointer argument is null.
+    NullPointer,
+    /// An argument is not valid, e.g. a conic matrix that is not symmetric.
+    InvalidArgument,
+    /// The computation panicked, e.g. on an overflow in an operation without a checked variant.
+    Panic,
+    PointAtInfinity,
+    LineAtInfinity,
+    InvalidTriangle,
+    Overflow,
+    Irrational,
+    SingularTransform,
+    NotInGeneralPosition,
+    InvalidCorrespondences,
+    NoHomography,
+    NotOnLine,
+    NotCollinear,
+    NotOnConic,
+    NotTangent,
+    SizeLimitExceeded,
+    ZeroDenominator,
+    NotFound,
+}
+
+impl From<GeometryError> for FfiError {
+    fn from(err: GeometryError) -> Self {
+        match err {
+            GeometryError::PointAtInfinity => FfiError::PointAtInfinity,
+            GeometryError::LineAtInfinity => FfiError::LineAtInfinity,
+            GeometryError::InvalidTriangle => FfiError::InvalidTriangle,
+            GeometryError::Overflow => FfiError::Overflow,
+            GeometryError::Irrational => FfiError::Irrational,
+            GeometryError::SingularTransform => FfiError::SingularTransform,
+            GeometryError::NotInGeneralPosition => FfiError::NotInGeneralPosition,
+            GeometryError::InvalidCorrespondences => FfiError::InvalidCorrespondences,
+            GeometryError::NoHomography => FfiError::NoHomography,
+            GeometryError::NotOnLine => FfiError::NotOnLine,
+            GeometryError::NotCollinear => FfiError::NotCollinear,
+            GeometryError::NotOnConic => FfiError::NotOnConic,
+            GeometryError::NotTangent => FfiError::NotTangent,
+            GeometryError::SizeLimitExceeded => FfiError::SizeLimitExceeded,
+            GeometryError::ZeroDenominator => FfiError::ZeroDenominator,
+            GeometryError::NotFound => FfiError::NotFound,
+        }
+    }
+}
+
+thread_local! {
+    /// The error code of the last failed call on this thread
+    static LAST_ERROR: Cell<FfiError> = const { Cell::new(FfiError::Ok) };
+}
+
+/// The error code of the last failed call on this thread, or `Ok` if none failed since the last
+/// `pg_clear_last_error`.
+#[no_mangle]
+pub extern "C" fn pg_get_last_error() -> FfiError {
+    LAST_ERROR.with(Cell::get)
+}
+
+/// Reset the error code of this thread to `Ok`.
+#[no_mangle]
+pub extern "C" fn pg_clear_last_error() {
+    LAST_ERROR.with(|last| last.set(FfiError::Ok));
+}
+
+/// Dereference a pointer argument.
+#[inline]
+fn arg<T>(ptr: Option<&T>) -> Result<&T, FfiError> {
+    ptr.ok_or(FfiError::NullPointer)
+}
+
+/// The transform of the matrix of an argument.
+#[inline]
+fn transform(trans: Option<&FfiTransform>) -> Result<Transform, FfiError> {
+    Ok(Transform::new(arg(trans)?.mat)?)
+}
+
+/// The conic of the matrix of an argument.
+fn conic(conic: Option<&FfiConic>) -> Result<Conic, FfiError> {
+    let mat = arg(conic)?.mat;
+    if (0..3).any(|i| (0..i).any(|j| mat[i][j] != mat[j][i])) {
+        return Err(FfiError::InvalidArgument);
+    }
+    Ok(Conic::new(mat))
+}
+
+/// Run the operation and store its result in `out`, or record the error code of the thread and
+/// return `false`. A panic is caught, so that it does not unwind into C.
+fn run<T>(out: Option<&mut T>, op: impl FnOnce() -> Result<T, FfiError> + UnwindSafe) -> bool {
+    let result = match out {
+        Some(out) => catch_unwind(op)
+            .unwrap_or(Err(FfiError::Panic))
+            .map(|value| *out = value),
+        None => Err(FfiError::NullPointer),
+    };
+    match result {
+        Ok(()) => true,
+        Err(err) => {
+            LAST_ERROR.with(|last| last.set(err));
+            false
+        }
+    }
+}
+
+impl From<PgPoint> for FfiPoint {
+    #[inline]
+    fn from(pt: PgPoint) -> Self {
+        FfiPoint { coord: pt.coord }
+    }
+}
+
+impl From<PgLine> for FfiLine {
+    #[inline]
+    fn from(ln: PgLine) -> Self {
+        FfiLine { coord: ln.coord }
+    }
+}
+
+impl From<Transform> for FfiTransform {
+    #[inline]
+    fn from(trans: Transform) -> Self {
+        FfiTransform { mat: trans.mat }
+    }
+}
+
+impl From<Conic> for FfiConic {
+    #[inline]
+    fn from(conic: Conic) -> Self {
+        FfiConic { mat: conic.mat }
+    }
+}
+
+/// The line through two points. Returns `false` if an argument is null or the line overflows
+/// i64.
+#[no_mangle]
+pub extern "C" fn pg_point_join(
+    pt_p: Option<&FfiPoint>,
+    pt_q: Option<&FfiPoint>,
+    out: Option<&mut FfiLine>,
+) -> bool {
+    run(out, || {
+        let coord = cross_product_checked(&arg(pt_p)?.coord, &arg(pt_q)?.coord);
+        Ok(FfiLine {
+            coord: coord.ok_or(GeometryError::Overflow)?,
+        })
+    })
+}
+
+/// The point where two lines meet. Returns `false` if an argument is null or the point overflows
+/// i64.
+#[no_mangle]
+pub extern "C" fn pg_line_meet(
+    ln_l: Option<&FfiLine>,
+    ln_m: Option<&FfiLine>,
+    out: Option<&mut FfiPoint>,
+) -> bool {
+    run(out, || {
+        let coord = cross_product_checked(&arg(ln_l)?.coord, &arg(ln_m)?.coord);
+        Ok(FfiPoint {
+            coord: coord.ok_or(GeometryError::Overflow)?,
+        })
+    })
+}
+
+/// Store in `out` whether the point lies on the line. Returns `false` if an argument is null.
+#[no_mangle]
+pub extern "C" fn pg_incident(
+    pt_p: Option<&FfiPoint>,
+    ln_l: Option<&FfiLine>,
+    out: Option<&mut bool>,
+) -> bool {
+    run(out, || {
+        let pt_p = PgPoint::new(arg(pt_p)?.coord);
+        Ok(pt_p.incident(&PgLine::new(arg(ln_l)?.coord)))
+    })
+}
+
+/// The harmonic conjugate of `pt_c` with respect to `pt_a` and `pt_b`. Returns `false` if an
+/// argument is null, the points are not collinear, `pt_a` and `pt_b` coincide, or a coordinate
+/// overflows i64.
+#[no_mangle]
+pub extern "C" fn pg_harm_conj(
+    pt_a: Option<&FfiPoint>,
+    pt_b: Option<&FfiPoint>,
+    pt_c: Option<&FfiPoint>,
+    out: Option<&mut FfiPoint>,
+) -> bool {
+    run(out, || {
+        let [pt_a, pt_b, pt_c] =
+            [arg(pt_a)?, arg(pt_b)?, arg(pt_c)?].map(|pt| PgPoint::new(pt.coord));
+        Ok(try_harm_conj(&pt_a, &pt_b, &pt_c)?.into())
+    })
+}
+
+/// The image of `pt_p` under the harmonic homology with center `origin` and axis `mirror`.
+/// Returns `false` if an argument is null, `pt_p` is `origin`, `origin` lies on `mirror`, or a
+/// coordinate overflows i64.
+#[no_mangle]
+pub extern "C" fn pg_involution(
+    origin: Option<&FfiPoint>,
+    mirror: Option<&FfiLine>,
+    pt_p: Option<&FfiPoint>,
+    out: Option<&mut FfiPoint>,
+) -> bool {
+    run(out, || {
+        let origin = PgPoint::new(arg(origin)?.coord);
+        let pt_p = PgPoint::new(arg(pt_p)?.coord);
+        // as `involution`, with the degenerate cases reported by `try_harm_conj`
+        let pt_b = pt_p.join(&origin).meet(&PgLine::new(arg(mirror)?.coord));
+        Ok(try_harm_conj(&origin, &pt_b, &pt_p)?.into())
+    })
+}
+
+/// The direction of the perpendiculars to a line of the Euclidean plane, a point at infinity.
+/// Returns `false` if an argument is null.
+#[no_mangle]
+pub extern "C" fn pg_euclid_perp(ln_l: Option<&FfiLine>, out: Option<&mut FfiPoint>) -> bool {
+    run(out, || {
+        let pt = EuclidLine::new(arg(ln_l)?.coord).perp();
+        Ok(FfiPoint { coord: pt.coord })
+    })
+}
+
+/// The perpendicular from a point to a line of the Euclidean plane. Returns `false` if an
+/// argument is null or a coordinate overflows i64.
+#[no_mangle]
+pub extern "C" fn pg_euclid_altitude(
+    pt_p: Option<&FfiPoint>,
+    ln_l: Option<&FfiLine>,
+    out: Option<&mut FfiLine>,
+) -> bool {
+    run(out, || {
+        let pt_p = EuclidPoint::new(arg(pt_p)?.coord);
+        let ln = EuclidLine::new(arg(ln_l)?.coord).altitude(&pt_p);
+        Ok(FfiLine { coord: ln.coord })
+    })
+}
+
+/// The midpoint of two points of the Euclidean plane. Returns `false` if an argument is null or
+/// a coordinate overflows i64.
+#[no_mangle]
+pub extern "C" fn pg_euclid_midpoint(
+    pt_a: Option<&FfiPoint>,
+    pt_b: Option<&FfiPoint>,
+    out: Option<&mut FfiPoint>,
+) -> bool {
+    run(out, || {
+        let [pt_a, pt_b] = [arg(pt_a)?, arg(pt_b)?].map(|pt| EuclidPoint::new(pt.coord));
+        Ok(FfiPoint {
+            coord: midpoint(&pt_a, &pt_b).coord,
+        })
+    })
+}
+
+/// The reflection of a point in a line of the Euclidean plane. Returns `false` if an argument is
+/// null or a coordinate overflows i64.
+#[no_mangle]
+pub extern "C" fn pg_euclid_reflect(
+    mirror: Option<&FfiLine>,
+    pt_p: Option<&FfiPoint>,
+    out: Option<&mut FfiPoint>,
+) -> bool {
+    run(out, || {
+        let mirror = EuclidLine::new(arg(mirror)?.coord);
+        let pt = reflect(&mirror, &EuclidPoint::new(arg(pt_p)?.coord));
+        Ok(FfiPoint { coord: pt.coord })
+    })
+}
+
+/// The polar line of a point of the hyperbolic plane. Returns `false` if an argument is null.
+#[no_mangle]
+pub extern "C" fn pg_hyperbolic_point_perp(
+    pt_p: Option<&FfiPoint>,
+    out: Option<&mut FfiLine>,
+) -> bool {
+    run(out, || {
+        let ln = HyperbolicPoint::new(arg(pt_p)?.coord).perp();
+        Ok(FfiLine { coord: ln.coord })
+    })
+}
+
+/// The pole of a line of the hyperbolic plane. Returns `false` if an argument is null.
+#[no_mangle]
+pub extern "C" fn pg_hyperbolic_line_perp(
+    ln_l: Option<&FfiLine>,
+    out: Option<&mut FfiPoint>,
+) -> bool {
+    run(out, || {
+        let pt = HyperbolicLine::new(arg(ln_l)?.coord).perp();
+        Ok(FfiPoint { coord: pt.coord })
+    })
+}
+
+/// The polar line of a point of the elliptic plane. Returns `false` if an argument is null.
+#[no_mangle]
+pub extern "C" fn pg_elliptic_point_perp(
+    pt_p: Option<&FfiPoint>,
+    out: Option<&mut FfiLine>,
+) -> bool {
+    run(out, || {
+        let ln = EllipticPoint::new(arg(pt_p)?.coord).perp();
+        Ok(FfiLine { coord: ln.coord })
+    })
+}
+
+/// The pole of a line of the elliptic plane. Returns `false` if an argument is null.
+#[no_mangle]
+pub extern "C" fn pg_elliptic_line_perp(
+    ln_l: Option<&FfiLine>,
+    out: Option<&mut FfiPoint>,
+) -> bool {
+    run(out, || {
+        let pt = EllipticLine::new(arg(ln_l)?.coord).perp();
+        Ok(FfiPoint { coord: pt.coord })
+    })
+}
+
+/// The transform of a matrix, reduced. Returns `false` if an argument is null, or the matrix is
+/// singular or its determinant overflows.
+#[no_mangle]
+pub extern "C" fn pg_transform_new(
+    mat: Option<&FfiTransform>,
+    out: Option<&mut FfiTransform>,
+) -> bool {
+    run(out, || Ok(transform(mat)?.into()))
+}
+
+/// The image of a point under a transform. Returns `false` if an argument is null, the matrix is
+/// singular, or a coordinate overflows i64.
+#[no_mangle]
+pub extern "C" fn pg_transform_apply_point(
+    trans: Option<&FfiTransform>,
+    pt_p: Option<&FfiPoint>,
+    out: Option<&mut FfiPoint>,
+) -> bool {
+    run(out, || {
+        let pt_p = PgPoint::new(arg(pt_p)?.coord);
+        Ok(transform(trans)?.try_apply(&pt_p)?.into())
+    })
+}
+
+/// The image of a line under a transform. Returns `false` if an argument is null, the matrix is
+/// singular, or a coordinate overflows i64.
+#[no_mangle]
+pub extern "C" fn pg_transform_apply_line(
+    trans: Option<&FfiTransform>,
+    ln_l: Option<&FfiLine>,
+    out: Option<&mut FfiLine>,
+) -> bool {
+    run(out, || {
+        let ln_l = PgLine::new(arg(ln_l)?.coord);
+        Ok(transform(trans)?.try_apply_line(&ln_l)?.into())
+    })
+}
+
+/// The transform applying `second` after `first`. Returns `false` if an argument is null, a
+/// matrix is singular, or an entry overflows i64.
+#[no_mangle]
+pub extern "C" fn pg_transform_compose(
+    second: Option<&FfiTransform>,
+    first: Option<&FfiTransform>,
+    out: Option<&mut FfiTransform>,
+) -> bool {
+    run(out, || {
+        Ok(transform(second)?.compose(&transform(first)?)?.into())
+    })
+}
+
+/// The inverse transform. Returns `false` if an argument is null, the matrix is singular, or an
+/// entry overflows i64.
+#[no_mangle]
+pub extern "C" fn pg_transform_inverse(
+    trans: Option<&FfiTransform>,
+    out: Option<&mut FfiTransform>,
+) -> bool {
+    run(out, || Ok(transform(trans)?.inverse()?.into()))
+}
+
+/// The conic `a x² + b xy + c y² + d xz + e yz + f z² = 0`, as `Conic::from_coefficients`.
+/// Returns `false` if `out` is null.
+#[no_mangle]
+pub extern "C" fn pg_conic_from_coefficients(
+    a: i64,
+    b: i64,
+    c: i64,
+    d: i64,
+    e: i64,
+    f: i64,
+    out: Option<&mut FfiConic>,
+) -> bool {
+    run(
+        out,
+        || Ok(Conic::from_coefficients(a, b, c, d, e, f).into()),
+    )
+}
+
+/// Store in `out` whether the point lies on the conic. Returns `false` if an argument is null,
+/// the matrix is not symmetric, or the value of the quadratic form overflows i128.
+#[no_mangle]
+pub extern "C" fn pg_conic_contains(
+    conic_c: Option<&FfiConic>,
+    pt_p: Option<&FfiPoint>,
+    out: Option<&mut bool>,
+) -> bool {
+    run(out, || {
+        Ok(conic(conic_c)?.contains(&PgPoint::new(arg(pt_p)?.coord)))
+    })
+}
+
+/// The polar line of a point, the tangent at a point of the conic. Returns `false` if an
+/// argument is null, the matrix is not symmetric, or a coordinate overflows i64.
+#[no_mangle]
+pub extern "C" fn pg_conic_polar(
+    conic_c: Option<&FfiConic>,
+    pt_p: Option<&FfiPoint>,
+    out: Option<&mut FfiLine>,
+) -> bool {
+    run(out, || {
+        Ok(conic(conic_c)?
+            .polar(&PgPoint::new(arg(pt_p)?.coord))
+            .into())
+    })
+}
+
+/// The pole of a line. Returns `false` if an argument is null, the matrix is not symmetric, or a
+/// coordinate overflows i64.
+#[no_mangle]
+pub extern "C" fn pg_conic_pole(
+    conic_c: Option<&FfiConic>,
+    ln_l: Option<&FfiLine>,
+    out: Option<&mut FfiPoint>,
+) -> bool {
+    run(out, || {
+        Ok(conic(conic_c)?.pole(&PgLine::new(arg(ln_l)?.coord)).into())
+    })
+}
+
+/// The image of a conic under a transform. Returns `false` if an argument is null, the matrix of
+/// the conic is not symmetric, that of the transform singular, or an entry overflows i64.
+#[no_mangle]
+pub extern "C" fn pg_conic_transformed(
+    conic_c: Option<&FfiConic>,
+    trans: Option<&FfiTransform>,
+    out: Option<&mut FfiConic>,
+) -> bool {
+    run(out, || {
+        Ok(transform(trans)?.try_apply_conic(&conic(conic_c)?)?.into())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ffi_projective() {
+        let [pt_p, pt_q] = [[1, 3, 2], [-2, 1, -1]].map(|coord| FfiPoint { coord });
+        let mut ln_l = FfiLine::default();
+        assert!(pg_point_join(Some(&pt_p), Some(&pt_q), Some(&mut ln_l)));
+        assert_eq!(ln_l.coord, [-5, -3, 7]);
+        let mut on_line = false;
+        assert!(pg_incident(Some(&pt_p), Some(&ln_l), Some(&mut on_line)));
+        assert!(on_line);
+        let mut pt_r = FfiPoint::default();
+        let ln_m = FfiLine { coord: [0, 0, 1] };
+        assert!(pg_line_meet(Some(&ln_l), Some(&ln_m), Some(&mut pt_r)));
+        assert_eq!(pt_r.coord, [-3, 5, 0]);
+        // null pointers and overflow are failures, not crashes
+        assert!(!pg_point_join(None, Some(&pt_q), Some(&mut ln_l)));
+        assert!(!pg_point_join(Some(&pt_p), Some(&pt_q), None));
+        let m = i64::MAX;
+        let [big_p, big_q] = [[m, 1, 1], [1, m, 0]].map(|coord| FfiPoint { coord });
+        assert!(!pg_point_join(Some(&big_p), Some(&big_q), Some(&mut ln_l)));
+
+        let [pt_a, pt_b, pt_c] = [[1, 0, 1], [5, 0, 1], [2, 0, 1]].map(|coord| FfiPoint { coord });
+        let mut pt_d = FfiPoint::default();
+        assert!(pg_harm_conj(
+            Some(&pt_a),
+            Some(&pt_b),
+            Some(&pt_c),
+            Some(&mut pt_d)
+        ));
+        assert_eq!(PgPoint::new(pt_d.coord), PgPoint::new([1, 0, -1]));
+        assert!(!pg_harm_conj(
+            Some(&pt_a),
+            Some(&pt_b),
+            Some(&pt_p),
+            Some(&mut pt_d)
+        ));
+        // the reflection in the y-axis, as a harmonic homology with center at infinity
+        let (origin, mirror) = (FfiPoint { coord: [1, 0, 0] }, FfiLine { coord: [1, 0, 0] });
+        assert!(pg_involution(
+            Some(&origin),
+            Some(&mirror),
+            Some(&pt_p),
+            Some(&mut pt_d)
+        ));
+        assert_eq!(PgPoint::new(pt_d.coord), PgPoint::new([-1, 3, 2]));
+    }
+
+    #[test]
+    fn test_ffi_ck_planes() {
+        let ln_l = FfiLine { coord: [1, -1, 0] };
+        let pt_p = FfiPoint { coord: [2, 0, 1] };
+        let (mut pt, mut ln) = (FfiPoint::default(), FfiLine::default());
+        assert!(pg_euclid_perp(Some(&ln_l), Some(&mut pt)));
+        assert_eq!(pt.coord, [1, -1, 0]);
+        assert!(pg_euclid_altitude(Some(&pt_p), Some(&ln_l), Some(&mut ln)));
+        assert_eq!(PgLine::new(ln.coord), PgLine::new([1, 1, -2]));
+        assert!(pg_euclid_reflect(Some(&ln_l), Some(&pt_p), Some(&mut pt)));
+        assert_eq!(PgPoint::new(pt.coord), PgPoint::new([0, 2, 1]));
+        let pt_q = FfiPoint { coord: [0, 4, 1] };
+        assert!(pg_euclid_midpoint(Some(&pt_p), Some(&pt_q), Some(&mut pt)));
+        assert_eq!(PgPoint::new(pt.coord), PgPoint::new([1, 2, 1]));
+
+        assert!(pg_hyperbolic_point_perp(Some(&pt_p), Some(&mut ln)));
+        assert_eq!(ln.coord, [2, 0, -1]);
+        assert!(pg_hyperbolic_line_perp(Some(&ln), Some(&mut pt)));
+        assert_eq!(PgPoint::new(pt.coord), PgPoint::new(pt_p.coord));
+        assert!(pg_elliptic_point_perp(Some(&pt_p), Some(&mut ln)));
+        assert_eq!(ln.coord, [2, 0, 1]);
+        assert!(pg_elliptic_line_perp(Some(&ln), Some(&mut pt)));
+        assert_eq!(pt.coord, pt_p.coord);
+        assert!(!pg_elliptic_line_perp(None, Some(&mut pt)));
+    }
+
+    #[test]
+    fn test_ffi_transform_conic() {
+        let mat = FfiTransform {
+            mat: [[2, 0, 2], [0, 2, -4], [0, 0, 2]],
+        };
+        let mut trans = FfiTransform::default();
+        assert!(pg_transform_new(Some(&mat), Some(&mut trans)));
+        assert_eq!(trans.mat, [[1, 0, 1], [0, 1, -2], [0, 0, 1]]);
+        let singular = FfiTransform {
+            mat: [[1, 2, 3], [2, 4, 6], [0, 0, 1]],
+        };
+        assert!(!pg_transform_new(Some(&singular), Some(&mut trans)));
+        assert!(pg_transform_new(Some(&mat), Some(&mut trans)));
+        let (mut pt, mut ln) = (FfiPoint::default(), FfiLine::default());
+        let pt_p = FfiPoint { coord: [3, 4, 1] };
+        assert!(pg_transform_apply_point(
+            Some(&trans),
+            Some(&pt_p),
+            Some(&mut pt)
+        ));
+        assert_eq!(pt.coord, [4, 2, 1]);
+        // x = 3 is mapped to x = 4
+        let ln_l = FfiLine { coord: [1, 0, -3] };
+        assert!(pg_transform_apply_line(
+            Some(&trans),
+            Some(&ln_l),
+            Some(&mut ln)
+        ));
+        assert_eq!(PgLine::new(ln.coord), PgLine::new([1, 0, -4]));
+        let (mut inverse, mut identity) = (FfiTransform::default(), FfiTransform::default());
+        assert!(pg_transform_inverse(Some(&trans), Some(&mut inverse)));
+        assert!(pg_transform_compose(
+            Some(&inverse),
+            Some(&trans),
+            Some(&mut identity)
+        ));
+        assert_eq!(identity.mat, Transform::IDENTITY.mat);
+
+        let mut circle = FfiConic::default();
+        assert!(pg_conic_from_coefficients(
+            1,
+            0,
+            1,
+            0,
+            0,
+            -25,
+            Some(&mut circle)
+        ));
+        let mut on_conic = false;
+        assert!(pg_conic_contains(
+            Some(&circle),
+            Some(&pt_p),
+            Some(&mut on_conic)
+        ));
+        assert!(on_conic);
+        assert!(pg_conic_polar(Some(&circle), Some(&pt_p), Some(&mut ln)));
+        assert_eq!(PgLine::new(ln.coord), PgLine::new([3, 4, -25]));
+        assert!(pg_conic_pole(Some(&circle), Some(&ln), Some(&mut pt)));
+        assert_eq!(PgPoint::new(pt.coord), PgPoint::new(pt_p.coord));
+        let mut moved = FfiConic::default();
+        assert!(pg_conic_transformed(
+            Some(&circle),
+            Some(&trans),
+            Some(&mut moved)
+        ));
+        assert!(pg_conic_contains(
+            Some(&moved),
+            Some(&FfiPoint { coord: [4, 2, 1] }),
+            Some(&mut on_conic)
+        ));
+        assert!(on_conic);
+        let skew = FfiConic {
+            mat: [[1, 1, 0], [0, 1, 0], [0, 0, -1]],
+        };
+        assert!(!pg_conic_contains(
+            Some(&skew),
+            Some(&pt_p),
+            Some(&mut on_conic)
+        ));
+    }
+
+    #[test]
+    fn test_ffi_errors() {
+        let pt_p = FfiPoint { coord: [1, 3, 2] };
+        let mut ln = FfiLine::default();
+        pg_clear_last_error();
+        assert!(pg_point_join(Some(&pt_p), Some(&pt_p), Some(&mut ln)));
+        assert_eq!(pg_get_last_error(), FfiError::Ok);
+        assert!(!pg_point_join(Some(&pt_p), None, Some(&mut ln)));
+        assert_eq!(pg_get_last_error(), FfiError::NullPointer);
+        // a success does not reset the code
+        assert!(pg_elliptic_point_perp(Some(&pt_p), Some(&mut ln)));
+        assert_eq!(pg_get_last_error(), FfiError::NullPointer);
+        pg_clear_last_error();
+        assert_eq!(pg_get_last_error(), FfiError::Ok);
+
+        let m = i64::MAX;
+        let [big_p, big_q] = [[m, 1, 1], [1, m, 0]].map(|coord| FfiPoint { coord });
+        assert!(!pg_point_join(Some(&big_p), Some(&big_q), Some(&mut ln)));
+        assert_eq!(pg_get_last_error(), FfiError::Overflow);
+        let mut pt = FfiPoint::default();
+        let (pt_a, pt_b) = (FfiPoint { coord: [0, 0, 1] }, FfiPoint { coord: [1, 0, 1] });
+        assert!(!pg_harm_conj(
+            Some(&pt_a),
+            Some(&pt_b),
+            Some(&pt_p),
+            Some(&mut pt)
+        ));
+        assert_eq!(pg_get_last_error(), FfiError::NotCollinear);
+        let mirror = FfiLine { coord: [1, 0, 0] };
+        assert!(!pg_involution(
+            Some(&pt_a),
+            Some(&mirror),
+            Some(&pt_p),
+            Some(&mut pt)
+        ));
+        assert_eq!(pg_get_last_error(), FfiError::NotInGeneralPosition);
+        let singular = FfiTransform {
+            mat: [[1, 2, 3], [2, 4, 6], [0, 0, 1]],
+        };
+        assert!(!pg_transform_apply_point(
+            Some(&singular),
+            Some(&pt_p),
+            Some(&mut pt)
+        ));
+        assert_eq!(pg_get_last_error(), FfiError::SingularTransform);
+        let skew = FfiConic {
+            mat: [[1, 1, 0], [0, 1, 0], [0, 0, -1]],
+        };
+        assert!(!pg_conic_polar(Some(&skew), Some(&pt_p), Some(&mut ln)));
+        assert_eq!(pg_get_last_error(), FfiError::InvalidArgument);
+        // the kernel of the Euclidean plane panics on overflow, which does not unwind into C
+        let (big_r, big_l) = (FfiPoint { coord: [1, m, 1] }, FfiLine { coord: [m, 1, 1] });
+        assert!(!pg_euclid_altitude(
+            Some(&big_r),
+            Some(&big_l),
+            Some(&mut ln)
+        ));
+        assert_eq!(pg_get_last_error(), FfiError::Panic);
+
+        // the code is per thread
+        let other = std::thread::spawn(|| pg_get_last_error()).join().unwrap();
+        assert_eq!(other, FfiError::Ok);
+        assert_eq!(FfiError::from(GeometryError::NotFound), FfiError::NotFound);
+    }
+}
@@ -0,0 +1,226 @@
+// Filtered predicates: floating-point evaluation with an error bound and an exact fallback
+
+use crate::error::GeometryError;
+use crate::pg_object::{det3_checked, PgLine, PgPoint};
+use crate::predicates::{orientation, Orientation};
+use core::cmp::Ordering;
+
+#[inline]
+fn to_f64(v: &[i64; 3]) -> [f64; 3] {
+    [v[0] as f64, v[1] as f64, v[2] as f64]
+}
+
+/// The sign of a value computed in floating point, given a bound on its absolute error, or `None`
+/// if the interval `value ± error` contains zero.
+#[inline]
+fn filtered_sign(value: f64, error: f64) -> Option<Ordering> {
+    if value > error {
+        Some(Ordering::Greater)
+    } else if value < -error {
+        Some(Ordering::Less)
+    } else if error == 0.0 {
+        Some(Ordering::Equal)
+    } else {
+        None
+    }
+}
+
+/// The determinant of three coordinate vectors in floating point, with a bound on its error.
+///
+/// Each i64 converts with a relative error of at most `u = 2^-53`, and each operation on the way
+/// adds at most `u` relative to the magnitude of its terms, so to first order the error is below
+/// `8 u` times the permanent (the determinant with absolute values and all signs positive). The
+/// bound `16 ε = 32 u` leaves ample room for the higher-order terms and the rounding of the
+/// permanent itself. A zero determinant is only certified if the permanent is zero; otherwise it
+/// takes the exact path.
+#[inline]
+fn det3_filtered(v_a: &[i64; 3], v_b: &[i64; 3], v_c: &[i64; 3]) -> Option<Ordering> {
+    let (a, b, c) = (to_f64(v_a), to_f64(v_b), to_f64(v_c));
+    let (m_00, m_01) = (b[1] * c[2], b[2] * c[1]);
+    let (m_10, m_11) = (b[0] * c[2], b[2] * c[0]);
+    let (m_20, m_21) = (b[0] * c[1], b[1] * c[0]);
+    let det = a[0] * (m_00 - m_01) - a[1] * (m_10 - m_11) + a[2] * (m_20 - m_21);
+    let permanent = a[0].abs() * (m_00.abs() + m_01.abs())
+        + a[1].abs() * (m_10.abs() + m_11.abs())
+        + a[2].abs() * (m_20.abs() + m_21.abs());
+    filtered_sign(det, 16.0 * f64::EPSILON * permanent)
+}
+
+/// The dot product of two coordinate vectors in floating point, with the analogous error bound.
+#[inline]
+fn dot_filtered(v_a: &[i64; 3], v_b: &[i64; 3]) -> Option<Ordering> {
+    let (a, b) = (to_f64(v_a), to_f64(v_b));
+    let dot = a[0] * b[0] + a[1] * b[1] + a[2] * b[2];
+    let magnitude = (a[0] * b[0]).abs() + (a[1] * b[1]).abs() + (a[2] * b[2]).abs();
+    filtered_sign(dot, 8.0 * f64::EPSILON * magnitude)
+}
+
+/// The function `orientation_filtered` is `orientation` with a floating-point filter: the
+/// determinant is first evaluated in floating point with an error bound, and only if its sign is
+/// uncertain is it recomputed exactly. The result is the same as that of `orientation`, except that the filter
+/// may decide some inputs for which the exact determinant overflows.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::filtered::orientation_filtered;
+/// use projgeom_rs::predicates::{orientation, Orientation};
+/// use projgeom_rs::PgPoint;
+/// let pt_p = PgPoint::new([0, 0, 1]);
+/// let pt_q = PgPoint::new([2, 0, 2]);
+/// let pt_r = PgPoint::new([0, -1, -1]);
+/// assert_eq!(orientation_filtered(&pt_p, &pt_q, &pt_r), Ok(Orientation::CounterClockwise));
+/// // nearly collinear points with large coordinates fall back to exact arithmetic
+/// let m = 1 << 60;
+/// let [pt_a, pt_b, pt_c] = [[m, m + 1, 1], [m + 1, m + 2, 1], [m + 2, m + 3, 1]].map(PgPoint::new);
+/// assert_eq!(orientation_filtered(&pt_a, &pt_b, &pt_c), orientation(&pt_a, &pt_b, &pt_c));
+/// ```
+pub fn orientation_filtered(
+    pt_p: &PgPoint,
+    pt_q: &PgPoint,
+    pt_r: &PgPoint,
+) -> Result<Orientation, GeometryError> {
+    let signs = [pt_p, pt_q, pt_r].map(|pt| pt.coord[2].signum());
+    if signs.contains(&0) {
+        return Err(GeometryError::PointAtInfinity);
+    }
+    match det3_filtered(&pt_p.coord, &pt_q.coord, &pt_r.coord) {
+        Some(sign) => {
+            let sign = if signs.iter().product::<i64>() < 0 {
+                sign.reverse()
+            } else {
+                sign
+            };
+            Ok(match sign {
+                Ordering::Greater => Orientation::CounterClockwise,
+                Ordering::Less => Orientation::Clockwise,
+                Ordering::Equal => Orientation::Collinear,
+            })
+        }
+        None => orientation(pt_p, pt_q, pt_r),
+    }
+}
+
+/// The function `incident_filtered` checks whether a point lies on a line, first in floating
+/// point and then, if the filter cannot decide, exactly in i128. Unlike
+/// `PgPoint::incident`, it does not panic for large coordinates.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::filtered::incident_filtered;
+/// use projgeom_rs::{PgLine, PgPoint};
+/// let ln_l = PgLine::new([1, -1, 0]);
+/// assert!(incident_filtered(&PgPoint::new([3, 3, 5]), &ln_l));
+/// assert!(!incident_filtered(&PgPoint::new([i64::MAX, i64::MAX - 1, 1]), &ln_l));
+/// ```
+pub fn incident_filtered(pt_p: &PgPoint, ln_l: &PgLine) -> bool {
+    match dot_filtered(&pt_p.coord, &ln_l.coord) {
+        Some(sign) => sign == Ordering::Equal,
+        // the sum of three products of i64 fits into i128
+        None => {
+            (0..3)
+                .map(|i| i128::from(pt_p.coord[i]) * i128::from(ln_l.coord[i]))
+                .sum::<i128>()
+                == 0
+        }
+    }
+}
+
+/// The function `coincident_filtered` checks whether three points are collinear, first in
+/// floating point and then, if the filter cannot decide, exactly in i128.
+///
+/// Returns:
+///
+/// Whether the points are collinear, or `GeometryError::Overflow` if the filter fails and the
+/// determinant does not fit into i128.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::filtered::coincident_filtered;
+/// use projgeom_rs::PgPoint;
+/// let [pt_p, pt_q, pt_r] = [[1, 2, 3], [4, 5, 6], [7, 8, 9]].map(PgPoint::new);
+/// assert_eq!(coincident_filtered(&pt_p, &pt_q, &pt_r), Ok(true));
+/// ```
+pub fn coincident_filtered(
+    pt_p: &PgPoint,
+    pt_q: &PgPoint,
+    pt_r: &PgPoint,
+) -> Result<bool, GeometryError> {
+    match det3_filtered(&pt_p.coord, &pt_q.coord, &pt_r.coord) {
+        Some(sign) => Ok(sign == Ordering::Equal),
+        None => det3_checked(&pt_p.coord, &pt_q.coord, &pt_r.coord)
+            .map(|det| det == 0)
+            .ok_or(GeometryError::Overflow),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pg_plane::{coincident, ProjectivePlanePrimitive};
+
+    #[test]
+    fn test_filtered() {
+        let coords = [
+            [0, 0, 1],
+            [1, 0, 1],
+            [0, 1, 1],
+            [2, 2, 2],
+            [-3, 5, -1],
+            [4, 0, 2],
+            [7, -2, 3],
+        ];
+        for c_p in coords {
+            for c_q in coords {
+                for c_r in coords {
+                    let [pt_p, pt_q, pt_r] = [c_p, c_q, c_r].map(PgPoint::new);
+                    assert_eq!(
+                        orientation_filtered(&pt_p, &pt_q, &pt_r),
+                        orientation(&pt_p, &pt_q, &pt_r)
+                    );
+                    assert_eq!(
+                        coincident_filtered(&pt_p, &pt_q, &pt_r),
+                        Ok(coincident(&pt_p, &pt_q, &pt_r))
+                    );
+                    let ln_l = PgLine::new(c_r);
+                    assert_eq!(incident_filtered(&pt_p, &ln_l), pt_p.incident(&ln_l));
+                }
+            }
+        }
+
+        // large, nearly degenerate inputs where f64 alone would be wrong
+        let m = 1_i64 << 60;
+        let [pt_a, pt_b] = [[m, m + 1, 1], [m + 1, m + 2, 1]].map(PgPoint::new);
+        for (offset, expected) in [
+            (0, Orientation::Collinear),
+            (1, Orientation::CounterClockwise),
+            (-1, Orientation::Clockwise),
+        ] {
+            let pt_c = PgPoint::new([m + 2, m + 3 + offset, 1]);
+            assert_eq!(orientation_filtered(&pt_a, &pt_b, &pt_c), Ok(expected));
+            assert_eq!(
+                coincident_filtered(&pt_a, &pt_b, &pt_c),
+                Ok(expected == Orientation::Collinear)
+            );
+        }
+        let ln_l = PgLine::new([1, -1, 1]);
+        assert!(incident_filtered(&pt_a, &ln_l));
+        assert!(!incident_filtered(&PgPoint::new([m, m + 2, 1]), &ln_l));
+        assert_eq!(
+            orientation_filtered(&pt_a, &pt_b, &PgPoint::new([1, 1, 0])),
+            Err(GeometryError::PointAtInfinity)
+        );
+        let big = [
+            [i64::MAX, 0, 1],
+            [0, i64::MAX, 1],
+            [-i64::MAX, -i64::MAX, 1],
+        ];
+        let [big_1, big_2, big_3] = big.map(PgPoint::new);
+        assert_eq!(
+            orientation_filtered(&big_1, &big_2, &big_3),
+            Ok(Orientation::CounterClockwise)
+        );
+    }
+}
@@ -0,0 +1,256 @@
+// Finite groups of collineations: closure, orbits and stabilizers
+
+use crate::conic::Conic;
+use crate::error::GeometryError;
+use crate::pg_object::{PgLine, PgPoint};
+use crate::transform::Transform;
+use std::collections::HashSet;
+use std::hash::Hash;
+
+/// The `GroupAction` trait is implemented by the objects that transforms act on.
+pub trait GroupAction: Sized + Clone + Eq + Hash {
+    /// Return the image under the transform, or `GeometryError::Overflow`.
+    fn act(&self, trans: &Transform) -> Result<Self, GeometryError>;
+}
+
+impl GroupAction for PgPoint {
+    #[inline]
+    fn act(&self, trans: &Transform) -> Result<Self, GeometryError> {
+        trans.try_apply(self)
+    }
+}
+
+impl GroupAction for PgLine {
+    #[inline]
+    fn act(&self, trans: &Transform) -> Result<Self, GeometryError> {
+        trans.try_apply_line(self)
+    }
+}
+
+/// Images of conics are reduced (see `Transform::apply_conic`), so that conics differing by a
+/// factor are identified.
+impl GroupAction for Conic {
+    #[inline]
+    fn act(&self, trans: &Transform) -> Result<Self, GeometryError> {
+        trans.try_apply_conic(self)
+    }
+}
+
+/// Breadth-first search for the closure of `start` under `step`, with at most `max_size`
+/// elements.
+fn close<T: Clone + Eq + Hash>(
+    start: T,
+    max_size: usize,
+    mut step: impl FnMut(&T) -> Vec<Result<T, GeometryError>>,
+) -> Result<Vec<T>, GeometryError> {
+    let mut elements = vec![start.clone()];
+    let mut seen = HashSet::from([start]);
+    let mut i = 0;
+    while i < elements.len() {
+        for next in step(&elements[i]) {
+            let next = next?;
+            if seen.insert(next.clone()) {
+                if elements.len() == max_size {
+                    return Err(GeometryError::SizeLimitExceeded);
+                }
+                elements.push(next);
+            }
+        }
+        i += 1;
+    }
+    Ok(elements)
+}
+
+/// The function `closure` computes the group generated by a set of transforms. In a finite group
+/// the inverses are powers, so the products of the generators are all there is.
+///
+/// Arguments:
+///
+/// * `generators`: The generating transforms.
+/// * `max_size`: The maximum number of elements.
+///
+/// Returns:
+///
+/// The function `closure` returns the elements of the group, starting with the identity, or
+/// `GeometryError::SizeLimitExceeded` if there are more than `max_size` (e.g. if the group is
+/// infinite), or `GeometryError::Overflow`.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::group::closure;
+/// use projgeom_rs::transform::Transform;
+/// use projgeom_rs::GeometryError;
+/// // the symmetries of the square
+/// let rotation = Transform::new([[0, -1, 0], [1, 0, 0], [0, 0, 1]]).unwrap();
+/// let reflection = Transform::new([[-1, 0, 0], [0, 1, 0], [0, 0, 1]]).unwrap();
+/// let group = closure(&[rotation, reflection], 100).unwrap();
+/// assert_eq!(group.len(), 8);
+/// assert_eq!(group[0], Transform::IDENTITY);
+/// let translation = Transform::new([[1, 0, 1], [0, 1, 0], [0, 0, 1]]).unwrap();
+/// assert_eq!(closure(&[translation], 100), Err(GeometryError::SizeLimitExceeded));
+/// ```
+pub fn closure(generators: &[Transform], max_size: usize) -> Result<Vec<Transform>, GeometryError> {
+    close(Transform::IDENTITY, max_size, |elem| {
+        generators.iter().map(|gen| gen.compose(elem)).collect()
+    })
+}
+
+/// The function `orbit` computes the orbit of an object under the group generated by a set of
+/// transforms, which may also be the whole group.
+///
+/// Arguments:
+///
+/// * `generators`: The generating transforms.
+/// * `obj`: The object.
+/// * `max_size`: The maximum size of the orbit.
+///
+/// Returns:
+///
+/// The function `orbit` returns the distinct images, starting with the object itself, or
+/// `GeometryError::SizeLimitExceeded` if there are more than `max_size`, or
+/// `GeometryError::Overflow`.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::group::orbit;
+/// use projgeom_rs::transform::Transform;
+/// use projgeom_rs::PgPoint;
+/// let rotation = Transform::new([[0, -1, 0], [1, 0, 0], [0, 0, 1]]).unwrap();
+/// let points = orbit(&[rotation], &PgPoint::new([2, 1, 1]), 100).unwrap();
+/// assert_eq!(points.len(), 4);
+/// assert!(points.contains(&PgPoint::new([1, -2, 1])));
+/// ```
+pub fn orbit<T: GroupAction>(
+    generators: &[Transform],
+    obj: &T,
+    max_size: usize,
+) -> Result<Vec<T>, GeometryError> {
+    close(obj.act(&Transform::IDENTITY)?, max_size, |elem| {
+        generators.iter().map(|gen| elem.act(gen)).collect()
+    })
+}
+
+/// The function `stabilizer` computes the subgroup of a finite group fixing an object.
+///
+/// Arguments:
+///
+/// * `group`: All elements of the group, e.g. from `closure`.
+/// * `obj`: The object.
+///
+/// Returns:
+///
+/// The function `stabilizer` returns the elements fixing the object, or
+/// `GeometryError::Overflow`.
+pub fn stabilizer<T: GroupAction>(
+    group: &[Transform],
+    obj: &T,
+) -> Result<Vec<Transform>, GeometryError> {
+    let obj = obj.act(&Transform::IDENTITY)?;
+    let mut result = Vec::new();
+    for trans in group {
+        if obj.act(trans)? == obj {
+            result.push(*trans);
+        }
+    }
+    Ok(result)
+}
+
+/// The function `set_stabilizer` computes the subgroup of a finite group mapping a configuration
+/// onto itself, i.e. the symmetries of the configuration within the group.
+///
+/// Arguments:
+///
+/// * `group`: All elements of the group, e.g. from `closure`.
+/// * `objs`: The objects of the configuration.
+///
+/// Returns:
+///
+/// The function `set_stabilizer` returns the elements permuting the objects, or
+/// `GeometryError::Overflow`.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::group::{closure, set_stabilizer};
+/// use projgeom_rs::transform::Transform;
+/// use projgeom_rs::PgPoint;
+/// let rotation = Transform::new([[0, -1, 0], [1, 0, 0], [0, 0, 1]]).unwrap();
+/// let reflection = Transform::new([[-1, 0, 0], [0, 1, 0], [0, 0, 1]]).unwrap();
+/// let group = closure(&[rotation, reflection], 100).unwrap();
+/// // a rectangle keeps the symmetries of the square that fix or swap the axes
+/// let rectangle = [[2, 1, 1], [-2, 1, 1], [-2, -1, 1], [2, -1, 1]].map(PgPoint::new);
+/// assert_eq!(set_stabilizer(&group, &rectangle).unwrap().len(), 4);
+/// ```
+pub fn set_stabilizer<T: GroupAction>(
+    group: &[Transform],
+    objs: &[T],
+) -> Result<Vec<Transform>, GeometryError> {
+    let set = objs
+        .iter()
+        .map(|obj| obj.act(&Transform::IDENTITY))
+        .collect::<Result<HashSet<T>, GeometryError>>()?;
+    let mut result = Vec::new();
+    for trans in group {
+        // a map of a finite set into itself that is injective is onto
+        let images = set
+            .iter()
+            .map(|obj| obj.act(trans))
+            .collect::<Result<Vec<T>, GeometryError>>()?;
+        if images.iter().all(|image| set.contains(image)) {
+            result.push(*trans);
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pg_plane::Join;
+
+    #[test]
+    fn test_group() {
+        // the monomial matrices with entries ±1, modulo -1: permutations and sign changes of the
+        // coordinates
+        let swap = Transform::new([[0, 1, 0], [1, 0, 0], [0, 0, 1]]).unwrap();
+        let cycle = Transform::new([[0, 0, 1], [1, 0, 0], [0, 1, 0]]).unwrap();
+        let sign = Transform::new([[-1, 0, 0], [0, 1, 0], [0, 0, 1]]).unwrap();
+        let group = closure(&[swap, cycle, sign], 1000).unwrap();
+        assert_eq!(group.len(), 24);
+        assert_eq!(
+            closure(&[swap, cycle, sign], 23),
+            Err(GeometryError::SizeLimitExceeded)
+        );
+        let inverses = group
+            .iter()
+            .all(|trans| group.contains(&trans.inverse().unwrap()));
+        assert!(inverses);
+
+        // orbit-stabilizer
+        for coord in [[1, 0, 0], [1, 1, 1], [1, 2, 0], [1, 2, 3], [0, 1, 1]] {
+            let pt = PgPoint::new(coord);
+            let points = orbit(&group, &pt, 100).unwrap();
+            assert_eq!(points, orbit(&[swap, cycle, sign], &pt, 100).unwrap());
+            let stab = stabilizer(&group, &pt).unwrap();
+            assert_eq!(points.len() * stab.len(), group.len());
+            let ln = PgLine::new(coord);
+            assert_eq!(orbit(&group, &ln, 100).unwrap().len(), points.len());
+        }
+
+        // the quadrangle [±1, ±1, 1] is invariant, and so is the conic x² + y² = 2 z² through it
+        let quadrangle = [[1, 1, 1], [1, 1, -1], [1, -1, 1], [-1, 1, 1]].map(PgPoint::new);
+        assert_eq!(set_stabilizer(&group, &quadrangle).unwrap().len(), 24);
+        let diagonals = [
+            quadrangle[0].join(&quadrangle[1]),
+            quadrangle[2].join(&quadrangle[3]),
+        ];
+        assert_eq!(set_stabilizer(&group, &diagonals).unwrap().len(), 8);
+        let conic = Conic::from_coefficients(1, 0, 1, 0, 0, -2);
+        assert_eq!(orbit(&group, &conic, 100).unwrap().len(), 3);
+        assert_eq!(stabilizer(&group, &conic).unwrap().len(), 8);
+        let scaled = Conic::new(conic.mat.map(|row| row.map(|x| 5 * x)));
+        assert_eq!(stabilizer(&group, &scaled).unwrap().len(), 8);
+    }
+}
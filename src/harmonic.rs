@@ -0,0 +1,209 @@
+// The projective scale and the harmonic net of three collinear points
+
+use crate::pg_plane::{harm_conj, ProjectivePlane};
+use core::marker::PhantomData;
+
+/// The `ProjectiveScale` iterator yields the integer points `0, 1, 2, 3, ...` of the projective
+/// scale on a line, given the points `0`, `1` and `∞`. Each point is the harmonic conjugate of
+/// its second predecessor with respect to its predecessor and `∞`, so the construction uses
+/// nothing but joins and meets.
+///
+/// The iterator is infinite; limit it with `take`. Without normalization (see `normalized_by`),
+/// the coordinates of integer points pick up common factors and soon overflow.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::harmonic::ProjectiveScale;
+/// use projgeom_rs::PgPoint;
+/// let scale = ProjectiveScale::new(
+///     PgPoint::new([0, 0, 1]),
+///     PgPoint::new([1, 0, 1]),
+///     PgPoint::new([1, 0, 0]),
+/// );
+/// let points: Vec<PgPoint> = scale.take(4).collect();
+/// assert_eq!(points, [[0, 0, 1], [1, 0, 1], [2, 0, 1], [3, 0, 1]].map(PgPoint::new));
+/// ```
+pub struct ProjectiveScale<Point, Line, Value> {
+    current: Point,
+    next: Point,
+    infinity: Point,
+    normalize: fn(&Point) -> Point,
+    _marker: PhantomData<(Line, Value)>,
+}
+
+impl<Point, Line, Value> ProjectiveScale<Point, Line, Value>
+where
+    Value: Default + Eq,
+    Point: ProjectivePlane<Line, Value> + Clone,
+    Line: ProjectivePlane<Point, Value>,
+{
+    /// Create the scale from the three distinct collinear points `0`, `1` and `∞`. Iterating
+    /// panics if the points are not collinear.
+    #[inline]
+    pub fn new(zero: Point, one: Point, infinity: Point) -> Self {
+        Self {
+            current: zero,
+            next: one,
+            infinity,
+            normalize: Point::clone,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Apply `normalize` to every constructed point, e.g. `PgPoint::normalized` to keep integer
+    /// coordinates from growing by common factors.
+    #[inline]
+    pub fn normalized_by(mut self, normalize: fn(&Point) -> Point) -> Self {
+        self.normalize = normalize;
+        self
+    }
+}
+
+impl<Point, Line, Value> Iterator for ProjectiveScale<Point, Line, Value>
+where
+    Value: Default + Eq,
+    Point: ProjectivePlane<Line, Value> + Clone,
+    Line: ProjectivePlane<Point, Value>,
+{
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Point> {
+        let following = (self.normalize)(&harm_conj(&self.next, &self.infinity, &self.current));
+        let next = core::mem::replace(&mut self.next, following);
+        Some(core::mem::replace(&mut self.current, next))
+    }
+}
+
+/// The `HarmonicNet` iterator yields, in order, the points `k / 2^depth` for
+/// `k = 0, 1, ..., 2^depth` between the points `0` and `1` of a projective scale with the point
+/// `∞`. Each new point is the harmonic conjugate of `∞` with respect to two neighbours, i.e. their
+/// midpoint on the scale, so the net is refined by `depth` rounds of harmonic conjugation.
+///
+/// As for `ProjectiveScale`, integer points should be normalized for deeper nets.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::harmonic::HarmonicNet;
+/// use projgeom_rs::PgPoint;
+/// let net = HarmonicNet::new(
+///     PgPoint::new([0, 0, 1]),
+///     PgPoint::new([1, 0, 1]),
+///     PgPoint::new([1, 0, 0]),
+///     2,
+/// )
+/// .normalized_by(PgPoint::normalized);
+/// let points: Vec<PgPoint> = net.collect();
+/// assert_eq!(points, [[0, 0, 4], [1, 0, 4], [2, 0, 4], [3, 0, 4], [4, 0, 4]].map(PgPoint::new));
+/// ```
+#[cfg(feature = "std")]
+pub struct HarmonicNet<Point, Line, Value> {
+    /// Intervals still to be refined, with their remaining depth, the leftmost on top
+    stack: Vec<(Point, Point, usize)>,
+    last: Option<Point>,
+    infinity: Point,
+    normalize: fn(&Point) -> Point,
+    _marker: PhantomData<(Line, Value)>,
+}
+
+#[cfg(feature = "std")]
+impl<Point, Line, Value> HarmonicNet<Point, Line, Value>
+where
+    Value: Default + Eq,
+    Point: ProjectivePlane<Line, Value> + Clone,
+    Line: ProjectivePlane<Point, Value>,
+{
+    /// Create the net of the given depth from the three distinct collinear points `0`, `1` and
+    /// `∞`. Iterating panics if the points are not collinear.
+    #[inline]
+    pub fn new(zero: Point, one: Point, infinity: Point, depth: usize) -> Self {
+        Self {
+            stack: vec![(zero, one.clone(), depth)],
+            last: Some(one),
+            infinity,
+            normalize: Point::clone,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Apply `normalize` to every constructed point, e.g. `PgPoint::normalized` to keep integer
+    /// coordinates from growing by common factors.
+    #[inline]
+    pub fn normalized_by(mut self, normalize: fn(&Point) -> Point) -> Self {
+        self.normalize = normalize;
+        self
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Point, Line, Value> Iterator for HarmonicNet<Point, Line, Value>
+where
+    Value: Default + Eq,
+    Point: ProjectivePlane<Line, Value> + Clone,
+    Line: ProjectivePlane<Point, Value>,
+{
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Point> {
+        while let Some((left, right, depth)) = self.stack.pop() {
+            if depth == 0 {
+                return Some(left);
+            }
+            let middle = (self.normalize)(&harm_conj(&left, &right, &self.infinity));
+            self.stack.push((middle.clone(), right, depth - 1));
+            self.stack.push((left, middle, depth - 1));
+        }
+        self.last.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pg_object::{PgLine, PgPoint};
+    use crate::pg_plane::{Join, ProjectivePlanePrimitive};
+
+    #[test]
+    fn test_projective_scale() {
+        // a scale on a general line, whose point n is zero + n * infinity
+        let zero = PgPoint::new([1, 2, 3]);
+        let infinity = PgPoint::new([2, -1, 1]);
+        let one = zero.parametrize(1, &infinity, 1);
+        let ln_l = zero.join(&infinity);
+        for (n, pt) in ProjectiveScale::new(zero.clone(), one, infinity.clone())
+            .normalized_by(PgPoint::normalized)
+            .take(50)
+            .enumerate()
+        {
+            assert!(pt.incident(&ln_l));
+            assert_eq!(pt, zero.parametrize(1, &infinity, n as i64));
+        }
+    }
+
+    #[test]
+    fn test_harmonic_net() {
+        let zero = PgPoint::new([1, 2, 3]);
+        let infinity = PgPoint::new([2, -1, 1]);
+        let one = zero.parametrize(1, &infinity, 1);
+        let net: Vec<PgPoint> =
+            HarmonicNet::<PgPoint, PgLine, i64>::new(zero.clone(), one, infinity.clone(), 5)
+                .normalized_by(PgPoint::normalized)
+                .collect();
+        assert_eq!(net.len(), 33);
+        for (k, pt) in net.iter().enumerate() {
+            assert_eq!(*pt, zero.parametrize(32, &infinity, k as i64));
+        }
+    }
+
+    #[test]
+    fn test_unnormalized() {
+        let scale = ProjectiveScale::new(
+            PgPoint::new([0, 0, 1]),
+            PgPoint::new([1, 0, 1]),
+            PgPoint::new([1, 0, 0]),
+        );
+        let points: Vec<PgPoint> = scale.take(3).collect();
+        assert_eq!(points[2], PgPoint::new([2, 0, 1]));
+    }
+}
@@ -1,31 +1,141 @@
-use crate::ck_plane::{CayleyKleinPlane, CayleyKleinPlanePrimitive};
+// Hyperbolic Geometry
+
+use crate::ck_plane::CayleyKleinPlanePrimitive;
+use crate::impl_cayley_klein_plane;
 use crate::pg_object::{HyperbolicLine, HyperbolicPoint};
+use crate::pg_plane::{Join, ProjectivePlanePrimitive};
+use core::cmp::Ordering;
+
+// The polarity of the hyperbolic plane is given by diag(1, 1, -1): the pole or polar is created by
+// negating the third coordinate.
+impl_cayley_klein_plane!(impl HyperbolicPoint, HyperbolicLine,
+    |pt| HyperbolicLine::new([pt.coord[0], pt.coord[1], -pt.coord[2]]),
+    |ln| HyperbolicPoint::new([ln.coord[0], ln.coord[1], -ln.coord[2]]),
+    test hyperbolic_polarity);
+
+/// Compare `x² + y²` with `z²` exactly. The squares of i64 values and the sum of two of them fit
+/// into u128.
+#[inline]
+fn cmp_absolute(coord: &[i64; 3]) -> Ordering {
+    let [x, y, z] = coord.map(|c| u128::from(c.unsigned_abs()));
+    (x * x + y * y).cmp(&(z * z))
+}
 
-/// The code block is implementing the hyperbolic geometry of a point in the CayleyKleinPlanePrimitive trait for the
-/// HyperbolicPoint struct.
-impl CayleyKleinPlanePrimitive<HyperbolicLine> for HyperbolicPoint {
+/// The `HyperbolicLinePair` enum lists the relative positions of two distinct lines of the
+/// hyperbolic plane.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HyperbolicLinePair {
+    /// The lines meet in an interior point.
+    Intersecting,
+    /// The lines meet on the absolute, in the given limit point.
+    Parallel(HyperbolicPoint),
+    /// The lines meet outside the absolute, so they have a common perpendicular.
+    Ultraparallel,
+}
+
+impl HyperbolicPoint {
+    /// Check if the point lies inside the absolute `x² + y² = z²`, i.e. is a point of the
+    /// hyperbolic plane.
     #[inline]
-    fn perp(&self) -> HyperbolicLine {
-        HyperbolicLine::new([self.coord[0], self.coord[1], -self.coord[2]])
+    pub fn is_interior(&self) -> bool {
+        cmp_absolute(&self.coord) == Ordering::Less
+    }
+
+    /// Check if the point lies on the absolute, i.e. is an ideal point (a point at infinity of the
+    /// hyperbolic plane).
+    #[inline]
+    pub fn is_ideal(&self) -> bool {
+        cmp_absolute(&self.coord) == Ordering::Equal
     }
-}
 
-/// The code block is implementing the hyperbolic geometry of a line in the CayleyKleinPlanePrimitive trait for the
-/// HyperbolicLine struct. It defines a method called `perp` that returns a perpendicular point to the line.
-/// The perpendicular point is created by negating the third coordinate of the line's coordinates and
-/// creating a new HyperbolicPoint with the updated coordinates.
-/// The code block is implementing the hyperbolic geometry of a line in the CayleyKleinPlanePrimitive trait for the
-/// HyperbolicLine struct. It defines a method called `perp` that returns a perpendicular point to the line.
-/// The perpendicular point is created by negating the third coordinate of the line's coordinates and
-/// creating a new HyperbolicPoint with the updated coordinates.
-/// Hyperbolic geometry of the line
-impl CayleyKleinPlanePrimitive<HyperbolicPoint> for HyperbolicLine {
+    /// Check if the point lies outside the absolute. Ultra-ideal points are the poles of the lines
+    /// of the hyperbolic plane.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::HyperbolicPoint;
+    /// assert!(HyperbolicPoint::new([1, 2, 3]).is_interior());
+    /// assert!(HyperbolicPoint::new([3, 4, 5]).is_ideal());
+    /// assert!(HyperbolicPoint::new([1, 1, 1]).is_ultra_ideal());
+    /// ```
     #[inline]
-    fn perp(&self) -> HyperbolicPoint {
-        HyperbolicPoint::new([self.coord[0], self.coord[1], -self.coord[2]])
+    pub fn is_ultra_ideal(&self) -> bool {
+        cmp_absolute(&self.coord) == Ordering::Greater
     }
 }
 
-impl CayleyKleinPlane<HyperbolicLine, i64> for HyperbolicPoint {}
+impl HyperbolicLine {
+    /// Check if the line meets the interior of the absolute, i.e. is a line of the hyperbolic
+    /// plane. Equivalently, its pole is ultra-ideal.
+    #[inline]
+    pub fn is_hyperbolic(&self) -> bool {
+        // the absolute is its own dual: the line conic is diag(1, 1, -1) as well
+        cmp_absolute(&self.coord) == Ordering::Greater
+    }
+
+    /// The function `classify_pair` determines whether two lines of the hyperbolic plane
+    /// intersect, are (limiting) parallel or are ultraparallel, by the position of their meet
+    /// relative to the absolute.
+    ///
+    /// Returns:
+    ///
+    /// The function `classify_pair` returns `None` if the lines coincide or one of them is not
+    /// hyperbolic (see `is_hyperbolic`).
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::hyp_object::HyperbolicLinePair;
+    /// use projgeom_rs::{HyperbolicLine, HyperbolicPoint};
+    /// let ln_x = HyperbolicLine::new([0, 1, 0]);
+    /// let ln_y = HyperbolicLine::new([1, 0, 0]);
+    /// assert_eq!(ln_x.classify_pair(&ln_y), Some(HyperbolicLinePair::Intersecting));
+    /// // x = 1 + y meets the x-axis on the absolute
+    /// let ln_l = HyperbolicLine::new([1, -1, -1]);
+    /// assert_eq!(
+    ///     ln_x.classify_pair(&ln_l),
+    ///     Some(HyperbolicLinePair::Parallel(HyperbolicPoint::new([1, 0, 1])))
+    /// );
+    /// let ln_m = HyperbolicLine::new([0, 2, -1]);
+    /// assert_eq!(ln_x.classify_pair(&ln_m), Some(HyperbolicLinePair::Ultraparallel));
+    /// assert_eq!(ln_x.classify_pair(&ln_x), None);
+    /// ```
+    pub fn classify_pair(&self, other: &HyperbolicLine) -> Option<HyperbolicLinePair> {
+        if !self.is_hyperbolic() || !other.is_hyperbolic() || self == other {
+            return None;
+        }
+        let pt_m = self.meet(other);
+        Some(match cmp_absolute(&pt_m.coord) {
+            Ordering::Less => HyperbolicLinePair::Intersecting,
+            Ordering::Equal => HyperbolicLinePair::Parallel(pt_m),
+            Ordering::Greater => HyperbolicLinePair::Ultraparallel,
+        })
+    }
 
-impl CayleyKleinPlane<HyperbolicPoint, i64> for HyperbolicLine {}
+    /// The function `common_perpendicular` computes the common perpendicular of two ultraparallel
+    /// lines, the line through their poles, which is the polar of their meet.
+    ///
+    /// Returns:
+    ///
+    /// The function `common_perpendicular` returns `None` unless the lines are ultraparallel.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::{is_perpendicular, HyperbolicLine};
+    /// // the vertical lines x = 1/2 and x = -1/2
+    /// let ln_l = HyperbolicLine::new([2, 0, -1]);
+    /// let ln_m = HyperbolicLine::new([2, 0, 1]);
+    /// let ln_p = ln_l.common_perpendicular(&ln_m).unwrap();
+    /// assert_eq!(ln_p, HyperbolicLine::new([0, 1, 0]));
+    /// assert!(is_perpendicular(&ln_p, &ln_l) && is_perpendicular(&ln_p, &ln_m));
+    /// assert_eq!(ln_l.common_perpendicular(&HyperbolicLine::new([0, 1, 0])), None);
+    /// ```
+    pub fn common_perpendicular(&self, other: &HyperbolicLine) -> Option<HyperbolicLine> {
+        match self.classify_pair(other)? {
+            HyperbolicLinePair::Ultraparallel => Some(self.perp().join(&other.perp())),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,497 @@
+// Exchange of configurations with GeoGebra and Asymptote
+
+use crate::conic::Conic;
+use crate::error::GeometryError;
+use crate::pg_object::{PgLine, PgPoint};
+use core::fmt;
+use num_integer::Integer;
+use std::fmt::Write;
+
+/// An object of a `Scene`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SceneObject {
+    Point(PgPoint),
+    Line(PgLine),
+    Conic(Conic),
+}
+
+/// The `ParseSceneError` enum describes why GeoGebra text could not be parsed. The line numbers
+/// start at 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseSceneError {
+    /// The line is not a supported command: `A = (x, y)`, or `name: <equation>` of degree one or
+    /// two in `x` and `y`.
+    InvalidCommand(usize),
+    /// A number on the line is not an integer (or, for points, a fraction) that fits into i64.
+    InvalidNumber(usize),
+}
+
+impl fmt::Display for ParseSceneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseSceneError::InvalidCommand(line) => {
+                write!(f, "line {}: unsupported command", line)
+            }
+            ParseSceneError::InvalidNumber(line) => write!(f, "line {}: invalid number", line),
+        }
+    }
+}
+
+impl core::error::Error for ParseSceneError {}
+
+/// The monomials of a conic equation, in the order of `Conic::from_coefficients`.
+const MONOMIALS: [&str; 6] = ["x^2", "x*y", "y^2", "x", "y", ""];
+
+/// Format `p / q` in lowest terms, as an integer if possible.
+fn format_ratio(p: i64, q: i64) -> String {
+    let common = p.gcd(&q);
+    let (p, q) = (p / common, q / common);
+    let (p, q) = if q < 0 { (-p, -q) } else { (p, q) };
+    if q == 1 {
+        format!("{}", p)
+    } else {
+        format!("{}/{}", p, q)
+    }
+}
+
+/// Format `Σ c_i m_i` with the given monomials, skipping zero terms, e.g. `2x - y + 5`.
+fn format_polynomial(coeffs: &[i64], monomials: &[&str]) -> String {
+    let mut result = String::new();
+    for (&c, &m) in coeffs.iter().zip(monomials) {
+        if c == 0 {
+            continue;
+        }
+        let sign = match (result.is_empty(), c < 0) {
+            (true, true) => "-",
+            (true, false) => "",
+            (false, true) => " - ",
+            (false, false) => " + ",
+        };
+        let magnitude = c.unsigned_abs();
+        let _ = match (magnitude, m.is_empty()) {
+            (1, false) => write!(result, "{}{}", sign, m),
+            (_, false) => write!(result, "{}{}{}", sign, magnitude, m),
+            (_, true) => write!(result, "{}{}", sign, magnitude),
+        };
+    }
+    if result.is_empty() {
+        result.push('0');
+    }
+    result
+}
+
+/// The coefficients of `x`, `y` and 1 of the equation of an affine line.
+fn line_coefficients(ln: &PgLine) -> Result<[i64; 3], GeometryError> {
+    if ln.coord[..2] == [0, 0] {
+        return Err(GeometryError::LineAtInfinity);
+    }
+    let common = ln.coord.iter().fold(0, |g, x| g.gcd(x));
+    Ok(ln.coord.map(|x| x / common))
+}
+
+/// The coefficients of the equation of a conic, in the order of `MONOMIALS`, without a common
+/// factor.
+fn conic_coefficients(conic: &Conic) -> Result<[i64; 6], GeometryError> {
+    let [[a, b, d], [_, c, e], [_, _, f]] = conic.mat;
+    let twice = |x: i64| x.checked_mul(2).ok_or(GeometryError::Overflow);
+    let coeffs = [a, twice(b)?, c, twice(d)?, twice(e)?, f];
+    let common = coeffs.iter().fold(0, |g, x| g.gcd(x)).max(1);
+    Ok(coeffs.map(|x| x / common))
+}
+
+/// The affine coordinates of a point, as formatted fractions.
+fn point_coordinates(pt: &PgPoint) -> Result<(String, String), GeometryError> {
+    let [x, y, z] = pt.coord;
+    if z == 0 {
+        return Err(GeometryError::PointAtInfinity);
+    }
+    Ok((format_ratio(x, z), format_ratio(y, z)))
+}
+
+/// Parse an integer or a fraction `p/q` with `q` nonzero.
+fn parse_ratio(s: &str) -> Option<(i64, i64)> {
+    let (p, q) = match s.split_once('/') {
+        Some((p, q)) => (p.trim().parse().ok()?, q.trim().parse().ok()?),
+        None => (s.trim().parse().ok()?, 1),
+    };
+    (q != 0).then_some((p, q))
+}
+
+/// Parse the point `(x, y)` with rational coordinates.
+fn parse_point(s: &str) -> Result<PgPoint, bool> {
+    let inner = s
+        .strip_prefix('(')
+        .and_then(|s| s.strip_suffix(')'))
+        .ok_or(false)?;
+    let (x, y) = inner.split_once(',').ok_or(false)?;
+    let ((x_p, x_q), (y_p, y_q)) = parse_ratio(x).zip(parse_ratio(y)).ok_or(true)?;
+    let coord = [
+        x_p.checked_mul(y_q),
+        y_p.checked_mul(x_q),
+        x_q.checked_mul(y_q),
+    ];
+    match coord {
+        [Some(x), Some(y), Some(z)] => {
+            let common = x.gcd(&y).gcd(&z);
+            Ok(PgPoint::new([x, y, z].map(|v| v / common)))
+        }
+        _ => Err(true),
+    }
+}
+
+/// Parse a polynomial of degree at most two in `x` and `y` with integer coefficients, e.g.
+/// `x^2 + 3x y - 2`, into coefficients in the order of `MONOMIALS`. The error is `true` for an
+/// invalid number and `false` otherwise.
+fn parse_polynomial(s: &str) -> Result<[i64; 6], bool> {
+    let s: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+    let mut coeffs = [0_i64; 6];
+    let mut rest = s.as_str();
+    if rest.is_empty() {
+        return Err(false);
+    }
+    while !rest.is_empty() {
+        let (negative, body) = match rest.as_bytes()[0] {
+            b'-' => (true, &rest[1..]),
+            b'+' => (false, &rest[1..]),
+            _ => (false, rest),
+        };
+        let end = body.find(['+', '-']).unwrap_or(body.len());
+        let (term, tail) = body.split_at(end);
+        rest = tail;
+        let split = term.find(['x', 'y']).unwrap_or(term.len());
+        let (number, monomial) = term.split_at(split);
+        let number = number.strip_suffix('*').unwrap_or(number);
+        let monomial = match monomial {
+            "x^2" | "x*x" | "xx" => 0,
+            "x*y" | "xy" | "y*x" | "yx" => 1,
+            "y^2" | "y*y" | "yy" => 2,
+            "x" => 3,
+            "y" => 4,
+            "" if !number.is_empty() => 5,
+            _ => return Err(false),
+        };
+        let value: i64 = if number.is_empty() {
+            1
+        } else {
+            number.parse().map_err(|_| true)?
+        };
+        let value = if negative {
+            value.checked_neg()
+        } else {
+            Some(value)
+        };
+        coeffs[monomial] = value
+            .and_then(|v| coeffs[monomial].checked_add(v))
+            .ok_or(true)?;
+    }
+    Ok(coeffs)
+}
+
+/// Parse the equation `lhs = rhs` into the coefficients of `lhs - rhs`.
+fn parse_equation(s: &str) -> Result<[i64; 6], bool> {
+    let (lhs, rhs) = s.split_once('=').ok_or(false)?;
+    let lhs = parse_polynomial(lhs)?;
+    let rhs = parse_polynomial(rhs)?;
+    let mut coeffs = [0_i64; 6];
+    for (c, (l, r)) in coeffs.iter_mut().zip(lhs.iter().zip(rhs)) {
+        *c = l.checked_sub(r).ok_or(true)?;
+    }
+    Ok(coeffs)
+}
+
+#[inline]
+fn is_name(s: &str) -> bool {
+    s.chars().next().is_some_and(char::is_alphabetic)
+        && s.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// The `Scene` struct is a list of named points, lines and conics, for export to GeoGebra
+/// command text or Asymptote source, so that constructions can be inspected in these tools.
+/// Names should be valid identifiers in the target language, e.g. `A`, `l_1` or `c`.
+///
+/// GeoGebra has no points or lines at infinity, so exporting them fails with
+/// `GeometryError::PointAtInfinity` or `GeometryError::LineAtInfinity`. Importing accepts the
+/// subset that `to_geogebra` writes.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::conic::Conic;
+/// use projgeom_rs::interop::Scene;
+/// use projgeom_rs::{Join, PgPoint};
+/// let pt_a = PgPoint::new([1, 3, 2]);
+/// let pt_b = PgPoint::new([-2, 1, 1]);
+/// let mut scene = Scene::new();
+/// scene
+///     .add_point("A", &pt_a)
+///     .add_point("B", &pt_b)
+///     .add_line("l", &pt_a.join(&pt_b))
+///     .add_conic("c", &Conic::from_coefficients(1, 0, 1, 0, 0, -25));
+/// let text = scene.to_geogebra().unwrap();
+/// assert_eq!(text, "A = (1/2, 3/2)\nB = (-2, 1)\nl: x - 5y + 7 = 0\nc: x^2 + y^2 - 25 = 0\n");
+/// assert_eq!(Scene::from_geogebra(&text), Ok(scene));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Scene {
+    objects: Vec<(String, SceneObject)>,
+}
+
+impl Scene {
+    /// Create an empty scene.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a named object.
+    #[inline]
+    pub fn add(&mut self, name: &str, obj: SceneObject) -> &mut Self {
+        self.objects.push((name.to_string(), obj));
+        self
+    }
+
+    /// Add a named point.
+    #[inline]
+    pub fn add_point(&mut self, name: &str, pt: &PgPoint) -> &mut Self {
+        self.add(name, SceneObject::Point(pt.clone()))
+    }
+
+    /// Add a named line.
+    #[inline]
+    pub fn add_line(&mut self, name: &str, ln: &PgLine) -> &mut Self {
+        self.add(name, SceneObject::Line(ln.clone()))
+    }
+
+    /// Add a named conic.
+    #[inline]
+    pub fn add_conic(&mut self, name: &str, conic: &Conic) -> &mut Self {
+        self.add(name, SceneObject::Conic(conic.clone()))
+    }
+
+    /// Return the object with the given name, the last one if the name occurs repeatedly.
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<&SceneObject> {
+        self.objects
+            .iter()
+            .rev()
+            .find(|(key, _)| key == name)
+            .map(|(_, obj)| obj)
+    }
+
+    /// Return the named objects in the order they were added.
+    #[inline]
+    pub fn objects(&self) -> &[(String, SceneObject)] {
+        &self.objects
+    }
+
+    /// The function `to_geogebra` writes the scene as GeoGebra commands, one per line, to be
+    /// pasted into the input bar: `A = (x, y)` for points and `name: <equation>` for lines and
+    /// conics.
+    ///
+    /// Returns:
+    ///
+    /// The function `to_geogebra` returns the commands, `GeometryError::PointAtInfinity` or
+    /// `GeometryError::LineAtInfinity` for objects at infinity, or `GeometryError::Overflow`.
+    pub fn to_geogebra(&self) -> Result<String, GeometryError> {
+        let mut result = String::new();
+        for (name, obj) in &self.objects {
+            let _ = match obj {
+                SceneObject::Point(pt) => {
+                    let (x, y) = point_coordinates(pt)?;
+                    writeln!(result, "{} = ({}, {})", name, x, y)
+                }
+                SceneObject::Line(ln) => {
+                    let coeffs = line_coefficients(ln)?;
+                    let poly = format_polynomial(&coeffs, &MONOMIALS[3..]);
+                    writeln!(result, "{}: {} = 0", name, poly)
+                }
+                SceneObject::Conic(conic) => {
+                    let poly = format_polynomial(&conic_coefficients(conic)?, &MONOMIALS);
+                    writeln!(result, "{}: {} = 0", name, poly)
+                }
+            };
+        }
+        Ok(result)
+    }
+
+    /// The function `from_geogebra` parses the subset of GeoGebra commands written by
+    /// `to_geogebra`: points `A = (x, y)` with integer or fractional coordinates, and equations
+    /// `name: lhs = rhs` with integer coefficients, which are lines if linear and conics if
+    /// quadratic. Blank lines are skipped. Conics are read by `Conic::from_coefficients`, so
+    /// their matrix may differ from the exported one by a factor.
+    ///
+    /// Returns:
+    ///
+    /// The function `from_geogebra` returns the scene, or a `ParseSceneError`.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::interop::{ParseSceneError, Scene, SceneObject};
+    /// use projgeom_rs::{PgLine, PgPoint};
+    /// let scene = Scene::from_geogebra("P = (3, -1/4)\nm: 2y = x - 1").unwrap();
+    /// assert_eq!(scene.get("P"), Some(&SceneObject::Point(PgPoint::new([12, -1, 4]))));
+    /// assert_eq!(scene.get("m"), Some(&SceneObject::Line(PgLine::new([-1, 2, 1]))));
+    /// assert_eq!(Scene::from_geogebra("\nCircle(A, 2)"), Err(ParseSceneError::InvalidCommand(2)));
+    /// ```
+    pub fn from_geogebra(text: &str) -> Result<Self, ParseSceneError> {
+        let mut scene = Scene::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let to_error = |number: bool| {
+                if number {
+                    ParseSceneError::InvalidNumber(index + 1)
+                } else {
+                    ParseSceneError::InvalidCommand(index + 1)
+                }
+            };
+            let (name, obj) = if let Some((name, equation)) = line.split_once(':') {
+                let [a, b, c, d, e, f] = parse_equation(equation).map_err(to_error)?;
+                let obj = match (a, b, c, d, e) {
+                    (0, 0, 0, 0, 0) => return Err(to_error(false)),
+                    (0, 0, 0, _, _) => SceneObject::Line(PgLine::new([d, e, f])),
+                    _ => SceneObject::Conic(Conic::from_coefficients(a, b, c, d, e, f)),
+                };
+                (name, obj)
+            } else if let Some((name, value)) = line.split_once('=') {
+                (
+                    name,
+                    SceneObject::Point(parse_point(value.trim()).map_err(to_error)?),
+                )
+            } else {
+                return Err(to_error(false));
+            };
+            let name = name.trim();
+            if !is_name(name) {
+                return Err(to_error(false));
+            }
+            scene.add(name, obj);
+        }
+        Ok(scene)
+    }
+
+    /// The function `to_asymptote` writes the scene as Asymptote source using the `geometry`
+    /// module: points are dotted and labelled with their names, lines and conics are drawn.
+    ///
+    /// Returns:
+    ///
+    /// The function `to_asymptote` returns the source, or the errors of `to_geogebra`.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::interop::Scene;
+    /// use projgeom_rs::{PgLine, PgPoint};
+    /// let mut scene = Scene::new();
+    /// scene.add_point("A", &PgPoint::new([1, 3, 2])).add_line("l", &PgLine::new([1, -5, 7]));
+    /// let source = scene.to_asymptote().unwrap();
+    /// assert!(source.contains("pair A = (1/2, 3/2);\ndot(\"$A$\", A);\n"));
+    /// assert!(source.contains("line l = line(1, -5, 7);\ndraw(l);\n"));
+    /// ```
+    pub fn to_asymptote(&self) -> Result<String, GeometryError> {
+        let mut result = String::from("import geometry;\nsize(10cm);\n\n");
+        let join = |coeffs: &[i64]| {
+            coeffs
+                .iter()
+                .map(|c| c.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        for (name, obj) in &self.objects {
+            let _ = match obj {
+                SceneObject::Point(pt) => {
+                    let (x, y) = point_coordinates(pt)?;
+                    writeln!(result, "pair {} = ({}, {});", name, x, y)
+                        .and_then(|_| writeln!(result, "dot(\"${}$\", {});", name, name))
+                }
+                SceneObject::Line(ln) => {
+                    let coeffs = line_coefficients(ln)?;
+                    writeln!(result, "line {} = line({});", name, join(&coeffs))
+                        .and_then(|_| writeln!(result, "draw({});", name))
+                }
+                SceneObject::Conic(conic) => {
+                    let coeffs = conic_coefficients(conic)?;
+                    writeln!(result, "conic {} = conic(bqe({}));", name, join(&coeffs))
+                        .and_then(|_| writeln!(result, "draw({});", name))
+                }
+            };
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::canonical::CanonicalForm;
+
+    #[test]
+    fn test_geogebra_round_trip() {
+        let conic = Conic::new([[3, 1, -2], [1, -4, 5], [-2, 5, 6]]);
+        let mut scene = Scene::new();
+        scene
+            .add_point("P_1", &PgPoint::new([-6, 4, -8]))
+            .add_line("h", &PgLine::new([0, 3, -6]))
+            .add_line("v", &PgLine::new([-1, 0, 0]))
+            .add_conic("k", &conic);
+        let text = scene.to_geogebra().unwrap();
+        assert_eq!(
+            text,
+            "P_1 = (3/4, -1/2)\nh: y - 2 = 0\nv: -x = 0\n\
+             k: 3x^2 + 2x*y - 4y^2 - 4x + 10y + 6 = 0\n"
+        );
+        let parsed = Scene::from_geogebra(&text).unwrap();
+        assert_eq!(parsed.objects().len(), 4);
+        for ((name, obj), (parsed_name, parsed_obj)) in scene.objects().iter().zip(parsed.objects())
+        {
+            assert_eq!(name, parsed_name);
+            match (obj, parsed_obj) {
+                (SceneObject::Conic(c), SceneObject::Conic(d)) => {
+                    assert_eq!(c.canonical_form(), d.canonical_form())
+                }
+                _ => assert_eq!(obj, parsed_obj),
+            }
+        }
+
+        // other spellings of the supported subset
+        let parsed = Scene::from_geogebra("  c : x y + 2 * x^2 = 3 - y^2 ").unwrap();
+        assert_eq!(
+            parsed.get("c"),
+            Some(&SceneObject::Conic(Conic::from_coefficients(
+                2, 1, 1, 0, 0, -3
+            )))
+        );
+        assert_eq!(parsed.get("d"), None);
+        assert_eq!(
+            Scene::from_geogebra("A = (1, 2/0)"),
+            Err(ParseSceneError::InvalidNumber(1))
+        );
+        assert_eq!(
+            Scene::from_geogebra("l: 99999999999999999999x = 1"),
+            Err(ParseSceneError::InvalidNumber(1))
+        );
+        assert_eq!(
+            Scene::from_geogebra("l: 2 = 1"),
+            Err(ParseSceneError::InvalidCommand(1))
+        );
+        assert_eq!(
+            Scene::from_geogebra("l: x^3 = 1"),
+            Err(ParseSceneError::InvalidCommand(1))
+        );
+        assert_eq!(
+            Scene::from_geogebra("1A = (0, 0)"),
+            Err(ParseSceneError::InvalidCommand(1))
+        );
+
+        // objects at infinity have no GeoGebra counterpart
+        let mut scene = Scene::new();
+        scene.add_point("A", &PgPoint::new([1, 2, 0]));
+        assert_eq!(scene.to_geogebra(), Err(GeometryError::PointAtInfinity));
+        let mut scene = Scene::new();
+        scene.add_line("l", &PgLine::new([0, 0, 1]));
+        assert_eq!(scene.to_asymptote(), Err(GeometryError::LineAtInfinity));
+    }
+}
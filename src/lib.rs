@@ -1,26 +1,111 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+/// Record a kernel operation and the coordinates of its result with the `audit` feature; a no-op
+/// without it.
+macro_rules! audit_record {
+    ($op:ident, $coord:expr) => {
+        #[cfg(feature = "audit")]
+        $crate::audit::record($crate::audit::Op::$op, $coord);
+    };
+}
+
+pub mod affine;
+#[cfg(any(test, feature = "approx"))]
+pub mod approx;
+#[cfg(any(test, feature = "quickcheck"))]
+pub mod arbitrary;
+#[cfg(feature = "std")]
+pub mod arrangement;
+#[cfg(feature = "audit")]
+pub mod audit;
+pub mod axioms;
+#[cfg(feature = "std")]
+pub mod batch;
+pub mod canonical;
+pub mod ck_geometry;
 pub mod ck_plane;
+pub mod compact;
+pub mod conic;
+#[cfg(feature = "std")]
+pub mod conic_pencil;
+#[cfg(feature = "std")]
+pub mod construction;
+pub mod coord_ring;
+pub mod duality;
 // pub mod hyperbolic;
 // pub mod elliptic;
 pub mod ell_object;
+pub mod error;
 pub mod euclid_object;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod filtered;
+#[cfg(feature = "std")]
+pub mod group;
+pub mod harmonic;
 pub mod hyp_object;
+#[cfg(feature = "std")]
+pub mod interop;
+#[cfg(any(feature = "nalgebra", feature = "glam"))]
+pub mod linalg;
+pub mod mat3;
 pub mod myck_object;
+pub mod normalization;
 pub mod persp_object;
+pub mod pg1;
+pub mod pg3d;
 pub mod pg_object;
 pub mod pg_plane;
-
+#[cfg(feature = "std")]
+pub mod polygon;
+pub mod predicates;
+/// The prelude brings the traits and the common point and line types into scope, so that methods
+/// such as `join`, `perp` and `parametrize` work without importing each trait.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::prelude::*;
+/// let pt_p = PgPoint::new([1, 3, 2]);
+/// let pt_q = PgPoint::new([-2, 1, -1]);
+/// let ln_l = pt_p.join(&pt_q);
+/// assert!(ln_l.incident(&pt_p.parametrize(2, &pt_q, 3)));
+/// let pt_e = EllipticPoint::new([1, 3, 2]);
+/// assert!(!pt_e.perp().incident(&pt_e));
+/// ```
+pub mod prelude;
+pub mod projectivity;
+pub mod sampling;
+#[cfg(feature = "serde")]
+pub mod scene;
+#[cfg(any(test, feature = "theorems"))]
+pub mod theorems;
+pub mod transform;
+pub mod triangle;
+#[cfg(feature = "std")]
+pub mod verification;
+#[cfg(feature = "std")]
+pub mod visualization;
+
+pub use crate::axioms::AxiomViolation;
+pub use crate::ck_geometry::{CkGeometry, GenericCKLine, GenericCKPoint};
 pub use crate::ck_plane::*;
+pub use crate::compact::{PgLine32, PgPoint32};
+pub use crate::coord_ring::{CoordRing, Zp};
+pub use crate::error::GeometryError;
+pub use crate::pg3d::{PgLine3, PgPlane3, PgPoint3, ProjectiveSpacePrimitive};
+pub use crate::pg_object::{Coord3, HomogeneousCoords};
+pub use crate::pg_object::{DiagonalCKLine, DiagonalCKPoint};
 pub use crate::pg_object::{EllipticLine, EllipticPoint};
 pub use crate::pg_object::{EuclidLine, EuclidPoint};
 pub use crate::pg_object::{HyperbolicLine, HyperbolicPoint};
 pub use crate::pg_object::{MyCKLine, MyCKPoint};
 pub use crate::pg_object::{PerspLine, PerspPoint};
 pub use crate::pg_object::{PgLine, PgPoint};
+pub use crate::pg_object::{PgLineOver, PgPointOver};
+pub use crate::pg_object::{RatLine, RatPoint};
 pub use crate::pg_plane::*;
 
-// pub mod fractions;
-// pub use crate::fractions::Fraction;
-
 #[cfg(test)]
 mod tests {
     use num_integer::gcd;
@@ -28,6 +113,8 @@ mod tests {
     // use crate::pg_plane::{check_axiom, coincident};
     // use crate::pg_object::*;
     use super::*;
+    use crate::pg_object::{check_desargues_involution, in_general_position, projective_rank};
+    use crate::pg_plane::Join;
     use quickcheck_macros::quickcheck;
 
     use fractions::Fraction;
@@ -150,10 +237,10 @@ mod tests {
     {
         let ln_l = pt_p.meet(&pt_q);
         assert_eq!(ln_l, pt_q.meet(&pt_p));
-        assert!(ln_l.incident(&pt_p));
-        assert!(ln_l.incident(&pt_q));
+        assert_incident!(ln_l, pt_p);
+        assert_incident!(ln_l, pt_q);
         let pq = Point::parametrize(&pt_p, 2, &pt_q, 3);
-        assert!(coincident(&pt_p, &pt_q, &pq));
+        assert_collinear!(pt_p, pt_q, pq);
 
         let h = harm_conj(&pt_p, &pt_q, &pq);
         assert_eq!(harm_conj(&pt_p, &pt_q, &h), pq);
@@ -189,6 +276,66 @@ mod tests {
         assert_eq!(pt_o, t_2.meet(&t_3));
     }
 
+    /// In the Euclidean and perspective geometries the absolute is degenerate: the polar of every
+    /// point is the line at infinity, so the polarity is not an involution and the altitude
+    /// theorem of `check_ck_plane` has no dual. Check the properties the polarity of lines does have.
+    fn check_parabolic_lines<Point, Line>(l_1: Line, l_2: Line, l_3: Line)
+    where
+        Point: AffineCKPlane<Line, i64> + std::fmt::Debug,
+        Line: CayleyKleinPlane<Point, i64> + std::fmt::Debug,
+    {
+        let l_inf = Point::l_inf();
+        for ln in [&l_1, &l_2, &l_3] {
+            // the perpendicular direction is an ideal point, whose polar is the line at infinity
+            assert!(ln.perp().incident(&l_inf));
+            assert_eq!(ln.perp().perp(), l_inf);
+            let par = parallel_through(&l_1.meet(&l_2), ln);
+            assert_eq!(par.perp(), ln.perp());
+        }
+        assert_eq!(is_perpendicular(&l_1, &l_2), is_perpendicular(&l_2, &l_1));
+        // lines perpendicular to the same line are parallel
+        let t_2 = altitude(&l_2.meet(&l_3), &l_1);
+        let t_3 = altitude(&l_1.meet(&l_3), &l_1);
+        assert!(is_perpendicular(&l_1, &t_2) && is_perpendicular(&t_2, &l_1));
+        assert!(is_parallel::<Point, Line, i64>(&t_2, &t_3));
+    }
+
+    fn check_self_polar<Point, Line>(a_1: Point, a_2: Point, a_3: Point)
+    where
+        Point: CayleyKleinPlane<Line, i64> + Clone + std::fmt::Debug,
+        Line: CayleyKleinPlane<Point, i64> + std::fmt::Debug,
+    {
+        let triangle = [a_1, a_2, a_3];
+        let polar = polar_triangle(&triangle);
+        assert_eq!(polar_triangle(&polar), triangle);
+        assert!(!is_self_polar(&triangle));
+        let [a_1, a_2, a_3] = triangle;
+        let self_polar = self_polar_triangle(&a_1, &a_2).unwrap();
+        assert!(is_self_polar(&self_polar));
+        assert_eq!(polar_triangle(&self_polar), self_polar);
+        assert!(coincident(&self_polar[0], &self_polar[1], &a_2));
+        // the polar of a self-polar triangle's vertex is its opposite side
+        let [_, pt_r, pt_s] = &self_polar;
+        assert_eq!(a_1.perp(), pt_r.meet(pt_s));
+        assert_eq!(
+            self_polar_triangle(&a_3, &a_3),
+            Err(GeometryError::NotInGeneralPosition)
+        );
+    }
+
+    #[test]
+    fn test_self_polar() {
+        let coords = [[1, 2, 3], [-2, 1, 1], [3, -1, 2]];
+        let [a_1, a_2, a_3] = coords.map(EllipticPoint::new);
+        check_self_polar(a_1, a_2, a_3);
+        let [a_1, a_2, a_3] = coords.map(HyperbolicPoint::new);
+        check_self_polar(a_1, a_2, a_3);
+        let [a_1, a_2, a_3] = coords.map(MyCKPoint::new);
+        check_self_polar(a_1, a_2, a_3);
+        let [a_1, a_2, a_3] = coords.map(EllipticLine::new);
+        check_self_polar(a_1, a_2, a_3);
+    }
+
     #[test]
     fn test_ell_point() {
         let a_1 = EllipticPoint::new([13, 23, 32]);
@@ -245,13 +392,21 @@ mod tests {
         check_ck_plane(a_1, a_2, a_3);
     }
 
-    // #[test]
-    // fn test_persp_line() {
-    //     let a_1 = PerspLine::new([13, 23, 32]);
-    //     let a_2 = PerspLine::new([44, -34, 2]);
-    //     let a_3 = PerspLine::new([-2, 12, 23]);
-    //     check_ck_plane(a_1, a_2, a_3);
-    // }
+    #[test]
+    fn test_persp_line() {
+        let a_1 = PerspLine::new([13, 23, 32]);
+        let a_2 = PerspLine::new([44, -34, 2]);
+        let a_3 = PerspLine::new([-2, 12, 23]);
+        check_parabolic_lines::<PerspPoint, PerspLine>(a_1, a_2, a_3);
+    }
+
+    #[test]
+    fn test_euclid_line() {
+        let a_1 = EuclidLine::new([13, 23, 32]);
+        let a_2 = EuclidLine::new([44, -34, 2]);
+        let a_3 = EuclidLine::new([-2, 12, 23]);
+        check_parabolic_lines::<EuclidPoint, EuclidLine>(a_1, a_2, a_3);
+    }
 
     #[test]
     fn test_euclid_point() {
@@ -261,6 +416,489 @@ mod tests {
         check_ck_plane(a_1, a_2, a_3);
     }
 
+    #[test]
+    fn test_quadrance_spread() {
+        use crate::euclid_object::{quadrance, quadrea, spread};
+
+        let pt_p = EuclidPoint::new([3, 4, 1]);
+        let pt_o = EuclidPoint::new([0, 0, 7]);
+        assert_eq!(quadrance(&pt_p, &pt_o), Ok(Fraction::from(25)));
+        assert_eq!(
+            quadrance(&pt_p, &EuclidPoint::new([1, 0, 0])),
+            Err(GeometryError::PointAtInfinity)
+        );
+
+        // the 3-4-5 triangle: the spreads at the acute vertices are 9/25 and 16/25
+        let triangle = [[0, 0, 1], [4, 0, 1], [0, 3, 1]].map(EuclidPoint::new);
+        let [l_1, l_2, l_3] = tri_dual(&triangle);
+        assert_eq!(spread(&l_2, &l_3), Ok(Fraction::from(1)));
+        assert_eq!(spread(&l_3, &l_1), Ok(Fraction::new(9, 25)));
+        assert_eq!(spread(&l_1, &l_2), Ok(Fraction::new(16, 25)));
+        // the spread law: s_1 / q_1 = s_2 / q_2
+        let q_1 = quadrance(&triangle[1], &triangle[2]).unwrap();
+        let q_2 = quadrance(&triangle[2], &triangle[0]).unwrap();
+        assert_eq!(
+            spread(&l_2, &l_3).unwrap() / q_1,
+            spread(&l_3, &l_1).unwrap() / q_2
+        );
+        assert_eq!(spread(&l_1, &l_1), Ok(Fraction::from(0)));
+
+        // Archimedes' formula and the quadrea as 4 Q_1 Q_2 s_3
+        let triangle = [[1, 2, 1], [14, 6, 2], [-3, 15, 3]].map(EuclidPoint::new);
+        let [l_1, l_2, _] = tri_dual(&triangle);
+        let [a_1, a_2, a_3] = &triangle;
+        let [q_1, q_2, q_3] =
+            [(a_2, a_3), (a_3, a_1), (a_1, a_2)].map(|(p, q)| quadrance(p, q).unwrap());
+        let area = quadrea(&triangle).unwrap();
+        let sum = q_1 + q_2 + q_3;
+        assert_eq!(
+            sum * sum,
+            Fraction::from(2) * (q_1 * q_1 + q_2 * q_2 + q_3 * q_3) + area
+        );
+        assert_eq!(
+            area,
+            Fraction::from(4) * q_1 * q_2 * spread(&l_1, &l_2).unwrap()
+        );
+        assert_eq!(
+            quadrea(&[a_1.clone(), a_2.clone(), a_1.midpoint(a_2)]),
+            Ok(Fraction::from(0))
+        );
+        assert_eq!(
+            spread(&l_1, &EuclidLine::new([0, 0, 1])),
+            Err(GeometryError::LineAtInfinity)
+        );
+    }
+
+    #[test]
+    fn test_euclid_circle() {
+        use crate::euclid_object::EuclidCircle;
+
+        let circle = EuclidCircle::new(&EuclidPoint::new([2, 3, 1]), Fraction::from(25)).unwrap();
+        let pt_p = EuclidPoint::new([10, 14, 2]);
+        assert!(circle.contains(&pt_p));
+        assert_eq!(circle.center(), EuclidPoint::new([4, 6, 2]));
+        assert_eq!(circle.quadrance(), Ok(Fraction::from(25)));
+        assert_eq!(
+            circle.power_of_point(&EuclidPoint::new([2, 3, 1])),
+            Ok(Fraction::from(-25))
+        );
+        assert_eq!(
+            circle.power_of_point(&EuclidPoint::new([5, 3, 2])),
+            Ok(Fraction::new(-45, 2))
+        );
+        assert_eq!(circle.power_of_point(&pt_p), Ok(Fraction::from(0)));
+        let tangent = circle.tangent_at(&pt_p).unwrap();
+        assert_eq!(tangent, EuclidLine::new([3, 4, -43]));
+        assert_eq!(
+            circle.intersect_line(&tangent),
+            Ok(Some([pt_p.clone(), pt_p]))
+        );
+        assert_eq!(
+            circle.tangent_at(&EuclidPoint::new([2, 3, 1])),
+            Err(GeometryError::NotOnConic)
+        );
+
+        // the same circle through three of its points
+        let triangle = [[5, 7, 1], [-2, 6, 1], [14, 6, 2]].map(EuclidPoint::new);
+        assert_eq!(
+            EuclidCircle::through_three_points(&triangle),
+            Ok(circle.clone())
+        );
+        let flat = [[0, 0, 1], [1, 1, 1], [2, 2, 1]].map(EuclidPoint::new);
+        assert_eq!(
+            EuclidCircle::through_three_points(&flat),
+            Err(GeometryError::InvalidTriangle)
+        );
+
+        let other = EuclidCircle::new(&EuclidPoint::new([8, 3, 1]), Fraction::from(25)).unwrap();
+        assert_eq!(circle.radical_axis(&other), Ok(EuclidLine::new([1, 0, -5])));
+        assert_eq!(
+            circle.intersect_circle(&other),
+            Ok(Some([[5, -1, 1], [5, 7, 1]].map(EuclidPoint::new)))
+        );
+        let inner = EuclidCircle::new(&EuclidPoint::new([2, 3, 1]), Fraction::new(1, 4)).unwrap();
+        assert_eq!(circle.radical_axis(&inner), Ok(EuclidLine::new([0, 0, 1])));
+        assert_eq!(circle.intersect_circle(&inner), Ok(None));
+        assert_eq!(
+            circle.radical_axis(&circle),
+            Err(GeometryError::NotInGeneralPosition)
+        );
+    }
+
+    #[test]
+    fn test_reflect_line_bisectors() {
+        use crate::euclid_object::{angle_bisectors, reflect_line};
+
+        let mirror = EuclidLine::new([3, -4, 5]);
+        let ln_l = EuclidLine::new([1, 2, -3]);
+        let ln_r = reflect_line(&mirror, &ln_l);
+        // the reflection of each point of the line lies on the reflected line
+        let pt_p = EuclidPoint::new([3, 0, 1]);
+        assert_incident!(ln_l, pt_p);
+        assert_incident!(ln_r, reflect(&mirror, &pt_p));
+        assert_incident!(ln_r, ln_l.meet(&mirror));
+        assert_projectively_eq!(reflect_line(&mirror, &ln_r), ln_l);
+        assert_projectively_eq!(reflect_line(&mirror, &mirror), mirror);
+        // a line perpendicular to the mirror is fixed
+        let normal = mirror.altitude(&pt_p);
+        assert_projectively_eq!(reflect_line(&mirror, &normal), normal);
+
+        // the bisectors of a line and its reflection are the mirror and its normal
+        let (bisector_1, bisector_2) = angle_bisectors(&mirror, &normal).unwrap();
+        assert!(bisector_1.is_perpendicular(&bisector_2));
+        assert_eq!(reflect_line(&bisector_1, &mirror), normal);
+        assert_eq!(reflect_line(&bisector_2, &mirror), normal);
+        let ln_m = EuclidLine::new([4, 3, -2]);
+        let (bisector_1, bisector_2) = angle_bisectors(&mirror, &ln_m).unwrap();
+        assert_eq!(reflect_line(&bisector_1, &mirror), ln_m);
+        assert_eq!(reflect_line(&bisector_2, &mirror), ln_m);
+        assert!(bisector_1.incident(&mirror.meet(&ln_m)));
+        // parallel lines with opposite normals: the line at infinity and the midline
+        let (infinity, midline) =
+            angle_bisectors(&EuclidLine::new([1, 0, -2]), &EuclidLine::new([-2, 0, 8])).unwrap();
+        assert_eq!(midline, EuclidLine::new([1, 0, -3]));
+        assert_eq!(infinity, EuclidLine::new([0, 0, 1]));
+        assert!(angle_bisectors(&ln_l, &ln_l).is_none());
+        assert!(angle_bisectors(&ln_l, &ln_m).is_none());
+    }
+
+    fn check_affine_ck_plane<Point, Line>(pt_a: Point, pt_b: Point, pt_c: Point)
+    where
+        Point: AffineCKPlane<Line, i64> + std::fmt::Debug,
+        Line: CayleyKleinPlane<Point, i64> + std::fmt::Debug,
+    {
+        // the midpoint is the harmonic conjugate of the point at infinity of the join
+        let ln_ab = pt_a.meet(&pt_b);
+        let pt_m = midpoint(&pt_a, &pt_b);
+        assert_incident!(ln_ab, pt_m);
+        assert_eq!(harm_conj(&pt_a, &pt_b, &ln_ab.meet(&Point::l_inf())), pt_m);
+
+        let ln_p = parallel_through(&pt_c, &ln_ab);
+        assert_incident!(ln_p, pt_c);
+        assert!(is_parallel::<Point, Line, i64>(&ln_p, &ln_ab));
+        assert!(!is_parallel::<Point, Line, i64>(&ln_p, &pt_a.meet(&pt_c)));
+        // the midline of a triangle is parallel to the third side
+        let ln_mid = midpoint(&pt_a, &pt_c).meet(&midpoint(&pt_b, &pt_c));
+        assert!(is_parallel::<Point, Line, i64>(&ln_mid, &ln_ab));
+    }
+
+    #[test]
+    fn test_affine_ck_plane() {
+        check_affine_ck_plane::<EuclidPoint, EuclidLine>(
+            EuclidPoint::new([1, 3, 2]),
+            EuclidPoint::new([-2, 1, -1]),
+            EuclidPoint::new([4, -5, 3]),
+        );
+        check_affine_ck_plane::<PerspPoint, PerspLine>(
+            PerspPoint::new([1, 3, 2]),
+            PerspPoint::new([-2, 1, -1]),
+            PerspPoint::new([4, -5, 3]),
+        );
+        let pt_a = EuclidPoint::new([1, 3, 2]);
+        let pt_b = EuclidPoint::new([-2, 1, -1]);
+        assert_eq!(pt_a.midpoint(&pt_b), midpoint(&pt_a, &pt_b));
+        let ln_l = PerspLine::new([1, 2, 3]);
+        assert!(ln_l.is_parallel(&parallel_through(&PerspPoint::new([2, 0, 1]), &ln_l)));
+    }
+
+    #[test]
+    fn test_desargues_involution() {
+        let quadrangle = [
+            PgPoint::new([1, 3, 2]),
+            PgPoint::new([-2, 1, -1]),
+            PgPoint::new([4, -5, 3]),
+            PgPoint::new([3, 2, 7]),
+        ];
+        assert!(check_desargues_involution(
+            &quadrangle,
+            &PgLine::new([2, -3, 1])
+        ));
+        assert!(check_desargues_involution(
+            &quadrangle,
+            &PgLine::new([0, 0, 1])
+        ));
+    }
+
+    #[test]
+    fn test_collinear_concurrent() {
+        let pt_p = PgPoint::new([1, 3, 2]);
+        let pt_q = PgPoint::new([-2, 1, -1]);
+        let points: Vec<PgPoint> = (0..6).map(|k| pt_p.parametrize(k, &pt_q, 5 - k)).collect();
+        assert!(are_collinear::<PgPoint, PgLine>(&points));
+        assert!(are_collinear::<PgPoint, PgLine>(&points[..1]));
+        assert!(are_collinear::<PgPoint, PgLine>(&[]));
+
+        let mut points = points;
+        points.push(PgPoint::new([0, 0, 1]));
+        assert!(!are_collinear::<PgPoint, PgLine>(&points));
+
+        let pt_o = PgPoint::new([2, 7, -3]);
+        let lines: Vec<PgLine> = points.iter().map(|pt| pt.join(&pt_o)).collect();
+        assert!(are_concurrent::<PgPoint, PgLine>(&lines));
+        assert!(!are_concurrent::<PgPoint, PgLine>(&[
+            PgLine::new([1, 0, 0]),
+            PgLine::new([0, 1, 0]),
+            PgLine::new([0, 0, 1]),
+        ]));
+    }
+
+    #[test]
+    fn test_projective_rank() {
+        let pt_p = PgPoint::new([1, 3, 2]);
+        let pt_q = PgPoint::new([-2, 1, -1]);
+        let pt_r = PgPoint::parametrize(&pt_p, 2, &pt_q, 3);
+        let pt_s = PgPoint::new([0, 0, 1]);
+        let zero = PgPoint::new([0, 0, 0]);
+        assert_eq!(projective_rank(&[]), 0);
+        assert_eq!(projective_rank(std::slice::from_ref(&zero)), 0);
+        assert_eq!(projective_rank(&[zero, pt_p.clone()]), 1);
+        assert_eq!(projective_rank(&[pt_p.clone(), pt_q.clone()]), 2);
+        assert_eq!(
+            projective_rank(&[pt_p.clone(), pt_q.clone(), pt_r.clone()]),
+            2
+        );
+        assert_eq!(
+            projective_rank(&[pt_p.clone(), pt_q.clone(), pt_r.clone(), pt_s.clone()]),
+            3
+        );
+        assert!(!in_general_position(&[
+            pt_p.clone(),
+            pt_q.clone(),
+            pt_r,
+            pt_s.clone()
+        ]));
+        assert!(in_general_position(&[
+            pt_p,
+            pt_q,
+            pt_s,
+            PgPoint::new([1, 1, 1])
+        ]));
+
+        // determinants that overflow i128 are decided exactly
+        let m = i64::MAX;
+        let [pt_a, pt_b, pt_c, pt_d] =
+            [[m, 0, m], [0, m, -m], [m, m, 0], [1, 1, 1]].map(PgPoint::new);
+        assert!(!in_general_position(&[
+            pt_a.clone(),
+            pt_b.clone(),
+            pt_c,
+            pt_d.clone()
+        ]));
+        assert!(in_general_position(&[
+            pt_a,
+            pt_b,
+            PgPoint::new([m, m, 1]),
+            pt_d
+        ]));
+    }
+
+    #[test]
+    fn test_display_from_str() {
+        let pt_p = PgPoint::new([1, 3, -2]);
+        assert_eq!(pt_p.to_string(), "[1 : 3 : -2]");
+        assert_eq!(
+            pt_p.to_string().parse::<PgPoint>().unwrap().coord,
+            [1, 3, -2]
+        );
+        assert_eq!("[2, 6, -4]".parse::<PgPoint>().unwrap(), pt_p);
+        assert_eq!("(2, 3)".parse::<EuclidPoint>().unwrap().coord, [2, 3, 1]);
+        assert_eq!(
+            " [ 1 : 0 : 0 ] "
+                .parse::<HyperbolicLine>()
+                .unwrap()
+                .to_string(),
+            "[1 : 0 : 0]"
+        );
+        assert!("1 3 2".parse::<PgLine>().is_err());
+        assert!("[1 : 3 : 2 : 4]".parse::<PgLine>().is_err());
+        assert!("(1, x)".parse::<EllipticPoint>().is_err());
+    }
+
+    #[test]
+    fn test_pg_conversion() {
+        let a_1 = EllipticPoint::new([13, 23, 32]);
+        let a_2 = EllipticPoint::new([44, -34, 2]);
+        let a_3 = EllipticPoint::parametrize(&a_1, 2, &a_2, 3);
+        let pt_h = harm_conj(
+            &PgPoint::from(a_1.clone()),
+            &a_2.clone().into(),
+            &a_3.clone().into(),
+        );
+        assert_eq!(EllipticPoint::from(pt_h), harm_conj(&a_1, &a_2, &a_3));
+
+        let ln_l: PgLine = a_1.join(&a_2).into();
+        assert_eq!(
+            EllipticLine::from(ln_l.clone()).perp(),
+            a_1.join(&a_2).perp()
+        );
+        assert_eq!(HyperbolicLine::from(ln_l.clone()).coord, ln_l.coord);
+        assert_eq!(PgPoint::from(EuclidPoint::new([1, 2, 3])).coord, [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_affine() {
+        let x = Fraction::new(3, 4);
+        let y = Fraction::new(-5, 6);
+        let pt_p = PgPoint::from_affine(x, y).unwrap();
+        assert_eq!(pt_p.coord, [9, -10, 12]);
+        assert_eq!(pt_p.to_affine(), Ok((x, y)));
+        assert_eq!(PgPoint::new([-9, 10, -12]).to_affine(), Ok((x, y)));
+        assert_eq!(
+            PgPoint::new([1, 1, 0]).to_affine(),
+            Err(GeometryError::PointAtInfinity)
+        );
+
+        let ln_l = PgLine::from_slope_intercept(y, x).unwrap();
+        assert_eq!(ln_l.to_slope_intercept(), Some((y, x)));
+        let pt_q = PgPoint::from_affine(Fraction::from(2), y * Fraction::from(2) + x).unwrap();
+        assert!(ln_l.incident(&pt_q));
+        assert_eq!(PgLine::new([0, 0, 1]).to_slope_intercept(), None);
+
+        // zero denominators and overflowing common denominators are reported
+        let inf = Fraction::new(1, 0);
+        assert_eq!(
+            PgPoint::from_affine(inf, x),
+            Err(GeometryError::ZeroDenominator)
+        );
+        assert_eq!(
+            PgLine::from_slope_intercept(x, inf),
+            Err(GeometryError::ZeroDenominator)
+        );
+        let (p, q) = (Fraction::new(1, i64::MAX), Fraction::new(1, i64::MAX - 1));
+        assert_eq!(PgPoint::from_affine(p, q), Err(GeometryError::Overflow));
+        let big = Fraction::new(i64::MAX, 2);
+        assert_eq!(
+            PgPoint::from_affine(big, Fraction::new(1, 3)),
+            Err(GeometryError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_wide_arithmetic() {
+        let m = i64::MAX;
+        let pt_p = PgPoint::new([m, 0, 1]);
+        let pt_q = PgPoint::new([0, m, 1]);
+        let ln_l = pt_p.join(&pt_q);
+        assert_eq!(ln_l.coord, [-1, -1, m]);
+        assert!(ln_l.incident(&pt_p) && ln_l.incident(&pt_q));
+        let pt_r = pt_p.parametrize(m, &pt_q, m);
+        assert_eq!(pt_r.coord, [m, m, 2]);
+        assert!(ln_l.incident(&pt_r));
+
+        // equality and incidence answer false instead of overflowing i64
+        let pt_s = PgPoint::new([m, m - 1, 1]);
+        let pt_t = PgPoint::new([m - 1, m, 1]);
+        assert_ne!(pt_s, pt_t);
+        assert_eq!(PgPoint::new([m, m, 0]), PgPoint::new([1, 1, 0]));
+        assert!(!pt_s.incident(&PgLine::new([1, 1, 0])));
+        assert!(!pt_s.incident_const(&PgLine::new([m, m, m])));
+        assert!(pt_s.incident(&PgLine::new([1, -1, -1])));
+        assert!(!coincident(&pt_p, &pt_q, &PgPoint::new([1, 1, m])));
+    }
+
+    #[test]
+    fn test_normalize() {
+        let pt_p = PgPoint::new([1, 3, 2]);
+        let pt_q = PgPoint::new([-2, 1, -1]);
+        let mut pt_r = pt_p.parametrize(6, &pt_q, 9);
+        assert_eq!(pt_r.coord, [-12, 27, 3]);
+        pt_r.normalize();
+        assert_eq!(pt_r.coord, [4, -9, -1]);
+        assert_eq!(pt_r, pt_p.parametrize(2, &pt_q, 3));
+
+        let ln_l = EuclidLine::new([0, -6, 4]).normalized();
+        assert_eq!(ln_l.coord, [0, 3, -2]);
+        assert_eq!(PgPoint::new([0, 0, 0]).normalized().coord, [0, 0, 0]);
+        assert_eq!(
+            PgPoint::new([i64::MIN, 2, 0]).normalized().coord,
+            [1 << 62, -1, 0]
+        );
+    }
+
+    #[test]
+    fn test_hash_ord() {
+        use std::collections::{BTreeSet, HashSet};
+
+        let points = [
+            PgPoint::new([1, 3, 2]),
+            PgPoint::new([-2, -6, -4]),
+            PgPoint::new([0, 1, -1]),
+            PgPoint::new([3, 9, 6]),
+            PgPoint::new([0, -5, 5]),
+        ];
+        let hashed: HashSet<PgPoint> = points.iter().cloned().collect();
+        assert_eq!(hashed.len(), 2);
+        let sorted: BTreeSet<PgPoint> = points.iter().cloned().collect();
+        assert_eq!(sorted.len(), 2);
+        assert_eq!(sorted.first().unwrap().normalized().coord, [0, 1, -1]);
+        for pt_p in &points {
+            for pt_q in &points {
+                assert_eq!(pt_p == pt_q, pt_p.cmp(pt_q) == std::cmp::Ordering::Equal);
+            }
+        }
+    }
+
+    #[test]
+    fn test_const_fn() {
+        const PT_P: PgPoint = PgPoint::new([1, 3, 2]);
+        const PT_Q: PgPoint = PgPoint::new([-2, 1, -1]);
+        const LN_L: PgLine = PT_P.meet_const(&PT_Q);
+        const PT_R: PgPoint = PT_P.parametrize_const(2, &PT_Q, 3);
+        const ON_LINE: bool = PT_R.incident_const(&LN_L);
+        const _: () = assert!(ON_LINE);
+        const DOT: i64 = PT_P.dot_const(&PT_P.aux_const());
+        assert_eq!(LN_L, PT_P.join(&PT_Q));
+        assert_eq!(PT_R, PT_P.parametrize(2, &PT_Q, 3));
+        assert_eq!(DOT, 14);
+    }
+
+    #[test]
+    fn test_coord_ring() {
+        // rational coordinates
+        let f = |n: i64, d: i64| Fraction::new(n, d);
+        let pt_p = PgPointOver::new([f(1, 2), f(3, 1), f(2, 1)]);
+        let pt_q = PgPointOver::new([f(-2, 1), f(1, 3), f(-1, 1)]);
+        let pt_r = PgPointOver::new([f(2, 1), f(-1, 2), f(1, 1)]);
+        check_axiom(&pt_p, &pt_q, &pt_p.join(&pt_q));
+        check_axiom2(&pt_p, &pt_q, &pt_r.join(&pt_p), f(3, 1), f(-2, 5));
+        assert_eq!(
+            PgPointOver::new([f(1, 2), f(3, 1), f(2, 1)]),
+            PgPointOver::new([f(1, 1), f(6, 1), f(4, 1)])
+        );
+
+        // lossless conversion between integer and rational coordinates
+        let pt_s = PgPoint::new([4, -6, 10]);
+        let rat_s = RatPoint::from(pt_s.clone());
+        let rat_t = RatPoint::new([f(1, 3), f(-1, 2), f(5, 6)]);
+        assert_eq!(rat_s, rat_t);
+        assert_eq!(PgPoint::try_from(rat_t), Ok(PgPoint::new([2, -3, 5])));
+        let ln_m = RatLine::from(pt_s.join(&PgPoint::new([1, 1, 1])));
+        assert!(rat_s.incident(&ln_m));
+        let huge = RatPoint::new([f(1, i64::MAX), f(1, i64::MAX - 1), f(1, 1)]);
+        assert_eq!(PgPoint::try_from(huge), Err(GeometryError::Overflow));
+
+        // the finite plane over GF(7): every line has 8 points
+        type P7 = PgPointOver<Zp<7>>;
+        let z = Zp::<7>::new;
+        let pt_a = P7::new([z(1), z(3), z(2)]);
+        let pt_b = P7::new([z(5), z(1), z(6)]);
+        let ln_l = pt_a.join(&pt_b);
+        let count = (0..7)
+            .map(|t| pt_a.parametrize(z(t), &pt_b, z(1)))
+            .chain(std::iter::once(pt_a.clone()))
+            .filter(|pt| pt.incident(&ln_l))
+            .count();
+        assert_eq!(count, 8);
+        assert!(!pt_a.incident(&pt_a.aux()));
+        check_axiom2(&pt_a, &pt_b, &pt_a.aux(), z(3), z(5));
+        assert_eq!(P7::new([z(2), z(6), z(4)]), pt_a);
+        let pt_d = P7::new([z(0), z(4), z(1)]);
+        let pt_e = P7::new([z(3), z(3), z(1)]);
+        let pt_c = pt_a.parametrize(z(2), &pt_b, z(1));
+        let pt_f = pt_d.parametrize(z(5), &pt_e, z(1));
+        assert!(check_pappus(&[pt_a, pt_b, pt_c], &[pt_d, pt_e, pt_f]));
+    }
+
     #[quickcheck]
     fn test_pg_point_q(pz: i32, qz: i32) -> bool {
         let pt_p = PgPoint::new([1, 3, pz.into()]);
@@ -272,15 +910,15 @@ mod tests {
     fn test_pg_point_q2(pz: i16, qz: i16) -> bool {
         let pt_p = PgPoint::new([10, 30, pz.into()]);
         let pt_q = PgPoint::new([-20, 10, qz.into()]);
-        let ln_l = pt_p.meet(&pt_q);
-        ln_l == pt_q.meet(&pt_p)
+        let ln_l = pt_p.join(&pt_q);
+        ln_l == pt_q.join(&pt_p)
     }
 
     #[quickcheck]
     fn test_pg_point_q3(pz: i16, qz: i16) -> bool {
         let pt_p = PgPoint::new([10, 30, pz.into()]);
         let pt_q = PgPoint::new([-20, 10, qz.into()]);
-        let ln_l = pt_p.meet(&pt_q);
+        let ln_l = pt_p.join(&pt_q);
         ln_l.incident(&pt_p) && ln_l.incident(&pt_q)
     }
 }
@@ -0,0 +1,325 @@
+// Conversions to and from the matrix and vector types of nalgebra and glam
+
+use crate::error::GeometryError;
+use crate::mat3::Mat3;
+use crate::pg_object::{PgLine, PgPoint};
+use crate::transform::{reduce, Transform};
+
+/// The entry as `m · 2^e` with an odd mantissa `m`, or `m = 0` for zero. Fails with
+/// `ZeroDenominator` if the entry is not finite.
+fn to_dyadic(x: f64) -> Result<(i64, i32), GeometryError> {
+    if !x.is_finite() {
+        return Err(GeometryError::ZeroDenominator);
+    }
+    let bits = x.to_bits();
+    let biased = ((bits >> 52) & 0x7ff) as i32;
+    let fraction = (bits & ((1 << 52) - 1)) as i64;
+    // subnormal numbers have no implicit leading bit
+    let (mantissa, exp) = if biased == 0 {
+        (fraction, -1074)
+    } else {
+        (fraction | 1 << 52, biased - 1075)
+    };
+    if mantissa == 0 {
+        return Ok((0, 0));
+    }
+    let shift = mantissa.trailing_zeros();
+    let odd = if x < 0.0 {
+        -(mantissa >> shift)
+    } else {
+        mantissa >> shift
+    };
+    Ok((odd, exp + shift as i32))
+}
+
+/// The transform with the matrix given by rows. Every finite float is a dyadic rational, so the
+/// matrix is an integer matrix up to a power of two, which does not change the transform.
+fn transform_from_rows(rows: [[f64; 3]; 3]) -> Result<Transform, GeometryError> {
+    let mut dyadic = [[(0_i64, 0_i32); 3]; 3];
+    for (row, src) in dyadic.iter_mut().zip(rows) {
+        for (x, y) in row.iter_mut().zip(src) {
+            *x = to_dyadic(y)?;
+        }
+    }
+    let min_exp = dyadic
+        .iter()
+        .flatten()
+        .filter(|(m, _)| *m != 0)
+        .map(|(_, e)| *e)
+        .min()
+        .ok_or(GeometryError::SingularTransform)?;
+    // the entry of the least exponent stays odd, so that a shift of 63 bits or more does not fit
+    // into i64 even after reduction
+    let mut mat = Mat3::new([[0_i128; 3]; 3]);
+    for (row, src) in mat.rows.iter_mut().zip(dyadic) {
+        for (x, (m, e)) in row.iter_mut().zip(src) {
+            if m != 0 {
+                let shift = e - min_exp;
+                if shift >= 63 {
+                    return Err(GeometryError::Overflow);
+                }
+                *x = i128::from(m) << shift;
+            }
+        }
+    }
+    Transform::new(reduce(mat)?)
+}
+
+/// The affine coordinates `(x / z, y / z)` of a point, or `PointAtInfinity`.
+fn perspective_divide(pt: &PgPoint) -> Result<[f64; 2], GeometryError> {
+    let [x, y, z] = pt.coord.map(|x| x as f64);
+    if z == 0.0 {
+        return Err(GeometryError::PointAtInfinity);
+    }
+    Ok([x / z, y / z])
+}
+
+#[cfg(feature = "nalgebra")]
+mod nalgebra_impls {
+    use super::*;
+    use nalgebra::{Matrix3, Point2, Vector3};
+
+    impl From<&Transform> for Matrix3<f64> {
+        /// The matrix of the transform, up to the scale of a homogeneous matrix.
+        fn from(trans: &Transform) -> Self {
+            Matrix3::from(Mat3::from(trans.mat))
+        }
+    }
+
+    impl From<Mat3<i64>> for Matrix3<f64> {
+        fn from(mat: Mat3<i64>) -> Self {
+            Matrix3::from(mat.map(|x| x as f64))
+        }
+    }
+
+    impl From<Mat3<f64>> for Matrix3<f64> {
+        fn from(mat: Mat3<f64>) -> Self {
+            Matrix3::from_fn(|i, j| mat.rows[i][j])
+        }
+    }
+
+    impl From<Matrix3<f64>> for Mat3<f64> {
+        fn from(mat: Matrix3<f64>) -> Self {
+            Mat3 {
+                rows: [0, 1, 2].map(|i| [0, 1, 2].map(|j| mat[(i, j)])),
+            }
+        }
+    }
+
+    impl TryFrom<&Matrix3<f64>> for Transform {
+        type Error = GeometryError;
+
+        /// Convert the matrix exactly, scaled by a power of two to integer entries. Fails with
+        /// `GeometryError::ZeroDenominator` for an entry that is not finite, with
+        /// `GeometryError::Overflow` if the integer entries do not fit into i64, or with
+        /// `GeometryError::SingularTransform`.
+        fn try_from(mat: &Matrix3<f64>) -> Result<Self, Self::Error> {
+            transform_from_rows(Mat3::from(*mat).rows)
+        }
+    }
+
+    impl From<&PgPoint> for Vector3<f64> {
+        /// The homogeneous coordinates of the point.
+        fn from(pt: &PgPoint) -> Self {
+            Vector3::from(pt.coord.map(|x| x as f64))
+        }
+    }
+
+    impl From<&PgLine> for Vector3<f64> {
+        /// The homogeneous coordinates of the line.
+        fn from(ln: &PgLine) -> Self {
+            Vector3::from(ln.coord.map(|x| x as f64))
+        }
+    }
+
+    impl TryFrom<&PgPoint> for Point2<f64> {
+        type Error = GeometryError;
+
+        /// The affine point, by the perspective divide; fails with
+        /// `GeometryError::PointAtInfinity`.
+        fn try_from(pt: &PgPoint) -> Result<Self, Self::Error> {
+            perspective_divide(pt).map(Point2::from)
+        }
+    }
+
+    impl From<Point2<i64>> for PgPoint {
+        /// The point `[x : y : 1]`.
+        fn from(pt: Point2<i64>) -> Self {
+            PgPoint::new([pt.x, pt.y, 1])
+        }
+    }
+}
+
+#[cfg(feature = "glam")]
+mod glam_impls {
+    use super::*;
+    use glam::{DMat3, DVec2, DVec3, Mat3 as GlamMat3, Vec2};
+
+    /// The columns of a matrix given by rows, as glam stores matrices by columns.
+    fn columns<T: Copy>(rows: [[T; 3]; 3]) -> [[T; 3]; 3] {
+        [0, 1, 2].map(|j| [0, 1, 2].map(|i| rows[i][j]))
+    }
+
+    impl From<&Transform> for DMat3 {
+        /// The matrix of the transform, up to the scale of a homogeneous matrix.
+        fn from(trans: &Transform) -> Self {
+            DMat3::from_cols_array_2d(&columns(trans.mat.map(|row| row.map(|x| x as f64))))
+        }
+    }
+
+    impl From<&Transform> for GlamMat3 {
+        /// The matrix of the transform in single precision, scaled by a power of two so that the
+        /// largest entry is about 1, which keeps large entries within the range of f32.
+        fn from(trans: &Transform) -> Self {
+            let max = trans.mat.iter().flatten().map(|x| x.unsigned_abs()).max();
+            let shift = (u64::BITS - max.unwrap_or(1).leading_zeros()) as i32;
+            let scale = 2_f64.powi(-shift);
+            let rows = trans.mat.map(|row| row.map(|x| (x as f64 * scale) as f32));
+            GlamMat3::from_cols_array_2d(&columns(rows))
+        }
+    }
+
+    impl From<Mat3<f64>> for DMat3 {
+        fn from(mat: Mat3<f64>) -> Self {
+            DMat3::from_cols_array_2d(&columns(mat.rows))
+        }
+    }
+
+    impl From<DMat3> for Mat3<f64> {
+        fn from(mat: DMat3) -> Self {
+            Mat3 {
+                rows: columns(mat.to_cols_array_2d()),
+            }
+        }
+    }
+
+    impl TryFrom<&DMat3> for Transform {
+        type Error = GeometryError;
+
+        /// Convert the matrix exactly, scaled by a power of two to integer entries. Fails with
+        /// `GeometryError::ZeroDenominator` for an entry that is not finite, with
+        /// `GeometryError::Overflow` if the integer entries do not fit into i64, or with
+        /// `GeometryError::SingularTransform`.
+        fn try_from(mat: &DMat3) -> Result<Self, Self::Error> {
+            transform_from_rows(columns(mat.to_cols_array_2d()))
+        }
+    }
+
+    impl From<&PgPoint> for DVec3 {
+        /// The homogeneous coordinates of the point.
+        fn from(pt: &PgPoint) -> Self {
+            DVec3::from_array(pt.coord.map(|x| x as f64))
+        }
+    }
+
+    impl From<&PgLine> for DVec3 {
+        /// The homogeneous coordinates of the line.
+        fn from(ln: &PgLine) -> Self {
+            DVec3::from_array(ln.coord.map(|x| x as f64))
+        }
+    }
+
+    impl TryFrom<&PgPoint> for DVec2 {
+        type Error = GeometryError;
+
+        /// The affine point, by the perspective divide; fails with
+        /// `GeometryError::PointAtInfinity`.
+        fn try_from(pt: &PgPoint) -> Result<Self, Self::Error> {
+            perspective_divide(pt).map(DVec2::from_array)
+        }
+    }
+
+    impl TryFrom<&PgPoint> for Vec2 {
+        type Error = GeometryError;
+
+        /// The affine point in single precision; fails with `GeometryError::PointAtInfinity`.
+        fn try_from(pt: &PgPoint) -> Result<Self, Self::Error> {
+            perspective_divide(pt).map(|[x, y]| Vec2::new(x as f32, y as f32))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[cfg(feature = "nalgebra")]
+    #[test]
+    fn test_nalgebra() {
+        use nalgebra::{Matrix3, Point2, Vector3};
+        let trans = Transform::new([[2, 1, 3], [0, 1, -2], [1, 0, 1]]).unwrap();
+        let mat = Matrix3::from(&trans);
+        assert_eq!(mat[(0, 2)], 3.0);
+        assert_eq!(mat[(2, 0)], 1.0);
+        assert_eq!(Transform::try_from(&mat), Ok(trans));
+        // floats are dyadic rationals, and scaling does not change the transform
+        assert_eq!(Transform::try_from(&(mat * 0.5)), Ok(trans));
+        let quarter = Matrix3::new(0.25, 0.5, 0.0, 0.0, 0.75, 0.0, 0.0, 0.0, 1.0);
+        assert_eq!(
+            Transform::try_from(&quarter),
+            Transform::new([[1, 2, 0], [0, 3, 0], [0, 0, 4]])
+        );
+        // 0.1 is not 1/10 but a fraction with the denominator 2⁵⁵
+        let tenth = Matrix3::new(0.1, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0);
+        let exact = [
+            [3_602_879_701_896_397, 0, 0],
+            [0, 1 << 55, 0],
+            [0, 0, 1 << 55],
+        ];
+        assert_eq!(Transform::try_from(&tenth), Transform::new(exact));
+        let tiny = Matrix3::new(1e-300, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0);
+        assert_eq!(Transform::try_from(&tiny), Err(GeometryError::Overflow));
+        let nan = Matrix3::new(f64::NAN, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0);
+        assert_eq!(
+            Transform::try_from(&nan),
+            Err(GeometryError::ZeroDenominator)
+        );
+        assert_eq!(
+            Transform::try_from(&Matrix3::zeros()),
+            Err(GeometryError::SingularTransform)
+        );
+        assert_eq!(Mat3::from(mat), Mat3::from(trans.mat).map(|x| x as f64));
+
+        // the matrix acts on homogeneous coordinates like the transform
+        let pt_p = PgPoint::from(Point2::new(3, -1));
+        let image = trans.apply(&pt_p);
+        assert_eq!(mat * Vector3::from(&pt_p), Vector3::from(&image));
+        let affine = Point2::try_from(&image).unwrap();
+        assert_eq!(affine, Point2::new(2.0, -0.75));
+        assert_eq!(
+            Point2::try_from(&PgPoint::new([1, 2, 0])),
+            Err(GeometryError::PointAtInfinity)
+        );
+        let ln_l = PgLine::new([1, 3, 0]);
+        assert_eq!(Vector3::from(&ln_l).dot(&Vector3::from(&pt_p)), 0.0);
+    }
+
+    #[cfg(feature = "glam")]
+    #[test]
+    fn test_glam() {
+        use glam::{DMat3, DVec2, DVec3, Mat3 as GlamMat3, Vec2, Vec3};
+        let trans = Transform::new([[2, 1, 3], [0, 1, -2], [1, 0, 1]]).unwrap();
+        let mat = DMat3::from(&trans);
+        // glam indexes columns first
+        assert_eq!(mat.col(2).x, 3.0);
+        assert_eq!(mat.col(0).z, 1.0);
+        assert_eq!(Transform::try_from(&mat), Ok(trans));
+        assert_eq!(Transform::try_from(&(mat * -0.125)), Ok(trans));
+        assert_eq!(Mat3::from(mat), Mat3::from(trans.mat).map(|x| x as f64));
+        assert_eq!(DMat3::from(Mat3::from(mat)), mat);
+
+        let pt_p = PgPoint::new([3, -1, 1]);
+        let image = trans.apply(&pt_p);
+        assert_eq!(mat * DVec3::from(&pt_p), DVec3::from(&image));
+        assert_eq!(DVec2::try_from(&image), Ok(DVec2::new(2.0, -0.75)));
+        assert_eq!(Vec2::try_from(&image), Ok(Vec2::new(2.0, -0.75)));
+        // the single precision matrix is scaled, which does not change the affine image
+        let single = GlamMat3::from(&trans) * Vec3::new(3.0, -1.0, 1.0);
+        assert_eq!(single.truncate() / single.z, Vec2::new(2.0, -0.75));
+        assert_eq!(
+            DVec2::try_from(&PgPoint::new([1, 2, 0])),
+            Err(GeometryError::PointAtInfinity)
+        );
+    }
+}
@@ -1,3 +1,219 @@
-fn main() {
-    println!("Hello, world!");
+// Command-line tool for quick geometric queries
+
+use projgeom_rs::conic::Conic;
+use projgeom_rs::scene::Scene;
+use projgeom_rs::{coincident, Join, PgPoint};
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage: projgeom <command> [arguments]
+
+Commands:
+  meet P Q                the line through two points, or the point on two lines
+  harmconj A B C          the harmonic conjugate of C with respect to A and B
+  conic-through P1 .. P5  the conic through five points, no three collinear
+  render SCENE OUT        draw a JSON scene as an SVG image, or as TikZ if OUT ends in .tex
+
+Coordinates are written x,y,z or [x : y : z], or x,y or (x, y) for an affine point.
+
+A scene lists named objects, given by coordinates or constructed from earlier ones, e.g.
+  {\"width\": 400, \"height\": 300, \"objects\": [
+    {\"name\": \"A\", \"point\": [0, 0, 1], \"color\": \"red\"},
+    {\"name\": \"B\", \"point\": [2, 1, 1]},
+    {\"name\": \"l\", \"join\": [\"A\", \"B\"], \"hidden\": true},
+    {\"name\": \"c\", \"conic\": [1, 0, 1, 0, 0, -4]},
+    {\"polar\": [\"B\", \"c\"], \"color\": \"blue\"},
+    {\"segment\": [\"A\", [0, 2, 1]]}]}
+where a conic lists the coefficients of a x² + b xy + c y² + d xz + e yz + f z².
+The constructions are join, meet, harm_conj, conic_through, polar, pole, segment and polygon.";
+
+/// Parse homogeneous coordinates in any of the accepted notations.
+fn parse_coord(arg: &str) -> Result<[i64; 3], String> {
+    if arg.contains(['[', '(']) {
+        return arg
+            .parse::<PgPoint>()
+            .map(|pt| pt.coord)
+            .map_err(|err| format!("invalid coordinates '{}': {}", arg, err));
+    }
+    let entries = arg
+        .split(',')
+        .map(|x| x.trim().parse::<i64>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| format!("invalid coordinates '{}': {}", arg, err))?;
+    match entries[..] {
+        [x, y] => Ok([x, y, 1]),
+        [x, y, z] if [x, y, z] != [0, 0, 0] => Ok([x, y, z]),
+        _ => Err(format!("invalid coordinates '{}'", arg)),
+    }
+}
+
+/// Parse exactly `N` points.
+fn parse_points<const N: usize>(args: &[String]) -> Result<[PgPoint; N], String> {
+    if args.len() != N {
+        return Err(format!("expected {} points, got {}", N, args.len()));
+    }
+    let mut points = [(); N].map(|_| PgPoint::new([0, 0, 1]));
+    for (pt, arg) in points.iter_mut().zip(args) {
+        *pt = PgPoint::new(parse_coord(arg)?);
+    }
+    Ok(points)
+}
+
+/// Format the equation of a conic, e.g. `x² + y² - 25z² = 0`.
+fn conic_equation(conic: &Conic) -> String {
+    let m = &conic.mat;
+    // the matrix is that of the quadratic form, so the mixed terms appear twice
+    let terms = [
+        (m[0][0], "x²"),
+        (2 * m[0][1], "xy"),
+        (m[1][1], "y²"),
+        (2 * m[0][2], "xz"),
+        (2 * m[1][2], "yz"),
+        (m[2][2], "z²"),
+    ];
+    let mut equation = String::new();
+    for (coef, monomial) in terms.into_iter().filter(|(coef, _)| *coef != 0) {
+        let sign = if coef < 0 { "-" } else { "+" };
+        if equation.is_empty() {
+            equation.push_str(if coef < 0 { "-" } else { "" });
+        } else {
+            equation.push_str(&format!(" {} ", sign));
+        }
+        if coef.abs() != 1 {
+            equation.push_str(&coef.abs().to_string());
+        }
+        equation.push_str(monomial);
+    }
+    equation + " = 0"
+}
+
+/// Run a command, returning its output or an error message.
+fn run(args: &[String]) -> Result<String, String> {
+    let (command, args) = args.split_first().ok_or(USAGE)?;
+    match command.as_str() {
+        "meet" => {
+            let [pt_p, pt_q] = parse_points::<2>(args)?;
+            if pt_p == pt_q {
+                return Err("the objects coincide".to_string());
+            }
+            Ok(pt_p.join(&pt_q).normalized().to_string())
+        }
+        "harmconj" => {
+            let [pt_a, pt_b, pt_c] = parse_points::<3>(args)?;
+            if pt_a == pt_b {
+                return Err("A and B coincide".to_string());
+            }
+            if !coincident(&pt_a, &pt_b, &pt_c) {
+                return Err("the points are not collinear".to_string());
+            }
+            Ok(pt_a.harm_conj(&pt_b, &pt_c).normalized().to_string())
+        }
+        "conic-through" => {
+            let points = parse_points::<5>(args)?;
+            let conic = Conic::through_points(&points).map_err(|err| err.to_string())?;
+            Ok(format!(
+                "{}\nmatrix: {:?}",
+                conic_equation(&conic),
+                conic.mat
+            ))
+        }
+        "render" => {
+            let [config, out] = args else {
+                return Err("expected a scene file and an output file".to_string());
+            };
+            let text = std::fs::read_to_string(config)
+                .map_err(|err| format!("cannot read '{}': {}", config, err))?;
+            let scene = Scene::from_json(&text).map_err(|err| format!("{}: {}", config, err))?;
+            let output = if out.ends_with(".tex") {
+                scene.to_tikz()
+            } else {
+                scene.to_svg()
+            };
+            let output = output.map_err(|err| format!("{}: {}", config, err))?;
+            std::fs::write(out, output)
+                .map_err(|err| format!("cannot write '{}': {}", out, err))?;
+            Ok(format!("wrote {}", out))
+        }
+        "help" | "-h" | "--help" => Ok(USAGE.to_string()),
+        _ => Err(format!("unknown command '{}'\n\n{}", command, USAGE)),
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(output) => {
+            println!("{}", output);
+            ExitCode::SUCCESS
+        }
+        Err(message) => {
+            eprintln!("projgeom: {}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_str(line: &str) -> Result<String, String> {
+        let args: Vec<String> = line.split_whitespace().map(String::from).collect();
+        run(&args)
+    }
+
+    #[test]
+    fn test_commands() {
+        assert_eq!(run_str("meet 1,0,1 0,1,1"), Ok("[1 : 1 : -1]".to_string()));
+        assert_eq!(run_str("meet [0:0:1] 2,2"), Ok("[1 : -1 : 0]".to_string()));
+        assert_eq!(
+            run_str("harmconj 1,0 5,0 2,0"),
+            Ok("[1 : 0 : -1]".to_string())
+        );
+        assert_eq!(
+            run_str("harmconj 1,0 5,0 2,1"),
+            Err("the points are not collinear".to_string())
+        );
+        let circle = run_str("conic-through 5,0 3,4 0,5 -4,3 0,-5").unwrap();
+        assert!(circle.starts_with("x² + y² - 25z² = 0\n"));
+        assert!(run_str("meet 1,2,3").is_err());
+        assert!(run_str("meet 1,x,3 1,2,3").is_err());
+        assert!(run_str("frobnicate").unwrap_err().contains("Usage"));
+        assert!(run_str("").unwrap_err().contains("Usage"));
+    }
+
+    #[test]
+    fn test_render() {
+        let dir = std::env::temp_dir().join(format!("projgeom-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let config = dir.join("scene.json");
+        std::fs::write(
+            &config,
+            r#"{"width": 200, "objects": [
+                {"point": [0, 0, 1], "label": "A", "color": "red"},
+                {"name": "B", "point": [2, 1, 1]},
+                {"line": [1, -1, 0], "color": "blue"},
+                {"segment": [[0, 0, 1], "B"]},
+                {"conic": [1, 0, 1, 0, 0, -4]}
+            ]}"#,
+        )
+        .unwrap();
+        let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+        let run_render = |out: &str| run(&["render".to_string(), path("scene.json"), path(out)]);
+        assert!(run_render("out.svg").is_ok());
+        let svg = std::fs::read_to_string(dir.join("out.svg")).unwrap();
+        assert!(svg.contains(">A</text>") && svg.contains(">B</text>"));
+        assert!(svg.contains("width=\"200\""));
+        assert!(run_render("out.tex").is_ok());
+        let tikz = std::fs::read_to_string(dir.join("out.tex")).unwrap();
+        assert!(tikz.starts_with("\\begin{tikzpicture}") && tikz.contains("{$B$}"));
+
+        std::fs::write(&config, r#"{"objects": [{"circle": 1}]}"#).unwrap();
+        assert!(run_render("out.svg").unwrap_err().contains("invalid scene"));
+        std::fs::write(&config, r#"{"objects": [{"join": ["A", "B"]}]}"#).unwrap();
+        assert!(run_render("out.svg")
+            .unwrap_err()
+            .ends_with("objects[0]: unknown name 'A'"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
@@ -0,0 +1,771 @@
+// Exact 3x3 matrices over a coordinate ring
+
+use crate::coord_ring::{ring_cross, ring_dot, CoordRing};
+#[cfg(feature = "std")]
+use crate::error::GeometryError;
+#[cfg(feature = "std")]
+use core::cmp::Ordering;
+use core::ops::{Add, Mul, Neg, Sub};
+use fractions::Fraction;
+#[cfg(feature = "std")]
+use num_integer::Integer;
+
+/// The `Mat3` struct is a 3x3 matrix over a `CoordRing`, e.g. `i64`, `i128` or `Fraction<i64>`.
+/// It acts on column vectors of homogeneous coordinates.
+///
+/// The ring operations are exact as long as the ring arithmetic is, i.e. they may overflow for
+/// machine integers. For `i128` there are checked variants, used by the exact kernels of the
+/// crate.
+///
+/// Examples:
+///
+/// ```rust
+/// use fractions::Fraction;
+/// use projgeom_rs::mat3::Mat3;
+/// let mat = Mat3::new([[2, 1, 0], [1, 1, 0], [0, 3, 1]]);
+/// assert_eq!(mat.det(), 1);
+/// assert_eq!(mat * mat.adjugate(), Mat3::scalar(1));
+/// let rat = Mat3::from(mat).map(Fraction::from);
+/// assert_eq!(rat * rat.inverse().unwrap(), Mat3::scalar(Fraction::from(1)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Mat3<T> {
+    /// Rows of the matrix
+    pub rows: [[T; 3]; 3],
+}
+
+impl<T> Mat3<T> {
+    /// Create a matrix from its rows.
+    #[inline]
+    pub const fn new(rows: [[T; 3]; 3]) -> Self {
+        Self { rows }
+    }
+}
+
+impl<T: CoordRing> Mat3<T> {
+    /// Return the zero matrix.
+    #[inline]
+    pub fn zero() -> Self {
+        Self::new(Default::default())
+    }
+
+    /// Return the scalar matrix `k I`, e.g. the identity for `k = 1`.
+    #[inline]
+    pub fn scalar(k: T) -> Self {
+        let mut result = Self::zero();
+        for (i, row) in result.rows.iter_mut().enumerate() {
+            row[i] = k.clone();
+        }
+        result
+    }
+
+    /// Apply a function to every entry, e.g. to convert the entries to another ring.
+    #[inline]
+    pub fn map<U>(&self, mut f: impl FnMut(T) -> U) -> Mat3<U> {
+        Mat3::new(self.rows.clone().map(|row| row.map(&mut f)))
+    }
+
+    /// Return the column `j`.
+    #[inline]
+    pub fn col(&self, j: usize) -> [T; 3] {
+        self.rows.clone().map(|row| row[j].clone())
+    }
+
+    /// Return the transposed matrix.
+    #[inline]
+    pub fn transpose(&self) -> Self {
+        Self::new([0, 1, 2].map(|j| self.col(j)))
+    }
+
+    /// Return the product `k M` with a scalar.
+    #[inline]
+    pub fn scale(&self, k: T) -> Self {
+        self.map(|x| k.clone() * x)
+    }
+
+    /// Return the product `M v` with a column vector.
+    #[inline]
+    pub fn apply(&self, vec: &[T; 3]) -> [T; 3] {
+        self.rows.clone().map(|row| ring_dot(&row, vec))
+    }
+
+    /// Return the determinant.
+    #[inline]
+    pub fn det(&self) -> T {
+        let [r0, r1, r2] = &self.rows;
+        ring_dot(r0, &ring_cross(r1, r2))
+    }
+
+    /// Return the matrix of cofactors, i.e. the transposed adjugate. Its row `i` is the cross
+    /// product of the other two rows.
+    #[inline]
+    pub fn cofactors(&self) -> Self {
+        let rows = &self.rows;
+        Self::new([0, 1, 2].map(|i| ring_cross(&rows[(i + 1) % 3], &rows[(i + 2) % 3])))
+    }
+
+    /// Return the adjugate, with `M adj(M) = det(M) I`. For a nonsingular matrix, this is a
+    /// multiple of the inverse, so it represents the inverse projective transform.
+    #[inline]
+    pub fn adjugate(&self) -> Self {
+        self.cofactors().transpose()
+    }
+
+    /// Return the trace.
+    #[inline]
+    pub fn trace(&self) -> T {
+        let [r0, r1, r2] = &self.rows;
+        r0[0].clone() + r1[1].clone() + r2[2].clone()
+    }
+
+    /// The function `char_poly` returns the coefficients `[det, c1, tr]` of the characteristic
+    /// polynomial `det(t I - M) = t³ - tr t² + c1 t - det`, where `c1` is the trace of the
+    /// adjugate.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::mat3::Mat3;
+    /// let mat = Mat3::new([[2, 0, 0], [0, 3, 0], [0, 0, 5]]);
+    /// assert_eq!(mat.char_poly(), [30, 31, 10]);
+    /// ```
+    #[inline]
+    pub fn char_poly(&self) -> [T; 3] {
+        [self.det(), self.cofactors().trace(), self.trace()]
+    }
+}
+
+impl Mat3<Fraction<i64>> {
+    /// Return the inverse of a rational matrix, or `None` if it is singular.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use fractions::Fraction;
+    /// use projgeom_rs::mat3::Mat3;
+    /// let mat = Mat3::new([[2, 0, 0], [0, 4, 0], [0, 0, 1]]).map(Fraction::from);
+    /// let inv = mat.inverse().unwrap();
+    /// assert_eq!(inv.rows[1][1], Fraction::new(1, 4));
+    /// assert_eq!(Mat3::<Fraction<i64>>::zero().inverse(), None);
+    /// ```
+    pub fn inverse(&self) -> Option<Self> {
+        let det = self.det();
+        if det.is_zero() {
+            return None;
+        }
+        Some(self.adjugate().map(|x| x / det))
+    }
+
+    /// The function `rational_eigenvalues` returns the eigenvalues of a rational matrix, repeated
+    /// by their multiplicity, in increasing order.
+    ///
+    /// Returns:
+    ///
+    /// The function `rational_eigenvalues` returns `GeometryError::Irrational` if an eigenvalue is
+    /// irrational or complex, or `GeometryError::Overflow`.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use fractions::Fraction;
+    /// use projgeom_rs::mat3::Mat3;
+    /// use projgeom_rs::GeometryError;
+    /// let mat = Mat3::new([[1, 2, 0], [2, 1, 0], [0, 0, 3]]).map(|x| Fraction::new(x, 2));
+    /// assert_eq!(
+    ///     mat.rational_eigenvalues(),
+    ///     Ok(vec![Fraction::new(-1, 2), Fraction::new(3, 2), Fraction::new(3, 2)])
+    /// );
+    /// // a rotation by 90 degrees
+    /// let rotation = Mat3::new([[0, -1, 0], [1, 0, 0], [0, 0, 1]]).map(Fraction::from);
+    /// assert_eq!(rotation.rational_eigenvalues(), Err(GeometryError::Irrational));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn rational_eigenvalues(&self) -> Result<Vec<Fraction<i64>>, GeometryError> {
+        // the eigenvalues of the integer matrix denom * M are integers
+        let denom = self
+            .rows
+            .iter()
+            .flatten()
+            .fold(1_i128, |l, x| l.lcm(&i128::from(x.denom)));
+        let scaled = self.map(|x| i128::from(x.numer) * (denom / i128::from(x.denom)));
+        let [_, c1, tr] = scaled.checked_char_poly().ok_or(GeometryError::Overflow)?;
+        let mut result = Vec::new();
+        for root in scaled.integer_eigenvalues()? {
+            // a multiple root is a root of the derivative 3 t² - 2 tr t + c1 as well
+            let slope = root
+                .checked_mul(3)
+                .and_then(|x| x.checked_sub(tr.checked_mul(2)?))
+                .and_then(|x| x.checked_mul(root))
+                .and_then(|x| x.checked_add(c1))
+                .ok_or(GeometryError::Overflow)?;
+            let multiplicity = match slope {
+                0 if root.checked_mul(3) == Some(tr) => 3,
+                0 => 2,
+                _ => 1,
+            };
+            let common = root.gcd(&denom);
+            let value = Fraction::new(
+                i64::try_from(root / common).map_err(|_| GeometryError::Overflow)?,
+                i64::try_from(denom / common).map_err(|_| GeometryError::Overflow)?,
+            );
+            result.extend(core::iter::repeat_n(value, multiplicity));
+        }
+        if result.len() < 3 {
+            return Err(GeometryError::Irrational);
+        }
+        Ok(result)
+    }
+
+    /// The function `eigenvectors` returns a basis of the eigenspace of `lambda`, i.e. of the
+    /// kernel of `M - lambda I`. The basis is empty if `lambda` is not an eigenvalue.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use fractions::Fraction;
+    /// use projgeom_rs::mat3::Mat3;
+    /// let mat = Mat3::new([[1, 2, 0], [2, 1, 0], [0, 0, 3]]).map(Fraction::from);
+    /// let basis = mat.eigenvectors(Fraction::from(3));
+    /// assert_eq!(basis.len(), 2);
+    /// for vec in basis {
+    ///     assert_eq!(mat.apply(&vec), vec.map(|x| x * Fraction::from(3)));
+    /// }
+    /// assert!(mat.eigenvectors(Fraction::from(2)).is_empty());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn eigenvectors(&self, lambda: Fraction<i64>) -> Vec<[Fraction<i64>; 3]> {
+        let shifted = *self - Self::scalar(lambda);
+        let rows = &shifted.rows;
+        for [i, j] in [[0, 1], [0, 2], [1, 2]] {
+            let cross = ring_cross(&rows[i], &rows[j]);
+            if cross.iter().any(|x| !x.is_zero()) {
+                // rank at least 2, and rank 3 if the remaining row is independent
+                return if shifted.det().is_zero() {
+                    vec![cross]
+                } else {
+                    vec![]
+                };
+            }
+        }
+        let unit = |k: usize| {
+            let mut vec = [Fraction::from(0); 3];
+            vec[k] = Fraction::from(1);
+            vec
+        };
+        // rank 1: the vectors orthogonal to a nonzero row, or rank 0: every vector
+        match rows.iter().find(|row| row.iter().any(|x| !x.is_zero())) {
+            Some(row) => {
+                let k = (0..3).find(|&k| !row[k].is_zero()).unwrap_or(0);
+                (0..3)
+                    .filter(|&m| m != k)
+                    .map(|m| ring_cross(row, &unit(3 - k - m)))
+                    .collect()
+            }
+            None => (0..3).map(unit).collect(),
+        }
+    }
+}
+
+impl Mat3<i128> {
+    /// Checked product `M v` with a column vector.
+    #[inline]
+    pub fn checked_apply(&self, vec: &[i128; 3]) -> Option<[i128; 3]> {
+        let [r0, r1, r2] = &self.rows;
+        Some([
+            checked_dot(r0, vec)?,
+            checked_dot(r1, vec)?,
+            checked_dot(r2, vec)?,
+        ])
+    }
+
+    /// Checked matrix sum.
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        let mut result = *self;
+        for (row, src) in result.rows.iter_mut().zip(&other.rows) {
+            for (x, y) in row.iter_mut().zip(src) {
+                *x = x.checked_add(*y)?;
+            }
+        }
+        Some(result)
+    }
+
+    /// Checked matrix difference.
+    #[inline]
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        self.checked_add(&other.checked_neg()?)
+    }
+
+    /// Checked negation.
+    pub fn checked_neg(&self) -> Option<Self> {
+        let mut result = *self;
+        for x in result.rows.iter_mut().flatten() {
+            *x = x.checked_neg()?;
+        }
+        Some(result)
+    }
+
+    /// Checked matrix product.
+    pub fn checked_mul(&self, other: &Self) -> Option<Self> {
+        let mut result = Self::zero();
+        for (row, row_a) in result.rows.iter_mut().zip(&self.rows) {
+            for (j, x) in row.iter_mut().enumerate() {
+                *x = checked_dot(row_a, &other.col(j))?;
+            }
+        }
+        Some(result)
+    }
+
+    /// Checked determinant.
+    #[inline]
+    pub fn checked_det(&self) -> Option<i128> {
+        let [r0, r1, r2] = &self.rows;
+        checked_dot(r0, &checked_cross(r1, r2)?)
+    }
+
+    /// Checked matrix of cofactors.
+    #[inline]
+    pub fn checked_cofactors(&self) -> Option<Self> {
+        let rows = &self.rows;
+        Some(Self::new([
+            checked_cross(&rows[1], &rows[2])?,
+            checked_cross(&rows[2], &rows[0])?,
+            checked_cross(&rows[0], &rows[1])?,
+        ]))
+    }
+
+    /// Checked adjugate.
+    #[inline]
+    pub fn checked_adjugate(&self) -> Option<Self> {
+        Some(self.checked_cofactors()?.transpose())
+    }
+
+    /// Checked trace.
+    #[inline]
+    pub fn checked_trace(&self) -> Option<i128> {
+        let [r0, r1, r2] = &self.rows;
+        r0[0].checked_add(r1[1])?.checked_add(r2[2])
+    }
+
+    /// Checked coefficients `[det, c1, tr]` of the characteristic polynomial, see `char_poly`.
+    #[inline]
+    pub fn checked_char_poly(&self) -> Option<[i128; 3]> {
+        Some([
+            self.checked_det()?,
+            self.checked_cofactors()?.checked_trace()?,
+            self.checked_trace()?,
+        ])
+    }
+
+    /// The function `integer_eigenvalues` returns the distinct integer eigenvalues in increasing
+    /// order. As the characteristic polynomial is monic with integer coefficients, these are all
+    /// rational eigenvalues.
+    ///
+    /// Returns:
+    ///
+    /// The function `integer_eigenvalues` returns `GeometryError::Overflow` if the characteristic
+    /// polynomial overflows i128.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::mat3::Mat3;
+    /// let mat = Mat3::new([[2, 1, 0], [0, 2, 0], [0, 0, -3]]);
+    /// assert_eq!(mat.integer_eigenvalues(), Ok(vec![-3, 2]));
+    /// // the eigenvalues of the rotation part are complex
+    /// let mat = Mat3::new([[0, -1, 0], [1, 0, 0], [0, 0, 5]]);
+    /// assert_eq!(mat.integer_eigenvalues(), Ok(vec![5]));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn integer_eigenvalues(&self) -> Result<Vec<i128>, GeometryError> {
+        let coeffs = self.checked_char_poly().ok_or(GeometryError::Overflow)?;
+        Ok(integer_roots(&coeffs))
+    }
+}
+
+/// The distinct integer roots, in increasing order, of the monic cubic `t³ - tr t² + c1 t - det`
+/// given by `[det, c1, tr]`.
+///
+/// The roots are isolated exactly: the derivative `3 t² - 2 tr t + c1` is monotone on either side
+/// of `tr / 3`, so bisection over the integers brackets its roots, and between those brackets the
+/// cubic is monotone and bisected in turn. Values are evaluated with `WideInt`, so no intermediate
+/// result overflows.
+#[cfg(feature = "std")]
+pub(crate) fn integer_roots(&[det, c1, tr]: &[i128; 3]) -> Vec<i128> {
+    let cubic = [
+        WideInt::from(1),
+        -WideInt::from(tr),
+        WideInt::from(c1),
+        -WideInt::from(det),
+    ];
+    let slope = [
+        WideInt::from(3),
+        WideInt::from(tr).mul_i128(-2),
+        WideInt::from(c1),
+    ];
+    let vertex = tr.div_euclid(3);
+    let mut breaks = vec![i128::MIN, vertex, vertex + 1, i128::MAX];
+    for (lo, hi) in [(i128::MIN, vertex), (vertex + 1, i128::MAX)] {
+        if let Some(k) = sign_change(&slope, lo, hi) {
+            breaks.push(k);
+            breaks.extend(k.checked_add(1));
+        }
+    }
+    breaks.sort_unstable();
+    breaks.dedup();
+    let mut roots: Vec<i128> = breaks
+        .windows(2)
+        .filter_map(|pair| sign_change(&cubic, pair[0], pair[1]))
+        .filter(|&t| poly_sign(&cubic, t) == Ordering::Equal)
+        .collect();
+    roots.sort_unstable();
+    roots.dedup();
+    roots
+}
+
+/// For a polynomial that is monotone on the integers of `[lo, hi]`, an integer `t` in the range
+/// where it vanishes, or else where its sign changes between `t` and `t + 1`; `None` if it has
+/// the same nonzero sign at both ends.
+#[cfg(feature = "std")]
+fn sign_change(coeffs: &[WideInt], mut lo: i128, mut hi: i128) -> Option<i128> {
+    let sign_lo = poly_sign(coeffs, lo);
+    if sign_lo == Ordering::Equal {
+        return Some(lo);
+    }
+    let sign_hi = poly_sign(coeffs, hi);
+    if sign_hi == Ordering::Equal {
+        return Some(hi);
+    }
+    if sign_lo == sign_hi {
+        return None;
+    }
+    while hi.abs_diff(lo) > 1 {
+        // the floor of the mean, without overflow
+        let mid = (lo >> 1) + (hi >> 1) + (lo & hi & 1);
+        match poly_sign(coeffs, mid) {
+            Ordering::Equal => return Some(mid),
+            sign if sign == sign_lo => lo = mid,
+            _ => hi = mid,
+        }
+    }
+    Some(lo)
+}
+
+/// Sign of the polynomial with the coefficients `coeffs`, highest degree first, at `t`.
+#[cfg(feature = "std")]
+fn poly_sign(coeffs: &[WideInt], t: i128) -> Ordering {
+    coeffs
+        .iter()
+        .fold(WideInt::from(0), |acc, c| acc.mul_i128(t) + c.clone())
+        .sign()
+}
+
+/// The `WideInt` struct is a signed integer of any size, with just the operations needed to
+/// evaluate polynomials exactly: the magnitude is stored in little-endian 64-bit limbs without
+/// leading zeros.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct WideInt {
+    negative: bool,
+    limbs: Vec<u64>,
+}
+
+#[cfg(feature = "std")]
+impl WideInt {
+    /// Drop leading zero limbs; zero is not negative.
+    fn trimmed(mut self) -> Self {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+        self.negative &= !self.limbs.is_empty();
+        self
+    }
+
+    fn sign(&self) -> Ordering {
+        match (self.limbs.is_empty(), self.negative) {
+            (true, _) => Ordering::Equal,
+            (false, true) => Ordering::Less,
+            (false, false) => Ordering::Greater,
+        }
+    }
+
+    fn mul_i128(&self, x: i128) -> Self {
+        let factor = x.unsigned_abs();
+        let factor = [factor as u64, (factor >> 64) as u64];
+        let mut limbs = vec![0_u64; self.limbs.len() + 2];
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0_u128;
+            for (j, &b) in factor.iter().enumerate() {
+                let cur = u128::from(limbs[i + j]) + u128::from(a) * u128::from(b) + carry;
+                limbs[i + j] = cur as u64;
+                carry = cur >> 64;
+            }
+            limbs[i + 2] = carry as u64;
+        }
+        Self {
+            negative: self.negative != (x < 0),
+            limbs,
+        }
+        .trimmed()
+    }
+
+    /// Compare the magnitudes.
+    fn cmp_magnitude(&self, other: &Self) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<i128> for WideInt {
+    fn from(x: i128) -> Self {
+        let magnitude = x.unsigned_abs();
+        Self {
+            negative: x < 0,
+            limbs: vec![magnitude as u64, (magnitude >> 64) as u64],
+        }
+        .trimmed()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Neg for WideInt {
+    type Output = Self;
+
+    fn neg(mut self) -> Self {
+        self.negative = !self.negative;
+        self.trimmed()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Add for WideInt {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let (large, small) = if self.cmp_magnitude(&other) == Ordering::Less {
+            (other, self)
+        } else {
+            (self, other)
+        };
+        let negative = large.negative;
+        let mut limbs = large.limbs;
+        limbs.push(0);
+        if negative == small.negative {
+            let mut carry = 0_u128;
+            for (i, limb) in limbs.iter_mut().enumerate() {
+                let cur = u128::from(*limb) + u128::from(*small.limbs.get(i).unwrap_or(&0)) + carry;
+                *limb = cur as u64;
+                carry = cur >> 64;
+            }
+        } else {
+            let mut borrow = false;
+            for (i, limb) in limbs.iter_mut().enumerate() {
+                let (diff, b1) = limb.overflowing_sub(*small.limbs.get(i).unwrap_or(&0));
+                let (diff, b2) = diff.overflowing_sub(u64::from(borrow));
+                *limb = diff;
+                borrow = b1 || b2;
+            }
+        }
+        Self { negative, limbs }.trimmed()
+    }
+}
+
+/// Checked dot product of two i128 vectors.
+#[inline]
+pub(crate) fn checked_dot(v_a: &[i128; 3], v_b: &[i128; 3]) -> Option<i128> {
+    (0..3).try_fold(0_i128, |sum, i| {
+        sum.checked_add(v_a[i].checked_mul(v_b[i])?)
+    })
+}
+
+/// Checked cross product of two i128 vectors.
+pub(crate) fn checked_cross(v_a: &[i128; 3], v_b: &[i128; 3]) -> Option<[i128; 3]> {
+    let minor = |i: usize, j: usize| {
+        v_a[i]
+            .checked_mul(v_b[j])?
+            .checked_sub(v_a[j].checked_mul(v_b[i])?)
+    };
+    Some([minor(1, 2)?, minor(2, 0)?, minor(0, 1)?])
+}
+
+impl<T> From<[[T; 3]; 3]> for Mat3<T> {
+    #[inline]
+    fn from(rows: [[T; 3]; 3]) -> Self {
+        Self::new(rows)
+    }
+}
+
+impl<T> From<Mat3<T>> for [[T; 3]; 3] {
+    #[inline]
+    fn from(mat: Mat3<T>) -> Self {
+        mat.rows
+    }
+}
+
+impl<T: CoordRing> Add for Mat3<T> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, other: Self) -> Self {
+        let mut result = self;
+        for (row, src) in result.rows.iter_mut().zip(other.rows) {
+            for (x, y) in row.iter_mut().zip(src) {
+                *x = x.clone() + y;
+            }
+        }
+        result
+    }
+}
+
+impl<T: CoordRing> Sub for Mat3<T> {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl<T: CoordRing> Neg for Mat3<T> {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        self.map(|x| -x)
+    }
+}
+
+impl<T: CoordRing> Mul for Mat3<T> {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, other: Self) -> Self {
+        let cols = [0, 1, 2].map(|j| other.col(j));
+        Self::new(
+            self.rows
+                .map(|row| cols.clone().map(|col| ring_dot(&row, &col))),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coord_ring::Zp;
+
+    #[test]
+    fn test_mat3() {
+        let mat_a = Mat3::new([[1, 2, 0], [0, 1, 0], [1, 0, 1]]);
+        let mat_b = Mat3::new([[2, -1, 3], [0, 4, 1], [5, 0, -2]]);
+        assert_eq!((mat_a * mat_b).det(), mat_a.det() * mat_b.det());
+        assert_eq!(
+            (mat_a * mat_b).transpose(),
+            mat_b.transpose() * mat_a.transpose()
+        );
+        assert_eq!(mat_b * mat_b.adjugate(), Mat3::scalar(mat_b.det()));
+        assert_eq!(mat_a + mat_b - mat_b, mat_a);
+        assert_eq!(mat_a.scale(3), mat_a + mat_a + mat_a);
+        assert_eq!(mat_b.apply(&[1, 0, 0]), mat_b.col(0));
+        let rows: [[i64; 3]; 3] = mat_b.into();
+        assert_eq!(Mat3::from(rows), mat_b);
+
+        // checked i128 arithmetic agrees and reports overflow
+        let wide_a = mat_a.map(i128::from);
+        let wide_b = mat_b.map(i128::from);
+        assert_eq!(wide_a.checked_mul(&wide_b), Some(wide_a * wide_b));
+        assert_eq!(wide_b.checked_det(), Some(wide_b.det()));
+        assert_eq!(wide_b.checked_adjugate(), Some(wide_b.adjugate()));
+        assert_eq!(wide_b.checked_sub(&wide_a), Some(wide_b - wide_a));
+        assert_eq!(
+            wide_b.checked_apply(&[1, 2, 3]),
+            Some(wide_b.apply(&[1, 2, 3]))
+        );
+        let huge = Mat3::scalar(i128::MAX);
+        assert_eq!(huge.checked_mul(&huge), None);
+        assert_eq!(huge.checked_add(&huge), None);
+        assert_eq!(Mat3::scalar(i128::MIN).checked_neg(), None);
+
+        // rational inverse
+        let rat_b = mat_b.map(Fraction::from);
+        let inv = rat_b.inverse().unwrap();
+        assert_eq!(inv * rat_b, Mat3::scalar(Fraction::from(1)));
+        let singular = Mat3::new([[1, 2, 3], [2, 4, 6], [0, 0, 1]]).map(Fraction::from);
+        assert_eq!(singular.inverse(), None);
+
+        // any coordinate ring
+        let z = Zp::<7>::new;
+        let mat_p = Mat3::new([[z(1), z(2), z(0)], [z(3), z(1), z(4)], [z(0), z(5), z(6)]]);
+        assert_eq!(mat_p * mat_p.adjugate(), Mat3::scalar(mat_p.det()));
+    }
+
+    #[test]
+    fn test_eigen() {
+        let mat = Mat3::new([[2, -1, 3], [0, 4, 1], [5, 0, -2]]);
+        let [det, c1, tr] = mat.char_poly();
+        assert_eq!([det, tr], [mat.det(), 4]);
+        // Cayley-Hamilton: M³ - tr M² + c1 M - det I = 0
+        let square = mat * mat;
+        let cube = square * mat;
+        assert_eq!(
+            cube - square.scale(tr) + mat.scale(c1) - Mat3::scalar(det),
+            Mat3::zero()
+        );
+        assert_eq!(
+            mat.map(i128::from).checked_char_poly(),
+            Some(mat.char_poly().map(i128::from))
+        );
+
+        // eigenvalues 1/2, 1/2 (a Jordan block) and -2/3
+        let jordan = Mat3::new([[1, 1, 0], [0, 1, 0], [0, 0, 0]]).map(|x| Fraction::new(x, 2))
+            + Mat3::new([[0, 0, 0], [0, 0, 0], [0, 0, -2]]).map(|x| Fraction::new(x, 3));
+        let basis = Mat3::new([[1, 2, 0], [0, 1, 1], [1, 0, 1]]).map(Fraction::from);
+        let mat = basis * jordan * basis.inverse().unwrap();
+        let half = Fraction::new(1, 2);
+        assert_eq!(
+            mat.rational_eigenvalues(),
+            Ok(vec![Fraction::new(-2, 3), half, half])
+        );
+        let vecs = mat.eigenvectors(half);
+        assert_eq!(vecs.len(), 1);
+        assert_eq!(mat.apply(&vecs[0]), vecs[0].map(|x| x * half));
+        assert_eq!(mat.eigenvectors(Fraction::new(-2, 3)).len(), 1);
+        assert!(mat.eigenvectors(Fraction::from(1)).is_empty());
+
+        // scalar matrices have a triple eigenvalue and every vector is an eigenvector
+        let scalar = Mat3::scalar(Fraction::new(-5, 7));
+        assert_eq!(
+            scalar.rational_eigenvalues(),
+            Ok(vec![Fraction::new(-5, 7); 3])
+        );
+        assert_eq!(scalar.eigenvectors(Fraction::new(-5, 7)).len(), 3);
+
+        // large eigenvalues, whose characteristic polynomial f64 cannot resolve
+        let big = Mat3::new([[1000000007, 0, 0], [0, 1000000009, 0], [0, 0, 1000000011]]);
+        assert_eq!(
+            big.integer_eigenvalues(),
+            Ok(vec![1000000007, 1000000009, 1000000011])
+        );
+        let m = (1_i128 << 41) - 1;
+        let big = Mat3::new([[m, 1, 0], [0, m, 0], [0, 0, -m]]);
+        assert_eq!(big.integer_eigenvalues(), Ok(vec![-m, m]));
+        let big = Mat3::new([[1 << 40, 0, 0], [0, (1 << 40) + 1, 0], [0, 0, 0]]);
+        assert_eq!(
+            big.map(|x| Fraction::new(x, 3)).rational_eigenvalues(),
+            Ok(vec![
+                Fraction::new(0, 1),
+                Fraction::new(1 << 40, 3),
+                Fraction::new((1 << 40) + 1, 3)
+            ])
+        );
+
+        // x³ - 2 has a single irrational real root
+        let companion = Mat3::new([[0, 0, 2], [1, 0, 0], [0, 1, 0]]);
+        assert_eq!(companion.map(i128::from).integer_eigenvalues(), Ok(vec![]));
+        assert_eq!(
+            companion.map(Fraction::from).rational_eigenvalues(),
+            Err(GeometryError::Irrational)
+        );
+    }
+}
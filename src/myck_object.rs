@@ -1,20 +1,140 @@
+// Cayley-Klein planes with a diagonal absolute, among them MyCK
+
 use crate::ck_plane::{CayleyKleinPlane, CayleyKleinPlanePrimitive};
-use crate::pg_object::{MyCKLine, MyCKPoint};
+use crate::pg_object::{DiagonalCKLine, DiagonalCKPoint};
+
+/// Greatest common divisor, usable in constants.
+const fn gcd(a: u64, b: u64) -> u64 {
+    let (mut a, mut b) = (a, b);
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+    a
+}
+
+/// Check at compile time that the absolute `diag(a, b, c)` is non-degenerate.
+const fn polar_diagonal(a: i64, b: i64, c: i64) -> [i64; 3] {
+    assert!(
+        a != 0 && b != 0 && c != 0,
+        "the absolute of a DiagonalCK plane must be non-degenerate"
+    );
+    [a, b, c]
+}
 
-impl CayleyKleinPlanePrimitive<MyCKLine> for MyCKPoint {
+/// The diagonal of the adjugate of `diag(a, b, c)`, divided by the common factor of its entries.
+const fn pole_diagonal(a: i64, b: i64, c: i64) -> [i64; 3] {
+    let [a, b, c] = polar_diagonal(a, b, c);
+    let adj = [b * c, a * c, a * b];
+    let g = gcd(
+        gcd(adj[0].unsigned_abs(), adj[1].unsigned_abs()),
+        adj[2].unsigned_abs(),
+    ) as i64;
+    [adj[0] / g, adj[1] / g, adj[2] / g]
+}
+
+impl<const A: i64, const B: i64, const C: i64> DiagonalCKPoint<A, B, C> {
+    const POLAR: [i64; 3] = polar_diagonal(A, B, C);
+}
+
+impl<const A: i64, const B: i64, const C: i64> DiagonalCKLine<A, B, C> {
+    const POLE: [i64; 3] = pole_diagonal(A, B, C);
+}
+
+/// The polar of a point is `diag(A, B, C)` times its coordinates.
+///
+/// The constants choose the geometry, e.g. `DiagonalCKPoint<1, 1, 1>` is elliptic and
+/// `DiagonalCKPoint<1, 1, -1>` hyperbolic, and `MyCKPoint` is `DiagonalCKPoint<-2, 1, -2>`. A zero
+/// constant, i.e. a degenerate absolute, is rejected at compile time.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::{orthocenter, tri_altitude, CayleyKleinPlanePrimitive, DiagonalCKPoint};
+/// use projgeom_rs::{DiagonalCKLine, ProjectivePlanePrimitive};
+/// type Point = DiagonalCKPoint<1, 3, -2>;
+/// let pt_p = Point::new([1, 1, 1]);
+/// assert_eq!(pt_p.perp(), DiagonalCKLine::new([1, 3, -2]));
+/// assert_eq!(pt_p.perp().perp(), pt_p);
+/// let triangle = [[1, 0, 2], [0, 1, 3], [1, 1, 1]].map(Point::new);
+/// let [t_1, t_2, _] = tri_altitude(&triangle);
+/// assert_eq!(orthocenter(&triangle), t_1.meet(&t_2));
+/// ```
+impl<const A: i64, const B: i64, const C: i64> CayleyKleinPlanePrimitive<DiagonalCKLine<A, B, C>>
+    for DiagonalCKPoint<A, B, C>
+{
     #[inline]
-    fn perp(&self) -> MyCKLine {
-        MyCKLine::new([-2 * self.coord[0], self.coord[1], -2 * self.coord[2]])
+    fn perp(&self) -> DiagonalCKLine<A, B, C> {
+        let [a, b, c] = Self::POLAR;
+        let [x, y, z] = self.coord;
+        DiagonalCKLine::new([a * x, b * y, c * z])
     }
 }
 
-impl CayleyKleinPlanePrimitive<MyCKPoint> for MyCKLine {
+/// The pole of a line is the adjugate of `diag(A, B, C)`, reduced, times its coordinates.
+impl<const A: i64, const B: i64, const C: i64> CayleyKleinPlanePrimitive<DiagonalCKPoint<A, B, C>>
+    for DiagonalCKLine<A, B, C>
+{
     #[inline]
-    fn perp(&self) -> MyCKPoint {
-        MyCKPoint::new([-self.coord[0], 2 * self.coord[1], -self.coord[2]])
+    fn perp(&self) -> DiagonalCKPoint<A, B, C> {
+        let [a, b, c] = Self::POLE;
+        let [x, y, z] = self.coord;
+        DiagonalCKPoint::new([a * x, b * y, c * z])
     }
 }
 
-impl CayleyKleinPlane<MyCKLine, i64> for MyCKPoint {}
+impl<const A: i64, const B: i64, const C: i64> CayleyKleinPlane<DiagonalCKLine<A, B, C>, i64>
+    for DiagonalCKPoint<A, B, C>
+{
+}
+
+impl<const A: i64, const B: i64, const C: i64> CayleyKleinPlane<DiagonalCKPoint<A, B, C>, i64>
+    for DiagonalCKLine<A, B, C>
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::axioms::verify_perp;
+    use crate::pg_object::{EllipticLine, EllipticPoint, MyCKLine, MyCKPoint};
 
-impl CayleyKleinPlane<MyCKPoint, i64> for MyCKLine {}
+    /// Check that the maps form a polarity on all points and lines with entries in `-2..=2`.
+    fn check_polarity<const A: i64, const B: i64, const C: i64>() {
+        let mut coords = Vec::new();
+        for x in -2..=2 {
+            for y in -2..=2 {
+                for z in -2..=2 {
+                    coords.push([x, y, z]);
+                }
+            }
+        }
+        coords.retain(|coord| *coord != [0, 0, 0]);
+        for coord_p in &coords {
+            for coord_l in &coords {
+                let pt_p = DiagonalCKPoint::<A, B, C>::new(*coord_p);
+                let ln_l = DiagonalCKLine::<A, B, C>::new(*coord_l);
+                assert_eq!(verify_perp(&pt_p, &ln_l), Ok(()));
+                assert_eq!(verify_perp(&ln_l, &pt_p), Ok(()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_polarity() {
+        check_polarity::<-2, 1, -2>();
+        check_polarity::<1, 1, -1>();
+        check_polarity::<2, 3, 5>();
+
+        // the maps MyCK had before it became an alias
+        let coord = [3, -5, 7];
+        assert_eq!(MyCKPoint::new(coord).perp().coord, [-6, -5, -14]);
+        assert_eq!(MyCKLine::new(coord).perp().coord, [-3, -10, -7]);
+        // diag(1, 1, 1) is the elliptic plane
+        let pt_p = DiagonalCKPoint::<1, 1, 1>::new(coord);
+        assert_eq!(pt_p.perp().coord, EllipticPoint::new(coord).perp().coord);
+        let ln_l = DiagonalCKLine::<1, 1, 1>::new(coord);
+        assert_eq!(ln_l.perp().coord, EllipticLine::new(coord).perp().coord);
+    }
+}
@@ -0,0 +1,338 @@
+// Pluggable policies for reducing homogeneous coordinates by their common factor
+
+use crate::pg_object::{normalize_homogeneous, Coord3};
+use crate::pg_plane::{harm_conj, Join, ProjectivePlane, ProjectivePlanePrimitive};
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+
+/// The `NormalizationPolicy` trait decides whether the coordinates of a computed point or line
+/// are reduced by their common factor. Reducing costs a gcd per result; skipping it lets the
+/// coordinates of deep constructions grow until they overflow. The kernel only reduces a result
+/// when it would not fit into i64 otherwise.
+///
+/// A policy is chosen per computation by passing it to `meet_with`, `parametrize_with` or
+/// `harm_conj_with`, or per type with the wrapper `Normalized<T, P>`, which applies it to every
+/// meet and parametrization, also inside generic constructions.
+///
+/// The cases `harm_conj policy` and `meet policy` of `benches/geometry_bench.rs` compare the
+/// policies on points with small coordinates, where `GcdThreshold<32>` reduces none of the
+/// results.
+pub trait NormalizationPolicy {
+    /// Return the coordinates of a result, reduced by their common factor if the policy says so.
+    fn apply(&self, coord: [i64; 3]) -> [i64; 3];
+}
+
+/// Never reduce the coordinates.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NoNormalization;
+
+impl NormalizationPolicy for NoNormalization {
+    #[inline]
+    fn apply(&self, coord: [i64; 3]) -> [i64; 3] {
+        coord
+    }
+}
+
+/// Always reduce the coordinates and make the first nonzero one positive, as in
+/// `normalize_homogeneous`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GcdAlways;
+
+impl NormalizationPolicy for GcdAlways {
+    #[inline]
+    fn apply(&self, coord: [i64; 3]) -> [i64; 3] {
+        normalize_homogeneous(&coord)
+    }
+}
+
+/// Reduce the coordinates only when one of them needs more than `BITS` bits, so that the gcd is
+/// paid only once coordinates have grown.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GcdThreshold<const BITS: u32>;
+
+impl<const BITS: u32> NormalizationPolicy for GcdThreshold<BITS> {
+    #[inline]
+    fn apply(&self, coord: [i64; 3]) -> [i64; 3] {
+        // the bits of all coordinates at once, without branching per coordinate
+        let bits = coord.iter().fold(0, |acc, x| acc | x.unsigned_abs());
+        if u64::BITS - bits.leading_zeros() <= BITS {
+            coord
+        } else {
+            normalize_homogeneous(&coord)
+        }
+    }
+}
+
+/// The function `meet_with` computes the meet (or join) of two objects and applies a
+/// normalization policy to the result.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::normalization::{meet_with, GcdAlways, NoNormalization};
+/// use projgeom_rs::{PgLine, PgPoint};
+/// let [pt_p, pt_q] = [[2, 0, 2], [0, 2, 2]].map(PgPoint::new);
+/// let ln_l: PgLine = meet_with(&pt_p, &pt_q, &NoNormalization);
+/// assert_eq!(ln_l.coord, [-4, -4, 4]);
+/// let ln_l: PgLine = meet_with(&pt_p, &pt_q, &GcdAlways);
+/// assert_eq!(ln_l.coord, [1, 1, -1]);
+/// ```
+#[inline]
+pub fn meet_with<Point, Line, P>(pt_a: &Point, pt_b: &Point, policy: &P) -> Line
+where
+    Point: ProjectivePlanePrimitive<Line>,
+    Line: Coord3,
+    P: NormalizationPolicy,
+{
+    Line::from_coord(policy.apply(*pt_a.meet(pt_b).coords()))
+}
+
+/// The function `parametrize_with` computes `lambda * pt_a + mu * pt_b` and applies a
+/// normalization policy to the result.
+#[inline]
+pub fn parametrize_with<Point, Line, P>(
+    pt_a: &Point,
+    lambda: i64,
+    pt_b: &Point,
+    mu: i64,
+    policy: &P,
+) -> Point
+where
+    Point: ProjectivePlane<Line, i64> + Coord3,
+    P: NormalizationPolicy,
+{
+    Point::from_coord(policy.apply(*pt_a.parametrize(lambda, pt_b, mu).coords()))
+}
+
+/// The function `harm_conj_with` constructs the harmonic conjugate by `harm_conj` over
+/// `Normalized<_, P>`, which applies the normalization policy to every intermediate line and to
+/// the result. The policy is applied as `P::default()`, as in `Normalized`.
+///
+/// # Panics
+///
+/// Panics if the points are not collinear, or `pt_a` and `pt_b` coincide.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::normalization::{harm_conj_with, GcdThreshold, NoNormalization};
+/// use projgeom_rs::{PgLine, PgPoint};
+/// let [pt_a, pt_b, pt_c] = [[1, 0, 1], [5, 0, 1], [2, 0, 1]].map(PgPoint::new);
+/// let pt_d = harm_conj_with::<_, PgLine, _>(&pt_a, &pt_b, &pt_c, &NoNormalization);
+/// assert_eq!(pt_d.coord, [-8, 0, 8]);
+/// let pt_d = harm_conj_with::<_, PgLine, _>(&pt_a, &pt_b, &pt_c, &GcdThreshold::<1>);
+/// assert_eq!(pt_d.coord, [1, 0, -1]);
+/// ```
+pub fn harm_conj_with<Point, Line, P>(
+    pt_a: &Point,
+    pt_b: &Point,
+    pt_c: &Point,
+    _policy: &P,
+) -> Point
+where
+    Point: ProjectivePlane<Line, i64> + Coord3 + Clone,
+    Line: ProjectivePlane<Point, i64> + Coord3,
+    P: NormalizationPolicy + Default,
+{
+    let [pt_a, pt_b, pt_c] = [pt_a, pt_b, pt_c].map(|pt| Normalized::<_, P>::new(pt.clone()));
+    harm_conj(&pt_a, &pt_b, &pt_c).into_inner()
+}
+
+/// The `Normalized` struct wraps a point or line so that every meet and parametrization applies
+/// the policy `P`. Generic constructions such as `harm_conj` or `check_pappus` then run with the
+/// policy at every step.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::normalization::{GcdAlways, Normalized};
+/// use projgeom_rs::{harm_conj, PgPoint, ProjectivePlanePrimitive};
+/// type Point = Normalized<PgPoint, GcdAlways>;
+/// let [pt_a, pt_b, pt_c] = [[1, 0, 1], [5, 0, 1], [2, 0, 1]].map(|c| Point::from(PgPoint::new(c)));
+/// assert_eq!(harm_conj(&pt_a, &pt_b, &pt_c).obj.coord, [1, 0, -1]);
+/// assert_eq!(pt_a.meet(&pt_b).obj.coord, [0, 1, 0]);
+/// ```
+pub struct Normalized<T, P> {
+    pub obj: T,
+    policy: PhantomData<P>,
+}
+
+impl<T, P> Normalized<T, P> {
+    /// Wrap an object.
+    #[inline]
+    pub const fn new(obj: T) -> Self {
+        Self {
+            obj,
+            policy: PhantomData,
+        }
+    }
+
+    /// Return the wrapped object.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.obj
+    }
+}
+
+impl<T, P> From<T> for Normalized<T, P> {
+    #[inline]
+    fn from(obj: T) -> Self {
+        Self::new(obj)
+    }
+}
+
+impl<T: fmt::Debug, P> fmt::Debug for Normalized<T, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.obj.fmt(f)
+    }
+}
+
+impl<T: fmt::Display, P> fmt::Display for Normalized<T, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.obj.fmt(f)
+    }
+}
+
+impl<T: Clone, P> Clone for Normalized<T, P> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self::new(self.obj.clone())
+    }
+}
+
+impl<T: PartialEq, P> PartialEq for Normalized<T, P> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.obj == other.obj
+    }
+}
+
+impl<T: Eq, P> Eq for Normalized<T, P> {}
+
+impl<T: Hash, P> Hash for Normalized<T, P> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.obj.hash(state);
+    }
+}
+
+impl<Point, Line, P> ProjectivePlanePrimitive<Normalized<Line, P>> for Normalized<Point, P>
+where
+    Point: ProjectivePlanePrimitive<Line>,
+    Line: Coord3,
+    P: NormalizationPolicy + Default,
+{
+    #[inline]
+    fn meet(&self, rhs: &Self) -> Normalized<Line, P> {
+        Normalized::new(meet_with(&self.obj, &rhs.obj, &P::default()))
+    }
+
+    #[inline]
+    fn incident(&self, line: &Normalized<Line, P>) -> bool {
+        self.obj.incident(&line.obj)
+    }
+}
+
+impl<Point, Line, P> Join<Normalized<Line, P>> for Normalized<Point, P>
+where
+    Point: Join<Line>,
+    Line: Coord3,
+    P: NormalizationPolicy + Default,
+{
+}
+
+impl<Point, Line, P> ProjectivePlane<Normalized<Line, P>, i64> for Normalized<Point, P>
+where
+    Point: ProjectivePlane<Line, i64> + Coord3,
+    Line: Coord3,
+    P: NormalizationPolicy + Default,
+{
+    #[inline]
+    fn aux(&self) -> Normalized<Line, P> {
+        Normalized::new(self.obj.aux())
+    }
+
+    #[inline]
+    fn dot(&self, line: &Normalized<Line, P>) -> i64 {
+        self.obj.dot(&line.obj)
+    }
+
+    #[inline]
+    fn parametrize(&self, lambda: i64, other: &Self, mu: i64) -> Self {
+        Normalized::new(parametrize_with(
+            &self.obj,
+            lambda,
+            &other.obj,
+            mu,
+            &P::default(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pg_object::{PgLine, PgPoint};
+    use crate::pg_plane::{check_axiom, check_pappus};
+
+    /// The last three points of a chain of harmonic conjugates, each of the last three points.
+    fn chain<P: NormalizationPolicy + Default>(policy: &P, steps: usize) -> [PgPoint; 3] {
+        let mut pts = [[0, 0, 1], [1, 0, 1], [3, 0, 2]].map(PgPoint::new);
+        for _ in 0..steps {
+            let pt_d = harm_conj_with::<_, PgLine, _>(&pts[0], &pts[1], &pts[2], policy);
+            pts = [pts[1].clone(), pts[2].clone(), pt_d];
+        }
+        pts
+    }
+
+    /// The bit length of the largest coordinate.
+    fn bits(pts: &[PgPoint]) -> u32 {
+        pts.iter()
+            .flat_map(|pt| pt.coord)
+            .map(|x| u64::BITS - x.unsigned_abs().leading_zeros())
+            .max()
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn test_policies() {
+        assert_eq!(NoNormalization.apply([2, 4, -6]), [2, 4, -6]);
+        assert_eq!(GcdAlways.apply([-2, 4, -6]), [1, -2, 3]);
+        assert_eq!(GcdThreshold::<3>.apply([-2, 4, -6]), [-2, 4, -6]);
+        assert_eq!(GcdThreshold::<2>.apply([-2, 4, -6]), [1, -2, 3]);
+        assert_eq!(GcdThreshold::<64>.apply([i64::MIN, 0, 0]), [i64::MIN, 0, 0]);
+
+        // the same chain of constructions yields the same points under every policy, with
+        // coordinates that grow fastest without normalization
+        let (none, always, threshold) = (
+            chain(&NoNormalization, 4),
+            chain(&GcdAlways, 4),
+            chain(&GcdThreshold::<16>, 4),
+        );
+        assert_eq!(none, always);
+        assert_eq!(threshold, always);
+        let (none, always, threshold) = (bits(&none), bits(&always), bits(&threshold));
+        assert!(always <= threshold && threshold <= 16 && threshold < none);
+    }
+
+    #[test]
+    fn test_normalized() {
+        type Point = Normalized<PgPoint, GcdAlways>;
+        type Line = Normalized<PgLine, GcdAlways>;
+        let pt_p = Point::new(PgPoint::new([2, 6, 4]));
+        let pt_q = Point::new(PgPoint::new([-4, 2, -2]));
+        let ln_l: Line = pt_p.meet(&pt_q);
+        assert_eq!(ln_l.obj.coord, [5, 3, -7]);
+        check_axiom(&pt_p, &pt_q, &ln_l);
+        let pt_r = pt_p.parametrize(1, &pt_q, 2);
+        assert_eq!(pt_r.obj.coord, [3, -5, 0]);
+        assert_eq!(
+            harm_conj(&pt_p, &pt_q, &pt_r).into_inner(),
+            harm_conj(&pt_p.obj, &pt_q.obj, &pt_r.obj)
+        );
+        let coline_1 = [[0, 0, 1], [1, 0, 1], [3, 0, 1]].map(|c| Point::from(PgPoint::new(c)));
+        let coline_2 = [[0, 1, 1], [2, 1, 1], [5, 1, 1]].map(|c| Point::from(PgPoint::new(c)));
+        assert!(check_pappus(&coline_1, &coline_2));
+    }
+}
@@ -1,13 +1,19 @@
 // Perspective Geometry
 
-use crate::ck_plane::{CayleyKleinPlane, CayleyKleinPlanePrimitive};
+use crate::ck_plane::{
+    is_parallel, is_perpendicular, midpoint, parallel_through, AffineCKPlane, CayleyKleinPlane,
+    CayleyKleinPlanePrimitive,
+};
 use crate::pg_object::{PerspLine, PerspPoint};
-use crate::pg_plane::{ProjectivePlane, ProjectivePlanePrimitive};
-// use crate::pg_object::{plucker_operation, dot};
+use crate::pg_plane::{Join, ProjectivePlane, ProjectivePlanePrimitive};
 
+// The absolute is the pair of complex conjugate points I_RE ± i I_IM on the line at infinity
+// `y = z`, the image of the circular points of the Euclidean plane under a projective
+// transformation. As in the Euclidean plane it is degenerate: the polar of every point is the line
+// at infinity, so the polarity is not an involution.
 static I_RE: PerspPoint = PerspPoint { coord: [0, 1, 1] };
 static I_IM: PerspPoint = PerspPoint { coord: [1, 0, 0] };
-static L_INF: PerspLine = PerspLine { coord: [0, -1, 1] };
+static L_INF: PerspLine = I_IM.meet_const(&I_RE);
 
 /// The code block is implementing the perspective geometry for the point `PerspPoint` in the context of
 /// the `CayleyKleinPlanePrimitive` trait for the line `PerspLine`.
@@ -23,8 +29,10 @@ impl CayleyKleinPlanePrimitive<PerspLine> for PerspPoint {
 impl CayleyKleinPlanePrimitive<PerspPoint> for PerspLine {
     #[inline]
     fn perp(&self) -> PerspPoint {
-        let alpha = I_RE.dot(self); // ???
-        let beta = I_IM.dot(self); // ???
+        // the pole with respect to the line conic `I_RE I_REᵀ + I_IM I_IMᵀ` of the absolute, the
+        // direction perpendicular to the line on the line at infinity
+        let alpha = I_RE.dot(self);
+        let beta = I_IM.dot(self);
         PerspPoint::parametrize(&I_RE, alpha, &I_IM, beta)
     }
 }
@@ -33,6 +41,13 @@ impl CayleyKleinPlane<PerspLine, i64> for PerspPoint {}
 
 impl CayleyKleinPlane<PerspPoint, i64> for PerspLine {}
 
+impl AffineCKPlane<PerspLine, i64> for PerspPoint {
+    #[inline]
+    fn l_inf() -> PerspLine {
+        L_INF.clone()
+    }
+}
+
 impl PerspLine {
     /// The function checks if two perspective lines are parallel.
     ///
@@ -45,13 +60,94 @@ impl PerspLine {
     /// a boolean value.
     #[inline]
     pub fn is_parallel(&self, other: &PerspLine) -> bool {
-        L_INF.dot(&self.meet(other)) == 0
+        is_parallel(self, other)
+    }
+
+    /// The `intersect` function returns the affine point where two perspective lines meet.
+    ///
+    /// Arguments:
+    ///
+    /// * `other`: `other` is a reference to an object of type `PerspLine`.
+    ///
+    /// Returns:
+    ///
+    /// The point of intersection, or `None` if the lines are parallel or coincide, where `meet`
+    /// would return a point on the line at infinity (or the zero vector).
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::{PerspLine, PerspPoint};
+    /// let ln_l = PerspLine::new([1, 0, 0]);
+    /// let ln_m = PerspLine::new([0, 1, 0]);
+    /// assert_eq!(ln_l.intersect(&ln_m), Some(PerspPoint::new([0, 0, 1])));
+    /// // both lines pass through the point [1, 0, 0] on the line at infinity
+    /// let ln_n = PerspLine::new([0, 1, -1]);
+    /// assert!(ln_m.is_parallel(&ln_n));
+    /// assert_eq!(ln_m.intersect(&ln_n), None);
+    /// assert_eq!(ln_m.meet_projective(&ln_n), PerspPoint::new([1, 0, 0]));
+    /// ```
+    #[inline]
+    pub fn intersect(&self, other: &PerspLine) -> Option<PerspPoint> {
+        if self.is_parallel(other) {
+            None
+        } else {
+            Some(self.meet(other))
+        }
+    }
+
+    /// The `meet_projective` function returns the meet of two lines in the projective plane, which
+    /// is a point on the line at infinity for parallel lines. It is the same as `meet`.
+    #[inline]
+    pub fn meet_projective(&self, other: &PerspLine) -> PerspPoint {
+        self.meet(other)
+    }
+
+    /// The function `is_perpendicular` checks if two lines are perpendicular, i.e. if each passes
+    /// through the direction perpendicular to the other. The relation is symmetric.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::{PerspLine, PerspPoint};
+    /// let ln_l = PerspLine::new([1, 2, 3]);
+    /// let ln_m = ln_l.altitude(&PerspPoint::new([2, 0, 1]));
+    /// assert!(ln_l.is_perpendicular(&ln_m));
+    /// assert!(ln_m.is_perpendicular(&ln_l));
+    /// assert!(!ln_l.is_perpendicular(&ln_l));
+    /// ```
+    #[inline]
+    pub fn is_perpendicular(&self, other: &PerspLine) -> bool {
+        is_perpendicular(self, other)
+    }
+
+    /// The `altitude` function returns the line through a point perpendicular to the line.
+    #[inline]
+    pub fn altitude(&self, pt_a: &PerspPoint) -> PerspLine {
+        self.perp().join(pt_a)
+    }
+
+    /// The `parallel_through` function returns the line through a point parallel to the line,
+    /// i.e. meeting it on the line at infinity `y = z`.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::{CayleyKleinPlanePrimitive, PerspLine, PerspPoint};
+    /// let ln_l = PerspLine::new([1, 2, 3]);
+    /// let ln_p = ln_l.parallel_through(&PerspPoint::new([2, 0, 1]));
+    /// assert!(ln_p.is_parallel(&ln_l));
+    /// // parallel lines have the same perpendicular direction
+    /// assert_eq!(ln_p.perp(), ln_l.perp());
+    /// ```
+    #[inline]
+    pub fn parallel_through(&self, pt_p: &PerspPoint) -> PerspLine {
+        parallel_through(pt_p, self)
     }
 }
 
 impl PerspPoint {
-    /// The `midpoint` function calculates the midpoint between two `PerspPoint` objects using the dot
-    /// product and Plücker coordinates.
+    /// The `midpoint` function calculates the midpoint between two `PerspPoint` objects.
     ///
     /// Arguments:
     ///
@@ -60,10 +156,20 @@ impl PerspPoint {
     /// Returns:
     ///
     /// The `midpoint` function returns a `PerspPoint` object.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::{harm_conj, Join, PerspLine, PerspPoint, ProjectivePlanePrimitive};
+    /// let pt_a = PerspPoint::new([0, 0, 1]);
+    /// let pt_b = PerspPoint::new([2, 0, 1]);
+    /// let pt_m = pt_a.midpoint(&pt_b);
+    /// // the midpoint is the harmonic conjugate of the point at infinity of the segment
+    /// let pt_inf = pt_a.join(&pt_b).meet(&PerspLine::new([0, -1, 1]));
+    /// assert_eq!(harm_conj(&pt_a, &pt_b, &pt_inf), pt_m);
+    /// ```
     #[inline]
     pub fn midpoint(&self, other: &PerspPoint) -> PerspPoint {
-        let alpha = L_INF.dot(other);
-        let beta = L_INF.dot(self);
-        PerspPoint::parametrize(self, alpha, other, beta)
+        midpoint(self, other)
     }
 }
@@ -0,0 +1,794 @@
+// The projective line P^1: points with two homogeneous coordinates and their projectivities
+
+use crate::error::GeometryError;
+use crate::mat3::checked_cross;
+use crate::pg_object::{det3_is_zero, plucker_operation, Coord3};
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use fractions::Fraction;
+use num_integer::{Integer, Roots};
+
+/// Divide a nonzero vector by the gcd of its entries and narrow it to i64.
+fn reduce2(v: [i128; 2]) -> Result<[i64; 2], GeometryError> {
+    let common = v[0].gcd(&v[1]).max(1);
+    let narrow = |x: i128| i64::try_from(x / common).map_err(|_| GeometryError::Overflow);
+    Ok([narrow(v[0])?, narrow(v[1])?])
+}
+
+/// The bracket `det(p, q) = p₀ q₁ - p₁ q₀`, which vanishes exactly when the points coincide.
+#[inline]
+fn bracket(pt_p: &Pg1Point, pt_q: &Pg1Point) -> i128 {
+    let ([p0, p1], [q0, q1]) = (pt_p.coord.map(i128::from), pt_q.coord.map(i128::from));
+    p0 * q1 - p1 * q0
+}
+
+/// The `Pg1Point` struct is a point `[x : y]` of the projective line, with equality up to a
+/// nonzero scalar factor. The affine point `x` is `[x : 1]`, and `[1 : 0]` is the point at
+/// infinity. The zero vector does not represent a point.
+#[derive(Debug, Clone, Copy)]
+pub struct Pg1Point {
+    /// Homogeneous coordinate
+    pub coord: [i64; 2],
+}
+
+impl Pg1Point {
+    /// The point at infinity `[1 : 0]`.
+    pub const INFINITY: Pg1Point = Pg1Point { coord: [1, 0] };
+
+    /// Create a new point with the given coordinates.
+    #[inline]
+    pub const fn new(coord: [i64; 2]) -> Self {
+        Self { coord }
+    }
+
+    /// The affine point `x`, i.e. `[x : 1]`.
+    #[inline]
+    pub const fn from_affine(x: i64) -> Self {
+        Self::new([x, 1])
+    }
+
+    /// The affine point `x = p / q` as `[p : q]`.
+    #[inline]
+    pub fn from_fraction(x: Fraction<i64>) -> Self {
+        Self::new([x.numer, x.denom])
+    }
+
+    /// The affine coordinate `x / y`, or `None` for the point at infinity.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use fractions::Fraction;
+    /// use projgeom_rs::pg1::Pg1Point;
+    /// assert_eq!(Pg1Point::new([3, -6]).to_affine(), Some(Fraction::new(-1, 2)));
+    /// assert_eq!(Pg1Point::INFINITY.to_affine(), None);
+    /// ```
+    pub fn to_affine(&self) -> Option<Fraction<i64>> {
+        let [x, y] = self.coord;
+        (y != 0).then(|| Fraction::new(x, y))
+    }
+
+    /// Return a copy with reduced coordinates whose first nonzero entry is positive.
+    pub fn normalized(&self) -> Self {
+        let [x, y] = self.coord;
+        let common = x.gcd(&y).max(1);
+        let sign = if x < 0 || (x == 0 && y < 0) { -1 } else { 1 };
+        Self::new([sign * (x / common), sign * (y / common)])
+    }
+}
+
+impl PartialEq for Pg1Point {
+    /// Check if two points are equal. The products are evaluated in i128.
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        bracket(self, other) == 0
+    }
+}
+
+impl Eq for Pg1Point {}
+
+impl Hash for Pg1Point {
+    /// Hash the normalized coordinates, so that equal points hash alike.
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.normalized().coord.hash(state);
+    }
+}
+
+impl fmt::Display for Pg1Point {
+    /// Format as homogeneous coordinates, e.g. `[1 : 3]`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [x, y] = self.coord;
+        write!(f, "[{} : {}]", x, y)
+    }
+}
+
+/// The function `cross_ratio` computes the cross ratio `(A, B; C, D) = [AC] [BD] / ([AD] [BC])`
+/// of four points of the projective line, where `[PQ]` is the determinant of the coordinates.
+///
+/// Returns:
+///
+/// The function `cross_ratio` returns the cross ratio as a point of the projective line, so that
+/// it is `[1 : 0]` when `D = A` or `C = B`. It returns `GeometryError::NotInGeneralPosition` if
+/// three of the points coincide, or `GeometryError::Overflow`.
+///
+/// Examples:
+///
+/// ```rust
+/// use fractions::Fraction;
+/// use projgeom_rs::pg1::{cross_ratio, Pg1Point};
+/// let [pt_a, pt_b, pt_c, pt_d] = [0, 2, 1, 4].map(Pg1Point::from_affine);
+/// let ratio = cross_ratio(&pt_a, &pt_b, &pt_c, &pt_d).unwrap();
+/// assert_eq!(ratio.to_affine(), Some(Fraction::new(-1, 2)));
+/// // harmonic points have the cross ratio -1
+/// let pt_h = Pg1Point::INFINITY;
+/// assert_eq!(cross_ratio(&pt_a, &pt_b, &pt_c, &pt_h), Ok(Pg1Point::from_affine(-1)));
+/// ```
+pub fn cross_ratio(
+    pt_a: &Pg1Point,
+    pt_b: &Pg1Point,
+    pt_c: &Pg1Point,
+    pt_d: &Pg1Point,
+) -> Result<Pg1Point, GeometryError> {
+    let numer = bracket(pt_a, pt_c).checked_mul(bracket(pt_b, pt_d));
+    let denom = bracket(pt_a, pt_d).checked_mul(bracket(pt_b, pt_c));
+    match (numer, denom) {
+        (Some(0), Some(0)) => Err(GeometryError::NotInGeneralPosition),
+        (Some(numer), Some(denom)) => reduce2([numer, denom]).map(Pg1Point::new),
+        _ => Err(GeometryError::Overflow),
+    }
+}
+
+type Matrix2 = [[i64; 2]; 2];
+
+/// Product of 2x2 matrices in i128.
+fn mat_mul2(m: &[[i128; 2]; 2], n: &[[i128; 2]; 2]) -> Option<[[i128; 2]; 2]> {
+    let entry = |i: usize, j: usize| {
+        m[i][0]
+            .checked_mul(n[0][j])?
+            .checked_add(m[i][1].checked_mul(n[1][j])?)
+    };
+    Some([[entry(0, 0)?, entry(0, 1)?], [entry(1, 0)?, entry(1, 1)?]])
+}
+
+/// The `Projectivity` struct is a projective transformation `x ↦ M x` of the projective line,
+/// an element of PGL(2), i.e. a Möbius transformation `x ↦ (a x + b) / (c x + d)` with integer
+/// coefficients. As for `Transform`, the matrix is reduced, so that two projectivities compare
+/// equal exactly when they act identically.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Projectivity {
+    /// Reduced nonsingular matrix `[[a, b], [c, d]]`
+    pub mat: Matrix2,
+}
+
+/// The `FixedPoints` enum lists the fixed points of a `Projectivity`: the real ones are the roots
+/// of the quadratic `c x² + (d - a) x y - b y²`, whose discriminant is `(a - d)² + 4 b c`. The
+/// points are those of P^1, or of a line of the plane through a `Chart`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixedPoints<Point = Pg1Point> {
+    /// Every point is fixed by the identity.
+    All,
+    /// Two distinct rational fixed points.
+    Two(Point, Point),
+    /// A single double fixed point.
+    One(Point),
+    /// Two distinct real fixed points with irrational coordinates.
+    Irrational,
+    /// No real fixed points.
+    None,
+}
+
+impl<Point> FixedPoints<Point> {
+    /// Map the fixed points, e.g. from P^1 onto a line of the plane.
+    pub fn map<Other>(self, mut f: impl FnMut(Point) -> Other) -> FixedPoints<Other> {
+        match self {
+            FixedPoints::All => FixedPoints::All,
+            FixedPoints::Two(pt_p, pt_q) => FixedPoints::Two(f(pt_p), f(pt_q)),
+            FixedPoints::One(pt_p) => FixedPoints::One(f(pt_p)),
+            FixedPoints::Irrational => FixedPoints::Irrational,
+            FixedPoints::None => FixedPoints::None,
+        }
+    }
+
+    /// The kind of a projectivity with these fixed points.
+    pub fn kind(&self) -> ProjectivityKind {
+        match self {
+            FixedPoints::All => ProjectivityKind::Identity,
+            FixedPoints::Two(..) | FixedPoints::Irrational => ProjectivityKind::Hyperbolic,
+            FixedPoints::One(_) => ProjectivityKind::Parabolic,
+            FixedPoints::None => ProjectivityKind::Elliptic,
+        }
+    }
+}
+
+/// The `ProjectivityKind` enum classifies the projectivities of a line other than the identity by
+/// their real fixed points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProjectivityKind {
+    /// The identity, which fixes every point.
+    Identity,
+    /// Two distinct real fixed points, e.g. a scaling `x ↦ k x`.
+    Hyperbolic,
+    /// A single double fixed point, e.g. a translation `x ↦ x + 1`.
+    Parabolic,
+    /// No real fixed points, e.g. the rotation `x ↦ -1 / x`.
+    Elliptic,
+}
+
+impl Projectivity {
+    /// The identity projectivity.
+    pub const IDENTITY: Projectivity = Projectivity {
+        mat: [[1, 0], [0, 1]],
+    };
+
+    /// Create a projectivity from a matrix.
+    ///
+    /// Returns:
+    ///
+    /// The function `new` returns `GeometryError::SingularTransform` if the matrix is singular.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::pg1::Projectivity;
+    /// assert_eq!(Projectivity::new([[3, 0], [0, 3]]), Ok(Projectivity::IDENTITY));
+    /// assert!(Projectivity::new([[1, 2], [2, 4]]).is_err());
+    /// ```
+    pub fn new(mat: Matrix2) -> Result<Self, GeometryError> {
+        Self::from_wide(mat.map(|row| row.map(i128::from)))
+    }
+
+    /// Create a projectivity from an i128 matrix, reducing it.
+    fn from_wide(mat: [[i128; 2]; 2]) -> Result<Self, GeometryError> {
+        let [[a, b], [c, d]] = mat;
+        let det = a
+            .checked_mul(d)
+            .zip(b.checked_mul(c))
+            .and_then(|(p, q)| p.checked_sub(q))
+            .ok_or(GeometryError::Overflow)?;
+        if det == 0 {
+            return Err(GeometryError::SingularTransform);
+        }
+        let common = [a, b, c, d].iter().fold(0_i128, |g, x| g.gcd(x));
+        let first = [a, b, c, d].into_iter().find(|x| *x != 0).unwrap_or(1);
+        let common = if first < 0 { -common } else { common };
+        let narrow = |x: i128| i64::try_from(x / common).map_err(|_| GeometryError::Overflow);
+        Ok(Self {
+            mat: [[narrow(a)?, narrow(b)?], [narrow(c)?, narrow(d)?]],
+        })
+    }
+
+    /// The matrix widened to i128.
+    #[inline]
+    fn wide(&self) -> [[i128; 2]; 2] {
+        self.mat.map(|row| row.map(i128::from))
+    }
+
+    /// The matrix whose columns are `p`, `q` scaled so that their sum is `r`; it maps `[1 : 0]`,
+    /// `[0 : 1]` and `[1 : 1]` to `p`, `q` and `r`.
+    fn from_frame([pt_p, pt_q, pt_r]: &[Pg1Point; 3]) -> Result<[[i128; 2]; 2], GeometryError> {
+        let (lambda, mu) = (bracket(pt_r, pt_q), bracket(pt_p, pt_r));
+        if lambda == 0 || mu == 0 || bracket(pt_p, pt_q) == 0 {
+            return Err(GeometryError::NotInGeneralPosition);
+        }
+        let (p, q) = (pt_p.coord.map(i128::from), pt_q.coord.map(i128::from));
+        let entry = |x: i128, y: i128| {
+            x.checked_mul(lambda)
+                .zip(y.checked_mul(mu))
+                .ok_or(GeometryError::Overflow)
+        };
+        let (m00, m01) = entry(p[0], q[0])?;
+        let (m10, m11) = entry(p[1], q[1])?;
+        Ok([[m00, m01], [m10, m11]])
+    }
+
+    /// The function `from_points` constructs the unique projectivity mapping three distinct points
+    /// to three distinct points, in order (the fundamental theorem of projective geometry on a
+    /// line).
+    ///
+    /// Returns:
+    ///
+    /// The function `from_points` returns `GeometryError::NotInGeneralPosition` if two of the
+    /// source or two of the target points coincide, or `GeometryError::Overflow`.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::pg1::{Pg1Point, Projectivity};
+    /// // x ↦ 1 / x swaps 0 and ∞ and fixes 1
+    /// let src = [Pg1Point::from_affine(0), Pg1Point::INFINITY, Pg1Point::from_affine(1)];
+    /// let dst = [Pg1Point::INFINITY, Pg1Point::from_affine(0), Pg1Point::from_affine(1)];
+    /// let inv = Projectivity::from_points(&src, &dst).unwrap();
+    /// assert_eq!(inv.mat, [[0, 1], [1, 0]]);
+    /// assert_eq!(inv.apply(&Pg1Point::from_affine(4)), Pg1Point::new([1, 4]));
+    /// ```
+    pub fn from_points(src: &[Pg1Point; 3], dst: &[Pg1Point; 3]) -> Result<Self, GeometryError> {
+        let [[a, b], [c, d]] = Self::from_frame(src)?;
+        // the adjugate inverts up to a scalar factor
+        let inverse = [[d, -b], [-c, a]];
+        let product = mat_mul2(&Self::from_frame(dst)?, &inverse).ok_or(GeometryError::Overflow)?;
+        Self::from_wide(product)
+    }
+
+    /// The function `involution` constructs the unique involution swapping `pair_1.0` with
+    /// `pair_1.1` and `pair_2.0` with `pair_2.1`. A pair of equal points is a fixed point, so two
+    /// such pairs give the harmonic involution with these fixed points.
+    ///
+    /// The pairs of an involution are the conjugate points `α x x' + β (x y' + x' y) + γ y y' = 0`
+    /// of a binary quadratic form, which two pairs determine up to a factor.
+    ///
+    /// Returns:
+    ///
+    /// The function `involution` returns `GeometryError::NotInGeneralPosition` if the pairs do
+    /// not determine an involution, e.g. if they are equal, or `GeometryError::Overflow`.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::pg1::{Pg1Point, Projectivity};
+    /// let [pt_0, pt_1, pt_2, pt_3] = [0, 1, 2, 3].map(Pg1Point::from_affine);
+    /// let inv = Projectivity::involution((&pt_0, &pt_3), (&pt_1, &pt_2)).unwrap();
+    /// assert!(inv.is_involution());
+    /// assert_eq!(inv.apply(&pt_3), pt_0);
+    /// // the fixed points 0 and ∞ give the reflection x ↦ -x
+    /// let inf = Pg1Point::INFINITY;
+    /// let refl = Projectivity::involution((&pt_0, &pt_0), (&inf, &inf)).unwrap();
+    /// assert_eq!(refl.mat, [[1, 0], [0, -1]]);
+    /// ```
+    pub fn involution(
+        pair_1: (&Pg1Point, &Pg1Point),
+        pair_2: (&Pg1Point, &Pg1Point),
+    ) -> Result<Self, GeometryError> {
+        let equation = |(pt_p, pt_q): (&Pg1Point, &Pg1Point)| {
+            let ([x, y], [u, v]) = (pt_p.coord.map(i128::from), pt_q.coord.map(i128::from));
+            [x * u, x * v + u * y, y * v]
+        };
+        let [alpha, beta, gamma] =
+            checked_cross(&equation(pair_1), &equation(pair_2)).ok_or(GeometryError::Overflow)?;
+        // the map to the conjugate point, J Q for the rotation J = [[0, -1], [1, 0]]
+        match Self::from_wide([[-beta, -gamma], [alpha, beta]]) {
+            Err(GeometryError::SingularTransform) => Err(GeometryError::NotInGeneralPosition),
+            result => result,
+        }
+    }
+
+    /// The function `apply` maps a point by the projectivity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a coordinate of the image overflows i64 after dividing out common factors.
+    pub fn apply(&self, pt: &Pg1Point) -> Pg1Point {
+        let [[a, b], [c, d]] = self.wide();
+        let [x, y] = pt.coord.map(i128::from);
+        let image = [a * x + b * y, c * x + d * y];
+        Pg1Point::new(reduce2(image).expect("coordinate overflows i64"))
+    }
+
+    /// The function `compose` returns the projectivity that applies `other` first and then `self`.
+    pub fn compose(&self, other: &Projectivity) -> Result<Projectivity, GeometryError> {
+        let product = mat_mul2(&self.wide(), &other.wide()).ok_or(GeometryError::Overflow)?;
+        Self::from_wide(product)
+    }
+
+    /// The function `inverse` returns the inverse projectivity, given by the adjugate matrix.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::pg1::Projectivity;
+    /// let proj = Projectivity::new([[2, 1], [1, 1]]).unwrap();
+    /// assert_eq!(proj.compose(&proj.inverse()), Ok(Projectivity::IDENTITY));
+    /// ```
+    pub fn inverse(&self) -> Projectivity {
+        let [[a, b], [c, d]] = self.mat;
+        Self::from_wide([[d, -b], [-c, a]].map(|row| row.map(i128::from)))
+            .expect("the adjugate of a reduced matrix is reduced and nonsingular")
+    }
+
+    /// The function `is_involution` checks if the projectivity is an involution, i.e. not the
+    /// identity and its own inverse; this is the case exactly when the trace vanishes.
+    #[inline]
+    pub fn is_involution(&self) -> bool {
+        self.mat[0][0] as i128 + self.mat[1][1] as i128 == 0
+    }
+
+    /// The function `fixed_points` finds the fixed points of the projectivity exactly; see
+    /// `FixedPoints`.
+    ///
+    /// Returns:
+    ///
+    /// The function `fixed_points` returns `GeometryError::Overflow` if the discriminant overflows
+    /// i128 or a rational fixed point does not fit into i64.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::pg1::{FixedPoints, Pg1Point, Projectivity};
+    /// // x ↦ 4 / x fixes ±2
+    /// let proj = Projectivity::new([[0, 4], [1, 0]]).unwrap();
+    /// assert_eq!(
+    ///     proj.fixed_points(),
+    ///     Ok(FixedPoints::Two(Pg1Point::from_affine(-2), Pg1Point::from_affine(2)))
+    /// );
+    /// // the translation x ↦ x + 1 fixes only ∞, and x ↦ -1 / x fixes no real point
+    /// let shift = Projectivity::new([[1, 1], [0, 1]]).unwrap();
+    /// assert_eq!(shift.fixed_points(), Ok(FixedPoints::One(Pg1Point::INFINITY)));
+    /// let rotation = Projectivity::new([[0, -1], [1, 0]]).unwrap();
+    /// assert_eq!(rotation.fixed_points(), Ok(FixedPoints::None));
+    /// let proj = Projectivity::new([[0, 2], [1, 0]]).unwrap();
+    /// assert_eq!(proj.fixed_points(), Ok(FixedPoints::Irrational));
+    /// ```
+    pub fn fixed_points(&self) -> Result<FixedPoints, GeometryError> {
+        if *self == Self::IDENTITY {
+            return Ok(FixedPoints::All);
+        }
+        let [[a, b], [c, d]] = self.wide();
+        let point = |x: i128, y: i128| reduce2([x, y]).map(Pg1Point::new);
+        let [pt_p, pt_q] = if c != 0 {
+            if self.discriminant_sign() == Ordering::Less {
+                return Ok(FixedPoints::None);
+            }
+            let disc = self.discriminant().ok_or(GeometryError::Overflow)?;
+            let root = disc.sqrt();
+            if root * root != disc {
+                return Ok(FixedPoints::Irrational);
+            }
+            [point(a - d - root, 2 * c)?, point(a - d + root, 2 * c)?]
+        } else {
+            // ∞ is fixed, and the other fixed point solves (d - a) x = b y
+            [Pg1Point::INFINITY, point(b, d - a)?]
+        };
+        if pt_p == pt_q {
+            Ok(FixedPoints::One(pt_p))
+        } else {
+            Ok(FixedPoints::Two(pt_p, pt_q))
+        }
+    }
+
+    /// The discriminant `(a - d)² + 4 b c = tr² - 4 det` of the characteristic equation, or `None`
+    /// if it overflows i128.
+    #[inline]
+    fn discriminant(&self) -> Option<i128> {
+        let [[a, b], [c, d]] = self.wide();
+        (a - d)
+            .checked_mul(a - d)?
+            .checked_add(b.checked_mul(c)?.checked_mul(4)?)
+    }
+
+    /// The sign of the discriminant, found by comparing `(a - d)²` with `-4 b c` in u128.
+    fn discriminant_sign(&self) -> Ordering {
+        let [[a, b], [c, d]] = self.wide();
+        let square = (a - d).unsigned_abs().pow(2);
+        // |b c| < 2^126 for a negative product of i64 entries
+        match (b * c).cmp(&0) {
+            Ordering::Less => square.cmp(&(4 * (b * c).unsigned_abs())),
+            Ordering::Equal => square.cmp(&0),
+            Ordering::Greater => Ordering::Greater,
+        }
+    }
+
+    /// The function `kind` classifies the projectivity by the sign of the discriminant of its
+    /// characteristic equation, whether or not the fixed points are rational.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::pg1::{Projectivity, ProjectivityKind};
+    /// let proj = Projectivity::new([[0, 2], [1, 0]]).unwrap();
+    /// assert_eq!(proj.kind(), ProjectivityKind::Hyperbolic);
+    /// let proj = Projectivity::new([[1, -1], [1, 1]]).unwrap();
+    /// assert_eq!(proj.kind(), ProjectivityKind::Elliptic);
+    /// ```
+    pub fn kind(&self) -> ProjectivityKind {
+        match self.discriminant_sign() {
+            _ if *self == Self::IDENTITY => ProjectivityKind::Identity,
+            Ordering::Equal => ProjectivityKind::Parabolic,
+            Ordering::Greater => ProjectivityKind::Hyperbolic,
+            Ordering::Less => ProjectivityKind::Elliptic,
+        }
+    }
+}
+
+/// The `Chart` struct identifies the points of a line of the plane (or, dually, the lines through
+/// a point) with the projective line: the point `λ A + μ B` of the line through the base points
+/// `A` and `B` has the coordinates `[λ : μ]`. Ranges, pencils and the projectivities between
+/// them can so be handled as objects of P^1.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::pg1::{Chart, Pg1Point};
+/// use projgeom_rs::PgPoint;
+/// let chart = Chart::new(PgPoint::new([0, 0, 1]), PgPoint::new([1, 0, 0])).unwrap();
+/// let pt_p = PgPoint::new([3, 0, 1]);
+/// assert_eq!(chart.coordinate(&pt_p), Ok(Pg1Point::new([1, 3])));
+/// assert_eq!(chart.point(&Pg1Point::new([1, 3])), pt_p);
+/// assert!(chart.coordinate(&PgPoint::new([3, 1, 1])).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chart<Point> {
+    base: [Point; 2],
+}
+
+impl<Point: Coord3 + Eq> Chart<Point> {
+    /// Create a chart with two distinct base points, which get the coordinates `[1 : 0]` and
+    /// `[0 : 1]`. Fails with `GeometryError::NotInGeneralPosition` if they coincide.
+    pub fn new(pt_a: Point, pt_b: Point) -> Result<Self, GeometryError> {
+        if pt_a == pt_b {
+            return Err(GeometryError::NotInGeneralPosition);
+        }
+        Ok(Self { base: [pt_a, pt_b] })
+    }
+
+    /// The two base points.
+    #[inline]
+    pub fn base(&self) -> &[Point; 2] {
+        &self.base
+    }
+
+    /// The function `coordinate` returns the coordinates `[λ : μ]` of a point `λ A + μ B` of the
+    /// line through the base points, computed from `P × B = λ (A × B)` and `A × P = μ (A × B)`.
+    ///
+    /// Returns:
+    ///
+    /// The function `coordinate` returns `GeometryError::NotOnLine` if the point does not lie on
+    /// the line through the base points, or `GeometryError::Overflow`.
+    pub fn coordinate(&self, pt: &Point) -> Result<Pg1Point, GeometryError> {
+        let coords = [&self.base[0], &self.base[1], pt].map(|obj| obj.coords());
+        // the determinant vanishes exactly, without overflow, when P lies on the line
+        if !det3_is_zero(coords[0], coords[1], coords[2]) || *coords[2] == [0, 0, 0] {
+            return Err(GeometryError::NotOnLine);
+        }
+        let [a, b, p] = coords.map(|v| v.map(i128::from));
+        let overflow = GeometryError::Overflow;
+        let ab = checked_cross(&a, &b).ok_or(overflow)?;
+        let pb = checked_cross(&p, &b).ok_or(overflow)?;
+        let ap = checked_cross(&a, &p).ok_or(overflow)?;
+        // P × B and A × P are then multiples of A × B
+        let k = (0..3).find(|&k| ab[k] != 0).unwrap_or(0);
+        reduce2([pb[k], ap[k]]).map(Pg1Point::new)
+    }
+
+    /// The function `point` returns the point `λ A + μ B` with the coordinates `[λ : μ]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a coordinate overflows i64.
+    pub fn point(&self, pt: &Pg1Point) -> Point {
+        let [lambda, mu] = pt.coord;
+        let [a, b] = &self.base;
+        Point::from_coord(plucker_operation(lambda, a.coords(), mu, b.coords()))
+    }
+
+    /// The function `projectivity` returns the projectivity of the line onto itself mapping
+    /// three distinct points `src[i]` to `dst[i]`, in the coordinates of the chart.
+    ///
+    /// Returns:
+    ///
+    /// The function `projectivity` returns `GeometryError::NotOnLine` if a point does not lie on
+    /// the line, and otherwise the errors of `Projectivity::from_points`.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::pg1::{Chart, FixedPoints, ProjectivityKind};
+    /// use projgeom_rs::PgPoint;
+    /// let chart = Chart::new(PgPoint::new([0, 1, 1]), PgPoint::new([2, 1, 1])).unwrap();
+    /// // the reflection of the line y = 1 in the point (1, 1)
+    /// let src = [[0, 1, 1], [2, 1, 1], [3, 1, 1]].map(PgPoint::new);
+    /// let dst = [[2, 1, 1], [0, 1, 1], [-1, 1, 1]].map(PgPoint::new);
+    /// let proj = chart.projectivity(&src, &dst).unwrap();
+    /// assert!(proj.is_involution());
+    /// assert_eq!(
+    ///     chart.fixed_points(&proj),
+    ///     Ok(FixedPoints::Two(PgPoint::new([1, 0, 0]), PgPoint::new([1, 1, 1])))
+    /// );
+    /// assert_eq!(proj.kind(), ProjectivityKind::Hyperbolic);
+    /// ```
+    pub fn projectivity(
+        &self,
+        src: &[Point; 3],
+        dst: &[Point; 3],
+    ) -> Result<Projectivity, GeometryError> {
+        let coords = |pts: &[Point; 3]| -> Result<[Pg1Point; 3], GeometryError> {
+            Ok([
+                self.coordinate(&pts[0])?,
+                self.coordinate(&pts[1])?,
+                self.coordinate(&pts[2])?,
+            ])
+        };
+        Projectivity::from_points(&coords(src)?, &coords(dst)?)
+    }
+
+    /// The function `fixed_points` returns the fixed points of a projectivity of the line, given
+    /// in the coordinates of the chart, as points of the line, or the errors of
+    /// `Projectivity::fixed_points`.
+    pub fn fixed_points(&self, proj: &Projectivity) -> Result<FixedPoints<Point>, GeometryError> {
+        Ok(proj.fixed_points()?.map(|pt| self.point(&pt)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pg_object::{PgLine, PgPoint};
+    use crate::pg_plane::{harm_conj, Join};
+
+    #[test]
+    fn test_pg1_point() {
+        let pt_p = Pg1Point::new([-2, 4]);
+        assert_eq!(pt_p, Pg1Point::new([1, -2]));
+        assert_eq!(pt_p.normalized().coord, [1, -2]);
+        assert_ne!(pt_p, Pg1Point::INFINITY);
+        assert_eq!(
+            Pg1Point::new([0, -3]).normalized(),
+            Pg1Point::from_affine(0)
+        );
+        assert_eq!(Pg1Point::new([0, -3]).normalized().coord, [0, 1]);
+        assert_eq!(pt_p.to_string(), "[-2 : 4]");
+        assert_eq!(
+            Pg1Point::from_fraction(Fraction::new(3, 4)).to_affine(),
+            Some(Fraction::new(3, 4))
+        );
+        let [pt_a, pt_b] = [0, 1].map(Pg1Point::from_affine);
+        assert_eq!(
+            cross_ratio(&pt_a, &pt_b, &pt_a, &pt_a),
+            Err(GeometryError::NotInGeneralPosition)
+        );
+        assert_eq!(
+            cross_ratio(&pt_a, &pt_b, &pt_b, &pt_p),
+            Ok(Pg1Point::INFINITY)
+        );
+    }
+
+    #[test]
+    fn test_projectivity() {
+        let src = [2, 3, 5].map(Pg1Point::from_affine);
+        let dst = [
+            Pg1Point::new([1, 2]),
+            Pg1Point::INFINITY,
+            Pg1Point::from_affine(-7),
+        ];
+        let proj = Projectivity::from_points(&src, &dst).unwrap();
+        for (pt_p, pt_q) in src.iter().zip(&dst) {
+            assert_eq!(proj.apply(pt_p), *pt_q);
+        }
+        // the cross ratio is invariant
+        let pt_x = Pg1Point::from_affine(11);
+        let before = cross_ratio(&src[0], &src[1], &src[2], &pt_x).unwrap();
+        let after = cross_ratio(&dst[0], &dst[1], &dst[2], &proj.apply(&pt_x)).unwrap();
+        assert_eq!(before, after);
+
+        let inv = proj.inverse();
+        assert_eq!(inv.compose(&proj), Ok(Projectivity::IDENTITY));
+        assert_eq!(inv.apply(&dst[1]), src[1]);
+        assert!(!proj.is_involution());
+        assert_eq!(
+            Projectivity::from_points(&[src[0], src[0], src[2]], &dst),
+            Err(GeometryError::NotInGeneralPosition)
+        );
+        assert_eq!(Projectivity::IDENTITY.fixed_points(), Ok(FixedPoints::All));
+        assert_eq!(
+            Projectivity::new([[2, 0], [0, 1]]).unwrap().fixed_points(),
+            Ok(FixedPoints::Two(
+                Pg1Point::INFINITY,
+                Pg1Point::from_affine(0)
+            ))
+        );
+        // x ↦ x / (x + 1) has the double fixed point 0
+        let parabolic = Projectivity::new([[1, 0], [1, 1]]).unwrap();
+        assert_eq!(
+            parabolic.fixed_points(),
+            Ok(FixedPoints::One(Pg1Point::from_affine(0)))
+        );
+        // the classification agrees with the fixed points, also for irrational ones
+        for mat in [
+            [[1, 0], [0, 1]],
+            [[0, 2], [1, 0]],
+            [[1, 0], [1, 1]],
+            [[1, -1], [1, 1]],
+        ] {
+            let proj = Projectivity::new(mat).unwrap();
+            assert_eq!(proj.kind(), proj.fixed_points().unwrap().kind());
+        }
+        assert_eq!(parabolic.kind(), ProjectivityKind::Parabolic);
+
+        // near-extreme entries are classified exactly
+        let (max, big) = (i64::MAX, 7_000_000_000_000_000_000);
+        let proj = Projectivity::new([[big, 1], [0, -big]]).unwrap();
+        assert_eq!(proj.kind(), ProjectivityKind::Hyperbolic);
+        assert_eq!(proj.fixed_points(), Err(GeometryError::Overflow));
+        let proj = Projectivity::new([[max, 1], [0, max - 2]]).unwrap();
+        assert_eq!(
+            proj.fixed_points(),
+            Ok(FixedPoints::Two(Pg1Point::INFINITY, Pg1Point::new([1, -2])))
+        );
+        let proj = Projectivity::new([[0, -max], [max, 1]]).unwrap();
+        assert_eq!(proj.kind(), ProjectivityKind::Elliptic);
+        assert_eq!(proj.fixed_points(), Ok(FixedPoints::None));
+        let proj = Projectivity::new([[max, max], [max, i64::MIN]]).unwrap();
+        assert_eq!(proj.kind(), ProjectivityKind::Hyperbolic);
+        assert_eq!(proj.fixed_points(), Err(GeometryError::Overflow));
+        let proj = Projectivity::new([[max, 1 << 62], [-1 << 62, -1]]).unwrap();
+        assert_eq!(proj.kind(), ProjectivityKind::Parabolic);
+        assert_eq!(
+            proj.fixed_points(),
+            Ok(FixedPoints::One(Pg1Point::new([1, -1])))
+        );
+    }
+
+    #[test]
+    fn test_involution() {
+        let [pt_a, pt_b, pt_c, pt_d] = [-1, 4, 2, 7].map(Pg1Point::from_affine);
+        let inv = Projectivity::involution((&pt_a, &pt_b), (&pt_c, &pt_d)).unwrap();
+        assert!(inv.is_involution());
+        assert_eq!(inv.compose(&inv), Ok(Projectivity::IDENTITY));
+        assert_eq!((inv.apply(&pt_a), inv.apply(&pt_d)), (pt_b, pt_c));
+        // an involution with two fixed points maps each point to its harmonic conjugate
+        let [pt_e, pt_f] = [1, 5].map(Pg1Point::from_affine);
+        let harmonic = Projectivity::involution((&pt_e, &pt_e), (&pt_f, &pt_f)).unwrap();
+        let pt_x = Pg1Point::from_affine(2);
+        assert_eq!(
+            cross_ratio(&pt_e, &pt_f, &pt_x, &harmonic.apply(&pt_x)),
+            Ok(Pg1Point::from_affine(-1))
+        );
+        assert_eq!(harmonic.fixed_points(), Ok(FixedPoints::Two(pt_e, pt_f)));
+        assert_eq!(
+            Projectivity::involution((&pt_a, &pt_b), (&pt_b, &pt_a)),
+            Err(GeometryError::NotInGeneralPosition)
+        );
+    }
+
+    #[test]
+    fn test_chart() {
+        let [pt_a, pt_b] = [[1, 0, 1], [5, 0, 1]].map(PgPoint::new);
+        let chart = Chart::new(pt_a.clone(), pt_b.clone()).unwrap();
+        let pt_c = PgPoint::new([2, 0, 1]);
+        let coords = [&pt_a, &pt_b, &pt_c].map(|pt| chart.coordinate(pt).unwrap());
+        assert_eq!(coords[0], Pg1Point::INFINITY);
+        assert_eq!(coords[1], Pg1Point::from_affine(0));
+        assert_eq!(chart.point(&coords[2]), pt_c);
+        // harmonic conjugates in the plane correspond to harmonic conjugates on the line
+        let pt_d = harm_conj(&pt_a, &pt_b, &pt_c);
+        let coord_d = chart.coordinate(&pt_d).unwrap();
+        assert_eq!(
+            cross_ratio(&coords[0], &coords[1], &coords[2], &coord_d),
+            Ok(Pg1Point::from_affine(-1))
+        );
+
+        // a pencil of lines through a point
+        let ln_l = PgLine::new([1, 1, -2]);
+        let ln_m = PgLine::new([1, -1, 0]);
+        let pencil = Chart::new(ln_l.clone(), ln_m.clone()).unwrap();
+        let ln_n = PgPoint::new([1, 1, 1]).join(&PgPoint::new([0, 1, 1]));
+        assert_eq!(
+            pencil.coordinate(&ln_n).map(|pt| pencil.point(&pt)),
+            Ok(ln_n)
+        );
+        assert_eq!(
+            Chart::new(pt_a.clone(), PgPoint::new([2, 0, 2])),
+            Err(GeometryError::NotInGeneralPosition)
+        );
+
+        // the incidence test is exact for large coordinates
+        let big = 1_i64 << 40;
+        let chart = Chart::new(PgPoint::new([big, 1, 0]), PgPoint::new([1, big, 1])).unwrap();
+        let pt_p = PgPoint::new([big + 1, big + 1, 1]);
+        assert_eq!(chart.coordinate(&pt_p), Ok(Pg1Point::new([1, 1])));
+        assert_eq!(
+            chart.coordinate(&PgPoint::new([big + 1, big + 1, 2])),
+            Err(GeometryError::NotOnLine)
+        );
+        let max = i64::MAX;
+        let chart = Chart::new(PgPoint::new([max, 1, 0]), PgPoint::new([0, 1, max])).unwrap();
+        assert_eq!(
+            chart.coordinate(&PgPoint::new([max, 2, max])),
+            Ok(Pg1Point::new([1, 1]))
+        );
+        assert_eq!(
+            chart.coordinate(&PgPoint::new([max, 2, max - 1])),
+            Err(GeometryError::NotOnLine)
+        );
+    }
+}
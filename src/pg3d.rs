@@ -0,0 +1,420 @@
+// Points, planes and lines of the projective 3-space
+
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use num_integer::Integer;
+
+/// The `ProjectiveSpacePrimitive` trait is the 3D counterpart of `ProjectivePlanePrimitive`.
+/// Points and planes are dual to each other; two distinct points span a line and two distinct
+/// planes cut a line, so `meet` returns a `PgLine3` for both.
+pub trait ProjectiveSpacePrimitive<Dual>: Eq {
+    fn meet(&self, rhs: &Self) -> PgLine3; // join or meet
+    fn incident(&self, rhs: &Dual) -> bool;
+}
+
+/// Narrow an exact i128 result back to i64, panicking on overflow.
+#[inline]
+fn narrow<const N: usize>(v: [i128; N]) -> [i64; N] {
+    v.map(|x| i64::try_from(x).expect("coordinate overflows i64"))
+}
+
+/// Check whether two vectors are proportional, i.e. every 2x2 minor vanishes.
+#[inline]
+fn proportional<const N: usize>(a: &[i64; N], b: &[i64; N]) -> bool {
+    (0..N).all(|i| (i + 1..N).all(|j| a[i] as i128 * b[j] as i128 == a[j] as i128 * b[i] as i128))
+}
+
+/// Divide by the common factor and make the first nonzero entry positive.
+#[inline]
+fn normalize<const N: usize>(coord: &[i64; N]) -> [i128; N] {
+    let v = coord.map(|x| x as i128);
+    let g = v.iter().fold(0_i128, |g, x| g.gcd(x));
+    if g == 0 {
+        return v;
+    }
+    let sign = v.iter().find(|x| **x != 0).map_or(1, |x| x.signum());
+    v.map(|x| x / g * sign)
+}
+
+/// Antisymmetric 4x4 matrix of a 6-vector `[l01, l02, l03, l23, l31, l12]`.
+#[inline]
+fn plucker_matrix(l: &[i64; 6]) -> [[i128; 4]; 4] {
+    let [l01, l02, l03, l23, l31, l12] = l.map(|x| x as i128);
+    [
+        [0, l01, l02, l03],
+        [-l01, 0, l12, -l31],
+        [-l02, -l12, 0, l23],
+        [-l03, l31, -l23, 0],
+    ]
+}
+
+#[inline]
+fn mat_vec(m: &[[i128; 4]; 4], v: &[i64; 4]) -> [i128; 4] {
+    m.map(|row| (0..4).map(|j| row[j] * v[j] as i128).sum())
+}
+
+/// Plücker coordinates `[p01, p02, p03, p23, p31, p12]` of the 2-vector `p ∧ q`.
+#[inline]
+fn wedge(p: &[i64; 4], q: &[i64; 4]) -> [i64; 6] {
+    let m = |i: usize, j: usize| p[i] as i128 * q[j] as i128 - p[j] as i128 * q[i] as i128;
+    narrow([m(0, 1), m(0, 2), m(0, 3), m(2, 3), m(3, 1), m(1, 2)])
+}
+
+macro_rules! define_pg3d_object {
+    (impl $object:ident, $n:expr, $doc:expr) => {
+        #[doc = $doc]
+        ///
+        /// Equality, hashing and ordering are all up to a nonzero scalar factor.
+        #[derive(Debug, Clone)]
+        pub struct $object {
+            /// Homogeneous coordinate
+            pub coord: [i64; $n],
+        }
+
+        impl $object {
+            /// Create a new object with the given coordinates.
+            #[inline]
+            pub const fn new(coord: [i64; $n]) -> Self {
+                Self { coord }
+            }
+
+            /// Return a copy with reduced coordinates whose first nonzero entry is positive.
+            #[inline]
+            pub fn normalized(&self) -> Self {
+                Self::new(narrow(normalize(&self.coord)))
+            }
+        }
+
+        impl PartialEq for $object {
+            #[inline]
+            fn eq(&self, other: &$object) -> bool {
+                proportional(&self.coord, &other.coord)
+            }
+        }
+        impl Eq for $object {}
+
+        impl Hash for $object {
+            /// Hash the normalized coordinates, so that equal objects hash alike.
+            #[inline]
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                normalize(&self.coord).hash(state);
+            }
+        }
+
+        impl PartialOrd for $object {
+            #[inline]
+            fn partial_cmp(&self, other: &$object) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for $object {
+            /// Compare the normalized coordinates lexicographically.
+            #[inline]
+            fn cmp(&self, other: &$object) -> Ordering {
+                normalize(&self.coord).cmp(&normalize(&other.coord))
+            }
+        }
+
+        impl fmt::Display for $object {
+            /// Format as homogeneous coordinates, e.g. `[1 : 0 : 2 : 1]`.
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "[")?;
+                for (i, x) in self.coord.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " : ")?;
+                    }
+                    write!(f, "{}", x)?;
+                }
+                write!(f, "]")
+            }
+        }
+    };
+}
+
+define_pg3d_object!(impl PgPoint3, 4, "A point of the projective 3-space.");
+define_pg3d_object!(impl PgPlane3, 4, "A plane of the projective 3-space.");
+define_pg3d_object!(
+    impl PgLine3,
+    6,
+    "A line of the projective 3-space in Plücker coordinates `[l01, l02, l03, l23, l31, l12]`."
+);
+
+macro_rules! define_point_or_plane3 {
+    (impl $object:ident, $dual:ident, $line:expr) => {
+        impl $object {
+            /// Return the dot product of Self and `rhs`
+            #[inline]
+            pub fn dot(&self, rhs: &$dual) -> i64 {
+                let sum: i128 = (0..4)
+                    .map(|i| self.coord[i] as i128 * rhs.coord[i] as i128)
+                    .sum();
+                i64::try_from(sum).expect("dot product overflows i64")
+            }
+        }
+
+        impl ProjectiveSpacePrimitive<$dual> for $object {
+            #[inline]
+            fn incident(&self, rhs: &$dual) -> bool {
+                self.dot(rhs) == 0
+            }
+
+            #[inline]
+            fn meet(&self, rhs: &Self) -> PgLine3 {
+                $line(PgLine3::new(wedge(&self.coord, &rhs.coord)))
+            }
+        }
+    };
+}
+
+define_point_or_plane3!(impl PgPoint3, PgPlane3, core::convert::identity);
+// `a ∧ b` of two planes gives the dual coordinates of their common line
+define_point_or_plane3!(impl PgPlane3, PgPoint3, |ln: PgLine3| ln.dual());
+
+impl PgLine3 {
+    /// The function `join` creates the line through two distinct points.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::pg3d::{PgLine3, PgPoint3};
+    /// let ln = PgLine3::join(&PgPoint3::new([1, 0, 0, 0]), &PgPoint3::new([0, 1, 0, 0]));
+    /// assert_eq!(ln, PgLine3::new([1, 0, 0, 0, 0, 0]));
+    /// ```
+    #[inline]
+    pub fn join(pt_p: &PgPoint3, pt_q: &PgPoint3) -> PgLine3 {
+        pt_p.meet(pt_q)
+    }
+
+    /// The function `intersect` creates the line cut by two distinct planes.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::pg3d::{PgLine3, PgPlane3, PgPoint3};
+    /// // the planes x2 = 0 and x3 = 0 meet in the line through [1:0:0:0] and [0:1:0:0]
+    /// let ln = PgLine3::intersect(&PgPlane3::new([0, 0, 1, 0]), &PgPlane3::new([0, 0, 0, 1]));
+    /// assert_eq!(ln, PgLine3::join(&PgPoint3::new([1, 0, 0, 0]), &PgPoint3::new([0, 1, 0, 0])));
+    /// ```
+    #[inline]
+    pub fn intersect(pi_a: &PgPlane3, pi_b: &PgPlane3) -> PgLine3 {
+        pi_a.meet(pi_b)
+    }
+
+    /// Return the dual line coordinates, i.e. the two halves swapped. This maps the line
+    /// spanned by two points to the line cut by the planes with the same coordinates.
+    #[inline]
+    pub const fn dual(&self) -> PgLine3 {
+        let [l01, l02, l03, l23, l31, l12] = self.coord;
+        PgLine3::new([l23, l31, l12, l01, l02, l03])
+    }
+
+    /// Check the Plücker relation `l01 l23 + l02 l31 + l03 l12 = 0`, which holds exactly for
+    /// the nonzero 6-vectors that represent a line (the Klein quadric).
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        let [l01, l02, l03, l23, l31, l12] = self.coord.map(|x| x as i128);
+        self.coord != [0; 6] && l01 * l23 + l02 * l31 + l03 * l12 == 0
+    }
+
+    /// The function `meet_plane` returns the point where the line meets a plane. The result
+    /// is the zero vector if the line lies in the plane.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::pg3d::{PgLine3, PgPlane3, PgPoint3};
+    /// let ln = PgLine3::join(&PgPoint3::new([1, 0, 0, 1]), &PgPoint3::new([1, 2, 3, 1]));
+    /// let pt = ln.meet_plane(&PgPlane3::new([0, 1, 0, -1]));
+    /// assert_eq!(pt, PgPoint3::new([2, 2, 3, 2]));
+    /// ```
+    #[inline]
+    pub fn meet_plane(&self, plane: &PgPlane3) -> PgPoint3 {
+        PgPoint3::new(narrow(mat_vec(&plucker_matrix(&self.coord), &plane.coord)))
+    }
+
+    /// The function `join_point` returns the plane through the line and a point. The result
+    /// is the zero vector if the point lies on the line.
+    #[inline]
+    pub fn join_point(&self, pt: &PgPoint3) -> PgPlane3 {
+        PgPlane3::new(narrow(mat_vec(
+            &plucker_matrix(&self.dual().coord),
+            &pt.coord,
+        )))
+    }
+
+    /// Check if the point lies on the line.
+    #[inline]
+    pub fn incident_point(&self, pt: &PgPoint3) -> bool {
+        mat_vec(&plucker_matrix(&self.dual().coord), &pt.coord) == [0; 4]
+    }
+
+    /// Check if the line lies in the plane.
+    #[inline]
+    pub fn incident_plane(&self, plane: &PgPlane3) -> bool {
+        mat_vec(&plucker_matrix(&self.coord), &plane.coord) == [0; 4]
+    }
+
+    /// The function `side` evaluates the bilinear side operator (the reciprocal product)
+    /// `l01 m23 + l02 m31 + l03 m12 + l23 m01 + l31 m02 + l12 m03` of two lines. It vanishes
+    /// exactly when the lines are coplanar, and its sign tells on which side of each other
+    /// two skew lines pass.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::pg3d::{PgLine3, PgPoint3};
+    /// let ln_l = PgLine3::join(&PgPoint3::new([1, 0, 0, 0]), &PgPoint3::new([0, 1, 0, 0]));
+    /// let ln_m = PgLine3::join(&PgPoint3::new([0, 0, 1, 0]), &PgPoint3::new([0, 0, 0, 1]));
+    /// assert_eq!(ln_l.side(&ln_m), 1);
+    /// assert_eq!(ln_l.side(&ln_l), 0);
+    /// ```
+    #[inline]
+    pub fn side(&self, other: &PgLine3) -> i64 {
+        let l = self.coord.map(|x| x as i128);
+        let m = other.coord.map(|x| x as i128);
+        let sum = l[0] * m[3] + l[1] * m[4] + l[2] * m[5] + l[3] * m[0] + l[4] * m[1] + l[5] * m[2];
+        i64::try_from(sum).expect("side operator overflows i64")
+    }
+
+    /// Check if two lines meet, i.e. they are coplanar (equal lines included).
+    #[inline]
+    pub fn intersects(&self, other: &PgLine3) -> bool {
+        self.side(other) == 0
+    }
+
+    /// The function `intersection` returns the common point of two distinct coplanar lines.
+    ///
+    /// Returns:
+    ///
+    /// The function `intersection` returns `None` if the lines are skew or equal.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::pg3d::{PgLine3, PgPoint3};
+    /// let pt_o = PgPoint3::new([1, 1, 1, 1]);
+    /// let ln_l = PgLine3::join(&pt_o, &PgPoint3::new([1, 0, 2, 1]));
+    /// let ln_m = PgLine3::join(&pt_o, &PgPoint3::new([0, 3, 1, 1]));
+    /// assert_eq!(ln_l.intersection(&ln_m), Some(pt_o));
+    /// ```
+    pub fn intersection(&self, other: &PgLine3) -> Option<PgPoint3> {
+        if !self.intersects(other) {
+            return None;
+        }
+        // a plane through `other` but not through `self` cuts `self` at the common point
+        (0..4)
+            .map(|k| {
+                let mut e_k = [0; 4];
+                e_k[k] = 1;
+                self.meet_plane(&other.join_point(&PgPoint3::new(e_k)))
+            })
+            .find(|pt| pt.coord != [0; 4])
+    }
+
+    /// The function `common_plane` returns the plane spanned by two distinct coplanar lines.
+    ///
+    /// Returns:
+    ///
+    /// The function `common_plane` returns `None` if the lines are skew or equal.
+    pub fn common_plane(&self, other: &PgLine3) -> Option<PgPlane3> {
+        if !self.intersects(other) {
+            return None;
+        }
+        // a point on `other` but not on `self` spans the common plane with `self`
+        (0..4)
+            .map(|k| {
+                let mut e_k = [0; 4];
+                e_k[k] = 1;
+                self.join_point(&other.meet_plane(&PgPlane3::new(e_k)))
+            })
+            .find(|plane| plane.coord != [0; 4])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_join_and_meet() {
+        let pt_p = PgPoint3::new([1, 2, -1, 3]);
+        let pt_q = PgPoint3::new([-2, 1, 4, 1]);
+        let pt_r = PgPoint3::new([3, 0, 1, -2]);
+        let ln_l = PgLine3::join(&pt_p, &pt_q);
+        assert!(ln_l.is_valid());
+        assert!(ln_l.incident_point(&pt_p));
+        assert!(ln_l.incident_point(&pt_q));
+        assert!(!ln_l.incident_point(&pt_r));
+
+        let pi = ln_l.join_point(&pt_r);
+        assert!(pt_p.incident(&pi));
+        assert!(pt_q.incident(&pi));
+        assert!(pt_r.incident(&pi));
+        assert!(ln_l.incident_plane(&pi));
+
+        let sigma = PgPlane3::new([2, -1, 1, 5]);
+        let pt_x = ln_l.meet_plane(&sigma);
+        assert!(pt_x.incident(&sigma));
+        assert!(ln_l.incident_point(&pt_x));
+    }
+
+    #[test]
+    fn test_duality() {
+        let pi_a = PgPlane3::new([1, 2, -1, 3]);
+        let pi_b = PgPlane3::new([-2, 1, 4, 1]);
+        let ln_m = PgLine3::intersect(&pi_a, &pi_b);
+        assert!(ln_m.is_valid());
+        assert!(ln_m.incident_plane(&pi_a));
+        assert!(ln_m.incident_plane(&pi_b));
+        assert_eq!(ln_m.dual(), PgLine3::new(wedge(&pi_a.coord, &pi_b.coord)));
+        assert_eq!(ln_m.dual().dual(), ln_m);
+
+        // the same line spanned by two of its points
+        let pt_p = ln_m.meet_plane(&PgPlane3::new([1, 0, 0, 0]));
+        let pt_q = ln_m.meet_plane(&PgPlane3::new([0, 1, 0, 0]));
+        assert_eq!(PgLine3::join(&pt_p, &pt_q), ln_m);
+    }
+
+    #[test]
+    fn test_line_line() {
+        let pt_o = PgPoint3::new([2, -1, 3, 1]);
+        let pt_p = PgPoint3::new([1, 4, 0, -2]);
+        let pt_q = PgPoint3::new([-3, 1, 5, 2]);
+        let pt_r = PgPoint3::new([0, 2, 1, 7]);
+        let ln_l = PgLine3::join(&pt_o, &pt_p);
+        let ln_m = PgLine3::join(&pt_o, &pt_q);
+        let ln_n = PgLine3::join(&pt_q, &pt_r);
+        assert!(ln_l.intersects(&ln_m));
+        assert_eq!(ln_l.intersection(&ln_m), Some(pt_o.clone()));
+        let pi = ln_l.common_plane(&ln_m).unwrap();
+        assert!(pt_o.incident(&pi));
+        assert!(pt_p.incident(&pi));
+        assert!(pt_q.incident(&pi));
+        assert_eq!(ln_l.side(&ln_l), 0);
+        assert_eq!(ln_l.intersection(&ln_l), None);
+
+        // skew lines, with the sign depending on the orientation
+        assert!(!ln_l.intersects(&ln_n));
+        assert_eq!(ln_l.side(&ln_n), -ln_l.side(&PgLine3::join(&pt_r, &pt_q)));
+        assert_eq!(ln_l.intersection(&ln_n), None);
+        assert_eq!(ln_l.common_plane(&ln_n), None);
+    }
+
+    #[test]
+    fn test_eq_hash_display() {
+        use std::collections::HashSet;
+        let pt_p = PgPoint3::new([2, -4, 6, 8]);
+        let pt_q = PgPoint3::new([-1, 2, -3, -4]);
+        assert_eq!(pt_p, pt_q);
+        assert_eq!(pt_p.normalized().coord, [1, -2, 3, 4]);
+        let set: HashSet<PgPoint3> = [pt_p, pt_q].into_iter().collect();
+        assert_eq!(set.len(), 1);
+        assert_eq!(
+            format!("{}", PgPlane3::new([1, 0, 2, 1])),
+            "[1 : 0 : 2 : 1]"
+        );
+        assert!(!PgLine3::new([1, 0, 0, 1, 0, 0]).is_valid());
+    }
+}
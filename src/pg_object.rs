@@ -1,4 +1,13 @@
-use crate::pg_plane::{ProjectivePlane, ProjectivePlanePrimitive};
+use crate::coord_ring::{CoordKernel, CoordRing, RingKernel};
+use crate::error::GeometryError;
+use crate::pg_plane::{Join, ProjectivePlanePrimitive};
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::num::ParseIntError;
+use core::str::FromStr;
+use fractions::Fraction;
+use num_integer::Integer;
 // use crate::pg_plane::{check_axiom, coincident};
 
 /// The `dot_product` function calculates the dot product of two 3-dimensional vectors.
@@ -7,7 +16,7 @@ use crate::pg_plane::{ProjectivePlane, ProjectivePlanePrimitive};
 ///
 /// * `v_a`: An array of three i64 values representing the first vector.
 /// * `v_b`: The parameter `v_b` is a reference to an array of `i64` integers with a length of 3. It
-/// represents a vector in .
+///   represents a vector in .
 ///
 /// Returns:
 ///
@@ -21,9 +30,70 @@ use crate::pg_plane::{ProjectivePlane, ProjectivePlanePrimitive};
 /// let result = dot_product(&[1, 2, 3], &[3, 4, 5]);
 /// assert_eq!(result, 26);
 /// ```
+///
+/// # Panics
+///
+/// Panics if the result does not fit into i64. The products are accumulated in i128, so
+/// intermediate overflow never wraps silently.
 #[inline]
 pub const fn dot_product(v_a: &[i64; 3], v_b: &[i64; 3]) -> i64 {
-    v_a[0] * v_b[0] + v_a[1] * v_b[1] + v_a[2] * v_b[2]
+    match dot_product_checked(v_a, v_b) {
+        Some(result) => result,
+        None => panic!("dot product overflows i64"),
+    }
+}
+
+/// Dot product, evaluated in i128
+///
+/// Returns `None` if the result does not fit into i64.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::pg_object::dot_product_checked;
+/// assert_eq!(dot_product_checked(&[1, 2, 3], &[3, 4, 5]), Some(26));
+/// assert_eq!(dot_product_checked(&[i64::MAX, 1, 0], &[1, 1, 0]), None);
+/// ```
+#[inline]
+pub const fn dot_product_checked(v_a: &[i64; 3], v_b: &[i64; 3]) -> Option<i64> {
+    let mut sum = 0i128;
+    let mut i = 0;
+    while i < 3 {
+        sum = match sum.checked_add(v_a[i] as i128 * v_b[i] as i128) {
+            Some(sum) => sum,
+            None => return None,
+        };
+        i += 1;
+    }
+    if sum < i64::MIN as i128 || sum > i64::MAX as i128 {
+        None
+    } else {
+        Some(sum as i64)
+    }
+}
+
+/// Check if the dot product vanishes, evaluated in i128 without narrowing to i64
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::pg_object::dot_is_zero;
+/// let m = i64::MAX;
+/// assert!(dot_is_zero(&[1, 2, 3], &[3, 0, -1]));
+/// assert!(!dot_is_zero(&[m, m, 0], &[1, 1, 0]));
+/// assert!(dot_is_zero(&[m, m, 1], &[m, -m, 0]));
+/// ```
+#[inline]
+pub const fn dot_is_zero(v_a: &[i64; 3], v_b: &[i64; 3]) -> bool {
+    // A partial sum that overflows exceeds 2^127 in magnitude, while the remaining product is
+    // at most 2^126, so the dot product cannot vanish.
+    match (v_a[0] as i128 * v_b[0] as i128).checked_add(v_a[1] as i128 * v_b[1] as i128) {
+        Some(sum) => match sum.checked_add(v_a[2] as i128 * v_b[2] as i128) {
+            Some(sum) => sum == 0,
+            None => false,
+        },
+        None => false,
+    }
 }
 
 /// Dot product (2d)
@@ -37,6 +107,10 @@ pub const fn dot_product(v_a: &[i64; 3], v_b: &[i64; 3]) -> i64 {
 /// ```
 #[inline]
 pub const fn dot1(v_a: &[i64], v_b: &[i64]) -> i64 {
+    if cfg!(feature = "audit") {
+        let sum = v_a[0] as i128 * v_b[0] as i128 + v_a[1] as i128 * v_b[1] as i128;
+        return narrow_audited(sum);
+    }
     v_a[0] * v_b[0] + v_a[1] * v_b[1]
 }
 
@@ -51,9 +125,27 @@ pub const fn dot1(v_a: &[i64], v_b: &[i64]) -> i64 {
 /// ```
 #[inline]
 pub const fn cross2(v_a: &[i64], v_b: &[i64]) -> i64 {
+    if cfg!(feature = "audit") {
+        let minor = v_a[0] as i128 * v_b[1] as i128 - v_a[1] as i128 * v_b[0] as i128;
+        return narrow_audited(minor);
+    }
     v_a[0] * v_b[1] - v_a[1] * v_b[0]
 }
 
+/// Narrow an exact result to i64 for the `audit` feature, which checks the arithmetic that is
+/// otherwise left to wrap in release builds
+///
+/// # Panics
+///
+/// Panics if the result does not fit into i64.
+#[inline]
+const fn narrow_audited(x: i128) -> i64 {
+    if x < i64::MIN as i128 || x > i64::MAX as i128 {
+        panic!("audit: arithmetic overflows i64");
+    }
+    x as i64
+}
+
 /// Cross product
 ///
 /// Examples:
@@ -63,13 +155,173 @@ pub const fn cross2(v_a: &[i64], v_b: &[i64]) -> i64 {
 /// let v_a = cross_product(&[1, 2, 3], &[3, 4, 5]);
 /// assert_eq!(v_a, [-2, 4, -2]);
 /// ```
+///
+/// # Panics
+///
+/// Panics if the result does not fit into i64 even after dividing out the common factor of its
+/// entries.
 #[inline]
 pub const fn cross_product(v_a: &[i64; 3], v_b: &[i64; 3]) -> [i64; 3] {
-    [
-        v_a[1] * v_b[2] - v_a[2] * v_b[1],
-        v_a[2] * v_b[0] - v_a[0] * v_b[2],
-        v_a[0] * v_b[1] - v_a[1] * v_b[0],
-    ]
+    match cross_product_checked(v_a, v_b) {
+        Some(result) => result,
+        None => panic!("cross product overflows i64"),
+    }
+}
+
+/// Cross product, evaluated in i128
+///
+/// The result is a vector of homogeneous coordinates, so when it does not fit into i64 it is
+/// divided by the greatest common divisor of its entries. Returns `None` if it still does not fit.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::pg_object::cross_product_checked;
+/// let m = i64::MAX;
+/// assert_eq!(cross_product_checked(&[1, 2, 3], &[3, 4, 5]), Some([-2, 4, -2]));
+/// assert_eq!(cross_product_checked(&[m, 0, 0], &[0, m, 0]), Some([0, 0, 1]));
+/// assert_eq!(cross_product_checked(&[m, 1, 1], &[1, m, 0]), None);
+/// ```
+#[inline]
+pub const fn cross_product_checked(v_a: &[i64; 3], v_b: &[i64; 3]) -> Option<[i64; 3]> {
+    /// Minor `a * b - c * d`
+    const fn minor(a: i64, b: i64, c: i64, d: i64) -> Option<i128> {
+        (a as i128 * b as i128).checked_sub(c as i128 * d as i128)
+    }
+    match (
+        minor(v_a[1], v_b[2], v_a[2], v_b[1]),
+        minor(v_a[2], v_b[0], v_a[0], v_b[2]),
+        minor(v_a[0], v_b[1], v_a[1], v_b[0]),
+    ) {
+        (Some(x), Some(y), Some(z)) => narrow_coord([x, y, z]),
+        _ => None,
+    }
+}
+
+/// Check if the cross product vanishes, i.e. if the vectors are proportional, comparing the i128
+/// minors with zero without narrowing them to i64
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::pg_object::cross_is_zero;
+/// let m = i64::MAX;
+/// assert!(cross_is_zero(&[1, 2, 3], &[-2, -4, -6]));
+/// assert!(cross_is_zero(&[m, m, 0], &[1, 1, 0]));
+/// assert!(!cross_is_zero(&[m, m - 1, 1], &[m - 1, m, 1]));
+/// ```
+#[inline]
+pub const fn cross_is_zero(v_a: &[i64; 3], v_b: &[i64; 3]) -> bool {
+    // Products of two i64 are at most 2^126 in magnitude, so their differences fit into i128.
+    const fn minor_is_zero(a: i64, b: i64, c: i64, d: i64) -> bool {
+        a as i128 * b as i128 == c as i128 * d as i128
+    }
+    minor_is_zero(v_a[1], v_b[2], v_a[2], v_b[1])
+        && minor_is_zero(v_a[2], v_b[0], v_a[0], v_b[2])
+        && minor_is_zero(v_a[0], v_b[1], v_a[1], v_b[0])
+}
+
+/// Determinant of the 3x3 matrix with rows `v_a`, `v_b` and `v_c`
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::pg_object::det3;
+/// let result = det3(&[1, 2, 3], &[3, 4, 5], &[2, 0, 7]);
+/// assert_eq!(result, -18);
+/// ```
+#[inline]
+pub const fn det3(v_a: &[i64; 3], v_b: &[i64; 3], v_c: &[i64; 3]) -> i64 {
+    if cfg!(feature = "audit") {
+        match det3_checked(v_a, v_b, v_c) {
+            Some(det) => return narrow_audited(det),
+            None => panic!("audit: arithmetic overflows i64"),
+        }
+    }
+    v_a[0] * (v_b[1] * v_c[2] - v_b[2] * v_c[1]) - v_a[1] * (v_b[0] * v_c[2] - v_b[2] * v_c[0])
+        + v_a[2] * (v_b[0] * v_c[1] - v_b[1] * v_c[0])
+}
+
+/// Determinant of the 3x3 matrix with rows `v_a`, `v_b` and `v_c`, evaluated in i128
+///
+/// Returns `None` if an intermediate result overflows i128.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::pg_object::det3_checked;
+/// let m = i64::MAX;
+/// assert_eq!(det3_checked(&[m, 0, 0], &[0, m, 0], &[0, 0, 1]), Some((m as i128) * (m as i128)));
+/// assert_eq!(det3_checked(&[m, 0, 1], &[0, m, 1], &[-m, -m, 1]), None);
+/// ```
+#[inline]
+pub const fn det3_checked(v_a: &[i64; 3], v_b: &[i64; 3], v_c: &[i64; 3]) -> Option<i128> {
+    /// Expansion term `a * (b * c - d * e)`
+    const fn term(a: i64, b: i64, c: i64, d: i64, e: i64) -> Option<i128> {
+        match (b as i128 * c as i128).checked_sub(d as i128 * e as i128) {
+            Some(minor) => (a as i128).checked_mul(minor),
+            None => None,
+        }
+    }
+    let m_0 = term(v_a[0], v_b[1], v_c[2], v_b[2], v_c[1]);
+    let m_1 = term(v_a[1], v_b[0], v_c[2], v_b[2], v_c[0]);
+    let m_2 = term(v_a[2], v_b[0], v_c[1], v_b[1], v_c[0]);
+    match (m_0, m_1, m_2) {
+        (Some(m_0), Some(m_1), Some(m_2)) => match m_0.checked_sub(m_1) {
+            Some(sum) => sum.checked_add(m_2),
+            None => None,
+        },
+        _ => None,
+    }
+}
+
+/// Check if the determinant of the 3x3 matrix with rows `v_a`, `v_b` and `v_c` vanishes
+///
+/// Unlike `det3_checked`, it never fails: if the determinant overflows i128, it is evaluated
+/// modulo four pairwise coprime moduli, whose product exceeds twice its magnitude.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::pg_object::{det3_checked, det3_is_zero};
+/// let m = i64::MAX;
+/// assert!(det3_is_zero(&[1, 2, 3], &[4, 5, 6], &[7, 8, 9]));
+/// let [v_a, v_b, v_c] = [[m, 0, m], [0, m, -m], [m, m, 0]];
+/// assert_eq!(det3_checked(&v_a, &v_b, &v_c), None);
+/// assert!(det3_is_zero(&v_a, &v_b, &v_c));
+/// assert!(!det3_is_zero(&v_a, &v_b, &[m, m, 1]));
+/// ```
+#[inline]
+pub const fn det3_is_zero(v_a: &[i64; 3], v_b: &[i64; 3], v_c: &[i64; 3]) -> bool {
+    /// Residue of `a * b - c * d` modulo `m`, for residues below 2^62
+    const fn minor_mod(a: i128, b: i128, c: i128, d: i128, m: i128) -> i128 {
+        (a * b % m - c * d % m).rem_euclid(m)
+    }
+    if let Some(det) = det3_checked(v_a, v_b, v_c) {
+        return det == 0;
+    }
+    // |det| <= 6 * 2^189 < 2^192, while the product of the moduli exceeds 2^247.
+    const MODULI: [i128; 4] = [1 << 62, (1 << 62) - 1, (1 << 62) - 3, (1 << 62) - 5];
+    let mut k = 0;
+    while k < MODULI.len() {
+        let m = MODULI[k];
+        let (mut a, mut b, mut c) = ([0_i128; 3], [0_i128; 3], [0_i128; 3]);
+        let mut i = 0;
+        while i < 3 {
+            a[i] = (v_a[i] as i128).rem_euclid(m);
+            b[i] = (v_b[i] as i128).rem_euclid(m);
+            c[i] = (v_c[i] as i128).rem_euclid(m);
+            i += 1;
+        }
+        let m_0 = a[0] * minor_mod(b[1], c[2], b[2], c[1], m) % m;
+        let m_1 = a[1] * minor_mod(b[0], c[2], b[2], c[0], m) % m;
+        let m_2 = a[2] * minor_mod(b[0], c[1], b[1], c[0], m) % m;
+        if (m_0 - m_1 + m_2).rem_euclid(m) != 0 {
+            return false;
+        }
+        k += 1;
+    }
+    true
 }
 
 /// Plucker operation
@@ -81,6 +333,11 @@ pub const fn cross_product(v_a: &[i64; 3], v_b: &[i64; 3]) -> [i64; 3] {
 /// let v_a = plucker_operation(1, &[1, 2, 3], -1, &[3, 4, 5]);
 /// assert_eq!(v_a, [-2, -2, -2]);
 /// ```
+///
+/// # Panics
+///
+/// Panics if the result does not fit into i64 even after dividing out the common factor of its
+/// entries.
 #[inline]
 pub const fn plucker_operation(
     lambda_a: i64,
@@ -88,88 +345,1018 @@ pub const fn plucker_operation(
     mu_b: i64,
     v_b: &[i64; 3],
 ) -> [i64; 3] {
-    [
-        lambda_a * v_a[0] + mu_b * v_b[0],
-        lambda_a * v_a[1] + mu_b * v_b[1],
-        lambda_a * v_a[2] + mu_b * v_b[2],
-    ]
+    match plucker_operation_checked(lambda_a, v_a, mu_b, v_b) {
+        Some(result) => result,
+        None => panic!("plucker operation overflows i64"),
+    }
 }
 
-macro_rules! define_point_or_line {
-    (impl $point:ident) => {
-        #[derive(Debug, Clone)]
-        pub struct $point {
+/// Plucker operation, evaluated in i128
+///
+/// Like `cross_product_checked`, a result that does not fit into i64 is divided by the greatest
+/// common divisor of its entries, and `None` is returned if it still does not fit.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::pg_object::plucker_operation_checked;
+/// let m = i64::MAX;
+/// assert_eq!(plucker_operation_checked(1, &[1, 2, 3], -1, &[3, 4, 5]), Some([-2, -2, -2]));
+/// assert_eq!(plucker_operation_checked(m, &[2, 2, 4], m, &[0, 2, 2]), Some([1, 2, 3]));
+/// ```
+#[inline]
+pub const fn plucker_operation_checked(
+    lambda_a: i64,
+    v_a: &[i64; 3],
+    mu_b: i64,
+    v_b: &[i64; 3],
+) -> Option<[i64; 3]> {
+    /// Entry `lambda * a + mu * b`
+    const fn entry(lambda: i64, a: i64, mu: i64, b: i64) -> Option<i128> {
+        (lambda as i128 * a as i128).checked_add(mu as i128 * b as i128)
+    }
+    match (
+        entry(lambda_a, v_a[0], mu_b, v_b[0]),
+        entry(lambda_a, v_a[1], mu_b, v_b[1]),
+        entry(lambda_a, v_a[2], mu_b, v_b[2]),
+    ) {
+        (Some(x), Some(y), Some(z)) => narrow_coord([x, y, z]),
+        _ => None,
+    }
+}
+
+/// Normalize homogeneous coordinates
+///
+/// The coordinates are divided by the greatest common divisor of their entries, and the sign is
+/// chosen so that the first nonzero entry is positive. Vectors that represent the same point or line
+/// have the same normalized form. The zero vector is returned unchanged.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::pg_object::normalize_homogeneous;
+/// assert_eq!(normalize_homogeneous(&[-4, 6, 10]), [2, -3, -5]);
+/// assert_eq!(normalize_homogeneous(&[0, -3, 0]), [0, 1, 0]);
+/// ```
+#[inline]
+pub const fn normalize_homogeneous(coord: &[i64; 3]) -> [i64; 3] {
+    let g = gcd_u128(
+        gcd_u128(
+            coord[0].unsigned_abs() as u128,
+            coord[1].unsigned_abs() as u128,
+        ),
+        coord[2].unsigned_abs() as u128,
+    ) as i128;
+    if g == 0 {
+        return *coord;
+    }
+    let mut v = [
+        coord[0] as i128 / g,
+        coord[1] as i128 / g,
+        coord[2] as i128 / g,
+    ];
+    if v[0] < 0 || (v[0] == 0 && (v[1] < 0 || (v[1] == 0 && v[2] < 0))) {
+        v = [-v[0], -v[1], -v[2]];
+    }
+    match narrow_coord(v) {
+        Some(result) => result,
+        // only [i64::MIN, ...] with coprime entries cannot be negated
+        None => [
+            coord[0] / g as i64,
+            coord[1] / g as i64,
+            coord[2] / g as i64,
+        ],
+    }
+}
+
+/// Greatest common divisor of the absolute values of `a` and `b`
+#[inline]
+const fn gcd_u128(a: u128, b: u128) -> u128 {
+    let (mut a, mut b) = (a, b);
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+    a
+}
+
+/// Narrow homogeneous coordinates to i64, dividing out their common factor if necessary
+#[inline]
+const fn narrow_coord(v: [i128; 3]) -> Option<[i64; 3]> {
+    const fn fits(v: &[i128; 3]) -> bool {
+        let (min, max) = (i64::MIN as i128, i64::MAX as i128);
+        v[0] >= min && v[0] <= max && v[1] >= min && v[1] <= max && v[2] >= min && v[2] <= max
+    }
+    if fits(&v) {
+        return Some([v[0] as i64, v[1] as i64, v[2] as i64]);
+    }
+    let g = gcd_u128(
+        gcd_u128(v[0].unsigned_abs(), v[1].unsigned_abs()),
+        v[2].unsigned_abs(),
+    ) as i128;
+    let v = [v[0] / g, v[1] / g, v[2] / g];
+    if fits(&v) {
+        Some([v[0] as i64, v[1] as i64, v[2] as i64])
+    } else {
+        None
+    }
+}
+
+/// Determinant of three coordinate vectors, or `None` if an intermediate result overflows i64
+#[inline]
+fn det3_small(v_a: &[i64; 3], v_b: &[i64; 3], v_c: &[i64; 3]) -> Option<i64> {
+    let minor = |p: i64, q: i64, r: i64, s: i64| p.checked_mul(q)?.checked_sub(r.checked_mul(s)?);
+    let m_0 = v_a[0].checked_mul(minor(v_b[1], v_c[2], v_b[2], v_c[1])?)?;
+    let m_1 = v_a[1].checked_mul(minor(v_b[0], v_c[2], v_b[2], v_c[0])?)?;
+    let m_2 = v_a[2].checked_mul(minor(v_b[0], v_c[1], v_b[1], v_c[0])?)?;
+    m_0.checked_sub(m_1)?.checked_add(m_2)
+}
+
+/// Coordinates of the harmonic conjugate `λ a + μ b` (see `PgPoint::harm_conj`), or `None` if an
+/// intermediate result overflows i64
+#[inline]
+fn harm_conj_coord(a: &[i64; 3], b: &[i64; 3], c: &[i64; 3]) -> Option<[i64; 3]> {
+    let (aa, ab, bb) = (
+        dot_product_checked(a, a)?,
+        dot_product_checked(a, b)?,
+        dot_product_checked(b, b)?,
+    );
+    let (ac, bc) = (dot_product_checked(a, c)?, dot_product_checked(b, c)?);
+    let lambda = bb.checked_mul(ac)?.checked_sub(ab.checked_mul(bc)?)?;
+    let mu = ab.checked_mul(ac)?.checked_sub(aa.checked_mul(bc)?)?;
+    let term = |i: usize| lambda.checked_mul(a[i])?.checked_add(mu.checked_mul(b[i])?);
+    Some([term(0)?, term(1)?, term(2)?])
+}
+
+/// Coordinates of the harmonic conjugate evaluated in i128, dividing out common factors
+fn harm_conj_coord_wide(a: &[i64; 3], b: &[i64; 3], c: &[i64; 3]) -> Option<[i64; 3]> {
+    // dot products of i64 vectors fit into i128
+    let dot = |v_a: &[i64; 3], v_b: &[i64; 3]| -> i128 {
+        (0..3).map(|i| v_a[i] as i128 * v_b[i] as i128).sum()
+    };
+    let (aa, ab, bb) = (dot(a, a), dot(a, b), dot(b, b));
+    let (ac, bc) = (dot(a, c), dot(b, c));
+    let lambda = bb.checked_mul(ac)?.checked_sub(ab.checked_mul(bc)?)?;
+    let mu = ab.checked_mul(ac)?.checked_sub(aa.checked_mul(bc)?)?;
+    let common = lambda.gcd(&mu).max(1);
+    let (lambda, mu) = (lambda / common, mu / common);
+    let term = |i: usize| {
+        lambda
+            .checked_mul(a[i] as i128)?
+            .checked_add(mu.checked_mul(b[i] as i128)?)
+    };
+    narrow_coord([term(0)?, term(1)?, term(2)?])
+}
+
+/// The `ParseCoordError` enum describes why a string could not be parsed into homogeneous coordinates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseCoordError {
+    /// The string is neither of the form `[x : y : z]` nor the affine shorthand `(x, y)`.
+    InvalidFormat,
+    /// One of the coordinates is not a valid integer.
+    InvalidInteger(ParseIntError),
+}
+
+impl fmt::Display for ParseCoordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseCoordError::InvalidFormat => {
+                write!(
+                    f,
+                    "expected homogeneous coordinates `[x : y : z]` or `(x, y)`"
+                )
+            }
+            ParseCoordError::InvalidInteger(err) => write!(f, "invalid coordinate: {}", err),
+        }
+    }
+}
+
+impl core::error::Error for ParseCoordError {}
+
+impl From<ParseIntError> for ParseCoordError {
+    fn from(err: ParseIntError) -> Self {
+        ParseCoordError::InvalidInteger(err)
+    }
+}
+
+/// The function `parse_coord` parses homogeneous coordinates written as `[x : y : z]` (commas are
+/// accepted as separators too), or an affine point written as `(x, y)`, which becomes `[x, y, 1]`.
+///
+/// Arguments:
+///
+/// * `s`: The string to be parsed.
+///
+/// Returns:
+///
+/// The function `parse_coord` returns the homogeneous coordinates, or a `ParseCoordError`.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::pg_object::parse_coord;
+/// assert_eq!(parse_coord("[1 : 3 : 2]"), Ok([1, 3, 2]));
+/// assert_eq!(parse_coord("(2, -3)"), Ok([2, -3, 1]));
+/// assert!(parse_coord("[1 : 3]").is_err());
+/// ```
+pub fn parse_coord(s: &str) -> Result<[i64; 3], ParseCoordError> {
+    let s = s.trim();
+    if let Some(inner) = s.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+        let mut coord = [0; 3];
+        let mut parts = inner.split([':', ',']);
+        for c in coord.iter_mut() {
+            *c = parts
+                .next()
+                .ok_or(ParseCoordError::InvalidFormat)?
+                .trim()
+                .parse()?;
+        }
+        if parts.next().is_some() {
+            return Err(ParseCoordError::InvalidFormat);
+        }
+        Ok(coord)
+    } else if let Some(inner) = s.strip_prefix('(').and_then(|s| s.strip_suffix(')')) {
+        let (x, y) = inner
+            .split_once(',')
+            .ok_or(ParseCoordError::InvalidFormat)?;
+        Ok([x.trim().parse()?, y.trim().parse()?, 1])
+    } else {
+        Err(ParseCoordError::InvalidFormat)
+    }
+}
+
+/// The `HomogeneousCoords` trait exposes the integer homogeneous coordinates of a point or line,
+/// so that coordinate-level algorithms, such as those in `predicates`, apply to the objects of
+/// every geometry without converting them to `PgPoint` or `PgLine` first.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::predicates::{orientation, Orientation};
+/// use projgeom_rs::{EuclidPoint, HomogeneousCoords, HyperbolicPoint};
+/// assert_eq!(EuclidPoint::new([1, 3, 2]).coords(), &[1, 3, 2]);
+/// let [pt_p, pt_q, pt_r] = [[0, 0, 1], [1, 0, 2], [0, 1, 2]].map(HyperbolicPoint::new);
+/// assert_eq!(orientation(&pt_p, &pt_q, &pt_r), Ok(Orientation::CounterClockwise));
+/// ```
+pub trait HomogeneousCoords {
+    /// Return the homogeneous coordinates.
+    fn coords(&self) -> &[i64; 3];
+}
+
+/// The `Coord3` trait adds construction from homogeneous coordinates to `HomogeneousCoords`, so
+/// that generic utilities, e.g. normalization, conversion between geometries or the quickcheck
+/// strategies of `theorems`, can be written once for all the generated point and line types.
+/// The accessor `coord` is `HomogeneousCoords::coords` under the name of the field.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::pg_object::Coord3;
+/// use projgeom_rs::EuclidLine;
+/// fn normalized<T: Coord3>(obj: &T) -> T {
+///     let [x, y, z] = *obj.coord();
+///     let sign = if z < 0 { -1 } else { 1 };
+///     T::from_coord([x * sign, y * sign, z * sign])
+/// }
+/// assert_eq!(normalized(&EuclidLine::new([1, 2, -3])).coord, [-1, -2, 3]);
+/// ```
+pub trait Coord3: HomogeneousCoords + Sized {
+    /// Return the homogeneous coordinates.
+    #[inline]
+    fn coord(&self) -> &[i64; 3] {
+        self.coords()
+    }
+
+    /// Create the object with the given homogeneous coordinates.
+    fn from_coord(coord: [i64; 3]) -> Self;
+}
+
+/// The function `convert` reinterprets the coordinates of a point or line as an object of
+/// another geometry, e.g. a `PgPoint` as a `HyperbolicPoint`. Incidence is preserved, as it does
+/// not depend on the geometry.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::pg_object::convert;
+/// use projgeom_rs::{EuclidPoint, HyperbolicPoint};
+/// let pt: HyperbolicPoint = convert(&EuclidPoint::new([1, 2, 3]));
+/// assert_eq!(pt, HyperbolicPoint::new([1, 2, 3]));
+/// ```
+#[inline]
+pub fn convert<Src: HomogeneousCoords, Dst: Coord3>(obj: &Src) -> Dst {
+    Dst::from_coord(*obj.coords())
+}
+
+/// The `I64Kernel` struct is the `CoordKernel` of the i64 point and line types. Results are
+/// computed in i128 and narrowed by the common factor of their entries; with the `audit`
+/// feature, the operations are recorded.
+pub(crate) struct I64Kernel;
+
+impl CoordKernel for I64Kernel {
+    type Scalar = i64;
+    type Value = i64;
+
+    #[inline]
+    fn proportional(v_a: &[i64; 3], v_b: &[i64; 3]) -> bool {
+        cross_is_zero(v_a, v_b)
+    }
+
+    #[inline]
+    fn orthogonal(v_a: &[i64; 3], v_b: &[i64; 3]) -> bool {
+        audit_record!(Incident, &[]);
+        dot_is_zero(v_a, v_b)
+    }
+
+    #[inline]
+    fn dot(v_a: &[i64; 3], v_b: &[i64; 3]) -> i64 {
+        let value = dot_product(v_a, v_b);
+        audit_record!(Dot, &[value]);
+        value
+    }
+
+    #[inline]
+    fn cross(v_a: &[i64; 3], v_b: &[i64; 3]) -> [i64; 3] {
+        let result = cross_product(v_a, v_b);
+        audit_record!(Meet, &result);
+        result
+    }
+
+    #[inline]
+    fn plucker(lambda: i64, v_a: &[i64; 3], mu: i64, v_b: &[i64; 3]) -> [i64; 3] {
+        let result = plucker_operation(lambda, v_a, mu, v_b);
+        audit_record!(Parametrize, &result);
+        result
+    }
+
+    #[inline]
+    fn aux(v: &[i64; 3]) -> [i64; 3] {
+        *v
+    }
+}
+
+/// Define the struct of a point or line type with coordinates of type `$scalar`, together with
+/// the parts shared by every coordinate type: projective equality and formatting. The arithmetic
+/// is that of the `CoordKernel` `$kernel`.
+macro_rules! define_object {
+    ($(#[$attr:meta])* impl [$($gen:tt)*] $point:ident [$($arg:tt)*], $scalar:ty, $kernel:ty) => {
+        $(#[$attr])*
+        pub struct $point<$($gen)*> {
             /// Homogeneous coordinate
-            pub coord: [i64; 3],
+            pub coord: [$scalar; 3],
         }
 
-        impl $point {
+        impl<$($gen)*> $point<$($arg)*> {
             /// Create a new point with the given coordinates.
             #[inline]
-            pub const fn new(coord: [i64; 3]) -> Self {
+            pub const fn new(coord: [$scalar; 3]) -> Self {
                 Self { coord }
             }
         }
 
-        impl PartialEq for $point {
+        impl<$($gen)*> PartialEq for $point<$($arg)*> {
             /// Check if two points are equal.
             #[inline]
-            fn eq(&self, other: &$point) -> bool {
-                cross_product(&self.coord, &other.coord) == [0, 0, 0]
+            fn eq(&self, other: &Self) -> bool {
+                <$kernel as $crate::coord_ring::CoordKernel>::proportional(
+                    &self.coord,
+                    &other.coord,
+                )
+            }
+        }
+        impl<$($gen)*> Eq for $point<$($arg)*> {}
+
+        impl<$($gen)*> core::fmt::Display for $point<$($arg)*>
+        where
+            $scalar: core::fmt::Display,
+        {
+            /// Format as homogeneous coordinates, e.g. `[1 : 3 : 2]`.
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                let [x, y, z] = &self.coord;
+                write!(f, "[{} : {} : {}]", x, y, z)
+            }
+        }
+    };
+}
+
+/// Implement the incidence of `$point` with `$line`, dot products of type `$value` and the
+/// join or meet with the `CoordKernel` `$kernel`.
+macro_rules! define_incidence {
+    (impl [$($gen:tt)*] $line:ident [$($arg:tt)*], $point:ident [$($parg:tt)*], $value:ty, $kernel:ty) => {
+        impl<$($gen)*> $crate::pg_plane::ProjectivePlane<$line<$($arg)*>, $value>
+            for $point<$($parg)*>
+        {
+            /// Return the Dual not incident with Self
+            #[inline]
+            fn aux(&self) -> $line<$($arg)*> {
+                $line::new(<$kernel as $crate::coord_ring::CoordKernel>::aux(&self.coord))
+            }
+
+            /// Return the dot product of Self and `line`
+            #[inline]
+            fn dot(&self, line: &$line<$($arg)*>) -> $value {
+                <$kernel as $crate::coord_ring::CoordKernel>::dot(&self.coord, &line.coord)
+            } // basic measurement
+
+            #[inline]
+            fn parametrize(&self, lambda: $value, pt_q: &Self, mu: $value) -> Self {
+                Self::new(<$kernel as $crate::coord_ring::CoordKernel>::plucker(
+                    lambda,
+                    &self.coord,
+                    mu,
+                    &pt_q.coord,
+                ))
+            }
+        }
+
+        impl<$($gen)*> $crate::pg_plane::ProjectivePlanePrimitive<$line<$($arg)*>>
+            for $point<$($parg)*>
+        {
+            #[inline]
+            fn incident(&self, _rhs: &$line<$($arg)*>) -> bool {
+                <$kernel as $crate::coord_ring::CoordKernel>::orthogonal(&self.coord, &_rhs.coord)
+            }
+
+            #[inline]
+            fn meet(&self, _rhs: &Self) -> $line<$($arg)*> {
+                $line::new(<$kernel as $crate::coord_ring::CoordKernel>::cross(
+                    &self.coord,
+                    &_rhs.coord,
+                ))
+            }
+        }
+    };
+}
+
+/// `Join` for a point type, with the old name `meet` of the join kept as a deprecated alias.
+macro_rules! define_join {
+    (impl [$($gen:tt)*] $point:ident [$($parg:tt)*], $line:ident [$($arg:tt)*]) => {
+        impl<$($gen)*> $crate::pg_plane::Join<$line<$($arg)*>> for $point<$($parg)*> {}
+
+        impl<$($gen)*> $point<$($parg)*> {
+            /// The line through two points; the old name of `join`.
+            #[deprecated(note = "the line through two points is their `join`")]
+            #[inline]
+            pub fn meet(&self, rhs: &Self) -> $line<$($arg)*> {
+                $crate::pg_plane::Join::join(self, rhs)
+            }
+        }
+    };
+}
+
+pub(crate) use {define_incidence, define_join, define_object};
+
+macro_rules! define_point_or_line {
+    (impl $point:ident<T>) => {
+        define_object!(
+            /// Equality is up to a nonzero scalar factor of the coordinate ring `T`.
+            #[derive(Debug, Clone)]
+            impl [T: CoordRing] $point [T], T, RingKernel<T>
+        );
+    };
+    (impl $point:ident) => {
+        define_point_or_line!(impl [] $point []);
+    };
+    (impl [$($gen:tt)*] $point:ident [$($arg:tt)*]) => {
+        define_object!(
+            /// Equality, hashing and ordering are all up to a nonzero scalar factor. The zero
+            /// vector does not represent a point or a line and must not be used as a key.
+            #[derive(Debug, Clone)]
+            impl [$($gen)*] $point [$($arg)*], i64, I64Kernel
+        );
+
+        impl<$($gen)*> $point<$($arg)*> {
+            /// Reduce the coordinates in place by their common factor and make the first
+            /// nonzero coordinate positive.
+            #[inline]
+            pub fn normalize(&mut self) {
+                self.coord = normalize_homogeneous(&self.coord);
+                audit_record!(Normalize, &self.coord);
+            }
+
+            /// Return a copy with reduced coordinates whose first nonzero entry is positive.
+            #[inline]
+            pub const fn normalized(&self) -> Self {
+                Self::new(normalize_homogeneous(&self.coord))
+            }
+        }
+
+        impl<$($gen)*> HomogeneousCoords for $point<$($arg)*> {
+            #[inline]
+            fn coords(&self) -> &[i64; 3] {
+                &self.coord
+            }
+        }
+
+        impl<$($gen)*> Coord3 for $point<$($arg)*> {
+            #[inline]
+            fn from_coord(coord: [i64; 3]) -> Self {
+                Self::new(coord)
+            }
+        }
+
+        impl<$($gen)*> Hash for $point<$($arg)*> {
+            /// Hash the normalized coordinates, so that equal objects hash alike.
+            #[inline]
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                normalize_homogeneous(&self.coord).hash(state);
+            }
+        }
+
+        impl<$($gen)*> PartialOrd for $point<$($arg)*> {
+            #[inline]
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl<$($gen)*> Ord for $point<$($arg)*> {
+            /// Compare the normalized coordinates lexicographically.
+            #[inline]
+            fn cmp(&self, other: &Self) -> Ordering {
+                normalize_homogeneous(&self.coord).cmp(&normalize_homogeneous(&other.coord))
+            }
+        }
+
+        impl<$($gen)*> FromStr for $point<$($arg)*> {
+            type Err = ParseCoordError;
+
+            /// Parse `[x : y : z]`, or the affine shorthand `(x, y)` for `[x : y : 1]`.
+            #[inline]
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                parse_coord(s).map(Self::new)
             }
         }
-        impl Eq for $point {}
     };
 }
 
 macro_rules! define_line_for_point {
+    (impl $line:ident<T>, $point:ident<T>) => {
+        define_incidence!(impl [T: CoordRing] $line [T], $point [T], T, RingKernel<T>);
+    };
     (impl $line:ident, $point:ident) => {
-        impl ProjectivePlane<$line, i64> for $point {
+        define_line_for_point!(impl [] $line [], $point []);
+    };
+    (impl [$($gen:tt)*] $line:ident [$($arg:tt)*], $point:ident [$($parg:tt)*]) => {
+        /// Const versions of the trait operations, usable to compute fixed
+        /// configurations at compile time.
+        impl<$($gen)*> $point<$($parg)*> {
             /// Return the Dual not incident with Self
             #[inline]
-            fn aux(&self) -> $line {
-                $line::new(self.coord.clone())
+            pub const fn aux_const(&self) -> $line<$($arg)*> {
+                $line::new(self.coord)
             }
 
             /// Return the dot product of Self and `line`
             #[inline]
-            fn dot(&self, line: &$line) -> i64 {
+            pub const fn dot_const(&self, line: &$line<$($arg)*>) -> i64 {
                 dot_product(&self.coord, &line.coord)
-            } // basic measurement
+            }
 
             #[inline]
-            fn parametrize(&self, lambda: i64, pt_q: &Self, mu: i64) -> Self {
+            pub const fn parametrize_const(&self, lambda: i64, pt_q: &Self, mu: i64) -> Self {
                 Self::new(plucker_operation(lambda, &self.coord, mu, &pt_q.coord))
             }
-        }
 
-        impl ProjectivePlanePrimitive<$line> for $point {
             #[inline]
-            fn incident(&self, _rhs: &$line) -> bool {
-                dot_product(&self.coord, &_rhs.coord) == 0
+            pub const fn incident_const(&self, line: &$line<$($arg)*>) -> bool {
+                dot_is_zero(&self.coord, &line.coord)
             }
 
             #[inline]
-            fn meet(&self, _rhs: &Self) -> $line {
-                $line::new(cross_product(&self.coord, &_rhs.coord))
+            pub const fn meet_const(&self, rhs: &Self) -> $line<$($arg)*> {
+                $line::new(cross_product(&self.coord, &rhs.coord))
             }
         }
+
+        define_incidence!(impl [$($gen)*] $line [$($arg)*], $point [$($parg)*], i64, I64Kernel);
     };
 }
 
 macro_rules! define_point_and_line {
+    (impl $point:ident<T>, $line:ident<T>) => {
+        define_point_or_line!(impl $point<T>);
+        define_point_or_line!(impl $line<T>);
+        define_line_for_point!(impl $line<T>, $point<T>);
+        define_line_for_point!(impl $point<T>, $line<T>);
+        define_join!(impl [T: CoordRing] $point [T], $line [T]);
+    };
     (impl $point:ident, $line:ident) => {
         define_point_or_line!(impl $point);
         define_point_or_line!(impl $line);
         define_line_for_point!(impl $line, $point);
         define_line_for_point!(impl $point, $line);
+        define_join!(impl [] $point [], $line []);
+    };
+    (impl [$($gen:tt)*] $point:ident [$($arg:tt)*], $line:ident) => {
+        define_point_or_line!(impl [$($gen)*] $point [$($arg)*]);
+        define_point_or_line!(impl [$($gen)*] $line [$($arg)*]);
+        define_line_for_point!(impl [$($gen)*] $line [$($arg)*], $point [$($arg)*]);
+        define_line_for_point!(impl [$($gen)*] $point [$($arg)*], $line [$($arg)*]);
+        define_join!(impl [$($gen)*] $point [$($arg)*], $line [$($arg)*]);
+    };
+}
+
+/// Reinterpret the homogeneous coordinates of a point or line as the projective
+/// base object, and vice versa.
+macro_rules! define_pg_conversion {
+    (impl $pg:ident, $object:ident) => {
+        define_pg_conversion!(impl [] $pg, $object []);
+    };
+    (impl [$($gen:tt)*] $pg:ident, $object:ident [$($arg:tt)*]) => {
+        impl<$($gen)*> From<$pg> for $object<$($arg)*> {
+            #[inline]
+            fn from(obj: $pg) -> Self {
+                Self::new(obj.coord)
+            }
+        }
+
+        impl<$($gen)*> From<$object<$($arg)*>> for $pg {
+            #[inline]
+            fn from(obj: $object<$($arg)*>) -> Self {
+                Self::new(obj.coord)
+            }
+        }
+    };
+}
+
+macro_rules! define_ck_point_and_line {
+    (impl $point:ident, $line:ident) => {
+        define_ck_point_and_line!(impl [] $point [], $line);
+    };
+    (impl [$($gen:tt)*] $point:ident [$($arg:tt)*], $line:ident) => {
+        define_point_and_line!(impl [$($gen)*] $point [$($arg)*], $line);
+        define_pg_conversion!(impl [$($gen)*] PgPoint, $point [$($arg)*]);
+        define_pg_conversion!(impl [$($gen)*] PgLine, $line [$($arg)*]);
     };
 }
 
 define_point_and_line!(impl PgPoint, PgLine);
-define_point_and_line!(impl HyperbolicPoint, HyperbolicLine);
-define_point_and_line!(impl EllipticPoint, EllipticLine);
-define_point_and_line!(impl MyCKPoint, MyCKLine);
-define_point_and_line!(impl PerspPoint, PerspLine);
-define_point_and_line!(impl EuclidPoint, EuclidLine);
+// Over any coordinate ring, e.g. `PgPointOver<Fraction<i64>>` or `PgPointOver<Zp<7>>`
+define_point_and_line!(impl PgPointOver<T>, PgLineOver<T>);
+define_ck_point_and_line!(impl HyperbolicPoint, HyperbolicLine);
+define_ck_point_and_line!(impl EllipticPoint, EllipticLine);
+// The Cayley-Klein planes whose absolute is `A x² + B y² + C z² = 0`
+define_ck_point_and_line!(
+    impl [const A: i64, const B: i64, const C: i64] DiagonalCKPoint [A, B, C],
+    DiagonalCKLine
+);
+define_ck_point_and_line!(impl PerspPoint, PerspLine);
+define_ck_point_and_line!(impl EuclidPoint, EuclidLine);
 // You may add your own geometry here
+
+/// Point of the Cayley-Klein plane with the absolute `-2 x² + y² - 2 z² = 0`
+pub type MyCKPoint = DiagonalCKPoint<-2, 1, -2>;
+/// Line of the Cayley-Klein plane with the absolute `-2 x² + y² - 2 z² = 0`
+pub type MyCKLine = DiagonalCKLine<-2, 1, -2>;
+
+/// Point with rational homogeneous coordinates
+pub type RatPoint = PgPointOver<Fraction<i64>>;
+/// Line with rational homogeneous coordinates
+pub type RatLine = PgLineOver<Fraction<i64>>;
+
+/// Scale finite rational coordinates to coprime integer coordinates. Fails with
+/// `GeometryError::ZeroDenominator` if a coordinate is not finite, and with
+/// `GeometryError::Overflow` if the integer coordinates do not fit into i64.
+fn clear_denominators(coord: &[Fraction<i64>; 3]) -> Result<[i64; 3], GeometryError> {
+    if coord.iter().any(|x| x.denom == 0) {
+        return Err(GeometryError::ZeroDenominator);
+    }
+    let common = coord
+        .iter()
+        .try_fold(1_i128, |acc, x| {
+            let denom = x.denom as i128;
+            acc.checked_mul(denom / acc.gcd(&denom))
+        })
+        .ok_or(GeometryError::Overflow)?;
+    let mut scaled = [0_i128; 3];
+    for (s, x) in scaled.iter_mut().zip(coord) {
+        *s = (x.numer as i128)
+            .checked_mul(common / x.denom as i128)
+            .ok_or(GeometryError::Overflow)?;
+    }
+    let divisor = scaled.iter().fold(0_i128, |g, x| g.gcd(x)).max(1);
+    let mut result = [0_i64; 3];
+    for (r, s) in result.iter_mut().zip(scaled) {
+        *r = i64::try_from(s / divisor).map_err(|_| GeometryError::Overflow)?;
+    }
+    Ok(result)
+}
+
+macro_rules! define_rat_conversion {
+    (impl $pg:ident, $rat:ident) => {
+        impl From<$pg> for $rat {
+            #[inline]
+            fn from(obj: $pg) -> Self {
+                Self::new(obj.coord.map(|x| Fraction::new(x, 1)))
+            }
+        }
+
+        impl TryFrom<$rat> for $pg {
+            type Error = GeometryError;
+
+            /// Clear the denominators. Fails with `GeometryError::ZeroDenominator` if a
+            /// coordinate is not finite, and with `GeometryError::Overflow` if the integer
+            /// coordinates do not fit into i64.
+            #[inline]
+            fn try_from(obj: $rat) -> Result<Self, Self::Error> {
+                Ok(Self::new(clear_denominators(&obj.coord)?))
+            }
+        }
+    };
+}
+
+define_rat_conversion!(impl PgPoint, RatPoint);
+define_rat_conversion!(impl PgLine, RatLine);
+
+impl PgPoint {
+    /// The function `from_affine` embeds the affine point `(x, y)` into the projective plane.
+    ///
+    /// Arguments:
+    ///
+    /// * `x`: The x-coordinate of the affine point.
+    /// * `y`: The y-coordinate of the affine point.
+    ///
+    /// Returns:
+    ///
+    /// The function `from_affine` returns the point `[x : y : 1]`, scaled to integer coordinates.
+    /// It fails with `GeometryError::ZeroDenominator` if a coordinate is not finite, and with
+    /// `GeometryError::Overflow` if the integer coordinates do not fit into i64.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use fractions::Fraction;
+    /// use projgeom_rs::pg_object::PgPoint;
+    /// use projgeom_rs::GeometryError;
+    /// let pt_p = PgPoint::from_affine(Fraction::new(1, 2), Fraction::new(-2, 3)).unwrap();
+    /// assert_eq!(pt_p.coord, [3, -4, 6]);
+    /// let inf = Fraction::new(1, 0);
+    /// assert_eq!(PgPoint::from_affine(inf, inf), Err(GeometryError::ZeroDenominator));
+    /// ```
+    pub fn from_affine(x: Fraction<i64>, y: Fraction<i64>) -> Result<PgPoint, GeometryError> {
+        let coord = clear_denominators(&[x, y, Fraction::from(1)])?;
+        Ok(PgPoint::new(coord))
+    }
+
+    /// The function `to_affine` extracts the affine coordinates of a point.
+    ///
+    /// Returns:
+    ///
+    /// The function `to_affine` returns `(x, y)`, or `GeometryError::PointAtInfinity` for a point
+    /// at infinity.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use fractions::Fraction;
+    /// use projgeom_rs::pg_object::PgPoint;
+    /// use projgeom_rs::GeometryError;
+    /// let pt_p = PgPoint::new([3, -4, 6]);
+    /// assert_eq!(pt_p.to_affine(), Ok((Fraction::new(1, 2), Fraction::new(-2, 3))));
+    /// assert_eq!(PgPoint::new([1, 2, 0]).to_affine(), Err(GeometryError::PointAtInfinity));
+    /// ```
+    pub fn to_affine(&self) -> Result<(Fraction<i64>, Fraction<i64>), GeometryError> {
+        let [x, y, z] = self.coord;
+        if z == 0 {
+            return Err(GeometryError::PointAtInfinity);
+        }
+        Ok((Fraction::new(x, z), Fraction::new(y, z)))
+    }
+
+    /// The `harm_conj` method is a fast path for the generic `harm_conj` on `PgPoint`, the
+    /// harmonic conjugate of `pt_c` with respect to `self` and `pt_b`. Instead of constructing the
+    /// intermediate lines, it expands them with `(a × b) × c = (a·c) b - (b·c) a`, so the result
+    /// is `λ a + μ b` with `λ = (b·b)(a·c) - (a·b)(b·c)` and `μ = (a·b)(a·c) - (a·a)(b·c)`,
+    /// evaluated in i128.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the points are not collinear, or if the result does not fit into i64 after
+    /// dividing out common factors.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::{harm_conj, PgPoint};
+    /// let [pt_a, pt_b, pt_c] = [[1, 0, 1], [5, 0, 1], [2, 0, 1]].map(PgPoint::new);
+    /// assert_eq!(pt_a.harm_conj(&pt_b, &pt_c), PgPoint::new([-1, 0, 1]));
+    /// assert_eq!(pt_a.harm_conj(&pt_b, &pt_c), harm_conj(&pt_a, &pt_b, &pt_c));
+    /// ```
+    pub fn harm_conj(&self, pt_b: &PgPoint, pt_c: &PgPoint) -> PgPoint {
+        let (a, b, c) = (&self.coord, &pt_b.coord, &pt_c.coord);
+        let det =
+            det3_small(a, b, c).map_or_else(|| det3_checked(a, b, c), |det| Some(det as i128));
+        assert!(det == Some(0), "points are not collinear");
+        let coord = harm_conj_coord(a, b, c)
+            .or_else(|| harm_conj_coord_wide(a, b, c))
+            .expect("harmonic conjugate overflows i64");
+        audit_record!(HarmConj, &coord);
+        PgPoint::new(coord)
+    }
+
+    /// The `involution` method is a fast path for the generic `involution` on `PgPoint`, the
+    /// image of `pt_p` under the harmonic homology with center `self` and axis `mirror`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a coordinate overflows i64.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::{involution, PgLine, PgPoint};
+    /// let origin = PgPoint::new([0, 0, 1]);
+    /// let mirror = PgLine::new([1, 0, -2]);
+    /// let pt_p = PgPoint::new([1, 3, 1]);
+    /// assert_eq!(origin.involution(&mirror, &pt_p), involution(&origin, &mirror, &pt_p));
+    /// ```
+    pub fn involution(&self, mirror: &PgLine, pt_p: &PgPoint) -> PgPoint {
+        let pt_b = pt_p.join(self).meet(mirror);
+        self.harm_conj(&pt_b, pt_p)
+    }
+}
+
+impl PgLine {
+    /// The function `from_slope_intercept` embeds the affine line `y = slope * x + intercept` into the
+    /// projective plane.
+    ///
+    /// Arguments:
+    ///
+    /// * `slope`: The slope of the affine line.
+    /// * `intercept`: The y-intercept of the affine line.
+    ///
+    /// Returns:
+    ///
+    /// The function `from_slope_intercept` returns the line `[slope : -1 : intercept]`, scaled to integer
+    /// coordinates. It fails with `GeometryError::ZeroDenominator` if an argument is not finite,
+    /// and with `GeometryError::Overflow` if the integer coordinates do not fit into i64.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use fractions::Fraction;
+    /// use projgeom_rs::pg_object::{PgLine, PgPoint};
+    /// use projgeom_rs::pg_plane::ProjectivePlanePrimitive;
+    /// let ln_l = PgLine::from_slope_intercept(Fraction::new(1, 2), Fraction::from(3)).unwrap();
+    /// assert!(ln_l.incident(&PgPoint::new([2, 4, 1])));
+    /// ```
+    pub fn from_slope_intercept(
+        slope: Fraction<i64>,
+        intercept: Fraction<i64>,
+    ) -> Result<PgLine, GeometryError> {
+        let coord = clear_denominators(&[slope, Fraction::from(-1), intercept])?;
+        Ok(PgLine::new(coord))
+    }
+
+    /// The function `to_slope_intercept` extracts the slope and the y-intercept of a line.
+    ///
+    /// Returns:
+    ///
+    /// The function `to_slope_intercept` returns `Some((slope, intercept))`, or `None` for a vertical line
+    /// or the line at infinity.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use fractions::Fraction;
+    /// use projgeom_rs::pg_object::PgLine;
+    /// let ln_l = PgLine::new([1, -2, 6]);
+    /// assert_eq!(ln_l.to_slope_intercept(), Some((Fraction::new(1, 2), Fraction::from(3))));
+    /// assert_eq!(PgLine::new([1, 0, 6]).to_slope_intercept(), None);
+    /// ```
+    pub fn to_slope_intercept(&self) -> Option<(Fraction<i64>, Fraction<i64>)> {
+        let [a, b, c] = self.coord;
+        if b == 0 {
+            return None;
+        }
+        Some((Fraction::new(-a, b), Fraction::new(-c, b)))
+    }
+}
+
+/// The function `projective_rank` calculates the rank of the matrix whose rows are the homogeneous
+/// coordinates of the given points, using exact integer arithmetic.
+///
+/// Arguments:
+///
+/// * `points`: A slice of points in the projective plane.
+///
+/// Returns:
+///
+/// The function `projective_rank` returns 0 if all coordinates vanish, 1 if all points coincide,
+/// 2 if they are collinear, and 3 otherwise.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::pg_object::{projective_rank, PgPoint};
+/// let pt_p = PgPoint::new([1, 2, 3]);
+/// let pt_q = PgPoint::new([2, 4, 6]);
+/// let pt_r = PgPoint::new([4, 5, 6]);
+/// assert_eq!(projective_rank(&[pt_p.clone(), pt_q.clone()]), 1);
+/// assert_eq!(projective_rank(&[pt_p, pt_q, pt_r]), 2);
+/// ```
+pub fn projective_rank(points: &[PgPoint]) -> usize {
+    let mut nonzero = points.iter().filter(|pt| pt.coord != [0, 0, 0]);
+    let Some(pt_p) = nonzero.next() else {
+        return 0;
+    };
+    // Every point skipped by `find` coincides with `pt_p`.
+    let Some(pt_q) = nonzero.find(|pt_q| *pt_q != pt_p) else {
+        return 1;
+    };
+    let ln_l = pt_p.join(pt_q);
+    if nonzero.all(|pt_r| ln_l.incident(pt_r)) {
+        2
+    } else {
+        3
+    }
+}
+
+/// The function `in_general_position` checks if no three of four points are collinear, i.e. if they
+/// form a projective frame.
+///
+/// Arguments:
+///
+/// * `quadrangle`: An array of four points in the projective plane.
+///
+/// Returns:
+///
+/// The function `in_general_position` returns a boolean value.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::pg_object::{in_general_position, PgPoint};
+/// let quadrangle = [
+///     PgPoint::new([1, 0, 0]),
+///     PgPoint::new([0, 1, 0]),
+///     PgPoint::new([0, 0, 1]),
+///     PgPoint::new([1, 1, 1]),
+/// ];
+/// assert!(in_general_position(&quadrangle));
+/// ```
+pub fn in_general_position(quadrangle: &[PgPoint; 4]) -> bool {
+    [[0, 1, 2], [0, 1, 3], [0, 2, 3], [1, 2, 3]]
+        .iter()
+        .all(|&[i, j, k]| {
+            !det3_is_zero(
+                &quadrangle[i].coord,
+                &quadrangle[j].coord,
+                &quadrangle[k].coord,
+            )
+        })
+}
+
+/// The function `check_desargues_involution` checks Desargues' involution theorem for the pencil of
+/// conics through four points. The three pairs of opposite sides of the quadrangle are the degenerate
+/// members of the pencil, so the three pairs of points in which they cut a line must belong to one
+/// involution.
+///
+/// Arguments:
+///
+/// * `quadrangle`: An array of four points, no three of them collinear, through which the pencil of
+///   conics passes.
+/// * `ln_l`: The line cut by the members of the pencil.
+///
+/// Returns:
+///
+/// The function `check_desargues_involution` returns a boolean value.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::pg_object::{check_desargues_involution, PgLine, PgPoint};
+/// let quadrangle = [
+///     PgPoint::new([1, 0, 0]),
+///     PgPoint::new([0, 1, 0]),
+///     PgPoint::new([0, 0, 1]),
+///     PgPoint::new([1, 1, 1]),
+/// ];
+/// assert!(check_desargues_involution(&quadrangle, &PgLine::new([1, 2, 5])));
+/// ```
+pub fn check_desargues_involution(quadrangle: &[PgPoint; 4], ln_l: &PgLine) -> bool {
+    let [pt_a, pt_b, pt_c, pt_d] = quadrangle;
+    let opposite_sides = [
+        (pt_a.join(pt_b), pt_c.join(pt_d)),
+        (pt_a.join(pt_c), pt_b.join(pt_d)),
+        (pt_a.join(pt_d), pt_b.join(pt_c)),
+    ];
+    // Project the line from a base point not on it, so that two coordinates
+    // serve as homogeneous parameters of the points on the line.
+    let k = ln_l.coord.iter().position(|&c| c != 0).unwrap_or(2);
+    let (i, j) = ((k + 1) % 3, (k + 2) % 3);
+    // Each pair {s, t} of an involution satisfies the same symmetric bilinear
+    // equation a*s0*t0 + b*(s0*t1 + s1*t0) + c*s1*t1 = 0.
+    let rows = opposite_sides.map(|(ln_m, ln_n)| {
+        let pt_s = ln_m.meet(ln_l);
+        let pt_t = ln_n.meet(ln_l);
+        let (s_0, s_1) = (pt_s.coord[i] as i128, pt_s.coord[j] as i128);
+        let (t_0, t_1) = (pt_t.coord[i] as i128, pt_t.coord[j] as i128);
+        [s_0 * t_0, s_0 * t_1 + s_1 * t_0, s_1 * t_1]
+    });
+    let [r_0, r_1, r_2] = rows;
+    r_0[0] * (r_1[1] * r_2[2] - r_1[2] * r_2[1]) - r_0[1] * (r_1[0] * r_2[2] - r_1[2] * r_2[0])
+        + r_0[2] * (r_1[0] * r_2[1] - r_1[1] * r_2[0])
+        == 0
+}
@@ -1,11 +1,43 @@
+use crate::error::GeometryError;
+
 /// The `ProjectivePlanePrimitive` trait defines the behavior of points and lines in a projective plane.
 /// It requires two associated types: `Dual`, which represents the dual object (line or point) in the
 /// projective plane, and `Self`, which represents the object implementing the trait.
+///
+/// By duality, one method `meet` computes both the meet of two lines and the join of two points,
+/// so that generic code serves points and lines alike. Points also implement `Join`.
 pub trait ProjectivePlanePrimitive<Dual>: Eq {
     fn meet(&self, rhs: &Self) -> Dual; // join or meet
     fn incident(&self, dual: &Dual) -> bool; // incidence
 }
 
+/// The `Join` trait is implemented by the points of a projective plane, and names their `meet` as
+/// what it is: the line through them. Lines do not implement it, so `join` cannot be applied to
+/// them by mistake.
+pub trait Join<Line>: ProjectivePlanePrimitive<Line> {
+    /// The line through two points.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::{Join, PgLine, PgPoint, ProjectivePlanePrimitive};
+    /// let ln_l = PgPoint::new([0, 0, 1]).join(&PgPoint::new([1, 1, 1]));
+    /// assert_eq!(ln_l, PgLine::new([1, -1, 0]));
+    /// assert_eq!(ln_l.meet(&PgLine::new([0, 1, -2])), PgPoint::new([2, 2, 1]));
+    /// ```
+    ///
+    /// Lines have no `join`:
+    ///
+    /// ```compile_fail
+    /// use projgeom_rs::{Join, PgLine};
+    /// let _ = PgLine::new([1, 0, 0]).join(&PgLine::new([0, 1, 0]));
+    /// ```
+    #[inline]
+    fn join(&self, rhs: &Self) -> Line {
+        self.meet(rhs)
+    }
+}
+
 /// The function `check_axiom` checks if certain axioms hold for points and lines in a projective plane.
 ///
 /// Arguments:
@@ -15,8 +47,8 @@ pub trait ProjectivePlanePrimitive<Dual>: Eq {
 /// * `ln_l`: The parameter `ln_l` represents a line in a projective plane.
 pub fn check_axiom<Point, Line>(pt_p: &Point, pt_q: &Point, ln_l: &Line)
 where
-    Point: ProjectivePlanePrimitive<Line> + std::fmt::Debug,
-    Line: ProjectivePlanePrimitive<Point> + std::fmt::Debug,
+    Point: ProjectivePlanePrimitive<Line> + core::fmt::Debug,
+    Line: ProjectivePlanePrimitive<Point> + core::fmt::Debug,
 {
     // assert_eq!(pt_p, pt_p);
     // assert_eq!(pt_p == pt_q, pt_q == pt_p);
@@ -72,6 +104,65 @@ where
     pt_p.meet(pt_q).incident(pt_r)
 }
 
+/// The function `are_collinear` checks if all points of a slice lie on a common line. It stops at the
+/// first point found off the line through the first two distinct points.
+///
+/// Arguments:
+///
+/// * `points`: A slice of points in the projective plane.
+///
+/// Returns:
+///
+/// The function `are_collinear` returns a boolean value. Slices with fewer than three distinct points
+/// are always collinear.
+///
+/// # Examples
+///
+/// ```
+/// use projgeom_rs::pg_plane::are_collinear;
+/// use projgeom_rs::pg_object::{PgLine, PgPoint};
+/// let points = [
+///     PgPoint::new([1, 2, 3]),
+///     PgPoint::new([4, 5, 6]),
+///     PgPoint::new([7, 8, 9]),
+///     PgPoint::new([2, 1, 0]),
+/// ];
+/// assert!(are_collinear::<PgPoint, PgLine>(&points));
+/// ```
+pub fn are_collinear<Point, Line>(points: &[Point]) -> bool
+where
+    Point: ProjectivePlanePrimitive<Line>,
+    Line: ProjectivePlanePrimitive<Point>,
+{
+    let Some(pt_p) = points.first() else {
+        return true;
+    };
+    let Some(pt_q) = points.iter().find(|pt_q| *pt_q != pt_p) else {
+        return true;
+    };
+    let ln_l = pt_p.meet(pt_q);
+    points.iter().all(|pt_r| ln_l.incident(pt_r))
+}
+
+/// The function `are_concurrent` checks if all lines of a slice pass through a common point. It is
+/// the dual of `are_collinear`.
+///
+/// Arguments:
+///
+/// * `lines`: A slice of lines in the projective plane.
+///
+/// Returns:
+///
+/// The function `are_concurrent` returns a boolean value.
+#[inline]
+pub fn are_concurrent<Point, Line>(lines: &[Line]) -> bool
+where
+    Point: ProjectivePlanePrimitive<Line>,
+    Line: ProjectivePlanePrimitive<Point>,
+{
+    are_collinear::<Line, Point>(lines)
+}
+
 /// The function `check_pappus` checks if three points on a projective plane and three lines on another
 /// projective plane satisfy Pappus' theorem.
 ///
@@ -79,8 +170,8 @@ where
 ///
 /// * `coline_1`: The parameter `coline_1` is an array of length 3 containing elements of type `Point`.
 /// * `coline_2`: The `coline_2` parameter is an array of three points `[pt_d, pt_e, pt_f]` in a projective plane. Each
-/// point is represented by a type `Point` that implements the `ProjectivePlanePrimitive<Line>` trait, and `Line` is a type
-/// that implements the `ProjectivePlane
+///   point is represented by a type `Point` that implements the `ProjectivePlanePrimitive<Line>` trait, and `Line` is a type
+///   that implements the `ProjectivePlane
 ///
 /// Returns:
 ///
@@ -124,16 +215,47 @@ where
 /// Returns:
 ///
 /// The function `tri_dual` returns an array of three elements, where each element is of type `Line`.
+///
+/// # Panics
+///
+/// Panics if the vertices are collinear; see `try_tri_dual`.
 )]
 #[inline]
 pub fn tri_dual<Point, Line>(triangle: &[Point; 3]) -> [Line; 3]
+where
+    Point: ProjectivePlanePrimitive<Line>,
+    Line: ProjectivePlanePrimitive<Point>,
+{
+    try_tri_dual(triangle).expect("vertices of the triangle are collinear")
+}
+
+/// The function `try_tri_dual` is the non-panicking version of `tri_dual`.
+///
+/// Returns:
+///
+/// The sides of the triangle, or `Err(InvalidTriangle)` if the vertices are collinear.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::{try_tri_dual, GeometryError, PgLine, PgPoint};
+/// let triangle = [[1, 0, 0], [0, 1, 0], [0, 0, 1]].map(PgPoint::new);
+/// let trilateral: [PgLine; 3] = try_tri_dual(&triangle).unwrap();
+/// assert_eq!(trilateral[0], PgLine::new([1, 0, 0]));
+/// let collinear = [[1, 0, 1], [2, 0, 1], [3, 0, 1]].map(PgPoint::new);
+/// assert_eq!(try_tri_dual::<_, PgLine>(&collinear), Err(GeometryError::InvalidTriangle));
+/// ```
+#[inline]
+pub fn try_tri_dual<Point, Line>(triangle: &[Point; 3]) -> Result<[Line; 3], GeometryError>
 where
     Point: ProjectivePlanePrimitive<Line>,
     Line: ProjectivePlanePrimitive<Point>,
 {
     let [a_1, a_2, a_3] = triangle;
-    assert!(!coincident(a_1, a_2, a_3));
-    [a_2.meet(a_3), a_1.meet(a_3), a_1.meet(a_2)]
+    if coincident(a_1, a_2, a_3) {
+        return Err(GeometryError::InvalidTriangle);
+    }
+    Ok([a_2.meet(a_3), a_1.meet(a_3), a_1.meet(a_2)])
 }
 
 /// The function `persp` determines whether two triangles are perspective.
@@ -165,7 +287,7 @@ where
 ///
 /// * `tri1`: An array of 3 points representing the first triangle in the projective plane.
 /// * `tri2`: tri2 is an array of 3 points representing the vertices of a triangle in a projective
-/// plane.
+///   plane.
 ///
 /// Returns:
 ///
@@ -200,8 +322,8 @@ pub trait ProjectivePlane<Dual, Value: Default + Eq>: ProjectivePlanePrimitive<D
 /// * `ln_l`: The parameter `ln_l` represents a line in a projective plane.
 /// * `pt_a`: The parameter `pt_a` represents a point in the projective plane.
 /// * `pt_b`: The parameter `pt_b` represents a value of type `Value` which is used as an argument in
-/// the function `check_axiom2`. The specific meaning or purpose of `pt_b` would depend on the
-/// implementation details of the `ProjectivePlane` trait and its associated types `Point`
+///   the function `check_axiom2`. The specific meaning or purpose of `pt_b` would depend on the
+///   implementation details of the `ProjectivePlane` trait and its associated types `Point`
 #[allow(dead_code)]
 pub fn check_axiom2<Point, Line, Value>(
     pt_p: &Point,
@@ -232,6 +354,10 @@ pub fn check_axiom2<Point, Line, Value>(
 ///
 /// The function `harm_conj` returns a value of type `Point`, which is the harmonic conjugate of the points
 /// `pt_a`, `pt_b`, and `pt_c`.
+///
+/// # Panics
+///
+/// Panics if the points are not collinear, or `pt_a` and `pt_b` coincide; see `try_harm_conj`.
 #[inline]
 pub fn harm_conj<Point, Line, Value>(pt_a: &Point, pt_b: &Point, pt_c: &Point) -> Point
 where
@@ -239,10 +365,49 @@ where
     Point: ProjectivePlane<Line, Value>,
     Line: ProjectivePlane<Point, Value>,
 {
-    assert!(coincident(pt_a, pt_b, pt_c));
+    try_harm_conj(pt_a, pt_b, pt_c).expect("points are not collinear and distinct")
+}
+
+/// The function `try_harm_conj` is the non-panicking version of `harm_conj`.
+///
+/// Returns:
+///
+/// The harmonic conjugate of `pt_c` with respect to `pt_a` and `pt_b`, `Err(NotCollinear)` if
+/// the points are not collinear, or `Err(NotInGeneralPosition)` if `pt_a` and `pt_b` coincide.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::{try_harm_conj, GeometryError, PgPoint};
+/// let [pt_a, pt_b, pt_c] = [[0, 0, 1], [2, 0, 1], [1, 0, 0]].map(PgPoint::new);
+/// assert_eq!(try_harm_conj(&pt_a, &pt_b, &pt_c), Ok(PgPoint::new([1, 0, 1])));
+/// let pt_d = PgPoint::new([1, 1, 1]);
+/// assert_eq!(try_harm_conj(&pt_a, &pt_b, &pt_d), Err(GeometryError::NotCollinear));
+/// assert_eq!(
+///     try_harm_conj(&pt_a, &pt_a, &pt_c),
+///     Err(GeometryError::NotInGeneralPosition)
+/// );
+/// ```
+#[inline]
+pub fn try_harm_conj<Point, Line, Value>(
+    pt_a: &Point,
+    pt_b: &Point,
+    pt_c: &Point,
+) -> Result<Point, GeometryError>
+where
+    Value: Default + Eq,
+    Point: ProjectivePlane<Line, Value>,
+    Line: ProjectivePlane<Point, Value>,
+{
+    if pt_a == pt_b {
+        return Err(GeometryError::NotInGeneralPosition);
+    }
+    if !coincident(pt_a, pt_b, pt_c) {
+        return Err(GeometryError::NotCollinear);
+    }
     let ln_ab = pt_a.meet(pt_b);
     let ln_xc = ln_ab.aux().meet(pt_c);
-    pt_a.parametrize(ln_xc.dot(pt_b), pt_b, ln_xc.dot(pt_a))
+    Ok(pt_a.parametrize(ln_xc.dot(pt_b), pt_b, ln_xc.dot(pt_a)))
 }
 
 /// The function `involution` performs an involution transformation on a point `pt_p` with respect to an
@@ -252,14 +417,18 @@ where
 ///
 /// * `origin`: The `origin` parameter represents the origin point in the projective plane.
 /// * `mirror`: The `mirror` parameter represents a mirror line or mirror plane in a projective
-/// geometry. It is used to perform an involution transformation on a point `pt_p` with respect to an
-/// origin point `origin`.
+///   geometry. It is used to perform an involution transformation on a point `pt_p` with respect to an
+///   origin point `origin`.
 /// * `pt_p`: The parameter `pt_p` represents a point in the projective plane.
 ///
 /// Returns:
 ///
 /// The function `involution` returns a value of type `Point`, which is the projected point on the
 /// projection plane.
+///
+/// # Panics
+///
+/// Panics if `pt_p` is `origin`, or `origin` lies on `mirror`.
 #[allow(dead_code)]
 #[inline]
 pub fn involution<Point, Line, Value>(origin: &Point, mirror: &Line, pt_p: &Point) -> Point
@@ -273,6 +442,106 @@ where
     harm_conj(origin, &pt_b, pt_p)
 }
 
+/// The `assert_projectively_eq!` macro asserts that two points or lines with i64 coordinates are
+/// equal up to a nonzero factor, like `assert_eq!` but printing the normalized coordinates (see
+/// `normalize_homogeneous`) on failure, so that the two sides are easy to compare. An optional
+/// message may follow, as for `assert_eq!`.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::{assert_projectively_eq, PgPoint};
+/// assert_projectively_eq!(PgPoint::new([1, 2, 3]), PgPoint::new([-2, -4, -6]));
+/// ```
+///
+/// ```should_panic
+/// use projgeom_rs::{assert_projectively_eq, PgPoint};
+/// // panics with "left: [1, 2, 3]" and "right: [1, 2, 4]"
+/// assert_projectively_eq!(PgPoint::new([-1, -2, -3]), PgPoint::new([2, 4, 8]));
+/// ```
+#[macro_export]
+macro_rules! assert_projectively_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::assert_projectively_eq!($left, $right, "")
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if !(*left == *right) {
+                    panic!(
+                        "assertion `left == right` (up to scale) failed: {}\n  left: {:?}\n right: {:?}",
+                        format_args!($($arg)+),
+                        $crate::pg_object::normalize_homogeneous(
+                            $crate::HomogeneousCoords::coords(left)
+                        ),
+                        $crate::pg_object::normalize_homogeneous(
+                            $crate::HomogeneousCoords::coords(right)
+                        ),
+                    );
+                }
+            }
+        }
+    };
+}
+
+/// The `assert_incident!` macro asserts that a point lies on a line (or, dually, a line passes
+/// through a point), printing both on failure.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::{assert_incident, PgLine, PgPoint};
+/// assert_incident!(PgPoint::new([1, 1, 1]), PgLine::new([1, -1, 0]));
+/// ```
+#[macro_export]
+macro_rules! assert_incident {
+    ($obj:expr, $dual:expr $(,)?) => {
+        match (&$obj, &$dual) {
+            (obj, dual) => {
+                if !$crate::pg_plane::ProjectivePlanePrimitive::incident(obj, dual) {
+                    panic!(
+                        "assertion failed: `{}` is incident with `{}`\n  left: {:?}\n right: {:?}",
+                        stringify!($obj),
+                        stringify!($dual),
+                        obj,
+                        dual,
+                    );
+                }
+            }
+        }
+    };
+}
+
+/// The `assert_collinear!` macro asserts that three points are collinear (or three lines are
+/// concurrent), printing them on failure; see `coincident`.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::{assert_collinear, PgPoint};
+/// assert_collinear!(PgPoint::new([1, 2, 3]), PgPoint::new([4, 5, 6]), PgPoint::new([7, 8, 9]));
+/// ```
+#[macro_export]
+macro_rules! assert_collinear {
+    ($obj_p:expr, $obj_q:expr, $obj_r:expr $(,)?) => {
+        match (&$obj_p, &$obj_q, &$obj_r) {
+            (obj_p, obj_q, obj_r) => {
+                if !$crate::pg_plane::coincident(obj_p, obj_q, obj_r) {
+                    panic!(
+                        "assertion failed: `{}`, `{}` and `{}` are collinear\n  {:?}\n  {:?}\n  {:?}",
+                        stringify!($obj_p),
+                        stringify!($obj_q),
+                        stringify!($obj_r),
+                        obj_p,
+                        obj_q,
+                        obj_r,
+                    );
+                }
+            }
+        }
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use crate::pg_plane::ProjectivePlanePrimitive;
@@ -333,4 +602,32 @@ mod tests {
         println!("{}", coincident(&pt_p, &pt_q, &pt_r));
         check_axiom(&pt_p, &pt_q, &ln_l);
     }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_join() {
+        use crate::pg_object::{PgLine, PgPoint};
+        use crate::pg_plane::Join;
+        let (pt_p, pt_q) = (PgPoint::new([1, 2, 1]), PgPoint::new([3, -1, 2]));
+        let ln_l = pt_p.join(&pt_q);
+        assert_eq!(ln_l, PgLine::new([5, 1, -7]));
+        // the old name of the join is kept as an alias
+        assert_eq!(pt_p.meet(&pt_q), ln_l);
+        assert_eq!(ProjectivePlanePrimitive::meet(&pt_p, &pt_q), ln_l);
+    }
+
+    #[test]
+    #[should_panic(expected = "left: [1, 2, 3]\n right: [1, 2, 4]")]
+    fn test_assert_projectively_eq() {
+        use crate::pg_object::PgPoint;
+        assert_projectively_eq!(PgPoint::new([-1, -2, -3]), PgPoint::new([2, 4, 8]));
+    }
+
+    #[test]
+    #[should_panic(expected = "`pt_p` is incident with `ln_l`")]
+    fn test_assert_incident() {
+        use crate::pg_object::{PgLine, PgPoint};
+        let (pt_p, ln_l) = (PgPoint::new([1, 2, 1]), PgLine::new([1, -1, 0]));
+        assert_incident!(pt_p, ln_l);
+    }
 }
@@ -0,0 +1,333 @@
+// Polygons and convex hulls with exact predicates
+
+use crate::error::GeometryError;
+use crate::pg_object::PgPoint;
+use crate::predicates::{compare_coord, orientation, Orientation};
+use core::cmp::Ordering;
+use fractions::Fraction;
+use num_integer::Integer;
+
+/// Compare two affine points lexicographically by `(x, y)`.
+#[inline]
+fn compare_xy(pt_p: &PgPoint, pt_q: &PgPoint) -> Result<Ordering, GeometryError> {
+    Ok(compare_coord(pt_p, pt_q, 0)?.then(compare_coord(pt_p, pt_q, 1)?))
+}
+
+/// Check if a point lies on the closed segment between two points.
+fn on_segment(pt_a: &PgPoint, pt_b: &PgPoint, pt_p: &PgPoint) -> Result<bool, GeometryError> {
+    if orientation(pt_a, pt_b, pt_p)? != Orientation::Collinear {
+        return Ok(false);
+    }
+    for axis in 0..2 {
+        let (lo, hi) = match compare_coord(pt_a, pt_b, axis)? {
+            Ordering::Greater => (pt_b, pt_a),
+            _ => (pt_a, pt_b),
+        };
+        if compare_coord(pt_p, lo, axis)? == Ordering::Less
+            || compare_coord(pt_p, hi, axis)? == Ordering::Greater
+        {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// The `Polygon` struct is a closed polygon given by its affine vertices in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polygon {
+    pub vertices: Vec<PgPoint>,
+}
+
+impl Polygon {
+    /// Create a polygon from its vertices.
+    #[inline]
+    pub fn new(vertices: Vec<PgPoint>) -> Self {
+        Self { vertices }
+    }
+
+    /// The edges `(v[i], v[i + 1])`, including the closing edge.
+    fn edges(&self) -> impl Iterator<Item = (&PgPoint, &PgPoint)> {
+        self.vertices
+            .iter()
+            .zip(self.vertices.iter().cycle().skip(1))
+    }
+
+    /// The function `signed_area` computes the exact signed area of the polygon by the shoelace
+    /// formula. It is positive for counter-clockwise vertices.
+    ///
+    /// Returns:
+    ///
+    /// The function `signed_area` returns the area as a reduced fraction,
+    /// `GeometryError::PointAtInfinity`, or `GeometryError::Overflow` if the result does not fit.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use fractions::Fraction;
+    /// use projgeom_rs::polygon::Polygon;
+    /// use projgeom_rs::PgPoint;
+    /// let triangle = Polygon::new(vec![
+    ///     PgPoint::new([0, 0, 1]),
+    ///     PgPoint::new([1, 0, 1]),
+    ///     PgPoint::new([0, 1, 2]),
+    /// ]);
+    /// assert_eq!(triangle.signed_area(), Ok(Fraction::new(1, 4)));
+    /// ```
+    pub fn signed_area(&self) -> Result<Fraction<i64>, GeometryError> {
+        let overflow = || GeometryError::Overflow;
+        // twice the area as numer / denom
+        let (mut numer, mut denom) = (0_i128, 1_i128);
+        for (pt_p, pt_q) in self.edges() {
+            if pt_p.coord[2] == 0 || pt_q.coord[2] == 0 {
+                return Err(GeometryError::PointAtInfinity);
+            }
+            let [p_x, p_y, p_z] = pt_p.coord.map(i128::from);
+            let [q_x, q_y, q_z] = pt_q.coord.map(i128::from);
+            let term = (p_x * q_y).checked_sub(q_x * p_y).ok_or_else(overflow)?;
+            let term_denom = p_z * q_z;
+            numer = numer
+                .checked_mul(term_denom)
+                .zip(term.checked_mul(denom))
+                .and_then(|(a, b)| a.checked_add(b))
+                .ok_or_else(overflow)?;
+            denom = denom.checked_mul(term_denom).ok_or_else(overflow)?;
+            let common = numer.gcd(&denom);
+            numer /= common;
+            denom /= common;
+        }
+        denom = denom.checked_mul(2).ok_or_else(overflow)?;
+        if denom < 0 {
+            (numer, denom) = (-numer, -denom);
+        }
+        let common = numer.gcd(&denom);
+        let numer = i64::try_from(numer / common).map_err(|_| overflow())?;
+        let denom = i64::try_from(denom / common).map_err(|_| overflow())?;
+        Ok(Fraction::new(numer, denom))
+    }
+
+    /// The function `contains_point` checks if an affine point lies inside the polygon or on its
+    /// boundary, by the winding number. For a self-intersecting polygon, points with a nonzero
+    /// winding number are inside.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::polygon::Polygon;
+    /// use projgeom_rs::PgPoint;
+    /// let square = Polygon::new(
+    ///     [[0, 0, 1], [2, 0, 1], [2, 2, 1], [0, 2, 1]].map(PgPoint::new).to_vec(),
+    /// );
+    /// assert_eq!(square.contains_point(&PgPoint::new([1, 1, 1])), Ok(true));
+    /// assert_eq!(square.contains_point(&PgPoint::new([2, 1, 1])), Ok(true));
+    /// assert_eq!(square.contains_point(&PgPoint::new([5, 1, 2])), Ok(false));
+    /// ```
+    pub fn contains_point(&self, pt: &PgPoint) -> Result<bool, GeometryError> {
+        let mut winding = 0;
+        for (pt_a, pt_b) in self.edges() {
+            if on_segment(pt_a, pt_b, pt)? {
+                return Ok(true);
+            }
+            let a_below = compare_coord(pt_a, pt, 1)? != Ordering::Greater;
+            let b_below = compare_coord(pt_b, pt, 1)? != Ordering::Greater;
+            let turn = orientation(pt_a, pt_b, pt)?;
+            if a_below && !b_below && turn == Orientation::CounterClockwise {
+                winding += 1;
+            } else if !a_below && b_below && turn == Orientation::Clockwise {
+                winding -= 1;
+            }
+        }
+        Ok(winding != 0)
+    }
+
+    /// The function `is_convex` checks if the polygon is convex: it turns in one direction only and
+    /// winds around once. Collinear consecutive vertices are allowed, but not a polygon whose
+    /// vertices are all collinear.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::polygon::Polygon;
+    /// use projgeom_rs::PgPoint;
+    /// let square = Polygon::new(
+    ///     [[0, 0, 1], [2, 0, 1], [2, 2, 1], [0, 2, 1]].map(PgPoint::new).to_vec(),
+    /// );
+    /// assert_eq!(square.is_convex(), Ok(true));
+    /// let dart = Polygon::new(
+    ///     [[0, 0, 1], [2, 1, 1], [4, 0, 1], [2, 3, 1]].map(PgPoint::new).to_vec(),
+    /// );
+    /// assert_eq!(dart.is_convex(), Ok(false));
+    /// ```
+    pub fn is_convex(&self) -> Result<bool, GeometryError> {
+        let n = self.vertices.len();
+        if n < 3 {
+            return Ok(false);
+        }
+        let mut turn = Orientation::Collinear;
+        for i in 0..n {
+            let current = orientation(
+                &self.vertices[i],
+                &self.vertices[(i + 1) % n],
+                &self.vertices[(i + 2) % n],
+            )?;
+            if current == Orientation::Collinear {
+                continue;
+            }
+            if turn != Orientation::Collinear && current != turn {
+                return Ok(false);
+            }
+            turn = current;
+        }
+        if turn == Orientation::Collinear {
+            return Ok(false);
+        }
+        // a convex polygon changes its direction along the x-axis at most twice
+        let mut directions = Vec::with_capacity(n);
+        for (pt_p, pt_q) in self.edges() {
+            match compare_coord(pt_p, pt_q, 0)? {
+                Ordering::Equal => {}
+                dir => directions.push(dir),
+            }
+        }
+        let changes = directions
+            .iter()
+            .zip(directions.iter().cycle().skip(1))
+            .filter(|(d_1, d_2)| d_1 != d_2)
+            .count();
+        Ok(changes <= 2)
+    }
+}
+
+/// Append a monotone chain of the hull, without its last point, which starts the next chain.
+fn push_chain<'a>(
+    hull: &mut Vec<PgPoint>,
+    points: impl Iterator<Item = &'a PgPoint>,
+) -> Result<(), GeometryError> {
+    let start = hull.len();
+    for pt in points {
+        while hull.len() >= start + 2
+            && orientation(&hull[hull.len() - 2], &hull[hull.len() - 1], pt)?
+                != Orientation::CounterClockwise
+        {
+            hull.pop();
+        }
+        hull.push(pt.clone());
+    }
+    hull.pop();
+    Ok(())
+}
+
+/// The function `convex_hull` computes the convex hull of a set of affine points by Andrew's
+/// monotone chain algorithm, using the exact orientation predicate.
+///
+/// Arguments:
+///
+/// * `points`: The points, possibly with repetitions.
+///
+/// Returns:
+///
+/// The function `convex_hull` returns the hull as a counter-clockwise `Polygon` without collinear
+/// vertices, starting from the lowest point in `(x, y)` order, or
+/// `GeometryError::PointAtInfinity`. For fewer than three non-collinear points, the polygon
+/// consists of the distinct extreme points.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::polygon::convex_hull;
+/// use projgeom_rs::PgPoint;
+/// let points = [[0, 0, 1], [1, 1, 1], [2, 0, 1], [1, 3, 1], [1, 0, 1], [1, 1, 2]].map(PgPoint::new);
+/// let hull = convex_hull(&points).unwrap();
+/// assert_eq!(hull.vertices, [[0, 0, 1], [2, 0, 1], [1, 3, 1]].map(PgPoint::new));
+/// ```
+pub fn convex_hull(points: &[PgPoint]) -> Result<Polygon, GeometryError> {
+    if points.iter().any(|pt| pt.coord[2] == 0) {
+        return Err(GeometryError::PointAtInfinity);
+    }
+    let mut sorted = points.to_vec();
+    let mut error = None;
+    sorted.sort_by(|pt_p, pt_q| {
+        compare_xy(pt_p, pt_q).unwrap_or_else(|err| {
+            error = Some(err);
+            Ordering::Equal
+        })
+    });
+    if let Some(err) = error {
+        return Err(err);
+    }
+    sorted.dedup();
+    if sorted.len() < 3 {
+        return Ok(Polygon::new(sorted));
+    }
+    let mut hull: Vec<PgPoint> = Vec::with_capacity(2 * sorted.len());
+    // lower and upper hull
+    push_chain(&mut hull, sorted.iter())?;
+    push_chain(&mut hull, sorted.iter().rev())?;
+    Ok(Polygon::new(hull))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convex_hull() {
+        let mut points: Vec<PgPoint> = (-3..=3)
+            .flat_map(|x| (-3..=3).map(move |y| PgPoint::new([x, y, 1])))
+            .filter(|pt| pt.coord[0].abs() + pt.coord[1].abs() <= 3)
+            .collect();
+        points.push(PgPoint::new([6, 0, -2]));
+        let hull = convex_hull(&points).unwrap();
+        assert_eq!(
+            hull.vertices,
+            [[-3, 0, 1], [0, -3, 1], [3, 0, 1], [0, 3, 1]].map(PgPoint::new)
+        );
+        assert_eq!(hull.signed_area(), Ok(Fraction::from(18)));
+        assert_eq!(hull.is_convex(), Ok(true));
+        for pt in &points {
+            assert_eq!(hull.contains_point(pt), Ok(true));
+        }
+        assert_eq!(hull.contains_point(&PgPoint::new([7, 7, 4])), Ok(false));
+
+        // collinear input
+        let line = [[2, 2, 1], [0, 0, 1], [1, 1, 1], [4, 4, 2]].map(PgPoint::new);
+        assert_eq!(
+            convex_hull(&line).unwrap().vertices,
+            [[0, 0, 1], [2, 2, 1]].map(PgPoint::new)
+        );
+        assert_eq!(
+            convex_hull(&[PgPoint::new([1, 0, 0])]),
+            Err(GeometryError::PointAtInfinity)
+        );
+    }
+
+    #[test]
+    fn test_polygon() {
+        // a clockwise L-shape with area 3
+        let shape = Polygon::new(
+            [
+                [0, 0, 1],
+                [0, 2, 1],
+                [1, 2, 1],
+                [1, 1, 1],
+                [2, 1, 1],
+                [2, 0, 1],
+            ]
+            .map(PgPoint::new)
+            .to_vec(),
+        );
+        assert_eq!(shape.signed_area(), Ok(Fraction::from(-3)));
+        assert_eq!(shape.is_convex(), Ok(false));
+        assert_eq!(shape.contains_point(&PgPoint::new([1, 1, 2])), Ok(true));
+        assert_eq!(shape.contains_point(&PgPoint::new([3, 3, 2])), Ok(false));
+        assert_eq!(shape.contains_point(&PgPoint::new([3, 2, 2])), Ok(true));
+
+        // the pentagram turns in one direction but winds around twice
+        let star = Polygon::new(
+            [[0, 10, 1], [6, -8, 1], [-10, 3, 1], [10, 3, 1], [-6, -8, 1]]
+                .map(PgPoint::new)
+                .to_vec(),
+        );
+        assert_eq!(star.is_convex(), Ok(false));
+        assert_eq!(star.contains_point(&PgPoint::new([0, 0, 1])), Ok(true));
+    }
+}
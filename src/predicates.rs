@@ -0,0 +1,512 @@
+// Exact affine predicates on homogeneous coordinates
+
+use crate::error::GeometryError;
+use crate::pg_object::{det3_checked, HomogeneousCoords, PgPoint};
+use core::cmp::Ordering;
+use fractions::Fraction;
+use num_integer::Integer;
+
+/// The `Orientation` enum describes the turn made by three affine points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    Clockwise,
+    Collinear,
+    CounterClockwise,
+}
+
+/// Sign of the homogenizing coordinate, which must not vanish for an affine point.
+#[inline]
+fn affine_sign<Point: HomogeneousCoords>(pt: &Point) -> Result<i128, GeometryError> {
+    match pt.coords()[2].signum() {
+        0 => Err(GeometryError::PointAtInfinity),
+        sign => Ok(sign as i128),
+    }
+}
+
+/// Narrow an i128 fraction to a reduced `Fraction<i64>`.
+#[inline]
+pub(crate) fn narrow(numer: i128, denom: i128) -> Result<Fraction<i64>, GeometryError> {
+    let common = numer.gcd(&denom);
+    let numer = i64::try_from(numer / common).map_err(|_| GeometryError::Overflow)?;
+    let denom = i64::try_from(denom / common).map_err(|_| GeometryError::Overflow)?;
+    Ok(Fraction::new(numer, denom))
+}
+
+/// The function `orientation` determines whether three affine points make a counter-clockwise turn,
+/// a clockwise turn, or are collinear. The computation is exact and does not divide by the
+/// homogenizing coordinate. It applies to the points of every geometry, as all of them have the
+/// same affine coordinates.
+///
+/// Arguments:
+///
+/// * `pt_p`: The first point.
+/// * `pt_q`: The second point.
+/// * `pt_r`: The third point.
+///
+/// Returns:
+///
+/// The function `orientation` returns the `Orientation` of the three points, or
+/// `GeometryError::PointAtInfinity` if one of them is an ideal point.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::pg_object::PgPoint;
+/// use projgeom_rs::predicates::{orientation, Orientation};
+/// let pt_p = PgPoint::new([0, 0, 1]);
+/// let pt_q = PgPoint::new([2, 0, 2]);
+/// let pt_r = PgPoint::new([0, -1, -1]);
+/// assert_eq!(orientation(&pt_p, &pt_q, &pt_r), Ok(Orientation::CounterClockwise));
+/// ```
+pub fn orientation<Point: HomogeneousCoords>(
+    pt_p: &Point,
+    pt_q: &Point,
+    pt_r: &Point,
+) -> Result<Orientation, GeometryError> {
+    let sign = affine_sign(pt_p)? * affine_sign(pt_q)? * affine_sign(pt_r)?;
+    let det =
+        det3_checked(pt_p.coords(), pt_q.coords(), pt_r.coords()).ok_or(GeometryError::Overflow)?;
+    Ok(match (det.signum() * sign).cmp(&0) {
+        Ordering::Greater => Orientation::CounterClockwise,
+        Ordering::Less => Orientation::Clockwise,
+        Ordering::Equal => Orientation::Collinear,
+    })
+}
+
+/// The function `line_position` determines on which side of a line an affine point lies. For a line
+/// `[a : b : c]` the positive side is where `a * x + b * y + c > 0`.
+///
+/// Arguments:
+///
+/// * `ln_l`: The line.
+/// * `pt_p`: The point.
+///
+/// Returns:
+///
+/// The function `line_position` returns `Ordering::Greater` on the positive side, `Ordering::Less` on
+/// the negative side and `Ordering::Equal` on the line, or `GeometryError::PointAtInfinity`.
+pub fn line_position<Point, Line>(ln_l: &Line, pt_p: &Point) -> Result<Ordering, GeometryError>
+where
+    Point: HomogeneousCoords,
+    Line: HomogeneousCoords,
+{
+    let sign = affine_sign(pt_p)?;
+    let (l, p) = (ln_l.coords(), pt_p.coords());
+    let dot = (0..3)
+        .try_fold(0i128, |acc, i| {
+            acc.checked_add(i128::from(l[i]) * i128::from(p[i]))
+        })
+        .ok_or(GeometryError::Overflow)?;
+    Ok((dot.signum() * sign).cmp(&0))
+}
+
+/// The function `point_in_triangle` checks if an affine point lies inside or on the boundary of a
+/// triangle.
+///
+/// Arguments:
+///
+/// * `pt_p`: The point.
+/// * `triangle`: The three vertices of the triangle.
+///
+/// Returns:
+///
+/// The function `point_in_triangle` returns a boolean value, `GeometryError::InvalidTriangle` for
+/// collinear vertices, or `GeometryError::PointAtInfinity`.
+pub fn point_in_triangle<Point: HomogeneousCoords>(
+    pt_p: &Point,
+    triangle: &[Point; 3],
+) -> Result<bool, GeometryError> {
+    let [a_1, a_2, a_3] = triangle;
+    let turn = orientation(a_1, a_2, a_3)?;
+    if turn == Orientation::Collinear {
+        return Err(GeometryError::InvalidTriangle);
+    }
+    for (pt_a, pt_b) in [(a_1, a_2), (a_2, a_3), (a_3, a_1)] {
+        let side = orientation(pt_a, pt_b, pt_p)?;
+        if side != turn && side != Orientation::Collinear {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// The function `compare_coord` compares two affine points by their x-coordinates (`axis == 0`) or
+/// y-coordinates (`axis == 1`) without dividing by the homogenizing coordinate.
+///
+/// Returns:
+///
+/// The function `compare_coord` returns the `Ordering` of the coordinates, or
+/// `GeometryError::PointAtInfinity`.
+///
+/// Examples:
+///
+/// ```rust
+/// use core::cmp::Ordering;
+/// use projgeom_rs::pg_object::PgPoint;
+/// use projgeom_rs::predicates::compare_coord;
+/// let pt_p = PgPoint::new([1, 5, 3]);
+/// let pt_q = PgPoint::new([-1, 2, -2]);
+/// assert_eq!(compare_coord(&pt_p, &pt_q, 0), Ok(Ordering::Less));
+/// assert_eq!(compare_coord(&pt_p, &pt_q, 1), Ok(Ordering::Greater));
+/// ```
+pub fn compare_coord<Point: HomogeneousCoords>(
+    pt_p: &Point,
+    pt_q: &Point,
+    axis: usize,
+) -> Result<Ordering, GeometryError> {
+    let sign = affine_sign(pt_p)? * affine_sign(pt_q)?;
+    let (p, q) = (pt_p.coords(), pt_q.coords());
+    let [p_a, p_z, q_a, q_z] = [p[axis], p[2], q[axis], q[2]].map(i128::from);
+    let diff = (p_a * q_z)
+        .checked_sub(q_a * p_z)
+        .ok_or(GeometryError::Overflow)?;
+    Ok((diff.signum() * sign).cmp(&0))
+}
+
+/// The function `squared_distance` calculates the exact squared Euclidean distance between two affine
+/// points.
+///
+/// Arguments:
+///
+/// * `pt_p`: The first point.
+/// * `pt_q`: The second point.
+///
+/// Returns:
+///
+/// The function `squared_distance` returns the squared distance as a reduced fraction,
+/// `GeometryError::PointAtInfinity`, or `GeometryError::Overflow` if the result does not fit.
+///
+/// Examples:
+///
+/// ```rust
+/// use fractions::Fraction;
+/// use projgeom_rs::pg_object::PgPoint;
+/// use projgeom_rs::predicates::squared_distance;
+/// let pt_p = PgPoint::new([1, 1, 2]);
+/// let pt_q = PgPoint::new([2, 3, 1]);
+/// assert_eq!(squared_distance(&pt_p, &pt_q), Ok(Fraction::new(17, 2)));
+/// ```
+pub fn squared_distance<Point: HomogeneousCoords>(
+    pt_p: &Point,
+    pt_q: &Point,
+) -> Result<Fraction<i64>, GeometryError> {
+    affine_sign(pt_p)?;
+    affine_sign(pt_q)?;
+    let [p_x, p_y, p_z] = pt_p.coords().map(i128::from);
+    let [q_x, q_y, q_z] = pt_q.coords().map(i128::from);
+    let d_x = (p_x * q_z)
+        .checked_sub(q_x * p_z)
+        .ok_or(GeometryError::Overflow)?;
+    let d_y = (p_y * q_z)
+        .checked_sub(q_y * p_z)
+        .ok_or(GeometryError::Overflow)?;
+    let numer = d_x
+        .checked_mul(d_x)
+        .zip(d_y.checked_mul(d_y))
+        .and_then(|(x_2, y_2)| x_2.checked_add(y_2))
+        .ok_or(GeometryError::Overflow)?;
+    let denom = (p_z * q_z)
+        .checked_mul(p_z * q_z)
+        .ok_or(GeometryError::Overflow)?;
+    narrow(numer, denom)
+}
+
+/// Signed ratio `BD / DC` of three collinear affine points, as a reduced i128 fraction.
+fn signed_ratio(
+    pt_b: &PgPoint,
+    pt_d: &PgPoint,
+    pt_c: &PgPoint,
+) -> Result<(i128, i128), GeometryError> {
+    let [b_z, d_z, c_z] = [pt_b, pt_d, pt_c].map(|pt| i128::from(pt.coord[2]));
+    // measure along an axis on which B and C differ
+    let axis = if pt_b.coord[0] as i128 * c_z != pt_c.coord[0] as i128 * b_z {
+        0
+    } else {
+        1
+    };
+    let [b_a, d_a, c_a] = [pt_b, pt_d, pt_c].map(|pt| i128::from(pt.coord[axis]));
+    // (d - b) / (c - d) = (d_a b_z - b_a d_z) c_z / ((c_a d_z - d_a c_z) b_z)
+    let numer = (d_a * b_z)
+        .checked_sub(b_a * d_z)
+        .and_then(|v| v.checked_mul(c_z))
+        .ok_or(GeometryError::Overflow)?;
+    let denom = (c_a * d_z)
+        .checked_sub(d_a * c_z)
+        .and_then(|v| v.checked_mul(b_z))
+        .ok_or(GeometryError::Overflow)?;
+    if denom == 0 {
+        return Err(GeometryError::NotInGeneralPosition);
+    }
+    let common = numer.gcd(&denom);
+    Ok((numer / common, denom / common))
+}
+
+/// Product of the signed ratios in which the points divide the sides of the triangle, where
+/// `points[i]` lies on the side opposite `triangle[i]`.
+fn side_ratio_product(
+    triangle: &[PgPoint; 3],
+    points: &[PgPoint; 3],
+) -> Result<Fraction<i64>, GeometryError> {
+    let [a_0, a_1, a_2] = triangle;
+    if orientation(a_0, a_1, a_2)? == Orientation::Collinear {
+        return Err(GeometryError::InvalidTriangle);
+    }
+    let (mut numer, mut denom) = (1_i128, 1_i128);
+    for (i, pt_p) in points.iter().enumerate() {
+        affine_sign(pt_p)?;
+        let (pt_b, pt_c) = (&triangle[(i + 1) % 3], &triangle[(i + 2) % 3]);
+        if det3_checked(&pt_b.coord, &pt_p.coord, &pt_c.coord).ok_or(GeometryError::Overflow)? != 0
+        {
+            return Err(GeometryError::NotOnLine);
+        }
+        let (r_n, r_d) = signed_ratio(pt_b, pt_p, pt_c)?;
+        numer = numer.checked_mul(r_n).ok_or(GeometryError::Overflow)?;
+        denom = denom.checked_mul(r_d).ok_or(GeometryError::Overflow)?;
+        let common = numer.gcd(&denom);
+        (numer, denom) = (numer / common, denom / common);
+    }
+    narrow(numer, denom)
+}
+
+/// The function `check_ceva` computes the product of signed ratios `(BD/DC) (CE/EA) (AF/FB)` of
+/// Ceva's theorem for a triangle `ABC` and points `D`, `E`, `F` on the sides opposite `A`, `B`,
+/// `C`. The cevians `AD`, `BE` and `CF` are concurrent (or parallel) exactly when the product is 1.
+///
+/// Arguments:
+///
+/// * `triangle`: The vertices `A`, `B`, `C`.
+/// * `cevian_points`: The points `D`, `E`, `F` on the side lines `BC`, `CA`, `AB`.
+///
+/// Returns:
+///
+/// The function `check_ceva` returns the product, `GeometryError::PointAtInfinity`,
+/// `GeometryError::InvalidTriangle`, `GeometryError::NotOnLine` if a point is not on its side
+/// line, `GeometryError::NotInGeneralPosition` if a ratio is undefined because a point is a
+/// vertex, or `GeometryError::Overflow`.
+///
+/// Examples:
+///
+/// ```rust
+/// use fractions::Fraction;
+/// use projgeom_rs::pg_object::PgPoint;
+/// use projgeom_rs::predicates::check_ceva;
+/// let triangle = [[0, 0, 1], [6, 0, 1], [0, 6, 1]].map(PgPoint::new);
+/// // the midpoints of the sides: the cevians are the medians
+/// let midpoints = [[3, 3, 1], [0, 3, 1], [3, 0, 1]].map(PgPoint::new);
+/// assert_eq!(check_ceva(&triangle, &midpoints), Ok(Fraction::from(1)));
+/// ```
+#[inline]
+pub fn check_ceva(
+    triangle: &[PgPoint; 3],
+    cevian_points: &[PgPoint; 3],
+) -> Result<Fraction<i64>, GeometryError> {
+    side_ratio_product(triangle, cevian_points)
+}
+
+/// The function `check_menelaus` computes the product of signed ratios `(BD/DC) (CE/EA) (AF/FB)`
+/// of Menelaus' theorem for a triangle `ABC` and points `D`, `E`, `F` on the side lines opposite
+/// `A`, `B`, `C`. The three points are collinear exactly when the product is -1.
+///
+/// Arguments:
+///
+/// * `triangle`: The vertices `A`, `B`, `C`.
+/// * `transversal_points`: The points `D`, `E`, `F` on the side lines `BC`, `CA`, `AB`.
+///
+/// Returns:
+///
+/// The function `check_menelaus` returns the product, or the errors of `check_ceva`.
+///
+/// Examples:
+///
+/// ```rust
+/// use fractions::Fraction;
+/// use projgeom_rs::pg_object::{PgLine, PgPoint};
+/// use projgeom_rs::predicates::check_menelaus;
+/// use projgeom_rs::{Join, ProjectivePlanePrimitive};
+/// let triangle = [[0, 0, 1], [6, 0, 1], [0, 6, 1]].map(PgPoint::new);
+/// let transversal = PgLine::new([1, 2, -4]);
+/// let [a_0, a_1, a_2] = &triangle;
+/// let points = [a_1.join(a_2), a_2.join(a_0), a_0.join(a_1)].map(|side| side.meet(&transversal));
+/// assert_eq!(check_menelaus(&triangle, &points), Ok(Fraction::from(-1)));
+/// ```
+#[inline]
+pub fn check_menelaus(
+    triangle: &[PgPoint; 3],
+    transversal_points: &[PgPoint; 3],
+) -> Result<Fraction<i64>, GeometryError> {
+    side_ratio_product(triangle, transversal_points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pg_object::{EuclidLine, EuclidPoint, HyperbolicPoint, PgLine};
+    use crate::pg_plane::{Join, ProjectivePlane, ProjectivePlanePrimitive};
+
+    #[test]
+    fn test_orientation() {
+        let pt_p = PgPoint::new([0, 0, 1]);
+        let pt_q = PgPoint::new([1, 0, 1]);
+        let pt_r = PgPoint::new([0, 1, 1]);
+        assert_eq!(
+            orientation(&pt_p, &pt_q, &pt_r),
+            Ok(Orientation::CounterClockwise)
+        );
+        assert_eq!(orientation(&pt_q, &pt_p, &pt_r), Ok(Orientation::Clockwise));
+        // Scaling by a negative factor does not change the affine point.
+        let pt_s = PgPoint::new([0, -3, -3]);
+        assert_eq!(
+            orientation(&pt_p, &pt_q, &pt_s),
+            Ok(Orientation::CounterClockwise)
+        );
+        let pt_t = PgPoint::new([4, 0, 2]);
+        assert_eq!(orientation(&pt_p, &pt_q, &pt_t), Ok(Orientation::Collinear));
+        let pt_inf = PgPoint::new([1, 1, 0]);
+        assert_eq!(
+            orientation(&pt_p, &pt_q, &pt_inf),
+            Err(GeometryError::PointAtInfinity)
+        );
+        let big_1 = PgPoint::new([i64::MAX, 0, 1]);
+        let big_2 = PgPoint::new([0, i64::MAX, 1]);
+        let big_3 = PgPoint::new([-i64::MAX, -i64::MAX, 1]);
+        assert_eq!(
+            orientation(&big_1, &big_2, &big_3),
+            Err(GeometryError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_line_position() {
+        let ln_l = PgLine::new([1, -1, 0]); // x = y
+        assert_eq!(
+            line_position(&ln_l, &PgPoint::new([2, 1, 1])),
+            Ok(Ordering::Greater)
+        );
+        assert_eq!(
+            line_position(&ln_l, &PgPoint::new([-2, -1, -1])),
+            Ok(Ordering::Greater)
+        );
+        assert_eq!(
+            line_position(&ln_l, &PgPoint::new([1, 2, 1])),
+            Ok(Ordering::Less)
+        );
+        assert_eq!(
+            line_position(&ln_l, &PgPoint::new([3, 3, 5])),
+            Ok(Ordering::Equal)
+        );
+    }
+
+    #[test]
+    fn test_other_geometries() {
+        // the predicates agree for every point type with the same coordinates
+        let coords = [[0, 0, 1], [4, 0, 1], [0, 4, 1]];
+        let pg = coords.map(PgPoint::new);
+        let euclid = coords.map(EuclidPoint::new);
+        let hyperbolic = coords.map(HyperbolicPoint::new);
+        let [a, b, c] = &euclid;
+        assert_eq!(orientation(a, b, c), orientation(&pg[0], &pg[1], &pg[2]));
+        let [a, b, c] = &hyperbolic;
+        assert_eq!(orientation(a, b, c), Ok(Orientation::CounterClockwise));
+        assert_eq!(
+            point_in_triangle(&HyperbolicPoint::new([1, 1, 1]), &hyperbolic),
+            Ok(true)
+        );
+        let ln_l = EuclidLine::new([1, -1, 0]);
+        assert_eq!(line_position(&ln_l, &euclid[1]), Ok(Ordering::Greater));
+        assert_eq!(
+            compare_coord(&euclid[1], &euclid[2], 0),
+            Ok(Ordering::Greater)
+        );
+        assert_eq!(
+            squared_distance(&euclid[1], &euclid[2]),
+            Ok(Fraction::from(32))
+        );
+    }
+
+    #[test]
+    fn test_point_in_triangle() {
+        let triangle = [
+            PgPoint::new([0, 0, 1]),
+            PgPoint::new([4, 0, 1]),
+            PgPoint::new([0, 4, 1]),
+        ];
+        assert_eq!(
+            point_in_triangle(&PgPoint::new([1, 1, 1]), &triangle),
+            Ok(true)
+        );
+        assert_eq!(
+            point_in_triangle(&PgPoint::new([4, 4, 2]), &triangle),
+            Ok(true)
+        );
+        assert_eq!(
+            point_in_triangle(&PgPoint::new([3, 3, 1]), &triangle),
+            Ok(false)
+        );
+        let flat = [
+            PgPoint::new([0, 0, 1]),
+            PgPoint::new([1, 1, 1]),
+            PgPoint::new([2, 2, 1]),
+        ];
+        assert_eq!(
+            point_in_triangle(&PgPoint::new([1, 0, 1]), &flat),
+            Err(GeometryError::InvalidTriangle)
+        );
+    }
+
+    #[test]
+    fn test_ceva_menelaus() {
+        let triangle = [[1, 2, 1], [14, 6, 2], [-3, 15, 3]].map(PgPoint::new);
+        let [a_0, a_1, a_2] = &triangle;
+        let sides = [a_1.join(a_2), a_2.join(a_0), a_0.join(a_1)];
+
+        // cevians through a common point
+        let pt_o = PgPoint::new([5, 13, 6]);
+        let feet = [a_0, a_1, a_2].map(|a_i| a_i.join(&pt_o));
+        let cevian_points: Vec<PgPoint> = feet.iter().zip(&sides).map(|(c, s)| c.meet(s)).collect();
+        let cevian_points: [PgPoint; 3] = cevian_points.try_into().unwrap();
+        assert_eq!(check_ceva(&triangle, &cevian_points), Ok(Fraction::from(1)));
+        // moving one point along its side breaks the concurrency
+        let mut moved = cevian_points.clone();
+        moved[0] = a_1.parametrize(1, a_2, 1).parametrize(1, &moved[0], 1);
+        let product = check_ceva(&triangle, &moved).unwrap();
+        assert_ne!(product, Fraction::from(1));
+
+        // a transversal meets the side lines in points with product -1
+        let transversal = PgLine::new([3, -1, 4]);
+        let points = sides.clone().map(|side| side.meet(&transversal));
+        assert_eq!(check_menelaus(&triangle, &points), Ok(Fraction::from(-1)));
+        assert_eq!(
+            check_menelaus(&triangle, &cevian_points),
+            Ok(Fraction::from(1))
+        );
+
+        let mut off = points.clone();
+        off[1] = PgPoint::new([1, 1, 1]);
+        assert_eq!(
+            check_menelaus(&triangle, &off),
+            Err(GeometryError::NotOnLine)
+        );
+        let mut vertex = points.clone();
+        vertex[0] = a_2.clone();
+        assert_eq!(
+            check_menelaus(&triangle, &vertex),
+            Err(GeometryError::NotInGeneralPosition)
+        );
+        let flat = [[0, 0, 1], [1, 1, 1], [2, 2, 1]].map(PgPoint::new);
+        assert_eq!(
+            check_ceva(&flat, &points),
+            Err(GeometryError::InvalidTriangle)
+        );
+    }
+
+    #[test]
+    fn test_squared_distance() {
+        let pt_p = PgPoint::new([3, 4, 1]);
+        let pt_o = PgPoint::new([0, 0, 7]);
+        assert_eq!(squared_distance(&pt_p, &pt_o), Ok(Fraction::from(25)));
+        assert_eq!(
+            squared_distance(&pt_p, &PgPoint::new([1, 0, 0])),
+            Err(GeometryError::PointAtInfinity)
+        );
+    }
+}
@@ -0,0 +1,24 @@
+// Traits and common types, to glob-import with `use projgeom_rs::prelude::*`
+
+pub use crate::canonical::{Canonical, CanonicalForm};
+pub use crate::ck_plane::{AffineCKPlane, CayleyKleinPlane, CayleyKleinPlanePrimitive};
+pub use crate::compact::{PgLine32, PgPoint32};
+pub use crate::conic::Conic;
+pub use crate::coord_ring::{CoordRing, Zp};
+pub use crate::duality::Dualize;
+pub use crate::error::GeometryError;
+#[cfg(feature = "std")]
+pub use crate::group::GroupAction;
+pub use crate::pg3d::{PgLine3, PgPlane3, PgPoint3, ProjectiveSpacePrimitive};
+pub use crate::pg_object::{Coord3, HomogeneousCoords};
+pub use crate::pg_object::{DiagonalCKLine, DiagonalCKPoint};
+pub use crate::pg_object::{EllipticLine, EllipticPoint};
+pub use crate::pg_object::{EuclidLine, EuclidPoint};
+pub use crate::pg_object::{HyperbolicLine, HyperbolicPoint};
+pub use crate::pg_object::{MyCKLine, MyCKPoint};
+pub use crate::pg_object::{PerspLine, PerspPoint};
+pub use crate::pg_object::{PgLine, PgPoint};
+pub use crate::pg_object::{PgLineOver, PgPointOver};
+pub use crate::pg_object::{RatLine, RatPoint};
+pub use crate::pg_plane::{Join, ProjectivePlane, ProjectivePlanePrimitive};
+pub use crate::transform::Transform;
@@ -0,0 +1,253 @@
+// Projectivities between ranges of points on two lines, after Chasles and Steiner
+
+use crate::error::GeometryError;
+use crate::pg_plane::{coincident, ProjectivePlanePrimitive};
+
+/// The `RangeProjectivity` struct is the unique projectivity from the range of points on a line
+/// `l` onto the range on another line `m` that maps three given points to three given points.
+///
+/// Following Steiner, it is built from at most two perspectivities. For a pair `A ↦ A'` with
+/// neither point on both lines, the first perspectivity projects `l` from the center `A'` onto
+/// the axis of the projectivity, and the second projects the axis from the center `A` onto `m`.
+/// The axis (of Pappus) carries the cross joins `AB' · A'B` of any two pairs, and meets `l` and
+/// `m` in the points corresponding to their intersection `O`. If `O` corresponds to itself, the
+/// projectivity is a single perspectivity, whose center lies on the joins `AA'` of all pairs.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::projectivity::RangeProjectivity;
+/// use projgeom_rs::{PgLine, PgPoint};
+/// let src = [[1, 0, 1], [2, 0, 1], [3, 0, 1]].map(PgPoint::new);
+/// let dst = [[0, 1, 1], [0, 3, 1], [0, 2, 1]].map(PgPoint::new);
+/// let proj = RangeProjectivity::new(&src, &dst).unwrap();
+/// assert_eq!(proj.apply(&src[1]), dst[1]);
+/// assert_eq!(proj.apply(&PgPoint::new([3, 0, 2])), PgPoint::new([0, -1, 1]));
+/// assert_eq!(proj.axis(), &PgLine::new([5, 5, -7]));
+/// assert!(proj.perspectivity_center().is_none());
+/// ```
+#[derive(Debug, Clone)]
+pub struct RangeProjectivity<Point, Line> {
+    source: Line,
+    target: Line,
+    axis: Line,
+    centers: [Point; 2],
+    center: Option<Point>,
+}
+
+impl<Point, Line> RangeProjectivity<Point, Line>
+where
+    Point: ProjectivePlanePrimitive<Line> + Clone,
+    Line: ProjectivePlanePrimitive<Point> + Clone,
+{
+    /// The function `new` constructs the projectivity mapping `src[i]` to `dst[i]`.
+    ///
+    /// Arguments:
+    ///
+    /// * `src`: Three distinct collinear points on the source line.
+    /// * `dst`: Three distinct collinear points on a different target line.
+    ///
+    /// Returns:
+    ///
+    /// The function `new` returns `GeometryError::NotCollinear` if either triple is not
+    /// collinear, and `GeometryError::NotInGeneralPosition` if two points of a triple coincide or
+    /// if both triples lie on the same line.
+    pub fn new(src: &[Point; 3], dst: &[Point; 3]) -> Result<Self, GeometryError> {
+        let source = Self::range(src)?;
+        let target = Self::range(dst)?;
+        if source == target {
+            return Err(GeometryError::NotInGeneralPosition);
+        }
+        let origin = source.meet(&target);
+        // at most one pair has a point at O on each side, so some pair has neither
+        let k = (0..3)
+            .find(|&k| src[k] != origin && dst[k] != origin)
+            .expect("the points of a triple are distinct");
+        let [i, j] = [(k + 1) % 3, (k + 2) % 3];
+        let cross_join = |i: usize| src[k].meet(&dst[i]).meet(&src[i].meet(&dst[k]));
+        let axis = cross_join(i).meet(&cross_join(j));
+        let mut proj = Self {
+            source,
+            target,
+            axis,
+            centers: [dst[k].clone(), src[k].clone()],
+            center: None,
+        };
+        if proj.apply(&origin) == origin {
+            let mut joins = (0..3)
+                .filter(|&n| src[n] != dst[n])
+                .map(|n| src[n].meet(&dst[n]));
+            if let (Some(ln_a), Some(ln_b)) = (joins.next(), joins.next()) {
+                proj.center = Some(ln_a.meet(&ln_b));
+            }
+        }
+        Ok(proj)
+    }
+
+    /// The line through three distinct collinear points.
+    fn range([pt_a, pt_b, pt_c]: &[Point; 3]) -> Result<Line, GeometryError> {
+        if pt_a == pt_b || pt_b == pt_c || pt_a == pt_c {
+            return Err(GeometryError::NotInGeneralPosition);
+        }
+        if !coincident(pt_a, pt_b, pt_c) {
+            return Err(GeometryError::NotCollinear);
+        }
+        Ok(pt_a.meet(pt_b))
+    }
+
+    /// The source line `l`.
+    #[inline]
+    pub fn source(&self) -> &Line {
+        &self.source
+    }
+
+    /// The target line `m`.
+    #[inline]
+    pub fn target(&self) -> &Line {
+        &self.target
+    }
+
+    /// The axis of the projectivity, which carries the cross joins of all pairs.
+    #[inline]
+    pub fn axis(&self) -> &Line {
+        &self.axis
+    }
+
+    /// The centers `A'` on `m` and `A` on `l` of the perspectivities from `l` onto the axis and
+    /// from the axis onto `m`.
+    #[inline]
+    pub fn centers(&self) -> &[Point; 2] {
+        &self.centers
+    }
+
+    /// The center of the projectivity if it is a single perspectivity, i.e. if it maps the
+    /// intersection of the lines to itself.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::projectivity::RangeProjectivity;
+    /// use projgeom_rs::PgPoint;
+    /// // the parallels x + y = t join (t, 0) to (0, t)
+    /// let src = [[1, 0, 1], [2, 0, 1], [3, 0, 1]].map(PgPoint::new);
+    /// let dst = [[0, 1, 1], [0, 2, 1], [0, 3, 1]].map(PgPoint::new);
+    /// let proj = RangeProjectivity::new(&src, &dst).unwrap();
+    /// assert_eq!(proj.perspectivity_center(), Some(&PgPoint::new([1, -1, 0])));
+    /// ```
+    #[inline]
+    pub fn perspectivity_center(&self) -> Option<&Point> {
+        self.center.as_ref()
+    }
+
+    /// The function `apply` maps a point of the source line to the target line.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the point does not lie on the source line; see `try_apply`.
+    #[inline]
+    pub fn apply(&self, pt: &Point) -> Point {
+        self.try_apply(pt).expect("point is not on the source line")
+    }
+
+    /// The function `try_apply` is the non-panicking version of `apply`.
+    ///
+    /// Returns:
+    ///
+    /// The image of the point, or `Err(NotOnLine)` if it does not lie on the source line.
+    pub fn try_apply(&self, pt: &Point) -> Result<Point, GeometryError> {
+        if !pt.incident(&self.source) {
+            return Err(GeometryError::NotOnLine);
+        }
+        if let Some(center) = &self.center {
+            return Ok(center.meet(pt).meet(&self.target));
+        }
+        let [center_1, center_2] = &self.centers;
+        let on_axis = center_1.meet(pt).meet(&self.axis);
+        Ok(center_2.meet(&on_axis).meet(&self.target))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pg1::{cross_ratio, Chart};
+    use crate::pg_object::{PgLine, PgPoint};
+    use crate::pg_plane::Join;
+
+    #[test]
+    fn test_range_projectivity() {
+        let src = [[2, 1, 1], [4, 2, 1], [0, 0, 1]].map(PgPoint::new);
+        let dst = [[1, 0, 1], [1, 5, 1], [1, -2, 1]].map(PgPoint::new);
+        let proj = RangeProjectivity::new(&src, &dst).unwrap();
+        assert_eq!(proj.source(), &PgLine::new([1, -2, 0]));
+        assert_eq!(proj.target(), &PgLine::new([1, 0, -1]));
+        for (pt_p, pt_q) in src.iter().zip(&dst) {
+            assert_eq!(proj.apply(pt_p), *pt_q);
+        }
+        // the axis passes through the cross joins
+        let [pt_a, pt_b, pt_c] = &src;
+        let [pt_a1, pt_b1, _] = &dst;
+        let cross = pt_a.join(pt_b1).meet(&pt_b.join(pt_a1));
+        assert!(cross.incident(proj.axis()));
+        assert!(proj.perspectivity_center().is_none());
+
+        // the cross ratio is preserved
+        let pt_x = PgPoint::new([6, 3, 1]);
+        let pt_y = proj.apply(&pt_x);
+        assert!(pt_y.incident(proj.target()));
+        let chart_l = Chart::new(pt_a.clone(), pt_b.clone()).unwrap();
+        let chart_m = Chart::new(pt_a1.clone(), pt_b1.clone()).unwrap();
+        let ratio = |chart: &Chart<PgPoint>, pts: [&PgPoint; 4]| {
+            let [p, q, r, s] = pts.map(|pt| chart.coordinate(pt).unwrap());
+            cross_ratio(&p, &q, &r, &s).unwrap()
+        };
+        assert_eq!(
+            ratio(&chart_l, [pt_a, pt_b, pt_c, &pt_x]),
+            ratio(&chart_m, [pt_a1, pt_b1, &dst[2], &pt_y])
+        );
+        assert_eq!(
+            proj.try_apply(&PgPoint::new([1, 1, 1])),
+            Err(GeometryError::NotOnLine)
+        );
+    }
+
+    #[test]
+    fn test_range_projectivity_degenerate() {
+        let src = [[1, 0, 1], [2, 0, 1], [3, 0, 1]].map(PgPoint::new);
+        let dst = [[0, 1, 1], [0, 2, 1], [0, 3, 1]].map(PgPoint::new);
+        let bent = [[0, 1, 1], [0, 2, 1], [1, 3, 1]].map(PgPoint::new);
+        let same = [[4, 0, 1], [5, 0, 1], [6, 0, 1]].map(PgPoint::new);
+        let twice = [src[0].clone(), src[0].clone(), src[2].clone()];
+        type Proj = RangeProjectivity<PgPoint, PgLine>;
+        assert_eq!(
+            Proj::new(&src, &bent).err(),
+            Some(GeometryError::NotCollinear)
+        );
+        assert_eq!(
+            Proj::new(&src, &same).err(),
+            Some(GeometryError::NotInGeneralPosition)
+        );
+        assert_eq!(
+            Proj::new(&twice, &dst).err(),
+            Some(GeometryError::NotInGeneralPosition)
+        );
+
+        // a perspectivity from the point at infinity [1 : -1 : 0], whose pairs include the origin
+        let src = [[0, 0, 1], [1, 0, 1], [2, 0, 1]].map(PgPoint::new);
+        let dst = [[0, 0, 1], [0, 1, 1], [0, 2, 1]].map(PgPoint::new);
+        let proj = Proj::new(&src, &dst).unwrap();
+        assert_eq!(proj.perspectivity_center(), Some(&PgPoint::new([1, -1, 0])));
+        assert_eq!(
+            proj.apply(&PgPoint::new([5, 0, 1])),
+            PgPoint::new([0, 5, 1])
+        );
+
+        // the origin maps elsewhere: A = O but A' ≠ O
+        let dst = [[0, 3, 1], [0, 1, 1], [0, 2, 1]].map(PgPoint::new);
+        let proj = Proj::new(&src, &dst).unwrap();
+        assert_eq!(proj.centers(), &[dst[1].clone(), src[1].clone()]);
+        for (pt_p, pt_q) in src.iter().zip(&dst) {
+            assert_eq!(proj.apply(pt_p), *pt_q);
+        }
+    }
+}
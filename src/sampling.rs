@@ -0,0 +1,194 @@
+// Sampling rational points on a line in order of increasing height
+
+use crate::pg_plane::ProjectivePlane;
+use core::marker::PhantomData;
+use num_integer::Integer;
+
+/// The `RationalParameters` struct iterates over the points `[p : q]` of the rational projective
+/// line, each once, in order of increasing height `max(|p|, |q|)`. Within a height, the
+/// parameters `p/q` come in increasing order, with `∞ = [1 : 0]` last. The parameters are reduced
+/// with `q ≥ 0`, so they are the fractions `p/q` of the Stern–Brocot tree together with their
+/// negatives, zero and infinity.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::sampling::RationalParameters;
+/// let params: Vec<(i64, i64)> = RationalParameters::new().take(8).collect();
+/// assert_eq!(
+///     params,
+///     [(-1, 1), (0, 1), (1, 1), (1, 0), (-2, 1), (-1, 2), (1, 2), (2, 1)]
+/// );
+/// // the number of parameters up to height n is 4 (φ(1) + ... + φ(n))
+/// assert_eq!(RationalParameters::with_max_height(4).count(), 4 * (1 + 1 + 2 + 2));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RationalParameters {
+    height: i64,
+    index: i64,
+    max_height: i64,
+}
+
+impl RationalParameters {
+    /// Iterate over the parameters of all heights.
+    #[inline]
+    pub const fn new() -> Self {
+        Self::with_max_height(i64::MAX)
+    }
+
+    /// Iterate over the parameters of height at most `max_height`.
+    #[inline]
+    pub const fn with_max_height(max_height: i64) -> Self {
+        Self {
+            height: 1,
+            index: 0,
+            max_height,
+        }
+    }
+
+    /// The candidate `index` of height `h`, in increasing order of `p/q`: first `-h/q` for
+    /// `q = 1, ..., h - 1`, then `p/h` for `p = -h, ..., h`, then `h/q` for `q = h - 1, ..., 1`,
+    /// and for `h = 1` finally `1/0`. `None` if the index is past the end.
+    fn candidate(h: i64, index: i64) -> Option<(i64, i64)> {
+        if index < h - 1 {
+            Some((-h, index + 1))
+        } else if index < 3 * h {
+            Some((index - 2 * h + 1, h))
+        } else if index < 4 * h - 1 {
+            Some((h, 4 * h - 1 - index))
+        } else if h == 1 && index == 3 {
+            Some((1, 0))
+        } else {
+            None
+        }
+    }
+}
+
+impl Default for RationalParameters {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Iterator for RationalParameters {
+    type Item = (i64, i64);
+
+    fn next(&mut self) -> Option<(i64, i64)> {
+        while self.height <= self.max_height {
+            match Self::candidate(self.height, self.index) {
+                Some((p, q)) => {
+                    self.index += 1;
+                    if p.gcd(&q) == 1 {
+                        return Some((p, q));
+                    }
+                }
+                None => {
+                    self.height = self.height.checked_add(1)?;
+                    self.index = 0;
+                }
+            }
+        }
+        None
+    }
+}
+
+/// The `RationalPoints` struct iterates over the points `q a + p b` of the line through two base
+/// points `a` and `b`, for the parameters `[p : q]` of `RationalParameters`. Every point of the
+/// line with rational coordinates relative to the base points is reached once, so the first
+/// points of the iteration are a dense, bounded-height sample of the line, e.g. for plots or tests.
+///
+/// The coordinates grow linearly with the height; a coordinate that overflows panics in
+/// `parametrize`.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::sampling::RationalPoints;
+/// use projgeom_rs::PgPoint;
+/// let pt_a = PgPoint::new([0, 0, 1]);
+/// let pt_b = PgPoint::new([1, 0, 0]);
+/// // the points x = p/q of the x-axis, and its point at infinity
+/// let points: Vec<PgPoint> = RationalPoints::new(&pt_a, &pt_b).take(6).collect();
+/// assert_eq!(points[1], pt_a);
+/// assert_eq!(points[3], pt_b);
+/// assert_eq!(points[5], PgPoint::new([-1, 0, 2]));
+/// assert_eq!(RationalPoints::with_max_height(&pt_a, &pt_b, 3).count(), 16);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RationalPoints<Point, Line> {
+    pt_a: Point,
+    pt_b: Point,
+    params: RationalParameters,
+    _line: PhantomData<Line>,
+}
+
+impl<Point, Line> RationalPoints<Point, Line>
+where
+    Point: ProjectivePlane<Line, i64> + Clone,
+    Line: ProjectivePlane<Point, i64>,
+{
+    /// Iterate over the points of all heights of the line through `pt_a` and `pt_b`.
+    #[inline]
+    pub fn new(pt_a: &Point, pt_b: &Point) -> Self {
+        Self::with_max_height(pt_a, pt_b, i64::MAX)
+    }
+
+    /// Iterate over the points of height at most `max_height` of the line through `pt_a` and
+    /// `pt_b`.
+    #[inline]
+    pub fn with_max_height(pt_a: &Point, pt_b: &Point, max_height: i64) -> Self {
+        Self {
+            pt_a: pt_a.clone(),
+            pt_b: pt_b.clone(),
+            params: RationalParameters::with_max_height(max_height),
+            _line: PhantomData,
+        }
+    }
+}
+
+impl<Point, Line> Iterator for RationalPoints<Point, Line>
+where
+    Point: ProjectivePlane<Line, i64> + Clone,
+    Line: ProjectivePlane<Point, i64>,
+{
+    type Item = Point;
+
+    #[inline]
+    fn next(&mut self) -> Option<Point> {
+        let (p, q) = self.params.next()?;
+        Some(self.pt_a.parametrize(q, &self.pt_b, p))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pg_object::PgPoint;
+    use crate::pg_plane::{coincident, Join, ProjectivePlanePrimitive};
+
+    #[test]
+    fn test_rational_points() {
+        let params: Vec<(i64, i64)> = RationalParameters::with_max_height(5).collect();
+        // increasing height, and distinct reduced parameters
+        let height = |(p, q): &(i64, i64)| p.abs().max(*q);
+        assert!(params.windows(2).all(|w| height(&w[0]) <= height(&w[1])));
+        for (i, a) in params.iter().enumerate() {
+            assert!(a.1 >= 0 && a.0.gcd(&a.1) == 1);
+            assert!(params[i + 1..].iter().all(|b| a.0 * b.1 != a.1 * b.0));
+        }
+        assert!(params.contains(&(-5, 3)) && params.contains(&(4, 5)));
+        assert!(!params.contains(&(2, 6)));
+
+        let pt_a = PgPoint::new([1, 2, 1]);
+        let pt_b = PgPoint::new([3, -1, 2]);
+        let ln_l = pt_a.join(&pt_b);
+        let points: Vec<PgPoint> = RationalPoints::with_max_height(&pt_a, &pt_b, 5).collect();
+        assert_eq!(points.len(), params.len());
+        for (i, pt) in points.iter().enumerate() {
+            assert!(pt.incident(&ln_l));
+            assert!(coincident(&pt_a, &pt_b, pt));
+            assert!(points[i + 1..].iter().all(|other| other != pt));
+        }
+        assert_eq!(RationalParameters::with_max_height(0).next(), None);
+    }
+}
@@ -0,0 +1,592 @@
+// Declarative scenes of named objects, constructions and style hints, read from JSON
+
+use crate::conic::Conic;
+use crate::error::GeometryError;
+use crate::interop::{self, SceneObject};
+use crate::pg_object::{PgLine, PgPoint};
+use crate::pg_plane::{try_harm_conj, Join, ProjectivePlanePrimitive};
+use crate::visualization::{Element, Figure, Style};
+use core::fmt;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A reference to an object of a `Scene`: the name of an earlier entry, or the homogeneous
+/// coordinates of a point or line given in place.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ObjectRef {
+    Name(String),
+    Coord([i64; 3]),
+}
+
+impl From<&str> for ObjectRef {
+    #[inline]
+    fn from(name: &str) -> Self {
+        ObjectRef::Name(name.to_string())
+    }
+}
+
+/// The `Definition` enum lists how an entry of a `Scene` is given: directly by coordinates, or
+/// constructed from earlier entries. In JSON, the variant is the key, e.g. `{"join": ["A", "B"]}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Definition {
+    /// A point given by homogeneous coordinates.
+    Point([i64; 3]),
+    /// A line given by homogeneous coordinates.
+    Line([i64; 3]),
+    /// A conic given by the coefficients of `a x² + b xy + c y² + d xz + e yz + f z²`, in the
+    /// order of `Conic::from_coefficients`.
+    Conic([i64; 6]),
+    /// The line through two points.
+    Join([ObjectRef; 2]),
+    /// The point on two lines.
+    Meet([ObjectRef; 2]),
+    /// The harmonic conjugate of the third point with respect to the first two.
+    HarmConj([ObjectRef; 3]),
+    /// The conic through five points, no three of them collinear.
+    ConicThrough([ObjectRef; 5]),
+    /// The polar of a point with respect to a conic.
+    Polar(ObjectRef, ObjectRef),
+    /// The pole of a line with respect to a conic.
+    Pole(ObjectRef, ObjectRef),
+    /// The segment between two affine points.
+    Segment([ObjectRef; 2]),
+    /// The closed polygon through affine points.
+    Polygon(Vec<ObjectRef>),
+}
+
+/// The `StyleHint` struct holds how an entry of a `Scene` is drawn. Unset hints take the
+/// defaults of the renderer: black, with points in layer 1 above the other objects in layer 0.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StyleHint {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub layer: Option<i32>,
+    /// The label of a point, by default its name; an empty label leaves the point unlabeled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Evaluate the entry without drawing it, e.g. for auxiliary objects of a construction.
+    #[serde(skip_serializing_if = "core::ops::Not::not")]
+    pub hidden: bool,
+}
+
+/// An entry of a `Scene`: an optionally named object with its style hints. In JSON, the three
+/// parts are keys of one object, e.g. `{"name": "A", "point": [0, 0, 1], "color": "red"}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SceneEntry {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(flatten)]
+    pub definition: Definition,
+    #[serde(flatten)]
+    pub style: StyleHint,
+}
+
+impl SceneEntry {
+    /// Create a named entry in the default style.
+    #[inline]
+    pub fn new(name: &str, definition: Definition) -> Self {
+        Self {
+            name: Some(name.to_string()),
+            definition,
+            style: StyleHint::default(),
+        }
+    }
+
+    /// Return the entry drawn in another color.
+    #[inline]
+    pub fn color(mut self, color: &str) -> Self {
+        self.style.color = Some(color.to_string());
+        self
+    }
+
+    /// Return the entry evaluated but not drawn.
+    #[inline]
+    pub fn hidden(mut self) -> Self {
+        self.style.hidden = true;
+        self
+    }
+}
+
+/// The `SceneError` enum describes why a scene could not be read or evaluated. Entries are
+/// identified by their index in the list of objects, starting at 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SceneError {
+    /// The document is not valid JSON or does not follow the schema.
+    Parse(String),
+    /// The entry refers to a name that no earlier entry has.
+    UnknownName { entry: usize, name: String },
+    /// The entry has the name of an earlier entry.
+    DuplicateName { entry: usize, name: String },
+    /// The entry refers to an object of the wrong kind, e.g. a line where a point is required.
+    WrongKind {
+        entry: usize,
+        expected: &'static str,
+    },
+    /// The construction of the entry is undefined, e.g. the join of two coinciding points.
+    Degenerate { entry: usize },
+    /// The construction of the entry failed.
+    Geometry { entry: usize, error: GeometryError },
+}
+
+impl fmt::Display for SceneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SceneError::Parse(message) => write!(f, "invalid scene: {}", message),
+            SceneError::UnknownName { entry, name } => {
+                write!(f, "objects[{}]: unknown name '{}'", entry, name)
+            }
+            SceneError::DuplicateName { entry, name } => {
+                write!(f, "objects[{}]: name '{}' is already taken", entry, name)
+            }
+            SceneError::WrongKind { entry, expected } => {
+                write!(f, "objects[{}]: expected a {}", entry, expected)
+            }
+            SceneError::Degenerate { entry } => {
+                write!(f, "objects[{}]: the construction is undefined", entry)
+            }
+            SceneError::Geometry { entry, error } => write!(f, "objects[{}]: {}", entry, error),
+        }
+    }
+}
+
+impl core::error::Error for SceneError {}
+
+/// The objects named so far, with `None` for segments and polygons, which cannot be referred to.
+type Names<'a> = HashMap<&'a str, Option<SceneObject>>;
+
+/// Resolves the references of one entry.
+struct Resolver<'a> {
+    entry: usize,
+    names: &'a Names<'a>,
+}
+
+impl Resolver<'_> {
+    fn lookup(&self, name: &str, expected: &'static str) -> Result<&SceneObject, SceneError> {
+        match self.names.get(name) {
+            Some(Some(obj)) => Ok(obj),
+            Some(None) => Err(self.wrong_kind(expected)),
+            None => Err(SceneError::UnknownName {
+                entry: self.entry,
+                name: name.to_string(),
+            }),
+        }
+    }
+
+    fn wrong_kind(&self, expected: &'static str) -> SceneError {
+        SceneError::WrongKind {
+            entry: self.entry,
+            expected,
+        }
+    }
+
+    fn point(&self, obj: &ObjectRef) -> Result<PgPoint, SceneError> {
+        match obj {
+            ObjectRef::Coord(coord) => self.nonzero(*coord).map(PgPoint::new),
+            ObjectRef::Name(name) => match self.lookup(name, "point")? {
+                SceneObject::Point(pt) => Ok(pt.clone()),
+                _ => Err(self.wrong_kind("point")),
+            },
+        }
+    }
+
+    fn line(&self, obj: &ObjectRef) -> Result<PgLine, SceneError> {
+        match obj {
+            ObjectRef::Coord(coord) => self.nonzero(*coord).map(PgLine::new),
+            ObjectRef::Name(name) => match self.lookup(name, "line")? {
+                SceneObject::Line(ln) => Ok(ln.clone()),
+                _ => Err(self.wrong_kind("line")),
+            },
+        }
+    }
+
+    fn conic(&self, obj: &ObjectRef) -> Result<Conic, SceneError> {
+        match obj {
+            ObjectRef::Coord(_) => Err(self.wrong_kind("conic")),
+            ObjectRef::Name(name) => match self.lookup(name, "conic")? {
+                SceneObject::Conic(conic) => Ok(conic.clone()),
+                _ => Err(self.wrong_kind("conic")),
+            },
+        }
+    }
+
+    /// Check that a constructed point or line is not the zero vector.
+    fn nonzero(&self, coord: [i64; 3]) -> Result<[i64; 3], SceneError> {
+        if coord == [0, 0, 0] {
+            Err(SceneError::Degenerate { entry: self.entry })
+        } else {
+            Ok(coord)
+        }
+    }
+
+    fn evaluate(&self, definition: &Definition) -> Result<Element, SceneError> {
+        let geometry = |error| SceneError::Geometry {
+            entry: self.entry,
+            error,
+        };
+        let point = |pt: PgPoint| -> Result<Element, SceneError> {
+            let pt = PgPoint::new(self.nonzero(pt.coord)?);
+            Ok(Element::Point { pt, label: None })
+        };
+        let line = |ln: PgLine| Ok(Element::Line(PgLine::new(self.nonzero(ln.coord)?)));
+        match definition {
+            Definition::Point(coord) => point(PgPoint::new(*coord)),
+            Definition::Line(coord) => line(PgLine::new(*coord)),
+            Definition::Conic([a, b, c, d, e, f]) => Ok(Element::Conic(Conic::from_coefficients(
+                *a, *b, *c, *d, *e, *f,
+            ))),
+            Definition::Join([pt_a, pt_b]) => line(self.point(pt_a)?.join(&self.point(pt_b)?)),
+            Definition::Meet([ln_l, ln_m]) => point(self.line(ln_l)?.meet(&self.line(ln_m)?)),
+            Definition::HarmConj([pt_a, pt_b, pt_c]) => {
+                let [pt_a, pt_b, pt_c] = [pt_a, pt_b, pt_c].map(|pt| self.point(pt));
+                point(try_harm_conj(&pt_a?, &pt_b?, &pt_c?).map_err(geometry)?)
+            }
+            Definition::ConicThrough(points) => {
+                let [p1, p2, p3, p4, p5] = points.each_ref().map(|pt| self.point(pt));
+                let conic = Conic::through_points(&[p1?, p2?, p3?, p4?, p5?]).map_err(geometry)?;
+                Ok(Element::Conic(conic))
+            }
+            Definition::Polar(pt, conic) => line(self.conic(conic)?.polar(&self.point(pt)?)),
+            Definition::Pole(ln, conic) => point(self.conic(conic)?.pole(&self.line(ln)?)),
+            Definition::Segment([pt_a, pt_b]) => {
+                Ok(Element::Segment(self.point(pt_a)?, self.point(pt_b)?))
+            }
+            Definition::Polygon(vertices) => Ok(Element::Polygon(
+                vertices
+                    .iter()
+                    .map(|pt| self.point(pt))
+                    .collect::<Result<_, _>>()?,
+            )),
+        }
+    }
+}
+
+fn default_size() -> u32 {
+    400
+}
+
+fn default_margin() -> i64 {
+    1
+}
+
+/// The `Scene` struct is a declarative document describing a figure: a list of entries, each an
+/// optionally named point, line or conic given by coordinates or constructed from earlier
+/// entries, with hints on how to draw it. It is read from and written to JSON, evaluated
+/// exactly, and rendered as a `Figure`, i.e. to SVG or TikZ.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::scene::Scene;
+/// use projgeom_rs::{coincident, interop::SceneObject};
+/// // Desargues' theorem: two triangles in perspective from O are in perspective from a line
+/// let scene = Scene::from_json(r#"{
+///     "width": 300, "height": 300,
+///     "objects": [
+///         {"name": "O", "point": [0, 0, 1], "color": "red"},
+///         {"name": "A", "point": [2, 0, 1]}, {"name": "B", "point": [0, 3, 1]},
+///         {"name": "C", "point": [-1, -1, 1]},
+///         {"name": "a", "point": [5, 0, 1]}, {"name": "b", "point": [0, 4, 2]},
+///         {"name": "c", "point": [-3, -3, 1]},
+///         {"name": "AB", "join": ["A", "B"], "hidden": true},
+///         {"name": "ab", "join": ["a", "b"], "hidden": true},
+///         {"name": "AC", "join": ["A", "C"], "hidden": true},
+///         {"name": "ac", "join": ["a", "c"], "hidden": true},
+///         {"name": "BC", "join": ["B", "C"], "hidden": true},
+///         {"name": "bc", "join": ["b", "c"], "hidden": true},
+///         {"name": "P", "meet": ["AB", "ab"]}, {"name": "Q", "meet": ["AC", "ac"]},
+///         {"name": "R", "meet": ["BC", "bc"]},
+///         {"name": "axis", "join": ["P", "Q"], "color": "blue"},
+///         {"polygon": ["A", "B", "C"]}, {"polygon": ["a", "b", "c"]}
+///     ]}"#).unwrap();
+/// let objects = scene.evaluate().unwrap();
+/// let point = |name| match objects.get(name) {
+///     Some(SceneObject::Point(pt)) => pt.clone(),
+///     _ => unreachable!(),
+/// };
+/// assert!(coincident(&point("P"), &point("Q"), &point("R")));
+/// let svg = scene.to_svg().unwrap();
+/// assert!(svg.contains(">R</text>") && !svg.contains(">AB</text>"));
+/// assert!(scene.to_tikz().unwrap().contains("{$R$}"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Scene {
+    /// Width of the rendered image, in pixels.
+    #[serde(default = "default_size")]
+    pub width: u32,
+    /// Height of the rendered image, in pixels.
+    #[serde(default = "default_size")]
+    pub height: u32,
+    /// Space around the fitted bounding box, in world units.
+    #[serde(default = "default_margin")]
+    pub margin: i64,
+    #[serde(default)]
+    pub objects: Vec<SceneEntry>,
+}
+
+impl Scene {
+    /// Create an empty scene of the given image size.
+    #[inline]
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            margin: default_margin(),
+            objects: Vec::new(),
+        }
+    }
+
+    /// Add an entry.
+    #[inline]
+    pub fn add(&mut self, entry: SceneEntry) -> &mut Self {
+        self.objects.push(entry);
+        self
+    }
+
+    /// Read a scene from a JSON document.
+    pub fn from_json(text: &str) -> Result<Self, SceneError> {
+        serde_json::from_str(text).map_err(|err| SceneError::Parse(err.to_string()))
+    }
+
+    /// Write the scene as a pretty-printed JSON document.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("a scene is always serializable")
+    }
+
+    /// Evaluate all entries in order, returning the element each of them describes.
+    fn elements(&self) -> Result<Vec<Element>, SceneError> {
+        let mut names = Names::new();
+        let mut elements = Vec::with_capacity(self.objects.len());
+        for (entry, object) in self.objects.iter().enumerate() {
+            let resolver = Resolver {
+                entry,
+                names: &names,
+            };
+            let element = resolver.evaluate(&object.definition)?;
+            if let Some(name) = &object.name {
+                let value = match &element {
+                    Element::Point { pt, .. } => Some(SceneObject::Point(pt.clone())),
+                    Element::Line(ln) => Some(SceneObject::Line(ln.clone())),
+                    Element::Conic(conic) => Some(SceneObject::Conic(conic.clone())),
+                    Element::Segment(..) | Element::Polygon(_) => None,
+                };
+                if names.insert(name, value).is_some() {
+                    return Err(SceneError::DuplicateName {
+                        entry,
+                        name: name.clone(),
+                    });
+                }
+            }
+            elements.push(element);
+        }
+        Ok(elements)
+    }
+
+    /// The function `evaluate` computes all entries exactly and collects the named points,
+    /// lines and conics, e.g. to export them with `interop::Scene::to_geogebra`.
+    ///
+    /// Returns:
+    ///
+    /// The function `evaluate` returns the named objects in the order of the entries, or the
+    /// first error of an entry.
+    pub fn evaluate(&self) -> Result<interop::Scene, SceneError> {
+        let mut result = interop::Scene::new();
+        for (object, element) in self.objects.iter().zip(self.elements()?) {
+            let Some(name) = &object.name else {
+                continue;
+            };
+            let _ = match element {
+                Element::Point { pt, .. } => result.add_point(name, &pt),
+                Element::Line(ln) => result.add_line(name, &ln),
+                Element::Conic(conic) => result.add_conic(name, &conic),
+                Element::Segment(..) | Element::Polygon(_) => continue,
+            };
+        }
+        Ok(result)
+    }
+
+    /// Evaluate all entries and collect the ones that are not hidden into a figure. Points are
+    /// labeled with their names unless a label is given.
+    pub fn to_figure(&self) -> Result<Figure, SceneError> {
+        let mut figure = Figure::new(self.width, self.height);
+        figure.margin = self.margin;
+        for (object, element) in self.objects.iter().zip(self.elements()?) {
+            let hint = &object.style;
+            if hint.hidden {
+                continue;
+            }
+            let style = Style::new(hint.color.as_deref().unwrap_or("black"));
+            let element = match element {
+                Element::Point { pt, .. } => {
+                    let label = hint.label.as_ref().or(object.name.as_ref());
+                    let label = label.filter(|text| !text.is_empty()).cloned();
+                    figure.add(
+                        Element::Point { pt, label },
+                        style.layer(hint.layer.unwrap_or(1)),
+                    );
+                    continue;
+                }
+                element => element,
+            };
+            figure.add(element, style.layer(hint.layer.unwrap_or(0)));
+        }
+        Ok(figure)
+    }
+
+    /// Render the scene as an SVG document; see `Figure::to_svg`.
+    pub fn to_svg(&self) -> Result<String, SceneError> {
+        Ok(self.to_figure()?.to_svg())
+    }
+
+    /// Render the scene as a TikZ picture; see `Figure::to_tikz`.
+    pub fn to_tikz(&self) -> Result<String, SceneError> {
+        Ok(self.to_figure()?.to_tikz())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pg_plane::coincident;
+
+    #[test]
+    fn test_scene_pappus() {
+        let mut scene = Scene::new(200, 200);
+        for (name, coord) in [
+            ("A", [0, 0, 1]),
+            ("B", [1, 0, 1]),
+            ("C", [3, 0, 1]),
+            ("D", [0, 1, 1]),
+            ("E", [2, 1, 1]),
+            ("F", [5, 1, 1]),
+        ] {
+            scene.add(SceneEntry::new(name, Definition::Point(coord)));
+        }
+        for (name, [pt_a, pt_b]) in [
+            ("AE", ["A", "E"]),
+            ("BD", ["B", "D"]),
+            ("AF", ["A", "F"]),
+            ("CD", ["C", "D"]),
+            ("BF", ["B", "F"]),
+            ("CE", ["C", "E"]),
+        ] {
+            let definition = Definition::Join([pt_a.into(), pt_b.into()]);
+            scene.add(SceneEntry::new(name, definition).color("gray"));
+        }
+        for (name, [ln_l, ln_m]) in [
+            ("G", ["AE", "BD"]),
+            ("H", ["AF", "CD"]),
+            ("I", ["BF", "CE"]),
+        ] {
+            let definition = Definition::Meet([ln_l.into(), ln_m.into()]);
+            scene.add(SceneEntry::new(name, definition).color("red"));
+        }
+
+        // the document survives a round trip through JSON
+        let text = scene.to_json();
+        assert!(text.contains(r#""join": ["#) && text.contains(r#""color": "gray""#));
+        assert!(!text.contains("hidden") && !text.contains("label"));
+        assert_eq!(Scene::from_json(&text), Ok(scene.clone()));
+
+        let objects = scene.evaluate().unwrap();
+        assert_eq!(objects.objects().len(), 15);
+        let [pt_g, pt_h, pt_i] = ["G", "H", "I"].map(|name| match objects.get(name) {
+            Some(SceneObject::Point(pt)) => pt.clone(),
+            _ => panic!("{} is not a point", name),
+        });
+        assert!(coincident(&pt_g, &pt_h, &pt_i));
+
+        let svg = scene.to_svg().unwrap();
+        assert_eq!(svg.matches("<circle ").count(), 9);
+        assert!(svg.contains(">G</text>") && svg.contains(r#"stroke="gray""#));
+        let tikz = scene.to_tikz().unwrap();
+        assert_eq!(tikz.matches("\\fill[color=red]").count(), 3);
+    }
+
+    #[test]
+    fn test_scene_constructions() {
+        let scene = Scene::from_json(
+            r#"{"objects": [
+                {"name": "c", "conic": [1, 0, 1, 0, 0, -25]},
+                {"name": "P", "point": [7, 1, 1], "label": "P_0"},
+                {"name": "p", "polar": ["P", "c"], "hidden": true},
+                {"pole": ["p", "c"], "label": ""},
+                {"name": "k", "conic_through": [[5, 0, 1], [3, 4, 1], [0, 5, 1], [-4, 3, 1], "P"]},
+                {"name": "M", "harm_conj": [[0, 0, 1], [4, 0, 1], [1, 0, 1]]},
+                {"segment": ["P", [0, 0, 1]], "layer": 2}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!((scene.width, scene.height, scene.margin), (400, 400, 1));
+        let objects = scene.evaluate().unwrap();
+        assert_eq!(
+            objects.get("p"),
+            Some(&SceneObject::Line(PgLine::new([7, 1, -25])))
+        );
+        assert_eq!(
+            objects.get("M"),
+            Some(&SceneObject::Point(PgPoint::new([-2, 0, 1])))
+        );
+        let figure = scene.to_figure().unwrap();
+        let svg = figure.to_svg();
+        // the pole is drawn but not labeled, and the polar is hidden
+        assert_eq!(svg.matches("<circle ").count(), 3);
+        assert!(svg.contains(r#"font-size="9">0</tspan>"#));
+    }
+
+    #[test]
+    fn test_scene_errors() {
+        let error = |text: &str| Scene::from_json(text).and_then(|scene| scene.evaluate());
+        assert!(matches!(
+            error(r#"{"objects": [{"circle": [0, 0, 1]}]}"#),
+            Err(SceneError::Parse(_))
+        ));
+        assert!(matches!(
+            error(r#"{"objects": [{"point": [0, 0]}]}"#),
+            Err(SceneError::Parse(_))
+        ));
+        assert_eq!(
+            error(r#"{"objects": [{"point": [0, 0, 1]}, {"join": ["A", [1, 0, 1]]}]}"#),
+            Err(SceneError::UnknownName {
+                entry: 1,
+                name: "A".to_string()
+            })
+        );
+        let taken =
+            r#"{"objects": [{"name": "A", "point": [0, 0, 1]}, {"name": "A", "line": [0, 0, 1]}]}"#;
+        assert_eq!(
+            error(taken).unwrap_err().to_string(),
+            "objects[1]: name 'A' is already taken"
+        );
+        assert_eq!(
+            error(r#"{"objects": [{"name": "l", "line": [1, 0, 0]}, {"join": ["l", [1, 0, 1]]}]}"#),
+            Err(SceneError::WrongKind {
+                entry: 1,
+                expected: "point"
+            })
+        );
+        assert_eq!(
+            error(
+                r#"{"objects": [{"name": "s", "segment": [[0, 0, 1], [1, 0, 1]]}, {"join": ["s", [1, 0, 1]]}]}"#
+            ),
+            Err(SceneError::WrongKind {
+                entry: 1,
+                expected: "point"
+            })
+        );
+        assert_eq!(
+            error(r#"{"objects": [{"join": [[1, 2, 1], [2, 4, 2]]}]}"#),
+            Err(SceneError::Degenerate { entry: 0 })
+        );
+        assert_eq!(
+            error(r#"{"objects": [{"harm_conj": [[0, 0, 1], [4, 0, 1], [1, 1, 1]]}]}"#),
+            Err(SceneError::Geometry {
+                entry: 0,
+                error: GeometryError::NotCollinear
+            })
+        );
+    }
+}
@@ -0,0 +1,389 @@
+// Theorems of projective geometry as reusable checks, with quickcheck strategies
+
+use crate::conic::opposite_meets;
+use crate::pg_plane::{coincident, harm_conj, ProjectivePlane, ProjectivePlanePrimitive};
+use core::ops::Mul;
+
+pub use crate::pg_plane::{check_desargue, check_pappus};
+
+/// The function `check_pascal` checks Pascal's theorem: the meets of the opposite sides of a
+/// hexagon inscribed in a conic are collinear.
+///
+/// Arguments:
+///
+/// * `hexagon`: Six points on a conic, e.g. from the strategy `ConicHexagon`.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::theorems::check_pascal;
+/// use projgeom_rs::PgPoint;
+/// // points of the parabola y = x²
+/// let hexagon = [-2, 0, 1, 2, -1, 3].map(|x| PgPoint::new([x, x * x, 1]));
+/// assert!(check_pascal(&hexagon));
+/// ```
+pub fn check_pascal<Point, Line>(hexagon: &[Point; 6]) -> bool
+where
+    Point: ProjectivePlanePrimitive<Line>,
+    Line: ProjectivePlanePrimitive<Point>,
+{
+    let [pt_p, pt_q, pt_r] = opposite_meets(hexagon);
+    coincident(&pt_p, &pt_q, &pt_r)
+}
+
+/// The function `check_brianchon` checks Brianchon's theorem, the dual of Pascal's theorem: the
+/// diagonals of a hexagon circumscribed about a conic are concurrent.
+///
+/// Arguments:
+///
+/// * `hexagon`: Six tangent lines of a conic, e.g. from the strategy `ConicHexagon`, as line
+///   coordinates on a conic are the tangents of the dual conic.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::theorems::check_brianchon;
+/// use projgeom_rs::PgLine;
+/// // tangents y = 2tx - t² of the parabola y = x²
+/// let hexagon = [-2, 0, 1, 2, -1, 3].map(|t| PgLine::new([2 * t, -1, -t * t]));
+/// assert!(check_brianchon(&hexagon));
+/// ```
+#[inline]
+pub fn check_brianchon<Point, Line>(hexagon: &[Line; 6]) -> bool
+where
+    Point: ProjectivePlanePrimitive<Line>,
+    Line: ProjectivePlanePrimitive<Point>,
+{
+    check_pascal(hexagon)
+}
+
+/// The function `check_harm_conj_involution` checks that taking the harmonic conjugate is an
+/// involution: the harmonic conjugate of the harmonic conjugate of `pt_c` is `pt_c`.
+///
+/// Arguments:
+///
+/// * `pt_a`, `pt_b`: Two distinct points.
+/// * `pt_c`: A third point on their line, distinct from both.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::theorems::check_harm_conj_involution;
+/// use projgeom_rs::PgPoint;
+/// let pt_a = PgPoint::new([1, 3, 2]);
+/// let pt_b = PgPoint::new([-2, 1, -1]);
+/// let pt_c = PgPoint::new([-1, 4, 1]);
+/// assert!(check_harm_conj_involution(&pt_a, &pt_b, &pt_c));
+/// ```
+pub fn check_harm_conj_involution<Point, Line, Value>(
+    pt_a: &Point,
+    pt_b: &Point,
+    pt_c: &Point,
+) -> bool
+where
+    Value: Default + Eq,
+    Point: ProjectivePlane<Line, Value>,
+    Line: ProjectivePlane<Point, Value>,
+{
+    let pt_d = harm_conj(pt_a, pt_b, pt_c);
+    harm_conj(pt_a, pt_b, &pt_d) == *pt_c
+}
+
+/// The bracket `det(pt_p, pt_q, pt_o)` of two points with a point `pt_o`, assuming that `meet` and
+/// `dot` are bilinear, as for homogeneous coordinates.
+#[inline]
+fn bracket<Point, Line, Value>(pt_p: &Point, pt_q: &Point, pt_o: &Point) -> Value
+where
+    Value: Default + Eq,
+    Point: ProjectivePlane<Line, Value>,
+    Line: ProjectivePlane<Point, Value>,
+{
+    pt_p.meet(pt_q).dot(pt_o)
+}
+
+/// The products `[AC] [BD]` and `[AD] [BC]` of brackets with a point off the line, whose
+/// quotient is the cross ratio `(A, B; C, D)`.
+fn cross_ratio_terms<Point, Line, Value>(quad: &[Point; 4]) -> (Value, Value)
+where
+    Value: Default + Eq + Mul<Output = Value>,
+    Point: ProjectivePlane<Line, Value>,
+    Line: ProjectivePlane<Point, Value>,
+{
+    let [pt_a, pt_b, pt_c, pt_d] = quad;
+    let pt_o = pt_a.meet(pt_b).aux();
+    let br = |pt_p: &Point, pt_q: &Point| bracket(pt_p, pt_q, &pt_o);
+    (
+        br(pt_a, pt_c) * br(pt_b, pt_d),
+        br(pt_a, pt_d) * br(pt_b, pt_c),
+    )
+}
+
+/// The function `check_cross_ratio_invariance` checks that a homography preserves the cross ratio
+/// of four collinear points. The cross ratios are compared as products of brackets, without
+/// division, assuming that `meet` and `dot` are bilinear, as for homogeneous coordinates.
+///
+/// Arguments:
+///
+/// * `quad`: Four distinct collinear points.
+/// * `homography`: A projective transformation of the points, e.g. `Transform::apply`, or a
+///   perspectivity onto another line.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::theorems::check_cross_ratio_invariance;
+/// use projgeom_rs::transform::Transform;
+/// use projgeom_rs::PgPoint;
+/// let quad = [[1, 0, 1], [2, 1, 1], [0, -1, 1], [3, 2, 1]].map(PgPoint::new);
+/// let trans = Transform::new([[2, 1, 0], [1, 1, 0], [0, 3, 1]]).unwrap();
+/// assert!(check_cross_ratio_invariance(&quad, |pt| trans.apply(pt)));
+/// // an affine map that is not a homography of the line does not preserve it
+/// let square = |pt: &PgPoint| PgPoint::new([pt.coord[0] * pt.coord[0], pt.coord[1], 1]);
+/// assert!(!check_cross_ratio_invariance(&quad, square));
+/// ```
+pub fn check_cross_ratio_invariance<Point, Line, Value>(
+    quad: &[Point; 4],
+    homography: impl Fn(&Point) -> Point,
+) -> bool
+where
+    Value: Default + Eq + Mul<Output = Value>,
+    Point: ProjectivePlane<Line, Value>,
+    Line: ProjectivePlane<Point, Value>,
+{
+    let image = [0, 1, 2, 3].map(|i| homography(&quad[i]));
+    let (numer, denom) = cross_ratio_terms(quad);
+    let (numer_image, denom_image) = cross_ratio_terms(&image);
+    numer * denom_image == numer_image * denom
+}
+
+#[cfg(any(test, feature = "quickcheck"))]
+pub use self::strategies::{CollinearPoints, ConicHexagon, PerspectiveTriangles};
+
+/// Strategies generating the configurations of the theorems for quickcheck.
+///
+/// The coordinates are kept small, below `STRATEGY_BOUND` before combination, so that checks of
+/// high degree in the coordinates, such as `check_pascal`, do not overflow i64. For checks of even
+/// higher degree, map the coordinates into a ring without overflow, e.g. `PgPointOver<Zp<P>>`.
+#[cfg(any(test, feature = "quickcheck"))]
+mod strategies {
+    use crate::arbitrary::small;
+    use crate::mat3::Mat3;
+    use crate::pg_object::{cross_product, dot_product, Coord3};
+    use quickcheck::{Arbitrary, Gen};
+
+    /// Bound on the absolute value of the coordinates the configurations are built from.
+    pub const STRATEGY_BOUND: i64 = 3;
+
+    /// Distinct points `[s : t]` of the projective line with `|s|, |t| <= 2`.
+    const RATIOS: [[i64; 2]; 8] = [
+        [0, 1],
+        [1, 0],
+        [1, 1],
+        [-1, 1],
+        [2, 1],
+        [-2, 1],
+        [1, 2],
+        [-1, 2],
+    ];
+
+    /// Draw a nonzero coordinate vector with entries bounded by `STRATEGY_BOUND`.
+    fn small_coord(g: &mut Gen) -> [i64; 3] {
+        loop {
+            let coord = [(); 3].map(|_| small(g, STRATEGY_BOUND));
+            if coord != [0, 0, 0] {
+                return coord;
+            }
+        }
+    }
+
+    /// Draw `N` distinct entries of `RATIOS`.
+    fn distinct_ratios<const N: usize>(g: &mut Gen) -> [[i64; 2]; N] {
+        assert!(N <= RATIOS.len());
+        let mut chosen = [0_usize; N];
+        for i in 0..N {
+            chosen[i] = loop {
+                let k = usize::arbitrary(g) % RATIOS.len();
+                if !chosen[..i].contains(&k) {
+                    break k;
+                }
+            };
+        }
+        chosen.map(|k| RATIOS[k])
+    }
+
+    /// `N` distinct points on a line, for Pappus' theorem (`N = 3`), harmonic conjugates (`N = 3`)
+    /// or cross ratios (`N = 4`). `N` is at most 8.
+    #[derive(Debug, Clone)]
+    pub struct CollinearPoints<Point, const N: usize>(pub [Point; N]);
+
+    impl<Point, const N: usize> Arbitrary for CollinearPoints<Point, N>
+    where
+        Point: Coord3 + Clone + Send + 'static,
+    {
+        fn arbitrary(g: &mut Gen) -> Self {
+            let (v_p, v_q) = loop {
+                let (v_p, v_q) = (small_coord(g), small_coord(g));
+                if cross_product(&v_p, &v_q) != [0, 0, 0] {
+                    break (v_p, v_q);
+                }
+            };
+            let points = distinct_ratios::<N>(g)
+                .map(|[s, t]| Point::from_coord([0, 1, 2].map(|i| s * v_p[i] + t * v_q[i])));
+            CollinearPoints(points)
+        }
+    }
+
+    /// Two triangles perspective from a point, for Desargues' theorem. Random triangles are
+    /// hardly ever perspective, which would leave the theorem untested.
+    #[derive(Debug, Clone)]
+    pub struct PerspectiveTriangles<Point>(pub [Point; 3], pub [Point; 3]);
+
+    impl<Point> Arbitrary for PerspectiveTriangles<Point>
+    where
+        Point: Coord3 + Clone + Send + 'static,
+    {
+        fn arbitrary(g: &mut Gen) -> Self {
+            let det = |[a, b, c]: &[[i64; 3]; 3]| dot_product(a, &cross_product(b, c));
+            loop {
+                let center = small_coord(g);
+                let tri1 = [(); 3].map(|_| small_coord(g));
+                let tri2 = tri1.map(|vertex| {
+                    let (lambda, mu) = (small(g, 2), small(g, 2));
+                    [0, 1, 2].map(|i| lambda * center[i] + mu * vertex[i])
+                });
+                // nondegenerate triangles whose corresponding vertices differ
+                let distinct = (0..3).all(|i| {
+                    cross_product(&tri1[i], &tri2[i]) != [0, 0, 0]
+                        && cross_product(&tri1[i], &center) != [0, 0, 0]
+                });
+                if distinct && det(&tri1) != 0 && det(&tri2) != 0 {
+                    return PerspectiveTriangles(
+                        tri1.map(Point::from_coord),
+                        tri2.map(Point::from_coord),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Six distinct points of a nondegenerate conic, for Pascal's theorem: the image of points of
+    /// `y² = xz` under a random nonsingular matrix. As lines, they are six tangents of a conic,
+    /// for Brianchon's theorem.
+    #[derive(Debug, Clone)]
+    pub struct ConicHexagon<Point>(pub [Point; 6]);
+
+    impl<Point> Arbitrary for ConicHexagon<Point>
+    where
+        Point: Coord3 + Clone + Send + 'static,
+    {
+        fn arbitrary(g: &mut Gen) -> Self {
+            let mat = loop {
+                let mat = Mat3::new([(); 3].map(|_| [(); 3].map(|_| small(g, 1))));
+                if mat.det() != 0 {
+                    break mat;
+                }
+            };
+            let hexagon = distinct_ratios::<6>(g)
+                .map(|[s, t]| Point::from_coord(mat.apply(&[s * s, s * t, t * t])));
+            ConicHexagon(hexagon)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ck_plane::CayleyKleinPlanePrimitive;
+    use crate::coord_ring::Zp;
+    use crate::mat3::Mat3;
+    use crate::pg_object::{EllipticPoint, EuclidPoint, PgLine, PgPoint, PgPointOver};
+    use crate::pg_plane::Join;
+    use crate::transform::Transform;
+    use quickcheck_macros::quickcheck;
+
+    /// A prime field large enough that the small configurations do not degenerate
+    type Big = Zp<1_000_000_007>;
+
+    /// The point of the plane over GF(p) with the same integer coordinates
+    fn lift(pt: &PgPoint) -> PgPointOver<Big> {
+        PgPointOver::new(pt.coord.map(Big::new))
+    }
+
+    #[quickcheck]
+    fn prop_pappus(
+        coline_1: CollinearPoints<PgPoint, 3>,
+        coline_2: CollinearPoints<PgPoint, 3>,
+    ) -> bool {
+        check_pappus(&coline_1.0, &coline_2.0)
+    }
+
+    #[quickcheck]
+    fn prop_desargue(triangles: PerspectiveTriangles<PgPoint>) -> bool {
+        let PerspectiveTriangles(tri1, tri2) = &triangles;
+        crate::pg_plane::persp(tri1, tri2) && check_desargue(tri1, tri2)
+    }
+
+    #[quickcheck]
+    fn prop_pascal_brianchon(points: ConicHexagon<PgPoint>, lines: ConicHexagon<PgLine>) -> bool {
+        check_pascal(&points.0) && check_brianchon(&lines.0)
+    }
+
+    #[quickcheck]
+    fn prop_pascal_euclid(points: ConicHexagon<EuclidPoint>) -> bool {
+        check_pascal(&points.0)
+    }
+
+    #[quickcheck]
+    fn prop_harm_conj_involution(triple: CollinearPoints<PgPoint, 3>) -> bool {
+        let [pt_a, pt_b, pt_c] = &triple.0.each_ref().map(lift);
+        check_harm_conj_involution(pt_a, pt_b, pt_c)
+    }
+
+    #[quickcheck]
+    fn prop_cross_ratio_invariance(
+        quad: CollinearPoints<PgPoint, 4>,
+        triangles: PerspectiveTriangles<PgPoint>,
+    ) -> bool {
+        // the homography mapping the standard frame to a quadrangle, over GF(p)
+        let PerspectiveTriangles(tri, _) = triangles;
+        let mat = Mat3::new([0, 1, 2].map(|i| [0, 1, 2].map(|j| Big::new(tri[j].coord[i]))));
+        let quad = quad.0.each_ref().map(lift);
+        check_cross_ratio_invariance(&quad, |pt| PgPointOver::new(mat.apply(&pt.coord)))
+    }
+
+    #[test]
+    fn test_theorems() {
+        let quad = [[1, 0, 1], [2, 1, 1], [0, -1, 1], [3, 2, 1]].map(PgPoint::new);
+        let trans = Transform::new([[2, 1, 0], [1, 1, 0], [0, 3, 1]]).unwrap();
+        assert!(check_cross_ratio_invariance(&quad, |pt| trans.apply(pt)));
+        // a perspectivity onto another line, from a center off both lines
+        let (center, target) = (PgPoint::new([0, 3, 1]), PgLine::new([1, 1, 5]));
+        assert!(check_cross_ratio_invariance(&quad, |pt| pt
+            .join(&center)
+            .meet(&target)));
+        // swapping two points changes the cross ratio
+        let swap = |pt: &PgPoint| {
+            if *pt == quad[2] {
+                quad[3].clone()
+            } else if *pt == quad[3] {
+                quad[2].clone()
+            } else {
+                pt.clone()
+            }
+        };
+        assert!(!check_cross_ratio_invariance(&quad, swap));
+
+        // the checks apply to the Cayley-Klein planes, e.g. to the polar of a Pascal hexagon
+        let hexagon = [-2, 0, 1, 2, -1, 3].map(|x| EllipticPoint::new([x, x * x, 1]));
+        assert!(check_pascal(&hexagon));
+        assert!(check_brianchon(&hexagon.map(|pt| pt.perp())));
+        let hexagon = [-2, 0, 1, 2, -1, 3].map(|x| PgPoint::new([x, x * x, 1]));
+        let mut off_conic = hexagon.clone();
+        off_conic[0] = PgPoint::new([1, 2, 3]);
+        assert!(check_pascal(&hexagon) && !check_pascal(&off_conic));
+        assert_eq!(
+            lift(&PgPoint::new([1, -2, 3])).coord,
+            [Big::new(1), Big::new(-2), Big::new(3)]
+        );
+    }
+}
@@ -0,0 +1,1421 @@
+// Projective transformations of the plane
+
+use crate::conic::Conic;
+use crate::error::GeometryError;
+use crate::mat3::{checked_cross, Mat3};
+#[cfg(feature = "std")]
+use crate::pg1::{cross_ratio, Chart};
+#[cfg(feature = "std")]
+use crate::pg_object::det3_is_zero;
+use crate::pg_object::{PgLine, PgPoint};
+use crate::pg_plane::ProjectivePlanePrimitive;
+use fractions::Fraction;
+use num_integer::Integer;
+
+type Matrix = [[i64; 3]; 3];
+
+/// Checked product of a matrix and a coordinate vector.
+#[inline]
+fn mat_vec_wide(mat: &Mat3<i128>, coord: &[i64; 3]) -> Result<[i128; 3], GeometryError> {
+    mat.checked_apply(&coord.map(i128::from))
+        .ok_or(GeometryError::Overflow)
+}
+
+/// Checked product of a matrix and a coordinate vector, narrowed to i64.
+fn mat_vec(mat: &Mat3<i128>, coord: &[i64; 3]) -> Result<[i64; 3], GeometryError> {
+    let image = mat_vec_wide(mat, coord)?;
+    let mut result = [0_i64; 3];
+    for (x, y) in result.iter_mut().zip(image) {
+        *x = i64::try_from(y).map_err(|_| GeometryError::Overflow)?;
+    }
+    Ok(result)
+}
+
+/// Widen a matrix to i128.
+#[inline]
+fn widen(mat: &Matrix) -> Mat3<i128> {
+    Mat3::from(*mat).map(i128::from)
+}
+
+/// Divide the entries by their gcd, make the first nonzero entry positive and narrow to i64.
+pub(crate) fn reduce(Mat3 { rows: mat }: Mat3<i128>) -> Result<Matrix, GeometryError> {
+    let common = mat.iter().flatten().fold(0_i128, |g, x| g.gcd(x));
+    let first = mat
+        .iter()
+        .flatten()
+        .find(|x| **x != 0)
+        .copied()
+        .unwrap_or(1);
+    let common = if first < 0 { -common } else { common };
+    let mut result = [[0_i64; 3]; 3];
+    for (row, src) in result.iter_mut().zip(&mat) {
+        for (x, y) in row.iter_mut().zip(src) {
+            *x = i64::try_from(y / common).map_err(|_| GeometryError::Overflow)?;
+        }
+    }
+    Ok(result)
+}
+
+/// Matrix of cofactors, i.e. the transposed adjugate.
+#[inline]
+fn cofactors(mat: &Matrix) -> Result<Mat3<i128>, GeometryError> {
+    widen(mat)
+        .checked_cofactors()
+        .ok_or(GeometryError::Overflow)
+}
+
+/// Divide the entries of a nonzero vector by their gcd and narrow to i64.
+fn reduce_vector(vec: [i128; 3]) -> Result<[i64; 3], GeometryError> {
+    let common = vec.iter().fold(0_i128, |g, x| g.gcd(x));
+    let mut result = [0_i64; 3];
+    for (x, y) in result.iter_mut().zip(vec) {
+        *x = i64::try_from(y / common).map_err(|_| GeometryError::Overflow)?;
+    }
+    Ok(result)
+}
+
+/// Divide a nonzero vector by its last nonzero entry, as reduced fractions.
+fn to_fractions(vec: [i128; 3]) -> Result<[Fraction<i64>; 3], GeometryError> {
+    let scale = *vec.iter().rev().find(|x| **x != 0).unwrap_or(&1);
+    let mut result = [Fraction::new(0, 1); 3];
+    for (x, y) in result.iter_mut().zip(vec) {
+        let common = y.gcd(&scale);
+        let sign = if scale < 0 { -1 } else { 1 };
+        let narrow =
+            |v: i128| i64::try_from(sign * v / common).map_err(|_| GeometryError::Overflow);
+        *x = Fraction::new(narrow(y)?, narrow(scale)?);
+    }
+    Ok(result)
+}
+
+/// The `FixedSpace` enum is a set of fixed points of a transform belonging to one eigenvalue of
+/// its matrix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FixedSpace {
+    /// An isolated fixed point.
+    Point(PgPoint),
+    /// A line of fixed points, e.g. the axis of a perspective collineation.
+    Line(PgLine),
+    /// Every point is fixed.
+    Plane,
+}
+
+/// Indices of the first four points in general position, in lexicographic order.
+fn find_frame(points: &[PgPoint]) -> Result<Option<[usize; 4]>, GeometryError> {
+    let n = points.len();
+    for i in 0..n {
+        for j in i + 1..n {
+            for k in j + 1..n {
+                for l in k + 1..n {
+                    let quad = [i, j, k, l];
+                    match Transform::from_standard_frame(&quad.map(|m| points[m].clone())) {
+                        Ok(_) => return Ok(Some(quad)),
+                        Err(GeometryError::NotInGeneralPosition) => {}
+                        Err(err) => return Err(err),
+                    }
+                }
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// The `Transform` struct is a projective transformation (homography) `x ↦ M x` of the plane,
+/// given by a nonsingular integer matrix `M`.
+///
+/// The matrix is kept reduced: its entries have no common factor and the first nonzero entry is
+/// positive. Hence two transforms compare equal exactly when they act identically.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Transform {
+    /// Reduced nonsingular matrix
+    pub mat: Matrix,
+}
+
+impl Transform {
+    /// The identity transform.
+    pub const IDENTITY: Transform = Transform {
+        mat: [[1, 0, 0], [0, 1, 0], [0, 0, 1]],
+    };
+
+    /// Create a transform from a matrix.
+    ///
+    /// Returns:
+    ///
+    /// The function `new` returns `GeometryError::SingularTransform` if the matrix is singular, or
+    /// `GeometryError::Overflow`.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::transform::Transform;
+    /// let trans = Transform::new([[2, 0, 0], [0, 2, 0], [0, 0, 2]]).unwrap();
+    /// assert_eq!(trans, Transform::IDENTITY);
+    /// assert!(Transform::new([[1, 2, 3], [2, 4, 6], [0, 0, 1]]).is_err());
+    /// ```
+    pub fn new(mat: Matrix) -> Result<Self, GeometryError> {
+        if widen(&mat).checked_det().ok_or(GeometryError::Overflow)? == 0 {
+            return Err(GeometryError::SingularTransform);
+        }
+        Ok(Self {
+            mat: reduce(widen(&mat))?,
+        })
+    }
+
+    /// Create a transform from an i128 matrix known to be nonsingular.
+    #[inline]
+    fn from_wide(mat: Mat3<i128>) -> Result<Self, GeometryError> {
+        Ok(Self { mat: reduce(mat)? })
+    }
+
+    /// The function `apply` maps a point by the transform.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a coordinate of the image overflows i64.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::transform::Transform;
+    /// use projgeom_rs::PgPoint;
+    /// let trans = Transform::new([[1, 0, 3], [0, 1, -2], [0, 0, 1]]).unwrap();
+    /// assert_eq!(trans.apply(&PgPoint::new([1, 1, 1])), PgPoint::new([4, -1, 1]));
+    /// ```
+    pub fn apply(&self, pt: &PgPoint) -> PgPoint {
+        self.try_apply(pt).expect("coordinate overflows i64")
+    }
+
+    /// Map a point by the transform, reporting overflow.
+    pub(crate) fn try_apply(&self, pt: &PgPoint) -> Result<PgPoint, GeometryError> {
+        Ok(PgPoint::new(mat_vec(&widen(&self.mat), &pt.coord)?))
+    }
+
+    /// The function `apply_point_fraction` maps a point by the transform and returns the exact
+    /// coordinates of the image scaled so that the last nonzero one is 1, i.e. `[x, y, 1]` for the
+    /// affine point `(x, y)`. The product is formed in i128 and reduced, so that it only fails if
+    /// a reduced fraction does not fit into i64.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a numerator or denominator overflows i64.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use fractions::Fraction;
+    /// use projgeom_rs::transform::Transform;
+    /// use projgeom_rs::PgPoint;
+    /// let trans = Transform::new([[1, 0, 0], [0, 1, 0], [1, 1, 2]]).unwrap();
+    /// let image = trans.apply_point_fraction(&PgPoint::new([2, 1, 1]));
+    /// assert_eq!(image, [Fraction::new(2, 5), Fraction::new(1, 5), Fraction::new(1, 1)]);
+    /// // a point mapped to infinity
+    /// let image = trans.apply_point_fraction(&PgPoint::new([2, 0, -1]));
+    /// assert_eq!(image, [Fraction::new(1, 1), Fraction::new(0, 1), Fraction::new(0, 1)]);
+    /// ```
+    pub fn apply_point_fraction(&self, pt: &PgPoint) -> [Fraction<i64>; 3] {
+        self.try_apply_point_fraction(pt)
+            .expect("coordinate overflows i64")
+    }
+
+    /// The function `try_apply_point_fraction` is `apply_point_fraction` returning
+    /// `GeometryError::Overflow` instead of panicking.
+    pub fn try_apply_point_fraction(
+        &self,
+        pt: &PgPoint,
+    ) -> Result<[Fraction<i64>; 3], GeometryError> {
+        mat_vec_wide(&widen(&self.mat), &pt.coord).and_then(to_fractions)
+    }
+
+    /// The function `apply_line` maps a line by the transform, i.e. by the inverse transpose of the
+    /// matrix, so that incidence is preserved.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a coordinate of the image overflows i64.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::transform::Transform;
+    /// use projgeom_rs::{PgLine, PgPoint, ProjectivePlanePrimitive};
+    /// let trans = Transform::new([[1, 2, 0], [0, 1, 0], [1, 0, 1]]).unwrap();
+    /// let pt_p = PgPoint::new([3, -1, 1]);
+    /// let ln_l = PgLine::new([1, 3, 0]);
+    /// assert!(trans.apply(&pt_p).incident(&trans.apply_line(&ln_l)));
+    /// ```
+    pub fn apply_line(&self, ln: &PgLine) -> PgLine {
+        self.try_apply_line(ln).expect("coordinate overflows i64")
+    }
+
+    /// Map a line by the transform, reporting overflow.
+    pub(crate) fn try_apply_line(&self, ln: &PgLine) -> Result<PgLine, GeometryError> {
+        Ok(PgLine::new(mat_vec(&cofactors(&self.mat)?, &ln.coord)?))
+    }
+
+    /// The function `apply_line_fraction` is the analogue of `apply_point_fraction` for lines: the
+    /// exact coordinates of the image line, scaled so that the last nonzero one is 1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a numerator or denominator overflows i64.
+    pub fn apply_line_fraction(&self, ln: &PgLine) -> [Fraction<i64>; 3] {
+        self.try_apply_line_fraction(ln)
+            .expect("coordinate overflows i64")
+    }
+
+    /// The function `try_apply_line_fraction` is `apply_line_fraction` returning
+    /// `GeometryError::Overflow` instead of panicking.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use fractions::Fraction;
+    /// use projgeom_rs::transform::Transform;
+    /// use projgeom_rs::{GeometryError, PgLine};
+    /// let trans = Transform::new([[2, 0, 0], [0, 1, 0], [0, 0, 1]]).unwrap();
+    /// // the line x = 1 becomes x = 2
+    /// let image = trans.try_apply_line_fraction(&PgLine::new([1, 0, -1]));
+    /// assert_eq!(image, Ok([Fraction::new(-1, 2), Fraction::new(0, 1), Fraction::new(1, 1)]));
+    /// let big = Transform::new([[i64::MAX, 0, 0], [0, 1, 0], [0, 0, 1]]).unwrap();
+    /// let image = big.try_apply_line_fraction(&PgLine::new([1, 0, i64::MAX]));
+    /// assert_eq!(image, Err(GeometryError::Overflow));
+    /// ```
+    pub fn try_apply_line_fraction(
+        &self,
+        ln: &PgLine,
+    ) -> Result<[Fraction<i64>; 3], GeometryError> {
+        mat_vec_wide(&cofactors(&self.mat)?, &ln.coord).and_then(to_fractions)
+    }
+
+    /// The function `apply_conic` maps a conic by the transform. Its matrix `C` becomes
+    /// `M⁻ᵀ C M⁻¹`, computed with the adjugate in place of the inverse and reduced by the common
+    /// factor of its entries (see `Transform`), so that the image contains the images of the
+    /// points of the conic.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an entry of the image overflows i64.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::conic::Conic;
+    /// use projgeom_rs::transform::Transform;
+    /// use projgeom_rs::PgPoint;
+    /// let circle = Conic::from_coefficients(1, 0, 1, 0, 0, -25);
+    /// let stretch = Transform::new([[2, 0, 0], [0, 1, 0], [0, 0, 1]]).unwrap();
+    /// let ellipse = stretch.apply_conic(&circle);
+    /// assert_eq!(ellipse, Conic::new([[1, 0, 0], [0, 4, 0], [0, 0, -100]]));
+    /// assert!(ellipse.contains(&stretch.apply(&PgPoint::new([3, 4, 1]))));
+    /// ```
+    pub fn apply_conic(&self, conic: &Conic) -> Conic {
+        self.try_apply_conic(conic)
+            .expect("coordinate overflows i64")
+    }
+
+    /// Map a conic by the transform, reporting overflow.
+    pub(crate) fn try_apply_conic(&self, conic: &Conic) -> Result<Conic, GeometryError> {
+        let cof = cofactors(&self.mat)?;
+        let image = cof
+            .checked_mul(&widen(&conic.mat))
+            .and_then(|prod| prod.checked_mul(&cof.transpose()))
+            .ok_or(GeometryError::Overflow)?;
+        if image.rows == [[0; 3]; 3] {
+            return Ok(Conic::new([[0; 3]; 3]));
+        }
+        Ok(Conic::new(reduce(image)?))
+    }
+
+    /// The function `compose` returns the transform that applies `other` first and then `self`.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::transform::Transform;
+    /// let trans = Transform::new([[1, 0, 3], [0, 1, -2], [0, 0, 1]]).unwrap();
+    /// let twice = trans.compose(&trans).unwrap();
+    /// assert_eq!(twice, Transform::new([[1, 0, 6], [0, 1, -4], [0, 0, 1]]).unwrap());
+    /// ```
+    pub fn compose(&self, other: &Transform) -> Result<Transform, GeometryError> {
+        let product = widen(&self.mat)
+            .checked_mul(&widen(&other.mat))
+            .ok_or(GeometryError::Overflow)?;
+        Self::from_wide(product)
+    }
+
+    /// The function `inverse` returns the inverse transform, computed from the adjugate matrix.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::transform::Transform;
+    /// let trans = Transform::new([[2, 1, 0], [1, 1, 0], [0, 3, 1]]).unwrap();
+    /// assert_eq!(trans.compose(&trans.inverse().unwrap()).unwrap(), Transform::IDENTITY);
+    /// ```
+    pub fn inverse(&self) -> Result<Transform, GeometryError> {
+        Self::from_wide(cofactors(&self.mat)?.transpose())
+    }
+
+    /// The function `is_involution` checks if the transform is an involution, i.e. if it is not the
+    /// identity and applying it twice gives the identity, so that `M²` is a scalar matrix.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an intermediate result overflows.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::transform::Transform;
+    /// let reflection = Transform::new([[-1, 0, 0], [0, 1, 0], [0, 0, 1]]).unwrap();
+    /// assert!(reflection.is_involution());
+    /// let translation = Transform::new([[1, 0, 3], [0, 1, -2], [0, 0, 1]]).unwrap();
+    /// assert!(!translation.is_involution());
+    /// assert!(!Transform::IDENTITY.is_involution());
+    /// ```
+    pub fn is_involution(&self) -> bool {
+        let mat = widen(&self.mat);
+        let square = mat.checked_mul(&mat).expect("coordinate overflow");
+        *self != Self::IDENTITY && square == Mat3::scalar(square.rows[0][0])
+    }
+
+    /// Coefficients `[det, c1, tr]` of the characteristic polynomial `t³ - tr t² + c1 t - det`.
+    #[inline]
+    fn char_poly(&self) -> Result<[i128; 3], GeometryError> {
+        widen(&self.mat)
+            .checked_char_poly()
+            .ok_or(GeometryError::Overflow)
+    }
+
+    /// The matrix `M - lambda I`.
+    fn shifted(&self, lambda: i128) -> Result<[[i128; 3]; 3], GeometryError> {
+        let shifted = widen(&self.mat)
+            .checked_sub(&Mat3::scalar(lambda))
+            .ok_or(GeometryError::Overflow)?;
+        Ok(shifted.rows)
+    }
+
+    /// The fixed points of the eigenvalue `lambda`, i.e. the kernel of `M - lambda I`.
+    fn eigenspace(&self, lambda: i128) -> Result<FixedSpace, GeometryError> {
+        let shifted = self.shifted(lambda)?;
+        for [i, j] in [[0, 1], [0, 2], [1, 2]] {
+            let cross = checked_cross(&shifted[i], &shifted[j]).ok_or(GeometryError::Overflow)?;
+            if cross != [0, 0, 0] {
+                return Ok(FixedSpace::Point(PgPoint::new(reduce_vector(cross)?)));
+            }
+        }
+        match shifted.iter().find(|row| **row != [0, 0, 0]) {
+            Some(row) => Ok(FixedSpace::Line(PgLine::new(reduce_vector(*row)?))),
+            None => Ok(FixedSpace::Plane),
+        }
+    }
+
+    /// The center and the axis of a perspective collineation other than the identity.
+    fn perspectivity(&self) -> Result<Option<(PgPoint, PgLine)>, GeometryError> {
+        // The eigenvalue belonging to the axis is a double root of the characteristic polynomial,
+        // so it is the integer (tr c1 - 9 det) / (2 (tr² - 3 c1)), or tr / 3 for a triple root.
+        let [det, c1, tr] = self.char_poly()?;
+        let disc = tr
+            .checked_mul(tr)
+            .zip(c1.checked_mul(3))
+            .and_then(|(p, q)| p.checked_sub(q))
+            .ok_or(GeometryError::Overflow)?;
+        let lambda = if disc == 0 {
+            tr / 3
+        } else {
+            tr.checked_mul(c1)
+                .zip(det.checked_mul(9))
+                .and_then(|(p, q)| p.checked_sub(q))
+                .zip(disc.checked_mul(2))
+                .map(|(p, q)| p / q)
+                .ok_or(GeometryError::Overflow)?
+        };
+        let FixedSpace::Line(axis) = self.eigenspace(lambda)? else {
+            return Ok(None);
+        };
+        // M - lambda I is the rank-one matrix v wᵀ of the center v and the axis w
+        let shifted = self.shifted(lambda)?;
+        let k = (0..3).find(|&k| axis.coord[k] != 0).unwrap_or(0);
+        let center = reduce_vector(shifted.map(|row| row[k]))?;
+        Ok(Some((PgPoint::new(center), axis)))
+    }
+
+    /// The function `fixed_points` returns the fixed points of the transform, grouped into the
+    /// eigenspaces of its integer eigenvalues. As the characteristic polynomial is monic with
+    /// integer coefficients, these are all rational eigenvalues; fixed points of irrational or
+    /// complex eigenvalues are not reported.
+    ///
+    /// Returns:
+    ///
+    /// The function `fixed_points` returns one `FixedSpace` per integer eigenvalue, in increasing
+    /// order of the eigenvalues of the reduced matrix `mat`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an intermediate result overflows.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::transform::{FixedSpace, Transform};
+    /// use projgeom_rs::{PgLine, PgPoint};
+    /// // the reflection in the line x = 0
+    /// let trans = Transform::new([[-1, 0, 0], [0, 1, 0], [0, 0, 1]]).unwrap();
+    /// assert_eq!(
+    ///     trans.fixed_points(),
+    ///     vec![
+    ///         FixedSpace::Line(PgLine::new([1, 0, 0])),
+    ///         FixedSpace::Point(PgPoint::new([1, 0, 0])),
+    ///     ]
+    /// );
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn fixed_points(&self) -> Vec<FixedSpace> {
+        widen(&self.mat)
+            .integer_eigenvalues()
+            .expect("coordinate overflow")
+            .into_iter()
+            .map(|lambda| self.eigenspace(lambda).expect("coordinate overflow"))
+            .collect()
+    }
+
+    /// The function `is_homology` checks if the transform is a homology: a perspective collineation
+    /// whose center does not lie on its axis.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::transform::Transform;
+    /// let scaling = Transform::new([[2, 0, 0], [0, 2, 0], [0, 0, 1]]).unwrap();
+    /// assert!(scaling.is_homology());
+    /// assert!(!Transform::IDENTITY.is_homology());
+    /// ```
+    pub fn is_homology(&self) -> bool {
+        matches!(self.perspectivity().expect("coordinate overflow"),
+            Some((center, axis)) if !axis.incident(&center))
+    }
+
+    /// The function `is_elation` checks if the transform is an elation: a perspective collineation
+    /// whose center lies on its axis, like a translation.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::transform::Transform;
+    /// let translation = Transform::new([[1, 0, 3], [0, 1, -2], [0, 0, 1]]).unwrap();
+    /// assert!(translation.is_elation());
+    /// assert!(!translation.is_homology());
+    /// ```
+    pub fn is_elation(&self) -> bool {
+        matches!(self.perspectivity().expect("coordinate overflow"),
+            Some((center, axis)) if axis.incident(&center))
+    }
+
+    /// The function `axis` returns the line of fixed points of a perspective collineation, or
+    /// `None` if the transform is not a perspective collineation or is the identity.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::transform::Transform;
+    /// use projgeom_rs::PgLine;
+    /// let translation = Transform::new([[1, 0, 3], [0, 1, -2], [0, 0, 1]]).unwrap();
+    /// // the line at infinity
+    /// assert_eq!(translation.axis(), Some(PgLine::new([0, 0, 1])));
+    /// ```
+    pub fn axis(&self) -> Option<PgLine> {
+        self.perspectivity()
+            .expect("coordinate overflow")
+            .map(|(_, axis)| axis)
+    }
+
+    /// The function `center` returns the point of a perspective collineation through which every
+    /// line is fixed, or `None` if the transform is not a perspective collineation or is the
+    /// identity.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::transform::Transform;
+    /// use projgeom_rs::PgPoint;
+    /// let translation = Transform::new([[1, 0, 3], [0, 1, -2], [0, 0, 1]]).unwrap();
+    /// // the direction of the translation
+    /// assert_eq!(translation.center(), Some(PgPoint::new([3, -2, 0])));
+    /// ```
+    pub fn center(&self) -> Option<PgPoint> {
+        self.perspectivity()
+            .expect("coordinate overflow")
+            .map(|(center, _)| center)
+    }
+
+    /// The function `is_affine` checks if the transform is affine, i.e. if it maps the line at
+    /// infinity to itself, so that the last row of the matrix is proportional to `[0, 0, 1]`.
+    #[inline]
+    pub fn is_affine(&self) -> bool {
+        self.mat[2][0] == 0 && self.mat[2][1] == 0
+    }
+
+    /// The function `as_affine` returns the matrix `[[a, b, tx], [c, d, ty]]` of an affine
+    /// transform `(x, y) ↦ (a x + b y + tx, c x + d y + ty)`, as used by 2D graphics libraries.
+    ///
+    /// Returns:
+    ///
+    /// The function `as_affine` returns the rational matrix, or `None` if the transform is not
+    /// affine.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use fractions::Fraction;
+    /// use projgeom_rs::transform::Transform;
+    /// let trans = Transform::new([[1, 0, 3], [0, 2, -1], [0, 0, 2]]).unwrap();
+    /// let affine = trans.as_affine().unwrap();
+    /// assert_eq!(affine[0], [Fraction::new(1, 2), Fraction::new(0, 1), Fraction::new(3, 2)]);
+    /// assert_eq!(Transform::from_affine(&affine), Ok(trans));
+    /// assert!(Transform::new([[1, 0, 0], [0, 1, 0], [1, 0, 1]]).unwrap().as_affine().is_none());
+    /// ```
+    pub fn as_affine(&self) -> Option<[[Fraction<i64>; 3]; 2]> {
+        if !self.is_affine() {
+            return None;
+        }
+        let scale = self.mat[2][2];
+        Some([self.mat[0], self.mat[1]].map(|row| row.map(|x| Fraction::new(x, scale))))
+    }
+
+    /// The function `from_affine` creates the affine transform with the rational matrix
+    /// `[[a, b, tx], [c, d, ty]]`; it inverts `as_affine`.
+    ///
+    /// Returns:
+    ///
+    /// The function `from_affine` returns `GeometryError::SingularTransform` if the linear part
+    /// is singular, `GeometryError::ZeroDenominator` if a denominator is zero, or
+    /// `GeometryError::Overflow`.
+    pub fn from_affine(affine: &[[Fraction<i64>; 3]; 2]) -> Result<Transform, GeometryError> {
+        if affine.iter().flatten().any(|x| x.denom == 0) {
+            return Err(GeometryError::ZeroDenominator);
+        }
+        let common = affine.iter().flatten().try_fold(1_i128, |lcm, x| {
+            let denom = i128::from(x.denom);
+            (lcm / lcm.gcd(&denom)).checked_mul(denom)
+        });
+        let common = common.ok_or(GeometryError::Overflow)?.abs();
+        let mut rows = [[0, 0, 0], [0, 0, 0], [0, 0, common]];
+        for (row, src) in rows.iter_mut().zip(affine) {
+            for (x, y) in row.iter_mut().zip(src) {
+                *x = i128::from(y.numer)
+                    .checked_mul(common / i128::from(y.denom))
+                    .ok_or(GeometryError::Overflow)?;
+            }
+        }
+        let mat = Mat3 { rows };
+        if mat.checked_det().ok_or(GeometryError::Overflow)? == 0 {
+            return Err(GeometryError::SingularTransform);
+        }
+        Self::from_wide(mat)
+    }
+
+    /// The function `from_affine_parts` creates the affine transform `x ↦ A x + t` from the linear
+    /// part `A` and the translation `t`.
+    ///
+    /// Returns:
+    ///
+    /// The function `from_affine_parts` returns `GeometryError::SingularTransform` if `A` is
+    /// singular.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::transform::Transform;
+    /// use projgeom_rs::PgPoint;
+    /// // a rotation by 90 degrees about the origin, followed by a translation by (2, 0)
+    /// let trans = Transform::from_affine_parts([[0, -1], [1, 0]], [2, 0]).unwrap();
+    /// assert!(trans.is_affine());
+    /// assert_eq!(trans.apply(&PgPoint::new([1, 0, 1])), PgPoint::new([2, 1, 1]));
+    /// ```
+    pub fn from_affine_parts(
+        linear: [[i64; 2]; 2],
+        translation: [i64; 2],
+    ) -> Result<Transform, GeometryError> {
+        let [[a, b], [c, d]] = linear;
+        let [tx, ty] = translation;
+        Self::new([[a, b, tx], [c, d, ty], [0, 0, 1]])
+    }
+
+    /// The transform mapping `e1`, `e2`, `e3` and `[1, 1, 1]` to the four points of a quadrangle.
+    fn from_standard_frame(quadrangle: &[PgPoint; 4]) -> Result<Transform, GeometryError> {
+        // columns are the first three points
+        let mut cols = [[0_i64; 3]; 3];
+        for (i, row) in cols.iter_mut().enumerate() {
+            *row = [0, 1, 2].map(|j| quadrangle[j].coord[i]);
+        }
+        if widen(&cols).checked_det().ok_or(GeometryError::Overflow)? == 0 {
+            return Err(GeometryError::NotInGeneralPosition);
+        }
+        // solve cols * lambda = pt_4 up to the factor det(cols), by the adjugate
+        let pt_4 = quadrangle[3].coord.map(i128::from);
+        let lambda = cofactors(&cols)?
+            .transpose()
+            .checked_apply(&pt_4)
+            .ok_or(GeometryError::Overflow)?;
+        if lambda.contains(&0) {
+            return Err(GeometryError::NotInGeneralPosition);
+        }
+        let [l_1, l_2, l_3] = lambda;
+        let scaling = Mat3::new([[l_1, 0, 0], [0, l_2, 0], [0, 0, l_3]]);
+        let result = widen(&cols)
+            .checked_mul(&scaling)
+            .ok_or(GeometryError::Overflow)?;
+        Self::from_wide(result)
+    }
+
+    /// The function `to_standard_frame` computes the transform mapping four points in general
+    /// position to the standard frame `e1`, `e2`, `e3` and the unit point `[1, 1, 1]`.
+    ///
+    /// Arguments:
+    ///
+    /// * `quadrangle`: Four points, no three of them collinear.
+    ///
+    /// Returns:
+    ///
+    /// The function `to_standard_frame` returns the transform, or
+    /// `GeometryError::NotInGeneralPosition` if three of the points are collinear.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::transform::Transform;
+    /// use projgeom_rs::PgPoint;
+    /// let quadrangle = [[0, 0, 1], [2, 0, 1], [3, 1, 1], [1, 1, 1]].map(PgPoint::new);
+    /// let trans = Transform::to_standard_frame(&quadrangle).unwrap();
+    /// assert_eq!(trans.apply(&quadrangle[0]), PgPoint::new([1, 0, 0]));
+    /// assert_eq!(trans.apply(&quadrangle[3]), PgPoint::new([1, 1, 1]));
+    /// ```
+    pub fn to_standard_frame(quadrangle: &[PgPoint; 4]) -> Result<Transform, GeometryError> {
+        Self::from_standard_frame(quadrangle)?.inverse()
+    }
+
+    /// The function `from_quadrangles` computes the unique transform mapping four points in
+    /// general position onto four others.
+    ///
+    /// Arguments:
+    ///
+    /// * `src`: Four points, no three of them collinear.
+    /// * `dst`: Their images, no three of them collinear.
+    ///
+    /// Returns:
+    ///
+    /// The function `from_quadrangles` returns the transform, or
+    /// `GeometryError::NotInGeneralPosition` if three points of either quadrangle are collinear.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::transform::Transform;
+    /// use projgeom_rs::PgPoint;
+    /// let src = [[0, 0, 1], [1, 0, 1], [1, 1, 1], [0, 1, 1]].map(PgPoint::new);
+    /// let dst = [[0, 0, 1], [2, 0, 1], [3, 1, 1], [1, 1, 1]].map(PgPoint::new);
+    /// let trans = Transform::from_quadrangles(&src, &dst).unwrap();
+    /// assert_eq!(trans, Transform::new([[2, 1, 0], [0, 1, 0], [0, 0, 1]]).unwrap());
+    /// ```
+    pub fn from_quadrangles(
+        src: &[PgPoint; 4],
+        dst: &[PgPoint; 4],
+    ) -> Result<Transform, GeometryError> {
+        let to_dst = Self::from_standard_frame(dst)?;
+        to_dst.compose(&Self::to_standard_frame(src)?)
+    }
+
+    /// The function `estimate` computes the transform mapping every source point to its target
+    /// point, from `N ≥ 4` correspondences.
+    ///
+    /// The transform is solved exactly from the first four source points in general position and
+    /// then checked against all other correspondences. As the arithmetic is exact, this is meant
+    /// for exact (e.g. synthetic) data: there is no least-squares compromise for noisy input.
+    ///
+    /// Arguments:
+    ///
+    /// * `src`: The source points.
+    /// * `dst`: The target points, in the same order.
+    ///
+    /// Returns:
+    ///
+    /// The function `estimate` returns the transform, or
+    ///
+    /// * `GeometryError::InvalidCorrespondences` if the slices differ in length or are shorter
+    ///   than four,
+    /// * `GeometryError::NotInGeneralPosition` if no four source points are in general position,
+    /// * `GeometryError::NoHomography` if no transform maps all source points to their targets.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::transform::Transform;
+    /// use projgeom_rs::PgPoint;
+    /// let trans = Transform::new([[1, 2, 0], [0, 1, 3], [1, 0, 1]]).unwrap();
+    /// let src: Vec<PgPoint> = (0..6).map(|i| PgPoint::new([i, i * i - 2, 1])).collect();
+    /// let dst: Vec<PgPoint> = src.iter().map(|pt| trans.apply(pt)).collect();
+    /// assert_eq!(Transform::estimate(&src, &dst), Ok(trans));
+    /// ```
+    pub fn estimate(src: &[PgPoint], dst: &[PgPoint]) -> Result<Transform, GeometryError> {
+        let n = src.len();
+        if n != dst.len() || n < 4 {
+            return Err(GeometryError::InvalidCorrespondences);
+        }
+        let quad = find_frame(src)?.ok_or(GeometryError::NotInGeneralPosition)?;
+        let trans =
+            Self::from_quadrangles(&quad.map(|m| src[m].clone()), &quad.map(|m| dst[m].clone()))
+                .map_err(|err| match err {
+                    GeometryError::NotInGeneralPosition => GeometryError::NoHomography,
+                    err => err,
+                })?;
+        for (pt_p, pt_q) in src.iter().zip(dst) {
+            if trans.try_apply(pt_p)? != *pt_q {
+                return Err(GeometryError::NoHomography);
+            }
+        }
+        Ok(trans)
+    }
+}
+
+/// The `Correlation` struct is a correlation of the plane: a map `x ↦ M x` from points to lines,
+/// given by a nonsingular integer matrix `M`. It maps collinear points to concurrent lines, and
+/// lines to points accordingly.
+///
+/// A correlation with a symmetric matrix is a polarity, e.g. the pole-polar relation of a
+/// nondegenerate conic. The `perp` maps of the Cayley-Klein planes are such polarities: the
+/// elliptic plane has the identity matrix and the hyperbolic plane `diag(1, 1, -1)`.
+///
+/// As for `Transform`, the matrix is kept reduced, so that equality is equality of the maps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Correlation {
+    /// Reduced nonsingular matrix
+    pub mat: Matrix,
+}
+
+impl Correlation {
+    /// Create a correlation from a matrix.
+    ///
+    /// Returns:
+    ///
+    /// The function `new` returns `GeometryError::SingularTransform` if the matrix is singular, or
+    /// `GeometryError::Overflow`.
+    #[inline]
+    pub fn new(mat: Matrix) -> Result<Self, GeometryError> {
+        Ok(Self {
+            mat: Transform::new(mat)?.mat,
+        })
+    }
+
+    /// The function `apply_point` maps a point to a line.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a coordinate of the image overflows i64.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::transform::Correlation;
+    /// use projgeom_rs::{PgLine, PgPoint};
+    /// let corr = Correlation::new([[1, 0, 0], [0, 1, 0], [0, 0, -1]]).unwrap();
+    /// assert_eq!(corr.apply_point(&PgPoint::new([1, 2, 3])), PgLine::new([1, 2, -3]));
+    /// ```
+    pub fn apply_point(&self, pt: &PgPoint) -> PgLine {
+        PgLine::new(mat_vec(&widen(&self.mat), &pt.coord).expect("coordinate overflows i64"))
+    }
+
+    /// The function `apply_line` maps a line to a point: the image of a line through two points is
+    /// the meet of the images of the points.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a coordinate of the image overflows i64.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::transform::Correlation;
+    /// use projgeom_rs::{Join, PgPoint, ProjectivePlanePrimitive};
+    /// let corr = Correlation::new([[2, 1, 0], [0, 1, 3], [1, 0, 1]]).unwrap();
+    /// let pt_p = PgPoint::new([1, -1, 2]);
+    /// let pt_q = PgPoint::new([3, 0, 1]);
+    /// assert_eq!(
+    ///     corr.apply_line(&pt_p.join(&pt_q)),
+    ///     corr.apply_point(&pt_p).meet(&corr.apply_point(&pt_q))
+    /// );
+    /// ```
+    pub fn apply_line(&self, ln: &PgLine) -> PgPoint {
+        let cof = cofactors(&self.mat).expect("coordinate overflows i64");
+        PgPoint::new(mat_vec(&cof, &ln.coord).expect("coordinate overflows i64"))
+    }
+
+    /// Check if the correlation is a polarity, i.e. an involution. This is the case exactly when
+    /// the matrix is symmetric.
+    #[inline]
+    pub fn is_polarity(&self) -> bool {
+        (0..3).all(|i| (0..i).all(|j| self.mat[i][j] == self.mat[j][i]))
+    }
+
+    /// The function `from_conic` returns the pole-polar correlation of a nondegenerate conic.
+    ///
+    /// Returns:
+    ///
+    /// The function `from_conic` returns `GeometryError::SingularTransform` for a degenerate
+    /// conic.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::conic::Conic;
+    /// use projgeom_rs::transform::Correlation;
+    /// use projgeom_rs::PgPoint;
+    /// let circle = Conic::from_coefficients(1, 0, 1, 0, 0, -1);
+    /// let polarity = Correlation::from_conic(&circle).unwrap();
+    /// let pt_p = PgPoint::new([2, 1, 1]);
+    /// assert!(polarity.is_polarity());
+    /// assert_eq!(polarity.apply_point(&pt_p), circle.polar(&pt_p));
+    /// ```
+    #[inline]
+    pub fn from_conic(conic: &Conic) -> Result<Self, GeometryError> {
+        Self::new(conic.mat)
+    }
+
+    /// The function `to_conic` returns the conic of a polarity, whose points are those lying on
+    /// their own polar line, or `None` if the correlation is not a polarity.
+    #[inline]
+    pub fn to_conic(&self) -> Option<Conic> {
+        self.is_polarity().then(|| Conic::new(self.mat))
+    }
+}
+
+/// The function `projectively_equivalent` decides whether two labeled point configurations differ
+/// by a projective transform, i.e. whether some transform maps `config_a[i]` to `config_b[i]` for
+/// every `i`.
+///
+/// Both configurations are mapped to the standard frame by the first four points of `config_a` in
+/// general position (and the points of `config_b` with the same labels), and the images are
+/// compared exactly. Without four points in general position, the distinct points of a
+/// configuration lie on a line, except for at most one point. Two such configurations are
+/// equivalent exactly when the same labels coincide, the same labels lie on the line, and the
+/// ranges on the line have the same cross ratios, since every projectivity of a line extends to
+/// the plane. In particular any two triangles are equivalent.
+///
+/// Arguments:
+///
+/// * `config_a`: The first configuration.
+/// * `config_b`: The second configuration.
+///
+/// Returns:
+///
+/// The function `projectively_equivalent` returns a boolean value, or `GeometryError::Overflow`
+/// if an intermediate result overflows.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::transform::projectively_equivalent;
+/// use projgeom_rs::PgPoint;
+/// // the intersection of the diagonals is the fifth point
+/// let square = [[0, 0, 1], [1, 0, 1], [1, 1, 1], [0, 1, 1], [1, 1, 2]].map(PgPoint::new);
+/// let trapezoid = [[0, 0, 1], [4, 0, 1], [3, 2, 1], [1, 2, 1], [6, 4, 3]].map(PgPoint::new);
+/// assert_eq!(projectively_equivalent(&square, &trapezoid), Ok(true));
+/// let other = [[0, 0, 1], [4, 0, 1], [3, 2, 1], [1, 2, 1], [2, 1, 1]].map(PgPoint::new);
+/// assert_eq!(projectively_equivalent(&square, &other), Ok(false));
+/// // ranges of four points on a line are compared by their cross ratio
+/// let range = [[0, 0, 1], [1, 0, 1], [2, 0, 1], [4, 0, 1]].map(PgPoint::new);
+/// let harmonic = [[0, 0, 1], [2, 0, 1], [1, 0, 1], [1, 0, 0]].map(PgPoint::new);
+/// assert_eq!(projectively_equivalent(&range[..3], &harmonic[..3]), Ok(true));
+/// assert_eq!(projectively_equivalent(&range, &harmonic), Ok(false));
+/// ```
+#[cfg(feature = "std")]
+pub fn projectively_equivalent(
+    config_a: &[PgPoint],
+    config_b: &[PgPoint],
+) -> Result<bool, GeometryError> {
+    if config_a.len() != config_b.len() {
+        return Ok(false);
+    }
+    let Some(quad) = find_frame(config_a)? else {
+        if find_frame(config_b)?.is_some() {
+            return Ok(false);
+        }
+        return sub_frame_equivalent(config_a, config_b);
+    };
+    let to_b = match Transform::to_standard_frame(&quad.map(|m| config_b[m].clone())) {
+        Err(GeometryError::NotInGeneralPosition) => return Ok(false),
+        to_b => to_b?,
+    };
+    let to_a = Transform::to_standard_frame(&quad.map(|m| config_a[m].clone()))?;
+    for (pt_p, pt_q) in config_a.iter().zip(config_b) {
+        if to_a.try_apply(pt_p)? != to_b.try_apply(pt_q)? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Decide `projectively_equivalent` for two configurations of equal length, neither of which
+/// contains four points in general position.
+#[cfg(feature = "std")]
+fn sub_frame_equivalent(config_a: &[PgPoint], config_b: &[PgPoint]) -> Result<bool, GeometryError> {
+    let n = config_a.len();
+    for i in 0..n {
+        for j in i + 1..n {
+            if (config_a[i] == config_a[j]) != (config_b[i] == config_b[j]) {
+                return Ok(false);
+            }
+        }
+    }
+    // labels of the distinct points, the same in both configurations
+    let reps: Vec<usize> = (0..n)
+        .filter(|&i| config_a[..i].iter().all(|pt| *pt != config_a[i]))
+        .collect();
+    if reps.len() < 3 {
+        return Ok(true);
+    }
+    let on_line = |config: &[PgPoint], i: usize, j: usize| -> Vec<usize> {
+        let (v_i, v_j) = (&config[i].coord, &config[j].coord);
+        reps.iter()
+            .copied()
+            .filter(|&m| det3_is_zero(v_i, v_j, &config[m].coord))
+            .collect()
+    };
+    // the line contains two of any three distinct points, and all of them but at most one
+    let [i, j] = [[reps[0], reps[1]], [reps[0], reps[2]], [reps[1], reps[2]]]
+        .into_iter()
+        .max_by_key(|&[i, j]| on_line(config_a, i, j).len())
+        .expect("three candidate lines");
+    let range = on_line(config_a, i, j);
+    if on_line(config_b, i, j) != range {
+        return Ok(false);
+    }
+    // three distinct points of a line can be mapped to any other three
+    if range.len() < 4 {
+        return Ok(true);
+    }
+    let (chart_a, chart_b) = (
+        Chart::new(config_a[i].clone(), config_a[j].clone())?,
+        Chart::new(config_b[i].clone(), config_b[j].clone())?,
+    );
+    let coords_a = range
+        .iter()
+        .map(|&m| chart_a.coordinate(&config_a[m]))
+        .collect::<Result<Vec<_>, _>>()?;
+    let coords_b = range
+        .iter()
+        .map(|&m| chart_b.coordinate(&config_b[m]))
+        .collect::<Result<Vec<_>, _>>()?;
+    for m in 3..range.len() {
+        let ratio_a = cross_ratio(&coords_a[0], &coords_a[1], &coords_a[2], &coords_a[m])?;
+        let ratio_b = cross_ratio(&coords_b[0], &coords_b[1], &coords_b[2], &coords_b[m])?;
+        if ratio_a != ratio_b {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// The standard frame `e1`, `e2`, `e3` and the unit point `[1, 1, 1]`, the basis in which the
+/// coordinates of points and lines are given.
+pub const STANDARD_FRAME: [PgPoint; 4] = [
+    PgPoint::new([1, 0, 0]),
+    PgPoint::new([0, 1, 0]),
+    PgPoint::new([0, 0, 1]),
+    PgPoint::new([1, 1, 1]),
+];
+
+/// The function `change_of_basis` computes the change of projective coordinates from one frame
+/// to another. A frame of four points in general position is a basis: its first three points
+/// get the coordinates `e1`, `e2`, `e3` and the fourth the unit point `[1, 1, 1]`. The transform
+/// maps the coordinates of a point relative to `from` to its coordinates relative to `to`; the
+/// frames themselves are given in standard coordinates (see `STANDARD_FRAME`).
+///
+/// Since a change of basis is a projective transformation, incidences and cross-ratios are the
+/// same in every frame.
+///
+/// Arguments:
+///
+/// * `from`: The frame the coordinates are relative to.
+/// * `to`: The frame of the new coordinates.
+///
+/// Returns:
+///
+/// The function `change_of_basis` returns the transform, or `GeometryError::NotInGeneralPosition`
+/// if three points of either frame are collinear.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::transform::{change_of_basis, STANDARD_FRAME};
+/// use projgeom_rs::PgPoint;
+/// let frame = [[0, 0, 1], [2, 0, 1], [3, 1, 1], [1, 1, 1]].map(PgPoint::new);
+/// let to_frame = change_of_basis(&STANDARD_FRAME, &frame).unwrap();
+/// assert_eq!(frame[0].in_basis(&to_frame), PgPoint::new([1, 0, 0]));
+/// assert_eq!(frame[3].in_basis(&to_frame), PgPoint::new([1, 1, 1]));
+/// // and back
+/// let pt_p = PgPoint::new([5, -2, 3]);
+/// let from_frame = change_of_basis(&frame, &STANDARD_FRAME).unwrap();
+/// assert_eq!(pt_p.in_basis(&to_frame).in_basis(&from_frame), pt_p);
+/// ```
+pub fn change_of_basis(from: &[PgPoint; 4], to: &[PgPoint; 4]) -> Result<Transform, GeometryError> {
+    // relative to `from` -> standard -> relative to `to`
+    Transform::to_standard_frame(to)?.compose(&Transform::from_standard_frame(from)?)
+}
+
+impl PgPoint {
+    /// Return the coordinates in another basis, given the change of basis from
+    /// `change_of_basis`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a coordinate overflows i64.
+    #[inline]
+    pub fn in_basis(&self, change: &Transform) -> PgPoint {
+        change.apply(self)
+    }
+}
+
+impl PgLine {
+    /// Return the coordinates in another basis, given the change of basis from
+    /// `change_of_basis`. Incidence with points in the same basis is preserved.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a coordinate overflows i64.
+    #[inline]
+    pub fn in_basis(&self, change: &Transform) -> PgLine {
+        change.apply_line(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pg_plane::Join;
+    use crate::theorems::check_cross_ratio_invariance;
+
+    #[test]
+    fn test_transform() {
+        let trans = Transform::new([[3, -1, 2], [1, 4, 0], [-2, 1, 5]]).unwrap();
+        let inv = trans.inverse().unwrap();
+        let pt_p = PgPoint::new([7, -3, 2]);
+        let pt_q = PgPoint::new([-1, 5, 4]);
+        assert_eq!(inv.apply(&trans.apply(&pt_p)), pt_p);
+        let ln_l = pt_p.join(&pt_q);
+        assert_eq!(
+            trans.apply_line(&ln_l),
+            trans.apply(&pt_p).join(&trans.apply(&pt_q))
+        );
+        assert_eq!(inv.compose(&trans).unwrap(), Transform::IDENTITY);
+        assert_eq!(
+            Transform::new([[0, 0, 0], [1, 2, 3], [4, 5, 6]]),
+            Err(GeometryError::SingularTransform)
+        );
+        let big = i64::MAX / 2;
+        let huge = Transform::new([[big, 0, 0], [0, 1, 0], [0, 0, 1]]).unwrap();
+        assert_eq!(huge.compose(&huge), Err(GeometryError::Overflow));
+
+        // exact images, scaled to a last coordinate of 1
+        let to_coord = |v: [Fraction<i64>; 3]| {
+            let lcm = v.iter().fold(1, |l, x| l.lcm(&x.denom));
+            v.map(|x| x.numer * (lcm / x.denom))
+        };
+        let image = trans.apply_point_fraction(&pt_p);
+        assert_eq!(image[2], Fraction::new(1, 1));
+        assert_eq!(PgPoint::new(to_coord(image)), trans.apply(&pt_p));
+        let image = trans.apply_line_fraction(&ln_l);
+        assert_eq!(PgLine::new(to_coord(image)), trans.apply_line(&ln_l));
+        // the product overflows i64, but not the reduced fractions
+        let pt_big = PgPoint::new([big, 0, big]);
+        assert!(huge.try_apply(&pt_big).is_err());
+        assert_eq!(
+            huge.apply_point_fraction(&pt_big),
+            [
+                Fraction::new(big, 1),
+                Fraction::new(0, 1),
+                Fraction::new(1, 1)
+            ]
+        );
+        let pt_far = PgPoint::new([big, 0, 1]);
+        assert_eq!(
+            huge.try_apply_point_fraction(&pt_far),
+            Err(GeometryError::Overflow)
+        );
+        assert_eq!(
+            huge.try_apply_line_fraction(&PgLine::new([0, 1, 0])),
+            Ok([
+                Fraction::new(0, 1),
+                Fraction::new(1, 1),
+                Fraction::new(0, 1)
+            ])
+        );
+    }
+
+    #[test]
+    fn test_affine() {
+        let trans = Transform::from_affine_parts([[2, 1], [-1, 3]], [5, -4]).unwrap();
+        let affine = trans.as_affine().unwrap();
+        assert_eq!(affine[1].map(|x| x.numer), [-1, 3, -4]);
+        assert_eq!(Transform::from_affine(&affine), Ok(trans));
+        // the affine transforms form a group
+        let other = Transform::new([[3, 0, 1], [2, 1, 0], [0, 0, 7]]).unwrap();
+        assert!(trans.compose(&other).unwrap().is_affine());
+        assert!(other.inverse().unwrap().is_affine());
+        let half = Fraction::new(1, 2);
+        let third = Fraction::new(-1, 3);
+        let zero = Fraction::new(0, 1);
+        let scaling = Transform::from_affine(&[[half, zero, zero], [zero, third, zero]]);
+        assert_eq!(scaling.unwrap().mat, [[3, 0, 0], [0, -2, 0], [0, 0, 6]]);
+        assert_eq!(
+            Transform::from_affine(&[[half, half, zero], [third, third, zero]]),
+            Err(GeometryError::SingularTransform)
+        );
+        let invalid = Fraction { numer: 1, denom: 0 };
+        assert_eq!(
+            Transform::from_affine(&[[invalid, zero, zero], [zero, half, zero]]),
+            Err(GeometryError::ZeroDenominator)
+        );
+        // coprime denominators near 2^41 and a numerator near i64::MAX
+        let [p, q, r] = [(1 << 41) - 1, 1 << 41, (1 << 41) + 1];
+        let big = [
+            [Fraction::new(i64::MAX, p), zero, zero],
+            [zero, Fraction::new(1, q), Fraction::new(1, r)],
+        ];
+        assert_eq!(Transform::from_affine(&big), Err(GeometryError::Overflow));
+        assert_eq!(
+            Transform::from_affine_parts([[1, 2], [2, 4]], [0, 0]),
+            Err(GeometryError::SingularTransform)
+        );
+    }
+
+    #[test]
+    fn test_change_of_basis() {
+        let frame_a = [[1, 0, 1], [0, 1, 1], [-1, -1, 1], [0, 0, 1]].map(PgPoint::new);
+        let frame_b = [[0, 0, 1], [2, 0, 1], [0, 1, 1], [1, 1, 1]].map(PgPoint::new);
+        let a_to_b = change_of_basis(&frame_a, &frame_b).unwrap();
+        let b_to_a = change_of_basis(&frame_b, &frame_a).unwrap();
+        assert_eq!(a_to_b.compose(&b_to_a).unwrap(), Transform::IDENTITY);
+        assert_eq!(
+            change_of_basis(&frame_a, &frame_a).unwrap(),
+            Transform::IDENTITY
+        );
+        // via the standard frame
+        let std_to_a = change_of_basis(&STANDARD_FRAME, &frame_a).unwrap();
+        let std_to_b = change_of_basis(&STANDARD_FRAME, &frame_b).unwrap();
+        assert_eq!(std_to_b, a_to_b.compose(&std_to_a).unwrap());
+
+        // incidence and cross-ratio are the same in both bases
+        let quad = [[1, 0, 1], [2, 1, 1], [0, -1, 1], [3, 2, 1]].map(PgPoint::new);
+        let ln_l = quad[0].join(&quad[1]);
+        for pt in &quad {
+            assert!(pt.in_basis(&a_to_b).incident(&ln_l.in_basis(&a_to_b)));
+            assert_eq!(pt.in_basis(&a_to_b).in_basis(&b_to_a), *pt);
+        }
+        assert!(check_cross_ratio_invariance(&quad, |pt| pt.in_basis(&a_to_b)));
+
+        let collinear = [[0, 0, 1], [1, 1, 1], [2, 2, 1], [1, 0, 1]].map(PgPoint::new);
+        assert_eq!(
+            change_of_basis(&collinear, &frame_a),
+            Err(GeometryError::NotInGeneralPosition)
+        );
+    }
+
+    #[test]
+    fn test_estimate() {
+        let trans = Transform::new([[2, -1, 3], [1, 1, -1], [0, 2, 5]]).unwrap();
+        let src: Vec<PgPoint> = (-3..5)
+            .map(|i| PgPoint::new([i, 2 * i * i - 1, i + 7]))
+            .collect();
+        let dst: Vec<PgPoint> = src.iter().map(|pt| trans.apply(pt)).collect();
+        assert_eq!(Transform::estimate(&src, &dst), Ok(trans));
+        assert_eq!(Transform::estimate(&src[..4], &dst[..4]), Ok(trans));
+        assert_eq!(
+            Transform::estimate(&src[..3], &dst[..3]),
+            Err(GeometryError::InvalidCorrespondences)
+        );
+
+        // a perturbed correspondence
+        let mut bad = dst.clone();
+        bad[6] = PgPoint::new([1, 2, 3]);
+        assert_eq!(
+            Transform::estimate(&src, &bad),
+            Err(GeometryError::NoHomography)
+        );
+
+        // collinear source points
+        let line: Vec<PgPoint> = (0..5).map(|i| PgPoint::new([i, i, 1])).collect();
+        assert_eq!(
+            Transform::estimate(&line, &dst[..5]),
+            Err(GeometryError::NotInGeneralPosition)
+        );
+    }
+
+    #[test]
+    fn test_standard_frame() {
+        let frame = [[1, 0, 0], [0, 1, 0], [0, 0, 1], [1, 1, 1]].map(PgPoint::new);
+        let quadrangle = [[3, -1, 2], [1, 4, 1], [-2, 1, 5], [0, 7, -3]].map(PgPoint::new);
+        let trans = Transform::to_standard_frame(&quadrangle).unwrap();
+        for (pt_p, pt_q) in quadrangle.iter().zip(&frame) {
+            assert_eq!(trans.apply(pt_p), *pt_q);
+        }
+        assert_eq!(
+            Transform::to_standard_frame(&frame),
+            Ok(Transform::IDENTITY)
+        );
+        let collinear = [[0, 0, 1], [1, 1, 1], [5, 2, 1], [2, 2, 1]].map(PgPoint::new);
+        assert_eq!(
+            Transform::to_standard_frame(&collinear),
+            Err(GeometryError::NotInGeneralPosition)
+        );
+    }
+
+    #[test]
+    fn test_projectively_equivalent() {
+        let trans = Transform::new([[1, -2, 3], [4, 1, 0], [2, 2, 7]]).unwrap();
+        let config: Vec<PgPoint> = (0..7)
+            .map(|i| PgPoint::new([i * i - 3, 2 * i + 1, 5 - i]))
+            .collect();
+        let image: Vec<PgPoint> = config.iter().map(|pt| trans.apply(pt)).collect();
+        assert_eq!(projectively_equivalent(&config, &image), Ok(true));
+        assert_eq!(projectively_equivalent(&config, &image[..6]), Ok(false));
+        // swapping labels breaks the equivalence
+        let mut swapped = image.clone();
+        swapped.swap(5, 6);
+        assert_eq!(projectively_equivalent(&config, &swapped), Ok(false));
+        // the labels of the frame must be in general position in both configurations
+        let mut collinear = image.clone();
+        collinear[3] = image[0].join(&image[1]).meet(&image[2].join(&image[4]));
+        assert_eq!(projectively_equivalent(&config, &collinear), Ok(false));
+        assert_eq!(projectively_equivalent(&collinear, &config), Ok(false));
+
+        // any two triangles are equivalent, but not to three collinear points
+        let triangle = [[1, 0, 0], [0, 1, 0], [0, 0, 1]].map(PgPoint::new);
+        let other = [[3, 1, 1], [-2, 5, 1], [7, 7, 2]].map(PgPoint::new);
+        let range = [[0, 0, 1], [1, 0, 1], [5, 0, 1]].map(PgPoint::new);
+        assert_eq!(projectively_equivalent(&triangle, &other), Ok(true));
+        assert_eq!(projectively_equivalent(&triangle, &range), Ok(false));
+        // ranges of up to three points are equivalent, with the same coincidences
+        let line: Vec<PgPoint> = (0..5).map(|i| PgPoint::new([i, 2 * i, 1])).collect();
+        assert_eq!(projectively_equivalent(&line, &line), Ok(true));
+        assert_eq!(projectively_equivalent(&line[..3], &range), Ok(true));
+        let repeated = [&range[0], &range[1], &range[0]].map(Clone::clone);
+        assert_eq!(projectively_equivalent(&range, &repeated), Ok(false));
+        // longer ranges by their cross ratios, also with a point off the line
+        let image: Vec<PgPoint> = line.iter().map(|pt| trans.apply(pt)).collect();
+        assert_eq!(projectively_equivalent(&line, &image), Ok(true));
+        let mut moved = image.clone();
+        moved[4] = image[0].join(&image[1]).meet(&PgLine::new([1, 1, 1]));
+        assert_eq!(projectively_equivalent(&line, &moved), Ok(false));
+        let mut with_apex = line.clone();
+        with_apex.push(PgPoint::new([1, 0, 1]));
+        let mut image_apex = image.clone();
+        image_apex.push(PgPoint::new([0, 0, 1]));
+        assert_eq!(projectively_equivalent(&with_apex, &image_apex), Ok(true));
+        image_apex.swap(0, 5);
+        assert_eq!(projectively_equivalent(&with_apex, &image_apex), Ok(false));
+        // large coordinates are handled exactly or reported
+        let big = i64::MAX / 2;
+        let far = [[big, 1, 0], [1, big, 0], [0, 1, big], [1, 1, 1]].map(PgPoint::new);
+        assert_eq!(projectively_equivalent(&far[..3], &triangle), Ok(true));
+        assert!(matches!(
+            projectively_equivalent(&far, &far),
+            Ok(true) | Err(GeometryError::Overflow)
+        ));
+    }
+
+    #[test]
+    fn test_perspective_collineations() {
+        // the homology with center [1, 2, 1], axis x + y + z = 0 and ratio 3
+        let center = PgPoint::new([1, 2, 1]);
+        let axis = PgLine::new([1, 1, 1]);
+        let mut mat = [[0; 3]; 3];
+        for (i, row) in mat.iter_mut().enumerate() {
+            for (j, x) in row.iter_mut().enumerate() {
+                *x = center.coord[i] * axis.coord[j] + if i == j { 2 } else { 0 };
+            }
+        }
+        let homology = Transform::new(mat).unwrap();
+        assert!(homology.is_homology());
+        assert!(!homology.is_elation());
+        assert_eq!(homology.center(), Some(center.clone()));
+        assert_eq!(homology.axis(), Some(axis.clone()));
+        assert_eq!(
+            homology.fixed_points(),
+            vec![FixedSpace::Line(axis), FixedSpace::Point(center)]
+        );
+
+        let shear = Transform::new([[1, 1, 0], [0, 1, 0], [0, 0, 1]]).unwrap();
+        assert!(shear.is_elation());
+        assert_eq!(shear.center(), Some(PgPoint::new([1, 0, 0])));
+        assert_eq!(shear.axis(), Some(PgLine::new([0, 1, 0])));
+
+        // a rotation by 90 degrees fixes only the origin
+        let rotation = Transform::new([[0, -1, 0], [1, 0, 0], [0, 0, 1]]).unwrap();
+        assert!(!rotation.is_homology() && !rotation.is_elation());
+        assert_eq!(rotation.axis(), None);
+        assert_eq!(
+            rotation.fixed_points(),
+            vec![FixedSpace::Point(PgPoint::new([0, 0, 1]))]
+        );
+        // a general transform with three distinct eigenvalues
+        let diag = Transform::new([[2, 0, 0], [0, -3, 0], [0, 0, 5]]).unwrap();
+        assert_eq!(diag.fixed_points().len(), 3);
+        assert_eq!(diag.center(), None);
+        let diag =
+            Transform::new([[1000000007, 0, 0], [0, 1000000009, 0], [0, 0, 1000000011]]).unwrap();
+        assert_eq!(
+            diag.fixed_points(),
+            [[1, 0, 0], [0, 1, 0], [0, 0, 1]].map(|c| FixedSpace::Point(PgPoint::new(c)))
+        );
+        assert_eq!(Transform::IDENTITY.fixed_points(), vec![FixedSpace::Plane]);
+        assert!(!Transform::IDENTITY.is_elation());
+
+        // involutions are harmonic homologies, e.g. the point reflection through the origin
+        assert!(!rotation.is_involution());
+        let half_turn = rotation.compose(&rotation).unwrap();
+        assert!(half_turn.is_involution() && half_turn.is_homology());
+        let trans = Transform::new([[2, 1, 0], [1, 1, 0], [0, 3, 1]]).unwrap();
+        let conjugate = trans.compose(&half_turn).unwrap();
+        let conjugate = conjugate.compose(&trans.inverse().unwrap()).unwrap();
+        assert!(conjugate.is_involution());
+        assert!(!diag.is_involution());
+    }
+
+    #[test]
+    fn test_correlation() {
+        use crate::ck_plane::CayleyKleinPlanePrimitive;
+        use crate::pg_object::{HyperbolicLine, HyperbolicPoint};
+
+        let corr = Correlation::new([[1, 2, 0], [0, 1, 3], [4, 0, 1]]).unwrap();
+        assert!(!corr.is_polarity());
+        assert_eq!(corr.to_conic(), None);
+        let pt_p = PgPoint::new([2, -1, 3]);
+        let pt_q = PgPoint::new([1, 4, -2]);
+        let ln_l = pt_p.join(&pt_q);
+        // incidence is reversed: the image of a line lies on the images of its points
+        assert!(corr.apply_line(&ln_l).incident(&corr.apply_point(&pt_p)));
+
+        // the hyperbolic perp is the polarity of diag(1, 1, -1)
+        let polarity = Correlation::new([[1, 0, 0], [0, 1, 0], [0, 0, -1]]).unwrap();
+        let pt_h = HyperbolicPoint::new([3, -2, 5]);
+        assert_eq!(polarity.apply_point(&pt_p).coord, [2, -1, -3]);
+        assert_eq!(
+            pt_h.perp().coord,
+            polarity.apply_point(&PgPoint::new(pt_h.coord)).coord
+        );
+        let ln_h = HyperbolicLine::new([1, 4, 2]);
+        assert_eq!(
+            PgPoint::new(ln_h.perp().coord),
+            polarity.apply_line(&PgLine::new(ln_h.coord))
+        );
+
+        let conic = Conic::from_coefficients(2, -3, 1, 5, -4, 7);
+        let polarity = Correlation::from_conic(&conic).unwrap();
+        assert_eq!(polarity.to_conic(), Some(conic));
+        let pair = Conic::from_coefficients(1, 0, -1, 0, 0, 0);
+        assert_eq!(
+            Correlation::from_conic(&pair),
+            Err(GeometryError::SingularTransform)
+        );
+    }
+}
@@ -0,0 +1,421 @@
+// Barycentric and trilinear coordinates and triangle centers of the Euclidean plane
+
+use crate::error::GeometryError;
+use crate::pg_object::{det3_checked, EuclidPoint};
+use crate::pg_plane::coincident;
+use num_integer::Integer;
+
+/// Divide the entries by their gcd, with the sign making the sum (or the first nonzero entry)
+/// positive.
+fn reduce_weights(weights: [i128; 3]) -> [i128; 3] {
+    let common = weights.iter().fold(0_i128, |g, x| g.gcd(x));
+    if common == 0 {
+        return weights;
+    }
+    let sum = weights.iter().try_fold(0_i128, |s, w| s.checked_add(*w));
+    let sign = match sum {
+        Some(sum) if sum != 0 => sum.signum(),
+        _ => weights.iter().find(|w| **w != 0).map_or(1, |w| w.signum()),
+    };
+    weights.map(|w| w / common * sign)
+}
+
+/// Narrow reduced weights to i64.
+fn narrow_weights(weights: [i128; 3]) -> Result<[i64; 3], GeometryError> {
+    let [w_0, w_1, w_2] = reduce_weights(weights).map(|w| i64::try_from(w).ok());
+    match (w_0, w_1, w_2) {
+        (Some(w_0), Some(w_1), Some(w_2)) => Ok([w_0, w_1, w_2]),
+        _ => Err(GeometryError::Overflow),
+    }
+}
+
+/// Check that the triangle is affine and not degenerate.
+fn check_triangle(triangle: &[EuclidPoint; 3]) -> Result<(), GeometryError> {
+    if triangle.iter().any(|pt| pt.coord[2] == 0) {
+        return Err(GeometryError::PointAtInfinity);
+    }
+    let [a_0, a_1, a_2] = triangle;
+    if coincident(a_0, a_1, a_2) {
+        return Err(GeometryError::InvalidTriangle);
+    }
+    Ok(())
+}
+
+/// The squared side lengths `[a², b², c²]`, all multiplied by `(z_0 z_1 z_2)²` so that they are
+/// integers. Side `i` is opposite vertex `i`.
+fn scaled_quadrances(triangle: &[EuclidPoint; 3]) -> Result<[i128; 3], GeometryError> {
+    let mut result = [0_i128; 3];
+    for (i, q) in result.iter_mut().enumerate() {
+        let [x_1, y_1, z_1] = triangle[(i + 1) % 3].coord.map(i128::from);
+        let [x_2, y_2, z_2] = triangle[(i + 2) % 3].coord.map(i128::from);
+        let z_0 = i128::from(triangle[i].coord[2]);
+        let (d_x, d_y) = (x_1 * z_2 - x_2 * z_1, y_1 * z_2 - y_2 * z_1);
+        *q = d_x
+            .checked_mul(d_x)
+            .zip(d_y.checked_mul(d_y))
+            .and_then(|(u, v)| u.checked_add(v))
+            .and_then(|v| v.checked_mul(z_0 * z_0))
+            .ok_or(GeometryError::Overflow)?;
+    }
+    Ok(result)
+}
+
+/// The side lengths, scaled like `scaled_quadrances`, or `GeometryError::Irrational`.
+fn scaled_sides(triangle: &[EuclidPoint; 3]) -> Result<[i128; 3], GeometryError> {
+    let quadrances = scaled_quadrances(triangle)?;
+    let mut result = [0_i128; 3];
+    for (side, q) in result.iter_mut().zip(quadrances) {
+        *side = q.isqrt();
+        if *side * *side != q {
+            return Err(GeometryError::Irrational);
+        }
+    }
+    Ok(result)
+}
+
+/// Point with the given (unreduced) barycentric weights.
+fn point_from_weights(
+    triangle: &[EuclidPoint; 3],
+    weights: [i128; 3],
+) -> Result<EuclidPoint, GeometryError> {
+    let weights = reduce_weights(weights);
+    if weights == [0, 0, 0] {
+        return Err(GeometryError::NotInGeneralPosition);
+    }
+    let z = triangle.each_ref().map(|pt| i128::from(pt.coord[2]));
+    let mut coord = [0_i128; 3];
+    for (i, (pt, w)) in triangle.iter().zip(weights).enumerate() {
+        // the vertex scaled to the homogenizing coordinate z_0 z_1 z_2
+        let scale = z[(i + 1) % 3] * z[(i + 2) % 3];
+        let vertex = pt.coord.map(|x| i128::from(x) * scale);
+        for (x, v) in coord.iter_mut().zip(vertex) {
+            *x = w
+                .checked_mul(v)
+                .and_then(|v| x.checked_add(v))
+                .ok_or(GeometryError::Overflow)?;
+        }
+    }
+    let common = coord.iter().fold(0_i128, |g, x| g.gcd(x));
+    let sign = if coord[2] < 0 { -1 } else { 1 };
+    let [x, y, z] = coord.map(|x| i64::try_from(x / common * sign).ok());
+    match (x, y, z) {
+        (Some(x), Some(y), Some(z)) => Ok(EuclidPoint::new([x, y, z])),
+        _ => Err(GeometryError::Overflow),
+    }
+}
+
+/// The function `from_barycentric` returns the point with the given barycentric coordinates
+/// with respect to a triangle, i.e. the weighted average of the vertices. Weights summing to zero
+/// give a point at infinity.
+///
+/// Returns:
+///
+/// The function `from_barycentric` returns the point, `GeometryError::PointAtInfinity` or
+/// `GeometryError::InvalidTriangle` for an invalid triangle,
+/// `GeometryError::NotInGeneralPosition` if all weights are zero, or `GeometryError::Overflow`.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::triangle::from_barycentric;
+/// use projgeom_rs::EuclidPoint;
+/// let triangle = [[0, 0, 1], [4, 0, 1], [0, 4, 1]].map(EuclidPoint::new);
+/// assert_eq!(from_barycentric(&triangle, [2, 1, 1]), Ok(EuclidPoint::new([1, 1, 1])));
+/// ```
+pub fn from_barycentric(
+    triangle: &[EuclidPoint; 3],
+    weights: [i64; 3],
+) -> Result<EuclidPoint, GeometryError> {
+    check_triangle(triangle)?;
+    point_from_weights(triangle, weights.map(i128::from))
+}
+
+/// The function `to_barycentric` returns the barycentric coordinates of a point with respect to a
+/// triangle, as coprime integers with a positive sum (for an affine point).
+///
+/// Returns:
+///
+/// The function `to_barycentric` returns the weights, the triangle errors of `from_barycentric`,
+/// or `GeometryError::Overflow`.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::triangle::to_barycentric;
+/// use projgeom_rs::EuclidPoint;
+/// let triangle = [[0, 0, 1], [4, 0, 1], [0, 4, 1]].map(EuclidPoint::new);
+/// assert_eq!(to_barycentric(&triangle, &EuclidPoint::new([2, 2, 2])), Ok([2, 1, 1]));
+/// ```
+pub fn to_barycentric(
+    triangle: &[EuclidPoint; 3],
+    pt: &EuclidPoint,
+) -> Result<[i64; 3], GeometryError> {
+    check_triangle(triangle)?;
+    // the homogeneous weight of vertex i is det(P, A_j, A_k), its affine weight that times z_i
+    let mut weights = [0_i128; 3];
+    for (i, w) in weights.iter_mut().enumerate() {
+        let (a_j, a_k) = (&triangle[(i + 1) % 3], &triangle[(i + 2) % 3]);
+        *w = det3_checked(&pt.coord, &a_j.coord, &a_k.coord)
+            .and_then(|det| det.checked_mul(i128::from(triangle[i].coord[2])))
+            .ok_or(GeometryError::Overflow)?;
+    }
+    narrow_weights(weights)
+}
+
+/// The function `from_trilinear` returns the point with the given trilinear coordinates, i.e. with
+/// signed distances to the side lines in the given ratio. This is exact when the side lengths of
+/// the triangle are rational.
+///
+/// Returns:
+///
+/// The function `from_trilinear` returns the point, `GeometryError::Irrational` if a side length
+/// is irrational, or the errors of `from_barycentric`.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::triangle::from_trilinear;
+/// use projgeom_rs::EuclidPoint;
+/// // the incenter of the 3-4-5 triangle
+/// let triangle = [[0, 0, 1], [4, 0, 1], [0, 3, 1]].map(EuclidPoint::new);
+/// assert_eq!(from_trilinear(&triangle, [1, 1, 1]), Ok(EuclidPoint::new([1, 1, 1])));
+/// ```
+pub fn from_trilinear(
+    triangle: &[EuclidPoint; 3],
+    trilinear: [i64; 3],
+) -> Result<EuclidPoint, GeometryError> {
+    check_triangle(triangle)?;
+    let sides = scaled_sides(triangle)?;
+    let mut weights = [0_i128; 3];
+    for (w, (t, side)) in weights.iter_mut().zip(trilinear.iter().zip(sides)) {
+        *w = side
+            .checked_mul(i128::from(*t))
+            .ok_or(GeometryError::Overflow)?;
+    }
+    point_from_weights(triangle, weights)
+}
+
+/// The function `to_trilinear` returns the trilinear coordinates of a point, as coprime integers.
+/// This is exact when the side lengths of the triangle are rational.
+///
+/// Returns:
+///
+/// The function `to_trilinear` returns the coordinates, `GeometryError::Irrational` if a side
+/// length is irrational, or the errors of `to_barycentric`.
+pub fn to_trilinear(
+    triangle: &[EuclidPoint; 3],
+    pt: &EuclidPoint,
+) -> Result<[i64; 3], GeometryError> {
+    let weights = to_barycentric(triangle, pt)?;
+    let sides = scaled_sides(triangle)?;
+    // w_i / a_i, multiplied by a_0 a_1 a_2
+    let mut trilinear = [0_i128; 3];
+    for (i, t) in trilinear.iter_mut().enumerate() {
+        *t = i128::from(weights[i])
+            .checked_mul(sides[(i + 1) % 3])
+            .and_then(|v| v.checked_mul(sides[(i + 2) % 3]))
+            .ok_or(GeometryError::Overflow)?;
+    }
+    narrow_weights(trilinear)
+}
+
+/// The `TriangleCenter` enum lists the triangle centers with exact constructions, numbered as in
+/// Kimberling's Encyclopedia of Triangle Centers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TriangleCenter {
+    /// X(1), the center of the incircle, at trilinears `1 : 1 : 1`.
+    Incenter,
+    /// X(2), the meet of the medians.
+    Centroid,
+    /// X(3), the center of the circumcircle.
+    Circumcenter,
+    /// X(4), the meet of the altitudes.
+    Orthocenter,
+    /// X(5), the center of the nine-point circle, midway between X(3) and X(4).
+    NinePointCenter,
+}
+
+impl TriangleCenter {
+    /// Return the center with the given Kimberling index, if listed.
+    #[inline]
+    pub fn from_index(index: u32) -> Option<Self> {
+        match index {
+            1 => Some(TriangleCenter::Incenter),
+            2 => Some(TriangleCenter::Centroid),
+            3 => Some(TriangleCenter::Circumcenter),
+            4 => Some(TriangleCenter::Orthocenter),
+            5 => Some(TriangleCenter::NinePointCenter),
+            _ => None,
+        }
+    }
+
+    /// Return the Kimberling index of the center.
+    #[inline]
+    pub fn index(&self) -> u32 {
+        *self as u32 + 1
+    }
+
+    /// Barycentric weights, unreduced.
+    fn weights(&self, triangle: &[EuclidPoint; 3]) -> Result<[i128; 3], GeometryError> {
+        let overflow = || GeometryError::Overflow;
+        if *self == TriangleCenter::Incenter {
+            return scaled_sides(triangle);
+        }
+        if *self == TriangleCenter::Centroid {
+            return Ok([1, 1, 1]);
+        }
+        let q = scaled_quadrances(triangle)?;
+        let mut weights = [0_i128; 3];
+        for (i, w) in weights.iter_mut().enumerate() {
+            let (q_a, q_b, q_c) = (q[i], q[(i + 1) % 3], q[(i + 2) % 3]);
+            // Conway's notation: S_A = (b² + c² - a²) / 2, up to the factor 2
+            let s = |q_a: i128, q_b: i128, q_c: i128| {
+                q_b.checked_add(q_c).and_then(|v| v.checked_sub(q_a))
+            };
+            *w = match self {
+                TriangleCenter::Circumcenter => {
+                    s(q_a, q_b, q_c).and_then(|s_a| q_a.checked_mul(s_a))
+                }
+                TriangleCenter::Orthocenter => s(q_b, q_c, q_a)
+                    .zip(s(q_c, q_a, q_b))
+                    .and_then(|(s_b, s_c)| s_b.checked_mul(s_c)),
+                _ => {
+                    // a² (b² + c²) - (b² - c²)²
+                    let diff = q_b - q_c;
+                    q_b.checked_add(q_c)
+                        .and_then(|v| v.checked_mul(q_a))
+                        .zip(diff.checked_mul(diff))
+                        .and_then(|(u, v)| u.checked_sub(v))
+                }
+            }
+            .ok_or_else(overflow)?;
+        }
+        Ok(weights)
+    }
+
+    /// The function `barycentric` returns the barycentric coordinates of the center of a
+    /// triangle, as coprime integers.
+    ///
+    /// Returns:
+    ///
+    /// The function `barycentric` returns the weights, `GeometryError::Irrational` for the
+    /// incenter of a triangle with an irrational side, or the errors of `from_barycentric`.
+    pub fn barycentric(&self, triangle: &[EuclidPoint; 3]) -> Result<[i64; 3], GeometryError> {
+        check_triangle(triangle)?;
+        narrow_weights(self.weights(triangle)?)
+    }
+
+    /// The function `point` returns the center of a triangle.
+    ///
+    /// Returns:
+    ///
+    /// The function `point` returns the center, or the errors of `barycentric`.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::triangle::TriangleCenter;
+    /// use projgeom_rs::EuclidPoint;
+    /// let triangle = [[0, 0, 1], [4, 0, 1], [0, 3, 1]].map(EuclidPoint::new);
+    /// let center = |index| TriangleCenter::from_index(index).unwrap().point(&triangle);
+    /// assert_eq!(center(1), Ok(EuclidPoint::new([1, 1, 1])));
+    /// assert_eq!(center(2), Ok(EuclidPoint::new([4, 3, 3])));
+    /// assert_eq!(center(3), Ok(EuclidPoint::new([4, 3, 2])));
+    /// assert_eq!(center(4), Ok(EuclidPoint::new([0, 0, 1])));
+    /// assert_eq!(center(5), Ok(EuclidPoint::new([4, 3, 4])));
+    /// ```
+    pub fn point(&self, triangle: &[EuclidPoint; 3]) -> Result<EuclidPoint, GeometryError> {
+        check_triangle(triangle)?;
+        point_from_weights(triangle, self.weights(triangle)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ck_plane::midpoint;
+    use crate::euclid_object::{angle_bisectors, orthocenter, EuclidCircle};
+    use crate::pg_plane::{Join, ProjectivePlanePrimitive};
+
+    #[test]
+    fn test_barycentric_trilinear() {
+        let triangle = [[1, 2, 1], [14, 6, 2], [-3, 15, 3]].map(EuclidPoint::new);
+        let pt_p = EuclidPoint::new([5, 13, 6]);
+        let weights = to_barycentric(&triangle, &pt_p).unwrap();
+        assert_eq!(from_barycentric(&triangle, weights), Ok(pt_p));
+        assert_eq!(
+            from_barycentric(&triangle, [1, 0, 0]),
+            Ok(triangle[0].clone())
+        );
+        // weights summing to zero give the direction of a_1 - a_2
+        let ideal = from_barycentric(&triangle, [0, 1, -1]).unwrap();
+        assert_eq!(ideal.coord[2], 0);
+        assert!(ideal.incident(&triangle[1].join(&triangle[2])));
+        assert_eq!(
+            to_trilinear(&triangle, &triangle[0]),
+            Err(GeometryError::Irrational)
+        );
+
+        // a triangle with sides 13, 14, 15
+        let triangle = [[0, 0, 1], [14, 0, 1], [5, 12, 1]].map(EuclidPoint::new);
+        let pt_p = EuclidPoint::new([7, 3, 2]);
+        let trilinear = to_trilinear(&triangle, &pt_p).unwrap();
+        assert_eq!(from_trilinear(&triangle, trilinear), Ok(pt_p));
+        let flat = [[0, 0, 1], [1, 1, 1], [2, 2, 1]].map(EuclidPoint::new);
+        assert_eq!(
+            from_barycentric(&flat, [1, 1, 1]),
+            Err(GeometryError::InvalidTriangle)
+        );
+    }
+
+    #[test]
+    fn test_triangle_centers() {
+        // sides 13, 14, 15, so that the incenter is rational
+        let triangle = [[0, 0, 2], [28, 0, 2], [10, 24, 2]].map(EuclidPoint::new);
+        let center = |c: TriangleCenter| c.point(&triangle).unwrap();
+        let [a_0, a_1, a_2] = &triangle;
+
+        let incenter = center(TriangleCenter::Incenter);
+        let (bisector, _) = angle_bisectors(&a_0.join(a_1), &a_0.join(a_2)).unwrap();
+        assert!(bisector.incident(&incenter));
+        let (bisector, _) = angle_bisectors(&a_1.join(a_2), &a_1.join(a_0)).unwrap();
+        assert!(bisector.incident(&incenter));
+        assert_eq!(incenter, EuclidPoint::new([6, 4, 1]));
+        assert_eq!(to_trilinear(&triangle, &incenter), Ok([1, 1, 1]));
+
+        let centroid = center(TriangleCenter::Centroid);
+        assert!(a_0.join(&a_1.midpoint(a_2)).incident(&centroid));
+        assert!(a_1.join(&a_2.midpoint(a_0)).incident(&centroid));
+
+        let circumcenter = center(TriangleCenter::Circumcenter);
+        let circle = EuclidCircle::through_three_points(&triangle).unwrap();
+        assert_eq!(circumcenter, circle.center());
+
+        let orthocenter_x4 = center(TriangleCenter::Orthocenter);
+        assert_eq!(orthocenter_x4, orthocenter(&triangle));
+
+        let nine_point = center(TriangleCenter::NinePointCenter);
+        assert_eq!(nine_point, midpoint(&circumcenter, &orthocenter_x4));
+        // the Euler line
+        assert!(circumcenter.join(&orthocenter_x4).incident(&centroid));
+
+        for index in 1..=5 {
+            assert_eq!(TriangleCenter::from_index(index).unwrap().index(), index);
+        }
+        assert_eq!(TriangleCenter::from_index(6), None);
+        let irrational = [[0, 0, 1], [1, 0, 1], [0, 1, 1]].map(EuclidPoint::new);
+        assert_eq!(
+            TriangleCenter::Incenter.point(&irrational),
+            Err(GeometryError::Irrational)
+        );
+        assert_eq!(
+            TriangleCenter::Circumcenter.barycentric(&irrational),
+            Ok([0, 1, 1])
+        );
+        assert_eq!(
+            TriangleCenter::Centroid
+                .point(&[[0, 0, 1], [1, 0, 0], [0, 1, 1]].map(EuclidPoint::new)),
+            Err(GeometryError::PointAtInfinity)
+        );
+    }
+}
@@ -0,0 +1,140 @@
+// Exhaustive theorem and axiom verification over large configurations
+
+use crate::axioms::verify_axiom;
+use crate::coord_ring::Zp;
+use crate::pg_object::{PgLineOver, PgPointOver};
+use crate::pg_plane::{check_pappus, ProjectivePlanePrimitive};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Check `pred` for all indices in `0..n`, in parallel with the `parallel` feature.
+#[inline]
+fn all_indices<F>(n: usize, pred: F) -> bool
+where
+    F: Fn(usize) -> bool + Sync + Send,
+{
+    #[cfg(feature = "parallel")]
+    {
+        (0..n).into_par_iter().all(pred)
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        (0..n).all(pred)
+    }
+}
+
+/// All index triples `i < j < k` below `n`.
+fn triples(n: usize) -> Vec<[usize; 3]> {
+    let mut result = Vec::new();
+    for i in 0..n {
+        for j in i + 1..n {
+            for k in j + 1..n {
+                result.push([i, j, k]);
+            }
+        }
+    }
+    result
+}
+
+/// The function `check_pappus_all_triples` checks Pappus' theorem for every choice of three
+/// points from each of two collinear point sets.
+///
+/// Arguments:
+///
+/// * `points_on_l1`: Distinct points on a line.
+/// * `points_on_l2`: Distinct points on another line.
+///
+/// Returns:
+///
+/// The function `check_pappus_all_triples` returns `true` if all `C(n, 3) * C(m, 3)` instances of
+/// the theorem hold. With the `parallel` feature, the instances are checked on the rayon thread
+/// pool.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::verification::check_pappus_all_triples;
+/// use projgeom_rs::PgPoint;
+/// let points_on_l1: Vec<PgPoint> = (0..5).map(|i| PgPoint::new([i, 0, 1])).collect();
+/// let points_on_l2: Vec<PgPoint> = (0..5).map(|i| PgPoint::new([i, 2 * i + 3, 1])).collect();
+/// assert!(check_pappus_all_triples(&points_on_l1, &points_on_l2));
+/// ```
+pub fn check_pappus_all_triples<Point, Line>(points_on_l1: &[Point], points_on_l2: &[Point]) -> bool
+where
+    Point: ProjectivePlanePrimitive<Line> + Clone + Sync,
+    Line: ProjectivePlanePrimitive<Point>,
+{
+    let triples_1 = triples(points_on_l1.len());
+    let triples_2 = triples(points_on_l2.len());
+    all_indices(triples_1.len(), |n| {
+        let coline_1 = triples_1[n].map(|i| points_on_l1[i].clone());
+        triples_2.iter().all(|t| {
+            let coline_2 = t.map(|i| points_on_l2[i].clone());
+            check_pappus(&coline_1, &coline_2)
+        })
+    })
+}
+
+/// Return the `P² + P + 1` points of the projective plane over GF(P), each with its first
+/// nonzero coordinate equal to one.
+pub fn finite_plane_points<const P: u64>() -> Vec<PgPointOver<Zp<P>>> {
+    let p = P as i64;
+    let z = Zp::<P>::new;
+    let mut points = vec![PgPointOver::new([z(0), z(0), z(1)])];
+    points.extend((0..p).map(|c| PgPointOver::new([z(0), z(1), z(c)])));
+    for b in 0..p {
+        points.extend((0..p).map(|c| PgPointOver::new([z(1), z(b), z(c)])));
+    }
+    points
+}
+
+/// The function `check_finite_plane` exhaustively checks the axioms of the projective plane over
+/// GF(P): the incidence axioms of `verify_axiom` for every pair of points and of lines, and that
+/// every line carries exactly `P + 1` points.
+///
+/// Returns:
+///
+/// The function `check_finite_plane` returns `true` if all checks pass. With the `parallel`
+/// feature, the points are distributed over the rayon thread pool.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::verification::check_finite_plane;
+/// assert!(check_finite_plane::<5>());
+/// ```
+pub fn check_finite_plane<const P: u64>() -> bool {
+    let points = finite_plane_points::<P>();
+    // by duality, the lines have the same coordinates as the points
+    let lines: Vec<PgLineOver<Zp<P>>> = points.iter().map(|pt| PgLineOver::new(pt.coord)).collect();
+    let n = points.len();
+    all_indices(n, |i| {
+        let on_line = lines.iter().filter(|ln| ln.incident(&points[i])).count();
+        on_line == P as usize + 1
+            && (0..n).filter(|&j| j != i).all(|j| {
+                verify_axiom(&points[i], &points[j], &lines[j]).is_ok()
+                    && verify_axiom(&lines[i], &lines[j], &points[j]).is_ok()
+            })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pg_object::PgPoint;
+
+    #[test]
+    fn test_pappus_all_triples() {
+        let points_on_l1: Vec<PgPoint> = (1..7).map(|i| PgPoint::new([i, -i, 1])).collect();
+        let points_on_l2: Vec<PgPoint> = (1..6).map(|i| PgPoint::new([3, i, 2 - i])).collect();
+        assert!(check_pappus_all_triples(&points_on_l1, &points_on_l2));
+    }
+
+    #[test]
+    fn test_finite_plane() {
+        assert_eq!(finite_plane_points::<2>().len(), 7);
+        assert_eq!(finite_plane_points::<7>().len(), 57);
+        assert!(check_finite_plane::<2>());
+        assert!(check_finite_plane::<7>());
+    }
+}
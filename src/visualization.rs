@@ -0,0 +1,1790 @@
+// SVG rendering of points, lines and conics, and of the hyperbolic disk models
+
+use crate::conic::{Conic, ConicType};
+use crate::error::GeometryError;
+use crate::pg_object::{HyperbolicLine, HyperbolicPoint, PgLine, PgPoint};
+use crate::pg_plane::ProjectivePlanePrimitive;
+use std::fmt::Write;
+
+/// The `Viewport` struct is the axis-parallel rectangle of the affine plane that is rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Viewport {
+    pub xmin: i64,
+    pub ymin: i64,
+    pub xmax: i64,
+    pub ymax: i64,
+}
+
+impl Viewport {
+    /// Create a new viewport; the minimum coordinates must be less than the maximum ones.
+    #[inline]
+    pub const fn new(xmin: i64, ymin: i64, xmax: i64, ymax: i64) -> Self {
+        assert!(xmin < xmax && ymin < ymax);
+        Self {
+            xmin,
+            ymin,
+            xmax,
+            ymax,
+        }
+    }
+
+    /// The four border lines `x = xmin`, `x = xmax`, `y = ymin` and `y = ymax`.
+    #[inline]
+    pub const fn borders(&self) -> [PgLine; 4] {
+        [
+            PgLine::new([1, 0, -self.xmin]),
+            PgLine::new([1, 0, -self.xmax]),
+            PgLine::new([0, 1, -self.ymin]),
+            PgLine::new([0, 1, -self.ymax]),
+        ]
+    }
+
+    /// Check exactly whether the point lies in the closed rectangle. Ideal points never do.
+    pub fn contains(&self, pt: &PgPoint) -> bool {
+        let [x, y, z] = pt.coord.map(|c| c as i128);
+        if z == 0 {
+            return false;
+        }
+        // scale the bounds by z instead of dividing, flipping them for negative z
+        let (x, y, z) = if z < 0 { (-x, -y, -z) } else { (x, y, z) };
+        self.xmin as i128 * z <= x
+            && x <= self.xmax as i128 * z
+            && self.ymin as i128 * z <= y
+            && y <= self.ymax as i128 * z
+    }
+
+    /// The function `clip_line` computes the segment of a line inside the viewport.
+    ///
+    /// Arguments:
+    ///
+    /// * `ln`: The line to clip.
+    ///
+    /// Returns:
+    ///
+    /// The function `clip_line` returns the two end points of the visible segment, or `None` if the
+    /// line misses the viewport, only touches a corner, or is the line at infinity.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::visualization::Viewport;
+    /// use projgeom_rs::{PgLine, PgPoint};
+    /// let view = Viewport::new(0, 0, 10, 10);
+    /// let [pt_a, pt_b] = view.clip_line(&PgLine::new([1, -1, 0])).unwrap();
+    /// assert_eq!(pt_a, PgPoint::new([0, 0, 1]));
+    /// assert_eq!(pt_b, PgPoint::new([10, 10, 1]));
+    /// assert_eq!(view.clip_line(&PgLine::new([0, 0, 1])), None);
+    /// ```
+    pub fn clip_line(&self, ln: &PgLine) -> Option<[PgPoint; 2]> {
+        let mut ends: Vec<PgPoint> = Vec::with_capacity(2);
+        for border in self.borders() {
+            // a line along a border meets it in the zero vector
+            let pt = ln.meet(&border);
+            if pt.coord != [0, 0, 0] && self.contains(&pt) && !ends.contains(&pt) {
+                ends.push(pt.normalized());
+            }
+        }
+        match ends[..] {
+            [ref pt_a, ref pt_b] => Some([pt_a.clone(), pt_b.clone()]),
+            _ => None,
+        }
+    }
+}
+
+/// The `BoundingBox` struct is the axis-parallel rectangle spanned by affine points, in world
+/// coordinates. Unlike a `Viewport`, its corners need not be integers and it may be degenerate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    pub xmin: f64,
+    pub ymin: f64,
+    pub xmax: f64,
+    pub ymax: f64,
+}
+
+impl BoundingBox {
+    /// The degenerate box containing just the affine point `(x, y)`.
+    #[inline]
+    pub const fn from_point((x, y): (f64, f64)) -> Self {
+        Self {
+            xmin: x,
+            ymin: y,
+            xmax: x,
+            ymax: y,
+        }
+    }
+
+    /// Extend the box to contain the affine point `(x, y)`.
+    #[inline]
+    pub fn extend(&mut self, (x, y): (f64, f64)) {
+        self.xmin = self.xmin.min(x);
+        self.ymin = self.ymin.min(y);
+        self.xmax = self.xmax.max(x);
+        self.ymax = self.ymax.max(y);
+    }
+
+    /// Return the smallest box containing both boxes.
+    #[inline]
+    pub fn union(&self, other: &Self) -> Self {
+        let mut bbox = *self;
+        bbox.extend((other.xmin, other.ymin));
+        bbox.extend((other.xmax, other.ymax));
+        bbox
+    }
+
+    /// The function `of_ellipse` computes the bounding box of the real affine points of a conic.
+    ///
+    /// Returns:
+    ///
+    /// The bounding box of an ellipse, or `None` for the other conics, which are either unbounded
+    /// or have no real affine points.
+    pub fn of_ellipse(conic: &Conic) -> Option<Self> {
+        if conic.conic_type() != ConicType::Ellipse {
+            return None;
+        }
+        let [[m00, m01, m02], [_, m11, m12], [_, _, m22]] =
+            conic.mat.map(|row| row.map(|x| x as f64));
+        // center -B⁻¹g and half extents sqrt(-k (B⁻¹)ᵢᵢ), with k the value at the center
+        let det = m00 * m11 - m01 * m01;
+        let (cx, cy) = ((m01 * m12 - m11 * m02) / det, (m01 * m02 - m00 * m12) / det);
+        let k = m02 * cx + m12 * cy + m22;
+        let (hx, hy) = ((-k * m11 / det).sqrt(), (-k * m00 / det).sqrt());
+        let mut bbox = Self::from_point((cx - hx, cy - hy));
+        bbox.extend((cx + hx, cy + hy));
+        Some(bbox)
+    }
+
+    /// Return the smallest viewport containing the box enlarged by `margin` on every side.
+    pub fn viewport(&self, margin: i64) -> Viewport {
+        Viewport::new(
+            self.xmin.floor() as i64 - margin,
+            self.ymin.floor() as i64 - margin,
+            self.xmax.ceil() as i64 + margin,
+            self.ymax.ceil() as i64 + margin,
+        )
+    }
+
+    /// The function `fit_viewport` computes a viewport containing the box enlarged by `margin`,
+    /// widened in one direction so that it has the aspect ratio of an image of the given size,
+    /// up to rounding to integers. The box stays centered.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::visualization::{BoundingBox, Viewport};
+    /// let mut bbox = BoundingBox::from_point((0.0, 0.0));
+    /// bbox.extend((4.0, 1.5));
+    /// assert_eq!(bbox.viewport(1), Viewport::new(-1, -1, 5, 3));
+    /// assert_eq!(bbox.fit_viewport(300, 300, 1), Viewport::new(-1, -2, 5, 4));
+    /// assert_eq!(bbox.fit_viewport(400, 100, 1), Viewport::new(-6, -1, 10, 3));
+    /// ```
+    pub fn fit_viewport(&self, width: u32, height: u32, margin: i64) -> Viewport {
+        let view = self.viewport(margin);
+        let (w, h) = (view.xmax - view.xmin, view.ymax - view.ymin);
+        let (width, height) = (i64::from(width), i64::from(height));
+        // compare w / h with width / height without dividing
+        let (extra_x, extra_y) = if w * height < h * width {
+            ((h * width + height - 1) / height - w, 0)
+        } else {
+            (0, (w * height + width - 1) / width - h)
+        };
+        Viewport::new(
+            view.xmin - extra_x / 2,
+            view.ymin - extra_y / 2,
+            view.xmax + extra_x - extra_x / 2,
+            view.ymax + extra_y - extra_y / 2,
+        )
+    }
+}
+
+/// The affine coordinates of a point, or `None` if it is ideal.
+#[inline]
+fn affine(pt: &PgPoint) -> Option<(f64, f64)> {
+    let [x, y, z] = pt.coord;
+    (z != 0).then(|| (x as f64 / z as f64, y as f64 / z as f64))
+}
+
+/// The function `bounding_box` computes the bounding box of the affine points among the given
+/// points, skipping the ideal ones. For segments, pass their end points.
+///
+/// Returns:
+///
+/// The bounding box, or `None` if there are no affine points.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::visualization::{bounding_box, try_bounding_box, BoundingBox};
+/// use projgeom_rs::{GeometryError, PgPoint};
+/// let points = [[1, 2, 1], [7, 1, 2], [1, 1, 0]].map(PgPoint::new);
+/// let mut expected = BoundingBox::from_point((1.0, 0.5));
+/// expected.extend((3.5, 2.0));
+/// assert_eq!(bounding_box(&points), Some(expected));
+/// assert_eq!(try_bounding_box(&points), Err(GeometryError::PointAtInfinity));
+/// assert_eq!(bounding_box(&points[2..]), None);
+/// ```
+pub fn bounding_box<'a, I>(points: I) -> Option<BoundingBox>
+where
+    I: IntoIterator<Item = &'a PgPoint>,
+{
+    points
+        .into_iter()
+        .filter_map(affine)
+        .fold(None, |bbox, pt| {
+            Some(match bbox {
+                None => BoundingBox::from_point(pt),
+                Some(mut bbox) => {
+                    bbox.extend(pt);
+                    bbox
+                }
+            })
+        })
+}
+
+/// The function `try_bounding_box` is `bounding_box` for points that must all be affine.
+///
+/// Returns:
+///
+/// The bounding box, `None` if there are no points, or `GeometryError::PointAtInfinity` if one
+/// of them is ideal.
+pub fn try_bounding_box<'a, I>(points: I) -> Result<Option<BoundingBox>, GeometryError>
+where
+    I: IntoIterator<Item = &'a PgPoint>,
+{
+    let mut bbox: Option<BoundingBox> = None;
+    for pt in points {
+        let pt = affine(pt).ok_or(GeometryError::PointAtInfinity)?;
+        match bbox.as_mut() {
+            None => bbox = Some(BoundingBox::from_point(pt)),
+            Some(bbox) => bbox.extend(pt),
+        }
+    }
+    Ok(bbox)
+}
+
+/// Font size of labels, in pixels; subscripts are set at three quarters of it.
+const LABEL_FONT_SIZE: f64 = 12.0;
+
+/// Distance in pixels between a point and its label.
+const LABEL_GAP: f64 = 5.0;
+
+/// Escape the characters that are special in SVG text.
+fn escape_text(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Split a label into runs of normal text and subscripts, following LaTeX: `_x` makes the next
+/// character a subscript and `_{...}` the braced text.
+fn label_runs(text: &str) -> Vec<(String, bool)> {
+    let mut runs: Vec<(String, bool)> = Vec::new();
+    let mut chars = text.chars();
+    let mut push = |run: String, sub: bool| match runs.last_mut() {
+        Some((last, last_sub)) if *last_sub == sub => last.push_str(&run),
+        _ if run.is_empty() => {}
+        _ => runs.push((run, sub)),
+    };
+    while let Some(c) = chars.next() {
+        if c != '_' {
+            push(c.to_string(), false);
+            continue;
+        }
+        match chars.next() {
+            Some('{') => push(chars.by_ref().take_while(|c| *c != '}').collect(), true),
+            Some(c) => push(c.to_string(), true),
+            None => push("_".to_string(), false),
+        }
+    }
+    runs
+}
+
+/// The SVG markup of a label, with subscripts as `tspan` elements.
+fn label_markup(text: &str) -> String {
+    let mut markup = String::new();
+    for (run, sub) in label_runs(text) {
+        if sub {
+            let _ = write!(
+                markup,
+                r#"<tspan baseline-shift="sub" font-size="{}">{}</tspan>"#,
+                0.75 * LABEL_FONT_SIZE,
+                escape_text(&run)
+            );
+        } else {
+            markup.push_str(&escape_text(&run));
+        }
+    }
+    markup
+}
+
+/// The estimated size in pixels of a rendered label, assuming an average glyph width of 0.6 em.
+fn label_size(text: &str) -> (f64, f64) {
+    let width = label_runs(text)
+        .iter()
+        .map(|(run, sub)| {
+            let size = if *sub { 0.75 } else { 1.0 } * LABEL_FONT_SIZE;
+            run.chars().count() as f64 * 0.6 * size
+        })
+        .sum();
+    (width, LABEL_FONT_SIZE)
+}
+
+/// An axis-parallel box in image coordinates, `(left, top, right, bottom)`.
+type LabelBox = (f64, f64, f64, f64);
+
+#[inline]
+fn overlaps(a: &LabelBox, b: &LabelBox) -> bool {
+    a.0 < b.2 && b.0 < a.2 && a.1 < b.3 && b.1 < a.3
+}
+
+/// The function `place_label` chooses the offset of a label from its point, trying the eight
+/// compass positions, starting at the upper right, for one that stays inside the image and
+/// overlaps neither the given point markers nor the labels placed before. If all positions
+/// collide, the upper right is used.
+///
+/// Returns:
+///
+/// The function `place_label` returns the offset of the baseline start and the box occupied by
+/// the label.
+fn place_label(
+    (x, y): (f64, f64),
+    text: &str,
+    (width, height): (f64, f64),
+    obstacles: &[LabelBox],
+) -> ((f64, f64), LabelBox) {
+    let (w, h) = label_size(text);
+    let gap = LABEL_GAP;
+    // offsets of the baseline start: NE, NW, SE, SW, E, W, N, S
+    let candidates = [
+        (gap, -gap),
+        (-gap - w, -gap),
+        (gap, gap + h),
+        (-gap - w, gap + h),
+        (gap + 1.0, h / 2.0),
+        (-gap - 1.0 - w, h / 2.0),
+        (-w / 2.0, -gap - 1.0),
+        (-w / 2.0, gap + 1.0 + h),
+    ];
+    let to_box = |(dx, dy): (f64, f64)| (x + dx, y + dy - h, x + dx + w, y + dy);
+    candidates
+        .iter()
+        .map(|offset| (*offset, to_box(*offset)))
+        .find(|(_, area)| {
+            area.0 >= 0.0
+                && area.1 >= 0.0
+                && area.2 <= width
+                && area.3 <= height
+                && !obstacles.iter().any(|other| overlaps(area, other))
+        })
+        .unwrap_or_else(|| (candidates[0], to_box(candidates[0])))
+}
+
+/// A line whose distance from the center of the viewport exceeds this multiple of the viewport's
+/// extent is near-ideal: `SvgRenderer::draw_line` marks its direction instead of clipping it.
+pub const NEAR_IDEAL_DISTANCE: f64 = 1000.0;
+
+/// The `SvgRenderer` struct collects SVG elements for points and lines of the affine part of the
+/// projective plane and maps the `Viewport` onto an image of the given size.
+#[derive(Debug, Clone)]
+pub struct SvgRenderer {
+    pub width: u32,
+    pub height: u32,
+    pub viewport: Viewport,
+    /// Width in pixels of the hatched band along the image border that marks the line at
+    /// infinity; 0 leaves the line at infinity undrawn.
+    pub ideal_border: u32,
+    elements: Vec<String>,
+}
+
+impl SvgRenderer {
+    /// Create a new renderer without any elements.
+    #[inline]
+    pub fn new(width: u32, height: u32, viewport: Viewport) -> Self {
+        Self {
+            width,
+            height,
+            viewport,
+            ideal_border: 6,
+            elements: Vec::new(),
+        }
+    }
+
+    /// The function `fit_to_view` creates a renderer whose viewport is fitted to a bounding box,
+    /// keeping the aspect ratio of the image; see `BoundingBox::fit_viewport`.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::visualization::{bounding_box, SvgRenderer, Viewport};
+    /// use projgeom_rs::PgPoint;
+    /// let points = [[0, 0, 1], [8, 2, 1], [3, -1, 1]].map(PgPoint::new);
+    /// let bbox = bounding_box(&points).unwrap();
+    /// let renderer = SvgRenderer::fit_to_view(300, 100, &bbox, 1);
+    /// assert_eq!(renderer.viewport, Viewport::new(-3, -2, 12, 3));
+    /// ```
+    #[inline]
+    pub fn fit_to_view(width: u32, height: u32, bbox: &BoundingBox, margin: i64) -> Self {
+        Self::new(width, height, bbox.fit_viewport(width, height, margin))
+    }
+
+    /// Map an affine point given by homogeneous coordinates to image coordinates, with the y-axis
+    /// pointing down.
+    pub fn to_image(&self, pt: &PgPoint) -> Option<(f64, f64)> {
+        let [x, y, z] = pt.coord;
+        if z == 0 {
+            return None;
+        }
+        Some(self.world_to_image((x as f64 / z as f64, y as f64 / z as f64)))
+    }
+
+    /// Map affine world coordinates to image coordinates.
+    fn world_to_image(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        let view = &self.viewport;
+        let sx = self.width as f64 / (view.xmax - view.xmin) as f64;
+        let sy = self.height as f64 / (view.ymax - view.ymin) as f64;
+        ((x - view.xmin as f64) * sx, (view.ymax as f64 - y) * sy)
+    }
+
+    /// Draw a point as a small circle. Returns `false` if it is outside the viewport.
+    pub fn draw_point(&mut self, pt: &PgPoint, color: &str) -> bool {
+        if !self.viewport.contains(pt) {
+            return false;
+        }
+        if let Some((x, y)) = self.to_image(pt) {
+            self.elements.push(format!(
+                r#"<circle cx="{:.3}" cy="{:.3}" r="3" fill="{}" />"#,
+                x, y, color
+            ));
+        }
+        true
+    }
+
+    /// The function `draw_line` draws the part of a line inside the viewport. The line at
+    /// infinity is marked by a hatched band of width `ideal_border` along the image border, and
+    /// near-ideal lines (see `NEAR_IDEAL_DISTANCE`) by a double arrow parallel to the line, at
+    /// the border on the side where the line lies.
+    ///
+    /// Returns:
+    ///
+    /// The function `draw_line` returns `false` if the line misses the viewport without being
+    /// near-ideal, or is the line at infinity and `ideal_border` is 0.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::visualization::{SvgRenderer, Viewport};
+    /// use projgeom_rs::PgLine;
+    /// let mut renderer = SvgRenderer::new(100, 100, Viewport::new(0, 0, 10, 10));
+    /// assert!(renderer.draw_line(&PgLine::new([0, 0, 1]), "gray"));
+    /// // the line x = 10^15 lies far to the right
+    /// assert!(renderer.draw_line(&PgLine::new([1, 0, -1_000_000_000_000_000]), "gray"));
+    /// let svg = renderer.to_svg();
+    /// assert!(svg.contains(r#"class="ideal-line""#));
+    /// assert!(svg.contains(r#"class="near-ideal-line""#));
+    /// ```
+    pub fn draw_line(&mut self, ln: &PgLine, color: &str) -> bool {
+        if ln.coord[..2] == [0, 0] {
+            return self.draw_ideal_line(color);
+        }
+        if let Some(direction) = self.near_ideal_direction(ln) {
+            self.draw_direction_arrow(direction, color);
+            return true;
+        }
+        let Some([pt_a, pt_b]) = self.viewport.clip_line(ln) else {
+            return false;
+        };
+        if let (Some((x1, y1)), Some((x2, y2))) = (self.to_image(&pt_a), self.to_image(&pt_b)) {
+            self.elements.push(format!(
+                r#"<line x1="{:.3}" y1="{:.3}" x2="{:.3}" y2="{:.3}" stroke="{}" />"#,
+                x1, y1, x2, y2, color
+            ));
+        }
+        true
+    }
+
+    /// Draw the hatched band marking the line at infinity.
+    fn draw_ideal_line(&mut self, color: &str) -> bool {
+        if self.ideal_border == 0 {
+            return false;
+        }
+        let (w, h) = (self.width as f64, self.height as f64);
+        let band = self.ideal_border as f64;
+        let mut path = format!(
+            "M {b:.3} {b:.3} H {:.3} V {:.3} H {b:.3} Z",
+            w - band,
+            h - band,
+            b = band
+        );
+        // hatches at 45 degrees, two band widths apart
+        let step = 2 * self.ideal_border as usize;
+        for x in (0..self.width).step_by(step).map(f64::from) {
+            let _ = write!(path, " M {:.3} 0 l {b:.3} {b:.3}", x, b = band);
+            let _ = write!(path, " M {:.3} {:.3} l {b:.3} {:.3}", x, h, -band, b = band);
+        }
+        for y in (0..self.height).step_by(step).map(f64::from) {
+            let _ = write!(path, " M 0 {:.3} l {b:.3} {b:.3}", y, b = band);
+            let _ = write!(path, " M {:.3} {:.3} l {:.3} {b:.3}", w, y, -band, b = band);
+        }
+        self.elements.push(format!(
+            r#"<path class="ideal-line" d="{}" fill="none" stroke="{}" />"#,
+            path, color
+        ));
+        true
+    }
+
+    /// For a near-ideal line, return the unit normal in image coordinates pointing from the
+    /// center of the image towards the line, and the unit direction of the line.
+    fn near_ideal_direction(&self, ln: &PgLine) -> Option<((f64, f64), (f64, f64))> {
+        let [a, b, c] = ln.coord.map(|x| x as f64);
+        let view = &self.viewport;
+        let (cx, cy) = (
+            (view.xmin as f64 + view.xmax as f64) / 2.0,
+            (view.ymin as f64 + view.ymax as f64) / 2.0,
+        );
+        let extent = (view.xmax - view.xmin).max(view.ymax - view.ymin) as f64;
+        let norm = a.hypot(b);
+        let value = a * cx + b * cy + c;
+        if value.abs() <= NEAR_IDEAL_DISTANCE * extent * norm {
+            return None;
+        }
+        // the foot of the perpendicular from the center lies in the direction -value (a, b)
+        let sign = -value.signum();
+        let normal = (sign * a / norm, -sign * b / norm);
+        Some((normal, (b / norm, a / norm)))
+    }
+
+    /// Draw a double arrow along `direction` at the image border in the direction `normal`.
+    fn draw_direction_arrow(&mut self, (normal, direction): ((f64, f64), (f64, f64)), color: &str) {
+        const INSET: f64 = 16.0;
+        const HALF_LENGTH: f64 = 12.0;
+        const HEAD: f64 = 5.0;
+        let (w, h) = (self.width as f64 / 2.0, self.height as f64 / 2.0);
+        let (nx, ny) = normal;
+        // move from the center along the normal until INSET pixels from the border
+        let reach = |half: f64, d: f64| {
+            if d == 0.0 {
+                f64::INFINITY
+            } else {
+                ((half - INSET) / d.abs()).max(0.0)
+            }
+        };
+        let t = reach(w, nx).min(reach(h, ny));
+        let (ax, ay) = (w + t * nx, h + t * ny);
+        let (dx, dy) = direction;
+        let mut path = format!(
+            "M {:.3} {:.3} L {:.3} {:.3}",
+            ax - HALF_LENGTH * dx,
+            ay - HALF_LENGTH * dy,
+            ax + HALF_LENGTH * dx,
+            ay + HALF_LENGTH * dy
+        );
+        for sign in [1.0, -1.0] {
+            let (tx, ty) = (ax + sign * HALF_LENGTH * dx, ay + sign * HALF_LENGTH * dy);
+            // the two strokes of the head, back along the shaft and out to either side
+            let (bx, by) = (tx - sign * HEAD * dx, ty - sign * HEAD * dy);
+            let _ = write!(
+                path,
+                " M {:.3} {:.3} L {:.3} {:.3} L {:.3} {:.3}",
+                bx - HEAD * dy,
+                by + HEAD * dx,
+                tx,
+                ty,
+                bx + HEAD * dy,
+                by - HEAD * dx
+            );
+        }
+        self.elements.push(format!(
+            r#"<path class="near-ideal-line" d="{}" fill="none" stroke="{}" />"#,
+            path, color
+        ));
+    }
+
+    /// The SVG transform attribute mapping world coordinates to image coordinates.
+    fn world_transform(&self) -> String {
+        let view = &self.viewport;
+        let sx = self.width as f64 / (view.xmax - view.xmin) as f64;
+        let sy = self.height as f64 / (view.ymax - view.ymin) as f64;
+        format!(
+            "matrix({} 0 0 {} {} {})",
+            sx,
+            -sy,
+            -view.xmin as f64 * sx,
+            view.ymax as f64 * sy
+        )
+    }
+
+    /// The function `draw_conic` draws the real affine points of a conic. Ellipses are drawn as an
+    /// SVG ellipse element; parabolas and hyperbolas are sampled along an axis and drawn as paths
+    /// restricted to the viewport.
+    ///
+    /// Returns:
+    ///
+    /// The function `draw_conic` returns `false` if nothing was drawn, e.g. for degenerate and
+    /// imaginary conics.
+    pub fn draw_conic(&mut self, conic: &Conic, color: &str) -> bool {
+        match conic_shape(conic, &self.viewport) {
+            Some(ConicShape::Ellipse {
+                center: (cx, cy),
+                radii: (rx, ry),
+                angle,
+            }) => {
+                self.elements.push(format!(
+                    r#"<ellipse cx="{:.3}" cy="{:.3}" rx="{:.3}" ry="{:.3}" transform="{} rotate({:.3} {:.3} {:.3})" fill="none" stroke="{}" vector-effect="non-scaling-stroke" />"#,
+                    cx,
+                    cy,
+                    rx,
+                    ry,
+                    self.world_transform(),
+                    angle,
+                    cx,
+                    cy,
+                    color
+                ));
+                true
+            }
+            Some(ConicShape::Paths(paths)) => {
+                for points in &paths {
+                    let mut path = String::new();
+                    for (i, (x, y)) in points.iter().enumerate() {
+                        let command = if i == 0 { 'M' } else { 'L' };
+                        let _ = write!(path, "{} {:.3} {:.3} ", command, x, y);
+                    }
+                    self.elements.push(format!(
+                        r#"<path d="{}" transform="{}" fill="none" stroke="{}" vector-effect="non-scaling-stroke" />"#,
+                        path.trim_end(),
+                        self.world_transform(),
+                        color
+                    ));
+                }
+                !paths.is_empty()
+            }
+            None => false,
+        }
+    }
+
+    /// Draw the segment between two affine points. Returns `false` if one of them is ideal.
+    pub fn draw_segment(&mut self, pt_a: &PgPoint, pt_b: &PgPoint, color: &str) -> bool {
+        let (Some((x1, y1)), Some((x2, y2))) = (self.to_image(pt_a), self.to_image(pt_b)) else {
+            return false;
+        };
+        self.elements.push(format!(
+            r#"<line x1="{:.3}" y1="{:.3}" x2="{:.3}" y2="{:.3}" stroke="{}" />"#,
+            x1, y1, x2, y2, color
+        ));
+        true
+    }
+
+    /// Draw a closed polygon through affine points. Returns `false` if one of them is ideal.
+    pub fn draw_polygon(&mut self, vertices: &[PgPoint], color: &str) -> bool {
+        let Some(points) = vertices
+            .iter()
+            .map(|pt| self.to_image(pt).map(|(x, y)| format!("{:.3},{:.3}", x, y)))
+            .collect::<Option<Vec<_>>>()
+        else {
+            return false;
+        };
+        self.elements.push(format!(
+            r#"<polygon points="{}" fill="none" stroke="{}" />"#,
+            points.join(" "),
+            color
+        ));
+        true
+    }
+
+    /// Draw a text label to the upper right of an affine point; see `draw_label_at`. Returns
+    /// `false` if the point is outside the viewport.
+    #[inline]
+    pub fn draw_label(&mut self, pt: &PgPoint, text: &str, color: &str) -> bool {
+        self.draw_label_at(pt, text, (LABEL_GAP, -LABEL_GAP), color)
+    }
+
+    /// The function `draw_label_at` draws a text label whose baseline starts at the given offset
+    /// in pixels from an affine point. LaTeX-style subscripts are supported: `A_1` and `P_{12}`
+    /// set `1` and `12` as subscripts.
+    ///
+    /// Returns:
+    ///
+    /// The function `draw_label_at` returns `false` if the point is outside the viewport.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::visualization::{SvgRenderer, Viewport};
+    /// use projgeom_rs::PgPoint;
+    /// let mut renderer = SvgRenderer::new(100, 100, Viewport::new(0, 0, 10, 10));
+    /// assert!(renderer.draw_label_at(&PgPoint::new([5, 5, 1]), "P_{12}", (-20.0, 15.0), "black"));
+    /// assert!(renderer.to_svg().contains(
+    ///     r#"<text x="30.000" y="65.000" font-size="12" fill="black">P<tspan baseline-shift="sub" font-size="9">12</tspan></text>"#
+    /// ));
+    /// ```
+    pub fn draw_label_at(
+        &mut self,
+        pt: &PgPoint,
+        text: &str,
+        (dx, dy): (f64, f64),
+        color: &str,
+    ) -> bool {
+        if !self.viewport.contains(pt) {
+            return false;
+        }
+        if let Some((x, y)) = self.to_image(pt) {
+            self.elements.push(format!(
+                r#"<text x="{:.3}" y="{:.3}" font-size="{}" fill="{}">{}</text>"#,
+                x + dx,
+                y + dy,
+                LABEL_FONT_SIZE,
+                color,
+                label_markup(text)
+            ));
+        }
+        true
+    }
+
+    /// Draw the absolute of the hyperbolic plane, the unit circle `x² + y² = z²`.
+    pub fn draw_absolute(&mut self, color: &str) {
+        self.elements.push(format!(
+            r#"<circle cx="0" cy="0" r="1" transform="{}" fill="none" stroke="{}" vector-effect="non-scaling-stroke" />"#,
+            self.world_transform(),
+            color
+        ));
+    }
+
+    /// Draw a hyperbolic point in the given disk model. Returns `false` if it lies outside the
+    /// closed disk.
+    pub fn draw_hyperbolic_point(
+        &mut self,
+        pt: &HyperbolicPoint,
+        model: DiskModel,
+        color: &str,
+    ) -> bool {
+        let Some((x, y)) = model.point(pt) else {
+            return false;
+        };
+        let (x, y) = self.world_to_image((x, y));
+        self.elements.push(format!(
+            r#"<circle cx="{:.3}" cy="{:.3}" r="3" fill="{}" />"#,
+            x, y, color
+        ));
+        true
+    }
+
+    /// Draw a hyperbolic line in the given disk model: a chord in the Beltrami-Klein model, and
+    /// an arc orthogonal to the absolute (or a diameter) in the Poincaré model. Returns `false` if
+    /// the line misses the disk.
+    pub fn draw_hyperbolic_line(
+        &mut self,
+        ln: &HyperbolicLine,
+        model: DiskModel,
+        color: &str,
+    ) -> bool {
+        let Some([(x1, y1), (x2, y2)]) = klein_chord(ln) else {
+            return false;
+        };
+        let [a, b, c] = ln.coord.map(|x| x as f64);
+        let path = match model {
+            DiskModel::Poincare if c != 0.0 => {
+                // the arc lies on the circle about the pole of the line, orthogonal to the absolute
+                let (px, py) = (-a / c, -b / c);
+                let radius = (px * px + py * py - 1.0).sqrt();
+                let sweep = ((x1 - px) * (y2 - py) - (y1 - py) * (x2 - px) > 0.0) as u8;
+                format!(
+                    "M {:.6} {:.6} A {:.6} {:.6} 0 0 {} {:.6} {:.6}",
+                    x1, y1, radius, radius, sweep, x2, y2
+                )
+            }
+            _ => format!("M {:.6} {:.6} L {:.6} {:.6}", x1, y1, x2, y2),
+        };
+        self.elements.push(format!(
+            r#"<path d="{}" transform="{}" fill="none" stroke="{}" vector-effect="non-scaling-stroke" />"#,
+            path,
+            self.world_transform(),
+            color
+        ));
+        true
+    }
+
+    /// Return the SVG document.
+    pub fn to_svg(&self) -> String {
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+            w = self.width,
+            h = self.height
+        );
+        for element in &self.elements {
+            let _ = writeln!(svg, "  {}", element);
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+/// The `DiskModel` enum selects the disk model in which the hyperbolic plane is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiskModel {
+    /// The Beltrami-Klein model: points keep their affine coordinates and lines are chords.
+    Klein,
+    /// The conformal Poincaré model: lines are arcs orthogonal to the absolute.
+    Poincare,
+}
+
+impl DiskModel {
+    /// Map a hyperbolic point into the unit disk, or return `None` if it is an exterior
+    /// (ultra-ideal) point. Ideal points map onto the unit circle.
+    pub fn point(&self, pt: &HyperbolicPoint) -> Option<(f64, f64)> {
+        let [x, y, z] = pt.coord.map(|c| c as i128);
+        if z == 0 || x * x + y * y > z * z {
+            return None;
+        }
+        let (x, y) = (x as f64 / z as f64, y as f64 / z as f64);
+        match self {
+            DiskModel::Klein => Some((x, y)),
+            DiskModel::Poincare => {
+                let scale = 1.0 + (1.0 - x * x - y * y).max(0.0).sqrt();
+                Some((x / scale, y / scale))
+            }
+        }
+    }
+}
+
+/// The function `klein_chord` returns the two ideal points of a hyperbolic line as points of the
+/// unit circle, i.e. the chord representing the line in the Beltrami-Klein model.
+///
+/// Returns:
+///
+/// The function `klein_chord` returns `None` if the line does not meet the open unit disk, which is
+/// decided exactly.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::visualization::klein_chord;
+/// use projgeom_rs::HyperbolicLine;
+/// let [(x1, y1), (x2, y2)] = klein_chord(&HyperbolicLine::new([0, 1, 0])).unwrap();
+/// assert_eq!((x1, y1, x2, y2), (1.0, 0.0, -1.0, 0.0));
+/// assert_eq!(klein_chord(&HyperbolicLine::new([1, 0, -1])), None);
+/// ```
+pub fn klein_chord(ln: &HyperbolicLine) -> Option<[(f64, f64); 2]> {
+    let [a, b, c] = ln.coord.map(|x| x as i128);
+    if c * c >= a * a + b * b {
+        return None;
+    }
+    let [a, b, c] = ln.coord.map(|x| x as f64);
+    let norm2 = a * a + b * b;
+    // the foot of the perpendicular from the origin, and half the chord length
+    let (fx, fy) = (-a * c / norm2, -b * c / norm2);
+    let half = (1.0 - c * c / norm2).sqrt();
+    let (dx, dy) = (-b / norm2.sqrt() * half, a / norm2.sqrt() * half);
+    Some([
+        (fx - dx + 0.0, fy - dy + 0.0),
+        (fx + dx + 0.0, fy + dy + 0.0),
+    ])
+}
+
+/// The real affine points of a conic in world coordinates, as computed by `conic_shape`.
+#[derive(Debug, Clone, PartialEq)]
+enum ConicShape {
+    /// An ellipse with the given center and radii, rotated by `angle` degrees.
+    Ellipse {
+        center: (f64, f64),
+        radii: (f64, f64),
+        angle: f64,
+    },
+    /// Polylines sampled along the branches of a parabola or hyperbola, each running inside the
+    /// viewport except for its first and last segment.
+    Paths(Vec<Vec<(f64, f64)>>),
+}
+
+/// Check if the affine point `(x, y)` lies in the viewport.
+fn in_view(view: &Viewport, (x, y): (f64, f64)) -> bool {
+    view.xmin as f64 <= x && x <= view.xmax as f64 && view.ymin as f64 <= y && y <= view.ymax as f64
+}
+
+/// Compute the shape of the real affine points of a conic: parabolas and hyperbolas are sampled
+/// along an axis, and only the parts reaching into the viewport are kept. Returns `None` for
+/// degenerate and imaginary conics.
+fn conic_shape(conic: &Conic, view: &Viewport) -> Option<ConicShape> {
+    let conic_type = conic.conic_type();
+    let [[m00, m01, m02], [_, m11, m12], [_, _, m22]] = conic.mat.map(|row| row.map(|x| x as f64));
+    // rotate onto the principal axes (u, v); for a parabola, u is the non-null direction
+    let theta = match conic_type {
+        ConicType::Parabola if m00 != 0.0 => m01.atan2(m00),
+        ConicType::Parabola => m11.atan2(m01),
+        _ => 0.5 * (2.0 * m01).atan2(m00 - m11),
+    };
+    let (s, c) = theta.sin_cos();
+    let lambda_u = m00 * c * c + 2.0 * m01 * s * c + m11 * s * s;
+    let lambda_v = if conic_type == ConicType::Parabola {
+        0.0
+    } else {
+        m00 + m11 - lambda_u
+    };
+    let g_u = m02 * c + m12 * s;
+    let g_v = -m02 * s + m12 * c;
+    let to_world = |u: f64, v: f64| (c * u - s * v, s * u + c * v);
+
+    let branches: Vec<Vec<(f64, f64)>> = match conic_type {
+        ConicType::Ellipse => {
+            let (u_0, v_0) = (-g_u / lambda_u, -g_v / lambda_v);
+            let k = m22 - g_u * g_u / lambda_u - g_v * g_v / lambda_v;
+            let (cx, cy) = to_world(u_0, v_0);
+            // adding zero turns -0.0 into 0.0 for the output
+            return Some(ConicShape::Ellipse {
+                center: (cx + 0.0, cy + 0.0),
+                radii: ((-k / lambda_u).sqrt(), (-k / lambda_v).sqrt()),
+                angle: theta.to_degrees(),
+            });
+        }
+        ConicType::ImaginaryEllipse | ConicType::Degenerate => return None,
+        ConicType::Parabola | ConicType::Hyperbola => {
+            // the range of the sampled axis covering the viewport
+            let corners = [
+                (view.xmin, view.ymin),
+                (view.xmin, view.ymax),
+                (view.xmax, view.ymin),
+                (view.xmax, view.ymax),
+            ]
+            .map(|(x, y)| (c * x as f64 + s * y as f64, -s * x as f64 + c * y as f64));
+            let range = |proj: fn(&(f64, f64)) -> f64| {
+                let lo = corners.iter().map(proj).fold(f64::INFINITY, f64::min);
+                let hi = corners.iter().map(proj).fold(f64::NEG_INFINITY, f64::max);
+                (lo, hi)
+            };
+            const SAMPLES: usize = 256;
+            let sample =
+                |(lo, hi): (f64, f64), i: usize| lo + (hi - lo) * i as f64 / SAMPLES as f64;
+            if conic_type == ConicType::Parabola {
+                // v as a function of u
+                let range_u = range(|p| p.0);
+                vec![(0..=SAMPLES)
+                    .map(|i| {
+                        let u = sample(range_u, i);
+                        let v = -(lambda_u * u * u + 2.0 * g_u * u + m22) / (2.0 * g_v);
+                        to_world(u, v)
+                    })
+                    .collect()]
+            } else {
+                let (u_0, v_0) = (-g_u / lambda_u, -g_v / lambda_v);
+                let k = m22 - g_u * g_u / lambda_u - g_v * g_v / lambda_v;
+                [-1.0, 1.0]
+                    .into_iter()
+                    .map(|sign| {
+                        // each branch is a graph over the axis it does not cross
+                        if -k / lambda_u > 0.0 {
+                            let range_v = range(|p| p.1);
+                            (0..=SAMPLES)
+                                .map(|i| {
+                                    let v = sample(range_v, i);
+                                    let w = v - v_0;
+                                    let u =
+                                        u_0 + sign * ((-k - lambda_v * w * w) / lambda_u).sqrt();
+                                    to_world(u, v)
+                                })
+                                .collect()
+                        } else {
+                            let range_u = range(|p| p.0);
+                            (0..=SAMPLES)
+                                .map(|i| {
+                                    let u = sample(range_u, i);
+                                    let w = u - u_0;
+                                    let v =
+                                        v_0 + sign * ((-k - lambda_u * w * w) / lambda_v).sqrt();
+                                    to_world(u, v)
+                                })
+                                .collect()
+                        }
+                    })
+                    .collect()
+            }
+        }
+    };
+    let mut paths: Vec<Vec<(f64, f64)>> = Vec::new();
+    for branch in branches {
+        let mut pen_down = false;
+        for pair in branch.windows(2) {
+            // keep the segments reaching into the viewport
+            if !(in_view(view, pair[0]) || in_view(view, pair[1])) {
+                pen_down = false;
+                continue;
+            }
+            if !pen_down {
+                paths.push(vec![pair[0]]);
+                pen_down = true;
+            }
+            if let Some(path) = paths.last_mut() {
+                path.push(pair[1]);
+            }
+        }
+    }
+    Some(ConicShape::Paths(paths))
+}
+
+/// The TikZ color for an SVG color: names are shared by both, and `#rrggbb` is converted to
+/// the RGB syntax of xcolor.
+fn tikz_color(color: &str) -> String {
+    let hex = color.strip_prefix('#').filter(|hex| hex.len() == 6);
+    match hex.and_then(|hex| u32::from_str_radix(hex, 16).ok()) {
+        Some(rgb) => format!(
+            "{{rgb,255:red,{};green,{};blue,{}}}",
+            rgb >> 16,
+            (rgb >> 8) & 0xff,
+            rgb & 0xff
+        ),
+        None => color.to_string(),
+    }
+}
+
+/// The `Style` struct holds the color of a figure element and the layer it is drawn in. Elements
+/// in higher layers are drawn on top.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Style {
+    pub color: String,
+    pub layer: i32,
+}
+
+impl Style {
+    /// Create a style with the given color in layer 0.
+    #[inline]
+    pub fn new(color: &str) -> Self {
+        Self {
+            color: color.to_string(),
+            layer: 0,
+        }
+    }
+
+    /// Return the style moved to another layer.
+    #[inline]
+    pub fn layer(mut self, layer: i32) -> Self {
+        self.layer = layer;
+        self
+    }
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Self::new("black")
+    }
+}
+
+/// The `Element` enum lists the objects a `Figure` can hold.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Element {
+    Point { pt: PgPoint, label: Option<String> },
+    Line(PgLine),
+    Segment(PgPoint, PgPoint),
+    Conic(Conic),
+    Polygon(Vec<PgPoint>),
+}
+
+/// The `Figure` struct collects styled elements and renders them in one go, fitting the viewport
+/// to the affine points, segments, polygons and ellipses it contains. Lines and the other conics
+/// are unbounded, so they do not take part in the fitting.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::visualization::{Figure, Style};
+/// use projgeom_rs::{Join, PgPoint};
+/// let pt_a = PgPoint::new([0, 0, 1]);
+/// let pt_b = PgPoint::new([4, 2, 1]);
+/// let mut fig = Figure::new(400, 300);
+/// fig.line(&pt_a.join(&pt_b), Style::new("gray"))
+///     .point(&pt_a, Some("A"), Style::new("red").layer(1))
+///     .point(&pt_b, Some("B"), Style::new("red").layer(1));
+/// let svg = fig.to_svg();
+/// assert!(svg.contains(">A</text>"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Figure {
+    pub width: u32,
+    pub height: u32,
+    /// Space around the fitted bounding box, in world units.
+    pub margin: i64,
+    items: Vec<(Element, Style)>,
+}
+
+impl Figure {
+    /// Create an empty figure of the given image size.
+    #[inline]
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            margin: 1,
+            items: Vec::new(),
+        }
+    }
+
+    /// Add an element.
+    #[inline]
+    pub fn add(&mut self, element: Element, style: Style) -> &mut Self {
+        self.items.push((element, style));
+        self
+    }
+
+    /// Add a point with an optional label.
+    #[inline]
+    pub fn point(&mut self, pt: &PgPoint, label: Option<&str>, style: Style) -> &mut Self {
+        let label = label.map(str::to_string);
+        self.add(
+            Element::Point {
+                pt: pt.clone(),
+                label,
+            },
+            style,
+        )
+    }
+
+    /// Add a point in the default style, with a label that is placed automatically so that it
+    /// avoids the other points and labels; see `SvgRenderer::draw_label_at` for subscripts.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::visualization::Figure;
+    /// use projgeom_rs::PgPoint;
+    /// let mut fig = Figure::new(200, 200);
+    /// fig.add_labeled_point("A_1", &PgPoint::new([0, 0, 1]))
+    ///     .add_labeled_point("A_2", &PgPoint::new([1, 0, 8]));
+    /// let svg = fig.to_svg();
+    /// // the first label goes to the upper right; the second would cover it there, so it goes to
+    /// // the upper left of its point
+    /// assert!(svg.contains(r#"<text x="71.667" y="95.000" font-size="12" fill="black">A<tspan"#));
+    /// assert!(svg.contains(r#"<text x="57.400" y="95.000" font-size="12" fill="black">A<tspan"#));
+    /// ```
+    #[inline]
+    pub fn add_labeled_point(&mut self, label: &str, pt: &PgPoint) -> &mut Self {
+        self.point(pt, Some(label), Style::default())
+    }
+
+    /// Add a line, clipped to the viewport when rendered.
+    #[inline]
+    pub fn line(&mut self, ln: &PgLine, style: Style) -> &mut Self {
+        self.add(Element::Line(ln.clone()), style)
+    }
+
+    /// Add the segment between two affine points.
+    #[inline]
+    pub fn segment(&mut self, pt_a: &PgPoint, pt_b: &PgPoint, style: Style) -> &mut Self {
+        self.add(Element::Segment(pt_a.clone(), pt_b.clone()), style)
+    }
+
+    /// Add a conic.
+    #[inline]
+    pub fn conic(&mut self, conic: &Conic, style: Style) -> &mut Self {
+        self.add(Element::Conic(conic.clone()), style)
+    }
+
+    /// Add a closed polygon.
+    #[inline]
+    pub fn polygon(&mut self, vertices: &[PgPoint], style: Style) -> &mut Self {
+        self.add(Element::Polygon(vertices.to_vec()), style)
+    }
+
+    /// Return the bounding box of the bounded elements, or `None` if there are none.
+    pub fn bounding_box(&self) -> Option<BoundingBox> {
+        self.items
+            .iter()
+            .filter_map(|(element, _)| match element {
+                Element::Point { pt, .. } => bounding_box([pt]),
+                Element::Segment(pt_a, pt_b) => bounding_box([pt_a, pt_b]),
+                Element::Polygon(vertices) => bounding_box(vertices),
+                Element::Conic(conic) => BoundingBox::of_ellipse(conic),
+                Element::Line(_) => None,
+            })
+            .reduce(|bbox, other| bbox.union(&other))
+    }
+
+    /// Return the viewport fitted to the bounded elements plus the margin.
+    pub fn viewport(&self) -> Viewport {
+        match self.bounding_box() {
+            Some(bbox) => bbox.viewport(self.margin),
+            None => Viewport::new(-self.margin, -self.margin, self.margin, self.margin),
+        }
+    }
+
+    /// Render all elements, layer by layer, into an SVG document. Labels are placed in drawing
+    /// order, each avoiding all point markers and the labels placed before it.
+    pub fn to_svg(&self) -> String {
+        self.render(self.viewport()).to_svg()
+    }
+
+    /// Render all elements into a renderer with the given viewport.
+    fn render(&self, viewport: Viewport) -> SvgRenderer {
+        let mut renderer = SvgRenderer::new(self.width, self.height, viewport);
+        let mut items: Vec<&(Element, Style)> = self.items.iter().collect();
+        items.sort_by_key(|(_, style)| style.layer);
+        let size = (self.width as f64, self.height as f64);
+        // the markers of all visible points, as boxes around their circles
+        let mut obstacles: Vec<LabelBox> = items
+            .iter()
+            .filter_map(|(element, _)| match element {
+                Element::Point { pt, .. } if renderer.viewport.contains(pt) => {
+                    renderer.to_image(pt)
+                }
+                _ => None,
+            })
+            .map(|(x, y)| (x - 3.0, y - 3.0, x + 3.0, y + 3.0))
+            .collect();
+        for (element, style) in items {
+            let color = style.color.as_str();
+            match element {
+                Element::Point { pt, label } => {
+                    renderer.draw_point(pt, color);
+                    if let Some(text) = label {
+                        if let Some(pos) = renderer.to_image(pt) {
+                            let (offset, area) = place_label(pos, text, size, &obstacles);
+                            if renderer.draw_label_at(pt, text, offset, color) {
+                                obstacles.push(area);
+                            }
+                        }
+                    }
+                }
+                Element::Line(ln) => {
+                    renderer.draw_line(ln, color);
+                }
+                Element::Segment(pt_a, pt_b) => {
+                    renderer.draw_segment(pt_a, pt_b, color);
+                }
+                Element::Conic(conic) => {
+                    renderer.draw_conic(conic, color);
+                }
+                Element::Polygon(vertices) => {
+                    renderer.draw_polygon(vertices, color);
+                }
+            }
+        }
+        renderer
+    }
+
+    /// Write the SVG document to a file.
+    pub fn save<P: AsRef<std::path::Path>>(&self, path: P) -> std::io::Result<()> {
+        std::fs::write(path, self.to_svg())
+    }
+
+    /// The function `to_tikz` renders all elements, layer by layer, as a TikZ picture for LaTeX
+    /// documents. The picture keeps world coordinates, scaled to the size of the SVG image at 96
+    /// dpi, and is clipped to the viewport. Labels are typeset in math mode to the upper right of
+    /// their points; unlike in `to_svg`, the line at infinity and near-ideal lines are not drawn.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// use projgeom_rs::conic::Conic;
+    /// use projgeom_rs::visualization::{Figure, Style};
+    /// use projgeom_rs::PgPoint;
+    /// let mut fig = Figure::new(192, 192);
+    /// fig.conic(&Conic::from_coefficients(1, 0, 1, 0, 0, -9), Style::new("blue"))
+    ///     .point(&PgPoint::new([3, 0, 1]), Some("P_1"), Style::new("#ff0000"));
+    /// let tikz = fig.to_tikz();
+    /// assert!(tikz.starts_with("\\begin{tikzpicture}[x=0.6350cm, y=0.6350cm]\n"));
+    /// assert!(tikz.contains("\\clip (-4, -4) rectangle (4, 4);"));
+    /// assert!(tikz.contains("(0.000, 0.000) ellipse [x radius=3.000, y radius=3.000];"));
+    /// assert!(tikz.contains("\\node[anchor=south west, text={rgb,255:red,255;green,0;blue,0}] at (3.000, 0.000) {$P_1$};"));
+    /// assert!(tikz.ends_with("\\end{tikzpicture}\n"));
+    /// ```
+    pub fn to_tikz(&self) -> String {
+        let view = self.viewport();
+        // centimeters per world unit
+        let unit = |pixels: u32, extent: i64| pixels as f64 * 2.54 / 96.0 / extent as f64;
+        let mut result = format!(
+            "\\begin{{tikzpicture}}[x={:.4}cm, y={:.4}cm]\n",
+            unit(self.width, view.xmax - view.xmin),
+            unit(self.height, view.ymax - view.ymin)
+        );
+        let _ = writeln!(
+            result,
+            "\\clip ({}, {}) rectangle ({}, {});",
+            view.xmin, view.ymin, view.xmax, view.ymax
+        );
+        // adding zero turns -0.0 into 0.0 for the output
+        let coord = |(x, y): (f64, f64)| format!("({:.3}, {:.3})", x + 0.0, y + 0.0);
+        let polyline = |points: &[(f64, f64)]| {
+            points
+                .iter()
+                .map(|&pos| coord(pos))
+                .collect::<Vec<_>>()
+                .join(" -- ")
+        };
+        let draw = |color: &str, points: &[(f64, f64)]| {
+            format!("\\draw[color={}] {};", color, polyline(points))
+        };
+        let mut items: Vec<&(Element, Style)> = self.items.iter().collect();
+        items.sort_by_key(|(_, style)| style.layer);
+        for (element, style) in items {
+            let color = tikz_color(&style.color);
+            match element {
+                Element::Point { pt, label } => {
+                    let Some(pos) = affine(pt).filter(|_| view.contains(pt)) else {
+                        continue;
+                    };
+                    let _ = writeln!(
+                        result,
+                        "\\fill[color={}] {} circle[radius=2.25pt];",
+                        color,
+                        coord(pos)
+                    );
+                    if let Some(text) = label {
+                        let _ = writeln!(
+                            result,
+                            "\\node[anchor=south west, text={}] at {} {{${}$}};",
+                            color,
+                            coord(pos),
+                            text
+                        );
+                    }
+                }
+                Element::Line(ln) => {
+                    let ends = view.clip_line(ln).map(|ends| ends.each_ref().map(affine));
+                    if let Some([Some(pos_a), Some(pos_b)]) = ends {
+                        let _ = writeln!(result, "{}", draw(&color, &[pos_a, pos_b]));
+                    }
+                }
+                Element::Segment(pt_a, pt_b) => {
+                    if let (Some(pos_a), Some(pos_b)) = (affine(pt_a), affine(pt_b)) {
+                        let _ = writeln!(result, "{}", draw(&color, &[pos_a, pos_b]));
+                    }
+                }
+                Element::Conic(conic) => match conic_shape(conic, &view) {
+                    Some(ConicShape::Ellipse {
+                        center,
+                        radii: (rx, ry),
+                        angle,
+                    }) => {
+                        let rotation =
+                            format!("rotate around={{{:.3}:{}}}", angle + 0.0, coord(center));
+                        let _ = writeln!(
+                            result,
+                            "\\draw[color={}, {}] {} ellipse [x radius={:.3}, y radius={:.3}];",
+                            color,
+                            rotation,
+                            coord(center),
+                            rx,
+                            ry
+                        );
+                    }
+                    Some(ConicShape::Paths(paths)) => {
+                        for points in &paths {
+                            let _ = writeln!(result, "{}", draw(&color, points));
+                        }
+                    }
+                    None => {}
+                },
+                Element::Polygon(vertices) => {
+                    let points = vertices.iter().map(affine).collect::<Option<Vec<_>>>();
+                    if let Some(points) = points.filter(|points| !points.is_empty()) {
+                        let _ = writeln!(
+                            result,
+                            "\\draw[color={}] {} -- cycle;",
+                            color,
+                            polyline(&points)
+                        );
+                    }
+                }
+            }
+        }
+        result.push_str("\\end{tikzpicture}\n");
+        result
+    }
+
+    /// Write the TikZ picture to a file, e.g. to `\input` it into a LaTeX document.
+    pub fn save_tikz<P: AsRef<std::path::Path>>(&self, path: P) -> std::io::Result<()> {
+        std::fs::write(path, self.to_tikz())
+    }
+}
+
+/// The `Animation` struct is a sequence of figures shown one after another, e.g. a construction
+/// re-evaluated while a free point moves along a line or conic. All frames share one viewport,
+/// fitted to the union of their bounding boxes, so that objects do not jump between frames.
+///
+/// Examples:
+///
+/// ```rust
+/// use projgeom_rs::visualization::{Animation, Figure, Style};
+/// use projgeom_rs::{harm_conj, PgPoint, ProjectivePlane};
+/// // the harmonic conjugate of a point moving between A and B
+/// let pt_a = PgPoint::new([0, 0, 1]);
+/// let pt_b = PgPoint::new([4, 0, 1]);
+/// let animation = Animation::from_fn(4, 0.5, |i| {
+///     let pt_p = pt_a.parametrize(3 - i as i64, &pt_b, 1 + i as i64);
+///     let mut fig = Figure::new(200, 100);
+///     fig.add_labeled_point("A", &pt_a)
+///         .add_labeled_point("B", &pt_b)
+///         .add_labeled_point("P", &pt_p)
+///         .point(&harm_conj(&pt_a, &pt_b, &pt_p), Some("Q"), Style::new("red"));
+///     fig
+/// });
+/// assert_eq!(animation.to_svg_frames().len(), 4);
+/// let svg = animation.to_animated_svg();
+/// assert_eq!(svg.matches("<animate ").count(), 4);
+/// assert!(svg.contains(r#"dur="2s""#));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Animation {
+    /// Time each frame is shown, in seconds.
+    pub frame_duration: f64,
+    frames: Vec<Figure>,
+}
+
+impl Animation {
+    /// Create an animation without frames.
+    #[inline]
+    pub fn new(frame_duration: f64) -> Self {
+        Self {
+            frame_duration,
+            frames: Vec::new(),
+        }
+    }
+
+    /// Create an animation of `count` frames, the figure for frame `i` given by `frame(i)`.
+    pub fn from_fn<F>(count: usize, frame_duration: f64, frame: F) -> Self
+    where
+        F: FnMut(usize) -> Figure,
+    {
+        Self {
+            frame_duration,
+            frames: (0..count).map(frame).collect(),
+        }
+    }
+
+    /// Append a frame.
+    #[inline]
+    pub fn push(&mut self, frame: Figure) -> &mut Self {
+        self.frames.push(frame);
+        self
+    }
+
+    /// Return the frames.
+    #[inline]
+    pub fn frames(&self) -> &[Figure] {
+        &self.frames
+    }
+
+    /// Return the common viewport: the union of the bounding boxes of the frames plus the
+    /// largest margin.
+    pub fn viewport(&self) -> Viewport {
+        let margin = self.frames.iter().map(|fig| fig.margin).max().unwrap_or(1);
+        match self
+            .frames
+            .iter()
+            .filter_map(Figure::bounding_box)
+            .reduce(|bbox, other| bbox.union(&other))
+        {
+            Some(bbox) => bbox.viewport(margin),
+            None => Viewport::new(-margin, -margin, margin, margin),
+        }
+    }
+
+    /// Render each frame into a separate SVG document, all with the common viewport.
+    pub fn to_svg_frames(&self) -> Vec<String> {
+        let viewport = self.viewport();
+        self.frames
+            .iter()
+            .map(|fig| fig.render(viewport).to_svg())
+            .collect()
+    }
+
+    /// The function `to_animated_svg` renders the frames into one SVG document that shows them
+    /// in turn, in an endless loop, using SMIL animation. The image size is that of the first
+    /// frame.
+    pub fn to_animated_svg(&self) -> String {
+        let viewport = self.viewport();
+        let (width, height) = self
+            .frames
+            .first()
+            .map_or((0, 0), |fig| (fig.width, fig.height));
+        let count = self.frames.len();
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+            w = width,
+            h = height
+        );
+        for (i, fig) in self.frames.iter().enumerate() {
+            // visible from i / count to (i + 1) / count of the cycle
+            let _ = writeln!(svg, r#"  <g visibility="hidden">"#);
+            let _ = writeln!(
+                svg,
+                r#"    <animate attributeName="visibility" values="hidden;visible;hidden" keyTimes="0;{};{}" dur="{}s" calcMode="discrete" repeatCount="indefinite" />"#,
+                i as f64 / count as f64,
+                (i + 1) as f64 / count as f64,
+                self.frame_duration * count as f64
+            );
+            for element in &fig.render(viewport).elements {
+                let _ = writeln!(svg, "    {}", element);
+            }
+            let _ = writeln!(svg, "  </g>");
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Write the frames to `{prefix}{i:04}.svg` in a directory, e.g. for conversion to a video
+    /// or an APNG by external tools, and return the paths.
+    pub fn save_frames<P: AsRef<std::path::Path>>(
+        &self,
+        dir: P,
+        prefix: &str,
+    ) -> std::io::Result<Vec<std::path::PathBuf>> {
+        let mut paths = Vec::with_capacity(self.frames.len());
+        for (i, frame) in self.to_svg_frames().into_iter().enumerate() {
+            let path = dir.as_ref().join(format!("{}{:04}.svg", prefix, i));
+            std::fs::write(&path, frame)?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pg_plane::Join;
+
+    #[test]
+    fn test_clip_line() {
+        let view = Viewport::new(-4, -2, 6, 8);
+        // vertical and horizontal lines
+        let [pt_a, pt_b] = view.clip_line(&PgLine::new([2, 0, -3])).unwrap();
+        assert_eq!(pt_a, PgPoint::new([3, -4, 2]));
+        assert_eq!(pt_b, PgPoint::new([3, 16, 2]));
+        let [pt_a, pt_b] = view.clip_line(&PgLine::new([0, 1, 0])).unwrap();
+        assert_eq!(pt_a, PgPoint::new([-4, 0, 1]));
+        assert_eq!(pt_b, PgPoint::new([6, 0, 1]));
+        // a border itself, and a line through opposite corners
+        let [pt_a, pt_b] = view.clip_line(&PgLine::new([1, 0, 4])).unwrap();
+        assert_eq!(pt_a, PgPoint::new([-4, -2, 1]));
+        assert_eq!(pt_b, PgPoint::new([-4, 8, 1]));
+        let diagonal = PgPoint::new([-4, -2, 1]).join(&PgPoint::new([6, 8, 1]));
+        assert_eq!(view.clip_line(&diagonal).unwrap().len(), 2);
+        // touching a corner only, missing the viewport, and the ideal line
+        assert_eq!(view.clip_line(&PgLine::new([1, 1, -14])), None);
+        assert_eq!(view.clip_line(&PgLine::new([1, 1, 100])), None);
+        assert_eq!(view.clip_line(&PgLine::new([0, 0, 1])), None);
+    }
+
+    #[test]
+    fn test_render() {
+        let mut renderer = SvgRenderer::new(100, 100, Viewport::new(0, 0, 10, 10));
+        assert!(renderer.draw_line(&PgLine::new([1, 1, -10]), "black"));
+        assert!(renderer.draw_point(&PgPoint::new([5, 5, 2]), "red"));
+        assert!(!renderer.draw_point(&PgPoint::new([1, 0, 0]), "red"));
+        let svg = renderer.to_svg();
+        assert!(svg.contains(
+            r#"<line x1="0.000" y1="0.000" x2="100.000" y2="100.000" stroke="black" />"#
+        ));
+        assert!(svg.contains(r#"<circle cx="25.000" cy="75.000" r="3" fill="red" />"#));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn test_draw_line() {
+        let mut renderer = SvgRenderer::new(100, 100, Viewport::new(0, 0, 10, 10));
+        // vertical, horizontal and diagonal lines
+        assert!(renderer.draw_line(&PgLine::new([1, 0, -5]), "black"));
+        assert!(renderer.draw_line(&PgLine::new([0, 2, -5]), "black"));
+        assert!(renderer.draw_line(&PgLine::new([-3, 3, 0]), "black"));
+        // a line missing the viewport is not drawn
+        assert!(!renderer.draw_line(&PgLine::new([1, 1, 100]), "black"));
+        let svg = renderer.to_svg();
+        assert!(svg.contains(
+            r#"<line x1="50.000" y1="100.000" x2="50.000" y2="0.000" stroke="black" />"#
+        ));
+        assert!(svg.contains(
+            r#"<line x1="0.000" y1="75.000" x2="100.000" y2="75.000" stroke="black" />"#
+        ));
+        assert!(svg.contains(
+            r#"<line x1="0.000" y1="100.000" x2="100.000" y2="0.000" stroke="black" />"#
+        ));
+        assert_eq!(svg.matches("<line").count(), 3);
+
+        // the line at infinity as a hatched border
+        assert!(renderer.draw_line(&PgLine::new([0, 0, -2]), "gray"));
+        let svg = renderer.to_svg();
+        assert!(svg.contains(
+            r#"<path class="ideal-line" d="M 6.000 6.000 H 94.000 V 94.000 H 6.000 Z M 0.000 0 l 6.000 6.000 M 0.000 100.000 l 6.000 -6.000"#
+        ));
+        assert_eq!(svg.matches(" l 6.000 6.000").count(), 18);
+        renderer.ideal_border = 0;
+        assert!(!renderer.draw_line(&PgLine::new([0, 0, 1]), "gray"));
+
+        // near-ideal lines: x = 10^15 far right, and y = -10^15 far below
+        let mut renderer = SvgRenderer::new(100, 100, Viewport::new(0, 0, 10, 10));
+        assert!(renderer.draw_line(&PgLine::new([1, 0, -1_000_000_000_000_000]), "blue"));
+        assert!(renderer.draw_line(&PgLine::new([0, -1, -1_000_000_000_000_000]), "blue"));
+        let svg = renderer.to_svg();
+        assert!(svg.contains(
+            r#"d="M 84.000 38.000 L 84.000 62.000 M 79.000 57.000 L 84.000 62.000 L 89.000 57.000"#
+        ));
+        assert!(svg.contains(r#"d="M 62.000 84.000 L 38.000 84.000"#));
+        assert_eq!(svg.matches(r#"class="near-ideal-line""#).count(), 2);
+    }
+
+    #[test]
+    fn test_draw_conic() {
+        let mut renderer = SvgRenderer::new(200, 100, Viewport::new(-4, -2, 4, 2));
+        // the ellipse x²/4 + y² = 1, rotated by 90 degrees
+        assert!(renderer.draw_conic(&Conic::from_coefficients(1, 0, 4, 0, 0, -4), "blue"));
+        let svg = renderer.to_svg();
+        assert!(svg.contains(r#"<ellipse cx="0.000" cy="0.000" rx="1.000" ry="2.000" transform="matrix(25 0 0 -25 100 50) rotate(90.000 0.000 0.000)""#));
+
+        // the parabola y = x² and the hyperbola xy = 1
+        let mut renderer = SvgRenderer::new(200, 100, Viewport::new(-4, -2, 4, 2));
+        assert!(renderer.draw_conic(&Conic::from_coefficients(1, 0, 0, 0, -1, 0), "red"));
+        assert_eq!(renderer.to_svg().matches("<path").count(), 1);
+        assert!(renderer.draw_conic(&Conic::from_coefficients(0, 1, 0, 0, 0, -1), "red"));
+        assert_eq!(renderer.to_svg().matches("<path").count(), 3);
+
+        // imaginary, degenerate and invisible conics
+        assert!(!renderer.draw_conic(&Conic::from_coefficients(1, 0, 1, 0, 0, 1), "red"));
+        assert!(!renderer.draw_conic(&Conic::from_coefficients(1, 0, -1, 0, 0, 0), "red"));
+        assert!(!renderer.draw_conic(&Conic::from_coefficients(1, 0, 0, 0, -1, 100), "red"));
+    }
+
+    #[test]
+    fn test_labels() {
+        let runs = |text: &str| label_runs(text);
+        assert_eq!(
+            runs("A_1"),
+            [("A".to_string(), false), ("1".to_string(), true)]
+        );
+        assert_eq!(
+            runs("P_{12}'"),
+            [
+                ("P".to_string(), false),
+                ("12".to_string(), true),
+                ("'".to_string(), false)
+            ]
+        );
+        assert_eq!(runs("a_"), [("a_".to_string(), false)]);
+        assert_eq!(
+            label_markup("<A>_{&}"),
+            r#"&lt;A&gt;<tspan baseline-shift="sub" font-size="9">&amp;</tspan>"#
+        );
+        assert_eq!(label_size("AB_1"), (0.6 * 12.0 * 2.0 + 0.6 * 9.0, 12.0));
+
+        // in the upper right corner only the lower left position fits
+        let ((dx, dy), _) = place_label((195.0, 5.0), "B", (200.0, 200.0), &[]);
+        assert!((dx + 12.2).abs() < 1e-9 && (dy - 17.0).abs() < 1e-9);
+        // blocked everywhere: fall back to the upper right
+        let everything = (0.0, 0.0, 200.0, 200.0);
+        let (offset, _) = place_label((100.0, 100.0), "B", (200.0, 200.0), &[everything]);
+        assert_eq!(offset, (5.0, -5.0));
+    }
+
+    #[test]
+    fn test_figure() {
+        let mut fig = Figure::new(300, 200);
+        assert_eq!(fig.viewport(), Viewport::new(-1, -1, 1, 1));
+        let pt_a = PgPoint::new([1, 2, 1]);
+        let pt_b = PgPoint::new([7, 1, 2]);
+        let pt_c = PgPoint::new([-1, 5, 1]);
+        fig.polygon(
+            &[pt_a.clone(), pt_b.clone(), pt_c.clone()],
+            Style::default(),
+        )
+        .point(&pt_a, Some("A<1>"), Style::new("red").layer(2))
+        .line(&pt_b.join(&pt_c), Style::new("gray").layer(-1))
+        .conic(
+            &Conic::from_coefficients(1, 0, 1, 0, 0, -4),
+            Style::new("blue"),
+        );
+        // the triangle and the circle of radius 2 about the origin
+        assert_eq!(fig.viewport(), Viewport::new(-3, -3, 5, 6));
+        let svg = fig.to_svg();
+        assert!(svg.contains(">A&lt;1&gt;</text>"));
+        // the line is drawn first and the labelled point last
+        let line = svg.find("<line").unwrap();
+        let polygon = svg.find("<polygon").unwrap();
+        let circle = svg.find("<circle").unwrap();
+        assert!(line < polygon && polygon < circle);
+    }
+
+    #[test]
+    fn test_animation() {
+        use crate::construction::Construction;
+        use crate::pg_plane::ProjectivePlane;
+
+        // a point moving along a line, and its harmonic conjugate
+        let mut cons = Construction::new();
+        let [a, b, p] = [(); 3].map(|_| cons.free_point());
+        let q = cons.harm_conj(a, b, p);
+        let (pt_a, pt_b) = (PgPoint::new([-2, 0, 1]), PgPoint::new([2, 0, 1]));
+        let animation = Animation::from_fn(3, 0.25, |i| {
+            let pt_p = pt_a.parametrize(1 + i as i64, &pt_b, 3 - i as i64);
+            let free = [pt_a.clone(), pt_b.clone(), pt_p];
+            let values = cons.evaluate::<PgLine, i64>(&free, &[]).unwrap();
+            let mut fig = Figure::new(100, 100);
+            fig.add_labeled_point("P", &values[p]);
+            if values[q].coord[2] != 0 {
+                fig.add_labeled_point("Q", &values[q]);
+            }
+            fig
+        });
+        assert_eq!(animation.frames().len(), 3);
+        // Q runs from (-4, 0) through infinity to (4, 0)
+        assert_eq!(animation.viewport(), Viewport::new(-5, -1, 5, 1));
+        let frames = animation.to_svg_frames();
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[1].matches("<circle").count(), 1);
+        assert!(frames.iter().all(|frame| frame.contains(">P</text>")));
+
+        let svg = animation.to_animated_svg();
+        assert_eq!(svg.matches("<g visibility=\"hidden\">").count(), 3);
+        assert!(svg.contains(r#"keyTimes="0;0.3333333333333333;0.6666666666666666" dur="0.75s""#));
+        assert_eq!(svg.matches("<circle").count(), 5);
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(Animation::new(1.0).viewport(), Viewport::new(-1, -1, 1, 1));
+    }
+
+    #[test]
+    fn test_disk_models() {
+        let pt = HyperbolicPoint::new([3, 0, 5]);
+        assert_eq!(DiskModel::Klein.point(&pt), Some((0.6, 0.0)));
+        assert_eq!(DiskModel::Poincare.point(&pt), Some((1.0 / 3.0, 0.0)));
+        assert_eq!(
+            DiskModel::Poincare.point(&HyperbolicPoint::new([3, 4, 5])),
+            Some((0.6, 0.8))
+        );
+        assert_eq!(
+            DiskModel::Klein.point(&HyperbolicPoint::new([1, 1, 1])),
+            None
+        );
+
+        // the chord x = 3/5 ends at (3/5, -4/5) and (3/5, 4/5)
+        let [(x1, y1), (x2, y2)] = klein_chord(&HyperbolicLine::new([5, 0, -3])).unwrap();
+        assert!((x1 - 0.6).abs() < 1e-12 && (y1 + 0.8).abs() < 1e-12);
+        assert!((x2 - 0.6).abs() < 1e-12 && (y2 - 0.8).abs() < 1e-12);
+        // tangent to the absolute
+        assert_eq!(klein_chord(&HyperbolicLine::new([0, 1, 1])), None);
+
+        let mut renderer = SvgRenderer::new(100, 100, Viewport::new(-1, -1, 1, 1));
+        renderer.draw_absolute("black");
+        assert!(renderer.draw_hyperbolic_point(&pt, DiskModel::Poincare, "red"));
+        assert!(renderer.draw_hyperbolic_line(
+            &HyperbolicLine::new([5, 0, -3]),
+            DiskModel::Klein,
+            "blue"
+        ));
+        assert!(renderer.draw_hyperbolic_line(
+            &HyperbolicLine::new([5, 0, -3]),
+            DiskModel::Poincare,
+            "blue"
+        ));
+        assert!(renderer.draw_hyperbolic_line(
+            &HyperbolicLine::new([0, 1, 0]),
+            DiskModel::Poincare,
+            "blue"
+        ));
+        assert!(!renderer.draw_hyperbolic_line(
+            &HyperbolicLine::new([1, 1, 2]),
+            DiskModel::Poincare,
+            "blue"
+        ));
+        let svg = renderer.to_svg();
+        // the arc of the circle about (5/3, 0) with radius 4/3
+        assert!(svg.contains("A 1.333333 1.333333 0 0 0 0.600000 0.800000"));
+        assert!(svg.contains("M 1.000000 0.000000 L -1.000000 0.000000"));
+    }
+}